@@ -0,0 +1,292 @@
+//! Нагрузочное тестирование сервера блога.
+//!
+//! Каждый симулированный пользователь - отдельный клиент blog-client, который
+//! регистрируется, а затем до окончания теста создает и читает посты и повторно
+//! входит в систему. Длительность запросов собирается наблюдателем за запросами.
+
+use blog_client::error::BlogClientError;
+use blog_client::observer::{ClientObserver, RequestInfo};
+use blog_client::{BlogClient, GrpcOptions, Page, Transport};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Пароль симулированных пользователей.
+const BENCH_PASSWORD: &str = "bench-password";
+
+/// Количество шагов в цикле действий пользователя.
+///
+/// На шагах 0-1 создается пост, на шаге 2 выполняется вход, на шагах 3-5
+/// запрашивается список постов, на остальных - один из созданных постов.
+const STEPS: usize = 10;
+
+/// Количество постов на странице при запросе списка постов.
+const PAGE_SIZE: i64 = 10;
+
+/// Перцентили длительности запросов в отчете.
+const PERCENTILES: [f64; 3] = [50.0, 90.0, 99.0];
+
+/// Разобрать длительность вида `500ms`, `60s`, `5m`, `1h` (без суффикса - секунды).
+pub(crate) fn parse_duration(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+
+    let number: u64 = number
+        .parse()
+        .map_err(|_| format!("некорректная длительность: {value}"))?;
+
+    match unit {
+        "ms" => Ok(Duration::from_millis(number)),
+        "" | "s" => Ok(Duration::from_secs(number)),
+        "m" => Ok(Duration::from_secs(number * 60)),
+        "h" => Ok(Duration::from_secs(number * 3600)),
+        _ => Err(format!("неизвестная единица длительности: {unit}")),
+    }
+}
+
+/// Статистика запросов одной операции.
+#[derive(Default)]
+struct OperationStats {
+    /// Длительности успешных запросов.
+    latencies: Vec<Duration>,
+
+    /// Количество запросов, завершившихся ошибкой.
+    errors: u64,
+}
+
+/// Наблюдатель, собирающий длительность запросов всех симулированных пользователей.
+#[derive(Default)]
+struct BenchObserver {
+    /// Статистика по названиям операций.
+    operations: Mutex<BTreeMap<&'static str, OperationStats>>,
+}
+
+impl ClientObserver for BenchObserver {
+    fn on_response(&self, request: &RequestInfo, elapsed: Duration) {
+        if let Ok(mut operations) = self.operations.lock() {
+            let stats = operations.entry(request.operation).or_default();
+            stats.latencies.push(elapsed);
+        }
+    }
+
+    fn on_error(&self, request: &RequestInfo, _error: &BlogClientError, _elapsed: Duration) {
+        if let Ok(mut operations) = self.operations.lock() {
+            operations.entry(request.operation).or_default().errors += 1;
+        }
+    }
+}
+
+/// Итоги нагрузочного теста по одной операции.
+struct OperationReport {
+    /// Название операции клиента.
+    operation: &'static str,
+
+    /// Общее количество запросов.
+    requests: u64,
+
+    /// Количество запросов, завершившихся ошибкой.
+    errors: u64,
+
+    /// Перцентили длительности успешных запросов ([`PERCENTILES`]).
+    percentiles: [Duration; PERCENTILES.len()],
+
+    /// Максимальная длительность успешного запроса.
+    max: Duration,
+}
+
+/// Итоги нагрузочного теста.
+pub(crate) struct BenchReport {
+    /// Количество симулированных пользователей.
+    users: usize,
+
+    /// Фактическая длительность теста.
+    elapsed: Duration,
+
+    /// Итоги по операциям.
+    operations: Vec<OperationReport>,
+}
+
+impl BenchReport {
+    /// Подвести итоги по собранной статистике.
+    fn new(
+        users: usize,
+        elapsed: Duration,
+        operations: BTreeMap<&'static str, OperationStats>,
+    ) -> Self {
+        let operations = operations
+            .into_iter()
+            .map(|(operation, mut stats)| {
+                stats.latencies.sort();
+
+                OperationReport {
+                    operation,
+                    requests: stats.latencies.len() as u64 + stats.errors,
+                    errors: stats.errors,
+                    percentiles: PERCENTILES.map(|p| percentile(&stats.latencies, p)),
+                    max: stats.latencies.last().copied().unwrap_or_default(),
+                }
+            })
+            .collect();
+
+        Self {
+            users,
+            elapsed,
+            operations,
+        }
+    }
+}
+
+impl fmt::Display for BenchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<20}  {:>8}  {:>6}  {:>7}  {:>8}  {:>8}  {:>8}  {:>8}",
+            "Операция", "Запросов", "Ошибок", "Ошибки%", "p50, мс", "p90, мс", "p99, мс", "max, мс"
+        )?;
+
+        for operation in &self.operations {
+            let [p50, p90, p99] = operation.percentiles;
+
+            writeln!(
+                f,
+                "{:<20}  {:>8}  {:>6}  {:>7.1}  {:>8}  {:>8}  {:>8}  {:>8}",
+                operation.operation,
+                operation.requests,
+                operation.errors,
+                error_rate(operation.errors, operation.requests),
+                p50.as_millis(),
+                p90.as_millis(),
+                p99.as_millis(),
+                operation.max.as_millis(),
+            )?;
+        }
+
+        let requests: u64 = self.operations.iter().map(|o| o.requests).sum();
+        let errors: u64 = self.operations.iter().map(|o| o.errors).sum();
+
+        writeln!(f)?;
+        writeln!(f, "Пользователей: {}", self.users)?;
+        writeln!(f, "Длительность: {:.1} с", self.elapsed.as_secs_f64())?;
+        writeln!(
+            f,
+            "Запросов: {requests} ({:.1} в секунду)",
+            requests as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+        )?;
+        write!(f, "Ошибок: {errors} ({:.1}%)", error_rate(errors, requests))
+    }
+}
+
+/// Доля ошибок в процентах.
+fn error_rate(errors: u64, requests: u64) -> f64 {
+    if requests == 0 {
+        return 0.0;
+    }
+
+    errors as f64 * 100.0 / requests as f64
+}
+
+/// Перцентиль отсортированных длительностей (метод ближайшего ранга).
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Запустить нагрузочный тест: `users` пользователей в течение `duration`.
+pub(crate) async fn run(
+    transport: Transport,
+    grpc_options: GrpcOptions,
+    users: usize,
+    duration: Duration,
+) -> anyhow::Result<BenchReport> {
+    let observer = Arc::new(BenchObserver::default());
+    let page = Page::first(PAGE_SIZE)?;
+    let run_id = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
+
+    let started = Instant::now();
+    let deadline = started + duration;
+
+    let mut tasks = Vec::with_capacity(users);
+
+    for user in 0..users {
+        let client = BlogClient::connect(transport.clone(), grpc_options.clone())
+            .await?
+            .with_observer(observer.clone());
+
+        tasks.push(tokio::spawn(simulate_user(
+            client,
+            format!("bench-{run_id}-{user}"),
+            page,
+            deadline,
+        )));
+    }
+
+    for task in tasks {
+        task.await?;
+    }
+
+    let elapsed = started.elapsed();
+    let operations = std::mem::take(
+        &mut *observer
+            .operations
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Статистика запросов недоступна"))?,
+    );
+
+    Ok(BenchReport::new(users, elapsed, operations))
+}
+
+/// Действия одного симулированного пользователя до наступления `deadline`.
+///
+/// Ошибки запросов учитываются наблюдателем и не прерывают тест; если
+/// пользователя не удалось зарегистрировать, он не выполняет других действий.
+async fn simulate_user(client: BlogClient, username: String, page: Page, deadline: Instant) {
+    let email = format!("{username}@example.com");
+
+    if client
+        .register(&username, &email, BENCH_PASSWORD, None)
+        .await
+        .is_err()
+    {
+        return;
+    }
+
+    let mut post_ids = vec![];
+    let mut step = 0;
+
+    while Instant::now() < deadline {
+        match step % STEPS {
+            0 | 1 => {
+                let title = format!("Пост {username} #{step}");
+
+                if let Ok(post) = client
+                    .create_post(&title, "Содержимое нагрузочного теста", false, None)
+                    .await
+                {
+                    post_ids.push(post.id);
+                }
+            }
+            2 => {
+                let _ = client.login(&username, BENCH_PASSWORD).await;
+            }
+            3..=5 => {
+                let _ = client.get_posts(page, None).await;
+            }
+            _ => {
+                if let Some(id) = post_ids.get(step % post_ids.len().max(1)) {
+                    let _ = client.get_post(*id).await;
+                }
+            }
+        }
+
+        step += 1;
+    }
+}
@@ -0,0 +1,169 @@
+//! Вывод ошибок консольного клиента и коды завершения процесса.
+//!
+//! Ошибки клиента блога разделяются на категории с отдельными кодами завершения,
+//! чтобы скрипты могли отличить, например, отсутствующий пост от истекшего токена
+//! или недоступного сервера. Код 2 зарезервирован за ошибками разбора аргументов.
+
+use crate::OutputFormat;
+use blog_client::error::BlogClientError;
+use std::process::ExitCode;
+
+/// Категория ошибки команды.
+#[derive(Debug, Clone, Copy)]
+enum ErrorKind {
+    /// Прочие ошибки (файлы, хранилище токенов, некорректный адрес сервера).
+    Other,
+
+    /// Пост или пользователь не найден.
+    NotFound,
+
+    /// Токен отсутствует, недействителен или учетные данные неверны.
+    Unauthorized,
+
+    /// Действие запрещено.
+    Forbidden,
+
+    /// Сервер или клиент отклонил параметры запроса.
+    InvalidInput,
+
+    /// Запрос конфликтует с текущим состоянием данных.
+    Conflict,
+
+    /// Превышен лимит частоты запросов.
+    RateLimited,
+
+    /// Не удалось подключиться к серверу.
+    Unreachable,
+
+    /// Ошибка на стороне сервера или нарушение протокола.
+    Server,
+}
+
+impl ErrorKind {
+    /// Определить категорию ошибки по первой ошибке клиента блога в цепочке причин.
+    fn of(error: &anyhow::Error) -> Self {
+        error
+            .chain()
+            .find_map(|e| e.downcast_ref::<BlogClientError>())
+            .map_or(Self::Other, Self::from)
+    }
+
+    /// Название категории в JSON-выводе.
+    fn name(self) -> &'static str {
+        match self {
+            Self::Other => "other",
+            Self::NotFound => "not_found",
+            Self::Unauthorized => "unauthorized",
+            Self::Forbidden => "forbidden",
+            Self::InvalidInput => "invalid_input",
+            Self::Conflict => "conflict",
+            Self::RateLimited => "rate_limited",
+            Self::Unreachable => "unreachable",
+            Self::Server => "server",
+        }
+    }
+
+    /// Код завершения процесса.
+    fn exit_code(self) -> u8 {
+        match self {
+            Self::Other => 1,
+            Self::NotFound => 3,
+            Self::Unauthorized => 4,
+            Self::Forbidden => 5,
+            Self::InvalidInput => 6,
+            Self::Conflict => 7,
+            Self::RateLimited => 8,
+            Self::Unreachable => 9,
+            Self::Server => 10,
+        }
+    }
+}
+
+impl From<&BlogClientError> for ErrorKind {
+    fn from(error: &BlogClientError) -> Self {
+        if error.is_unreachable() {
+            return Self::Unreachable;
+        }
+
+        match error {
+            BlogClientError::UserNotFound | BlogClientError::PostNotFound => Self::NotFound,
+            BlogClientError::InvalidToken
+            | BlogClientError::TokenNotFound
+            | BlogClientError::UserUnauthorized
+            | BlogClientError::InvalidCredentials => Self::Unauthorized,
+            BlogClientError::Forbidden
+            | BlogClientError::AdminRequired
+            | BlogClientError::RegistrationClosed
+            | BlogClientError::MediaUploadsDisabled => Self::Forbidden,
+            BlogClientError::InvalidRegistrationCredentials
+            | BlogClientError::InvalidUser
+            | BlogClientError::InvalidPostContent
+            | BlogClientError::InvalidMedia
+            | BlogClientError::MediaTooLarge
+            | BlogClientError::InvalidQuery
+            | BlogClientError::InvalidPagination { .. } => Self::InvalidInput,
+            BlogClientError::UserAlreadyExists
+            | BlogClientError::Conflict
+            | BlogClientError::DuplicatePost(_)
+            | BlogClientError::PostArchived => Self::Conflict,
+            BlogClientError::RateLimited(_) => Self::RateLimited,
+            BlogClientError::Unexpected
+            | BlogClientError::Http(_)
+            | BlogClientError::Grpc(_)
+            | BlogClientError::MsgpackEncode(_)
+            | BlogClientError::MsgpackDecode(_)
+            | BlogClientError::GrpcStatus(_) => Self::Server,
+        }
+    }
+}
+
+/// Сообщение об ошибке вместе с причинами.
+///
+/// Ошибки клиента блога уже включают текст причины в свое сообщение, поэтому
+/// причины, текст которых уже выведен, пропускаются.
+fn message(error: &anyhow::Error) -> String {
+    let mut message = String::new();
+
+    for cause in error.chain() {
+        let text = cause.to_string();
+
+        if message.contains(&text) {
+            continue;
+        }
+
+        if !message.is_empty() {
+            message.push_str(": ");
+        }
+
+        message.push_str(&text);
+    }
+
+    message
+}
+
+/// Вывести ошибку команды в stderr и вернуть соответствующий ей код завершения.
+///
+/// В текстовом формате выводится строка `Ошибка: <сообщение>`, в формате JSON -
+/// объект `{"error": {"kind": ..., "code": ..., "message": ...}}`.
+pub(crate) fn report(error: &anyhow::Error, format: OutputFormat) -> ExitCode {
+    let kind = ErrorKind::of(error);
+    let code = kind.exit_code();
+    let message = message(error);
+
+    match format {
+        OutputFormat::Text => eprintln!("Ошибка: {message}"),
+        OutputFormat::Json => {
+            let body = serde_json::json!({
+                "error": {
+                    "kind": kind.name(),
+                    "code": code,
+                    "message": message,
+                }
+            });
+
+            eprintln!("{body}");
+        }
+    }
+
+    ExitCode::from(code)
+}
@@ -2,10 +2,37 @@
 
 #![deny(unreachable_pub)]
 
-use clap::{Parser, Subcommand};
+mod bench;
+mod exit;
+mod token_store;
+
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::engine::{ArgValueCandidates, CompletionCandidate};
+use clap_complete::env::Shells;
+use clap_complete::{CompleteEnv, Shell};
 use std::borrow::Cow;
 
-use blog_client::{BlogClient, Transport};
+use blog_client::error::BlogClientError;
+use blog_client::observer::{ClientObserver, RequestInfo};
+use blog_client::trace::TraceContext;
+use blog_client::{
+    BlogClient, ContentFormat, GrpcOptions, Page, PostPreview, Transport, UploadProgress,
+};
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
+use token_store::TokenStore;
+
+/// Escape-последовательность выделения найденных слов (жирный желтый).
+const HIGHLIGHT: &str = "\x1b[1;33m";
+
+/// Ширина индикатора прогресса загрузки (в символах).
+const PROGRESS_WIDTH: usize = 30;
+
+/// Escape-последовательность сброса цвета.
+const RESET: &str = "\x1b[0m";
 
 /// Взаимодействие с системой блога.
 #[derive(Parser)]
@@ -19,9 +46,73 @@ struct Cli {
     #[arg(long)]
     grpc: bool,
 
-    /// Адрес сервера (по-умолчанию localhost:3000 для HTTP или localhost:50051 для gRPC).
+    /// Передавать данные по HTTP в формате MessagePack вместо JSON (экономит трафик).
+    #[arg(long, conflicts_with = "grpc")]
+    msgpack: bool,
+
+    /// Адрес сервера (по-умолчанию localhost:3000 для HTTP или localhost:50051 для gRPC)
+    /// или путь к Unix-сокету в виде `unix:/path/to.sock`.
     #[arg(long)]
     server: Option<String>,
+
+    /// Сколько ждать подключения к gRPC-серверу, повторяя попытки (например, `500ms`, `30s`).
+    ///
+    /// Позволяет запускать клиент одновременно с сервером.
+    #[arg(long, default_value = "10s", value_parser = bench::parse_duration)]
+    connect_timeout: Duration,
+
+    /// Выводить в stderr длительность, результат и идентификатор трассировки запросов к серверу.
+    #[arg(long, short)]
+    verbose: bool,
+
+    /// Родительский контекст трассировки W3C (`00-<trace-id>-<span-id>-<flags>`),
+    /// чтобы связать запросы клиента с трассировкой вызывающего процесса.
+    #[arg(long, value_name = "TRACEPARENT")]
+    traceparent: Option<TraceContext>,
+
+    /// Формат вывода результатов и ошибок (ошибки выводятся в stderr).
+    #[arg(long, value_enum, default_value_t, global = true)]
+    output: OutputFormat,
+
+    /// Профиль, под которым сохраняется токен авторизации (для нескольких учетных записей).
+    #[arg(long, default_value = "default", add = ArgValueCandidates::new(profile_candidates))]
+    profile: String,
+}
+
+/// Наблюдатель, выводящий длительность запросов к серверу в stderr.
+struct VerboseObserver;
+
+impl ClientObserver for VerboseObserver {
+    fn on_response(&self, request: &RequestInfo, elapsed: Duration) {
+        eprintln!(
+            "[{}] {}: {} мс (трассировка {})",
+            request.transport,
+            request.operation,
+            elapsed.as_millis(),
+            request.trace.trace_id()
+        );
+    }
+
+    fn on_error(&self, request: &RequestInfo, error: &BlogClientError, elapsed: Duration) {
+        eprintln!(
+            "[{}] {}: ошибка за {} мс (трассировка {}): {error}",
+            request.transport,
+            request.operation,
+            elapsed.as_millis(),
+            request.trace.trace_id()
+        );
+    }
+}
+
+/// Формат вывода результатов команды.
+#[derive(Clone, Copy, Default, ValueEnum)]
+enum OutputFormat {
+    /// Текст для чтения в терминале.
+    #[default]
+    Text,
+
+    /// JSON.
+    Json,
 }
 
 /// Доступные команды.
@@ -40,6 +131,10 @@ enum Commands {
         /// Пароль пользователя.
         #[arg(long)]
         password: String,
+
+        /// Код приглашения (если регистрация возможна только по приглашениям).
+        #[arg(long)]
+        invite_code: Option<String>,
     },
 
     /// Авторизация текущего пользователя.
@@ -62,6 +157,14 @@ enum Commands {
         /// Содержимое поста.
         #[arg(long)]
         content: String,
+
+        /// Сделать пост приватным.
+        #[arg(long)]
+        private: bool,
+
+        /// Формат содержимого: `markdown`, `plain` (по умолчанию) или `html`.
+        #[arg(long)]
+        format: Option<ContentFormat>,
     },
 
     /// Получить пост.
@@ -80,6 +183,32 @@ enum Commands {
         /// Сдвиг от первого поста.
         #[arg(default_value_t = 0)]
         offset: i64,
+
+        /// Выводимые поля поста через запятую (например, `title,created_at`).
+        #[arg(long)]
+        fields: Option<String>,
+
+        /// Выводить только архивные (`--archived`) или только неархивные (`--archived false`) посты.
+        #[arg(long, num_args = 0..=1, default_missing_value = "true")]
+        archived: Option<bool>,
+    },
+
+    /// Найти посты по поисковому запросу.
+    Search {
+        /// Строка поиска.
+        query: String,
+
+        /// Искать только среди постов автора с указанным идентификатором.
+        #[arg(long)]
+        author: Option<i64>,
+
+        /// Максимальное количество постов в ответе.
+        #[arg(long, default_value_t = 10)]
+        limit: i64,
+
+        /// Сдвиг от первого найденного поста.
+        #[arg(long, default_value_t = 0)]
+        offset: i64,
     },
 
     /// Обновить пост.
@@ -95,6 +224,14 @@ enum Commands {
         /// Содержимое поста.
         #[arg(long)]
         content: Option<String>,
+
+        /// Формат содержимого: `markdown`, `plain` или `html`.
+        #[arg(long)]
+        format: Option<ContentFormat>,
+
+        /// Ожидаемая версия поста (обновление отклоняется, если пост уже изменен).
+        #[arg(long)]
+        expected_version: Option<i64>,
     },
 
     /// Удалить пост.
@@ -103,11 +240,162 @@ enum Commands {
         #[arg(long)]
         id: i64,
     },
+
+    /// Удалить несколько постов за один запрос.
+    BulkDelete {
+        /// Идентификаторы постов через запятую (не более 100).
+        #[arg(long, required = true, value_delimiter = ',')]
+        ids: Vec<i64>,
+    },
+
+    /// Перевести пост в архив.
+    ArchivePost {
+        /// Идентификатор поста.
+        #[arg(long)]
+        id: i64,
+    },
+
+    /// Вернуть пост из архива.
+    UnarchivePost {
+        /// Идентификатор поста.
+        #[arg(long)]
+        id: i64,
+    },
+
+    /// Добавить соавтора поста.
+    AddPostAuthor {
+        /// Идентификатор поста.
+        #[arg(long)]
+        id: i64,
+
+        /// Идентификатор пользователя-соавтора.
+        #[arg(long)]
+        user_id: i64,
+    },
+
+    /// Создать ссылку для совместного доступа к посту.
+    SharePost {
+        /// Идентификатор поста.
+        #[arg(long)]
+        id: i64,
+
+        /// Время жизни ссылки в секундах (по умолчанию 7 дней).
+        #[arg(long)]
+        ttl: Option<u64>,
+    },
+
+    /// Получить пост по токену ссылки для совместного доступа.
+    GetSharedPost {
+        /// Токен доступа к посту.
+        #[arg(long)]
+        token: String,
+    },
+
+    /// Загрузить изображение на сервер.
+    ///
+    /// Выводит идентификатор и ссылку загруженного изображения.
+    Upload {
+        /// Путь к файлу изображения.
+        path: PathBuf,
+
+        /// Установить изображение обложкой поста с указанным идентификатором.
+        #[arg(long)]
+        post_id: Option<i64>,
+    },
+
+    /// Административные команды.
+    Admin {
+        /// Административная команда.
+        #[clap(subcommand)]
+        command: AdminCommands,
+    },
+
+    /// Нагрузочное тестирование сервера.
+    ///
+    /// Симулированные пользователи регистрируются, создают и читают посты
+    /// и повторно входят в систему; в отчет выводятся перцентили длительности
+    /// запросов и доля ошибок по каждой операции.
+    Bench {
+        /// Количество одновременно работающих пользователей.
+        #[arg(long, default_value_t = 10, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+        users: usize,
+
+        /// Длительность теста (например, `500ms`, `60s`, `5m`).
+        #[arg(long, default_value = "60s", value_parser = bench::parse_duration)]
+        duration: Duration,
+    },
+
+    /// Вывести скрипт автодополнения команд для командной оболочки.
+    ///
+    /// Скрипт вызывает blog-cli при автодополнении, поэтому в подсказках
+    /// для `--profile` всегда актуальный список профилей.
+    Completions {
+        /// Командная оболочка.
+        shell: Shell,
+    },
+
+    /// Сгенерировать man-страницу.
+    ///
+    /// Без `--out-dir` страница основной команды выводится в stdout.
+    Man {
+        /// Каталог, в который записываются страницы основной команды и всех подкоманд.
+        #[arg(long)]
+        out_dir: Option<PathBuf>,
+    },
+}
+
+/// Административные команды.
+#[derive(Subcommand)]
+enum AdminCommands {
+    /// Получить статистику блога.
+    Stats {
+        /// Период статистики в днях (по умолчанию 30).
+        #[arg(long)]
+        days: Option<i32>,
+    },
 }
 
 #[tokio::main]
-async fn main() -> anyhow::Result<()> {
+async fn main() -> ExitCode {
+    // При вызове из скрипта автодополнения (переменная COMPLETE) выводит варианты и завершает процесс.
+    CompleteEnv::with_factory(Cli::command).complete();
+
     let args = Cli::parse();
+    let output = args.output;
+
+    match run(args).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => exit::report(&e, output),
+    }
+}
+
+/// Выполнить команду.
+async fn run(args: Cli) -> anyhow::Result<()> {
+    match &args.command {
+        Commands::Completions { shell } => {
+            let shells = Shells::builtins();
+            let completer = shells
+                .completer(&shell.to_string())
+                .ok_or_else(|| anyhow::anyhow!("Оболочка {shell} не поддерживается"))?;
+            let bin = env!("CARGO_BIN_NAME");
+
+            completer.write_registration("COMPLETE", bin, bin, bin, &mut std::io::stdout())?;
+
+            return Ok(());
+        }
+        Commands::Man { out_dir: Some(dir) } => {
+            std::fs::create_dir_all(dir)?;
+            clap_mangen::generate_to(Cli::command(), dir)?;
+
+            return Ok(());
+        }
+        Commands::Man { out_dir: None } => {
+            clap_mangen::Man::new(Cli::command()).render(&mut std::io::stdout())?;
+
+            return Ok(());
+        }
+        _ => {}
+    }
 
     let server = match args.server {
         Some(server) => server,
@@ -120,24 +408,46 @@ async fn main() -> anyhow::Result<()> {
         }
     };
 
+    let token_store = TokenStore::new(&args.profile, &server);
+
     let transport = if args.grpc {
         Transport::Grpc(server.parse()?)
+    } else if args.msgpack {
+        Transport::HttpMsgpack(server.parse()?)
     } else {
         Transport::Http(server.parse()?)
     };
 
-    let token_path = ".blog_token";
-
-    let token = if std::fs::exists(token_path)? {
-        Cow::Owned(std::fs::read_to_string(".blog_token")?)
-    } else {
-        Cow::Borrowed("")
+    let grpc_options = GrpcOptions {
+        connect_timeout: args.connect_timeout,
+        ..GrpcOptions::default()
     };
 
-    let mut client = BlogClient::new(transport).await?;
+    if let Commands::Bench { users, duration } = args.command {
+        println!(
+            "Нагрузочный тест {server}: {users} польз., {} с\n",
+            duration.as_secs_f64()
+        );
+
+        let report = bench::run(transport, grpc_options, users, duration).await?;
+
+        println!("{report}");
+
+        return Ok(());
+    }
+
+    let mut client = BlogClient::connect(transport, grpc_options).await?;
 
-    if !token.is_empty() {
-        client.set_token(token.to_string());
+    if args.verbose {
+        client = client.with_observer(VerboseObserver);
+    }
+
+    if let Some(context) = args.traceparent {
+        client = client.with_trace_context(context);
+    }
+
+    if let Some(token) = token_store.load()? {
+        client.set_token(token);
     }
 
     match args.command {
@@ -145,16 +455,15 @@ async fn main() -> anyhow::Result<()> {
             username,
             email,
             password,
+            invite_code,
         } => {
-            let user = client.register(&username, &email, &password).await?;
+            let user = client
+                .register(&username, &email, &password, invite_code.as_deref())
+                .await?;
 
-            tokio::fs::write(
-                token_path,
-                client.get_token().ok_or(anyhow::anyhow!(
-                    "Токен не был установлен после успешной регистрации!"
-                ))?,
-            )
-            .await?;
+            token_store.save(&client.get_token().ok_or(anyhow::anyhow!(
+                "Токен не был установлен после успешной регистрации!"
+            ))?)?;
 
             println!("Зарегистрированный пользователь:");
 
@@ -163,20 +472,23 @@ async fn main() -> anyhow::Result<()> {
         Commands::Login { username, password } => {
             let user = client.login(&username, &password).await?;
 
-            tokio::fs::write(
-                token_path,
-                client.get_token().ok_or(anyhow::anyhow!(
-                    "Токен не был установлен после успешного логина!"
-                ))?,
-            )
-            .await?;
+            token_store.save(&client.get_token().ok_or(anyhow::anyhow!(
+                "Токен не был установлен после успешного логина!"
+            ))?)?;
 
             println!("Авторизованный пользователь:");
 
             println!("{}", user);
         }
-        Commands::CreatePost { title, content } => {
-            let post = client.create_post(&title, &content).await?;
+        Commands::CreatePost {
+            title,
+            content,
+            private,
+            format,
+        } => {
+            let post = client
+                .create_post(&title, &content, private, format)
+                .await?;
 
             println!("Созданный пост:");
 
@@ -189,18 +501,90 @@ async fn main() -> anyhow::Result<()> {
 
             println!("{}", post);
         }
-        Commands::GetPosts { limit, offset } => {
-            let posts = client.get_posts(limit, offset).await?;
+        Commands::GetPosts {
+            limit,
+            offset,
+            fields: None,
+            archived,
+        } => {
+            let page = client
+                .get_posts(Page::new(limit, offset)?, archived)
+                .await?;
 
-            println!("Полученные посты:\n");
+            if !page.is_empty() {
+                println!("Полученные посты:\n");
 
-            for post in posts {
-                println!("{}\n", post);
-                println!("-----------");
+                print_posts_table(&page.posts);
+                println!();
             }
+
+            println!("{page}");
         }
-        Commands::UpdatePost { id, title, content } => {
-            let post = client.update_post(id, title, content).await?;
+        Commands::GetPosts {
+            limit,
+            offset,
+            fields: Some(fields),
+            archived,
+        } => {
+            let fields: Vec<&str> = fields.split(',').map(str::trim).collect();
+            let page = client
+                .get_post_previews(Page::new(limit, offset)?, &fields, archived)
+                .await?;
+
+            if !page.is_empty() {
+                println!("Полученные посты:\n");
+
+                for post in &page.posts {
+                    println!("{}\n", post);
+                    println!("-----------");
+                }
+            }
+
+            println!("{page}");
+        }
+        Commands::Search {
+            query,
+            author,
+            limit,
+            offset,
+        } => {
+            let posts = client
+                .search_posts(&query, author, Page::new(limit, offset)?)
+                .await?;
+
+            match args.output {
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&posts)?),
+                OutputFormat::Text if posts.is_empty() => println!("Постов не найдено"),
+                OutputFormat::Text => {
+                    let terms = search_terms(&query);
+                    let color = std::io::stdout().is_terminal();
+
+                    for post in &posts {
+                        println!(
+                            "{:>6}  {}",
+                            post.id,
+                            highlight(post.title.as_deref().unwrap_or("-"), &terms, color)
+                        );
+
+                        if let Some(excerpt) = &post.excerpt {
+                            println!("        {}", highlight(excerpt, &terms, color));
+                        }
+                    }
+
+                    println!("\nНайдено постов: {}", posts.len());
+                }
+            }
+        }
+        Commands::UpdatePost {
+            id,
+            title,
+            content,
+            format,
+            expected_version,
+        } => {
+            let post = client
+                .update_post(id, title, content, format, expected_version)
+                .await?;
 
             println!("Обновленный пост:");
 
@@ -211,7 +595,205 @@ async fn main() -> anyhow::Result<()> {
 
             println!("Пост удален!")
         }
+        Commands::BulkDelete { ids } => {
+            let results = client.bulk_delete(&ids).await?;
+
+            match args.output {
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&results)?),
+                OutputFormat::Text => {
+                    for result in &results {
+                        println!("{result}");
+                    }
+
+                    let deleted = results.iter().filter(|result| result.deleted).count();
+                    println!("\nУдалено постов: {deleted} из {}", results.len());
+                }
+            }
+        }
+        Commands::ArchivePost { id } => {
+            let post = client.archive_post(id).await?;
+
+            println!("Пост перемещен в архив:");
+
+            println!("{}", post);
+        }
+        Commands::UnarchivePost { id } => {
+            let post = client.unarchive_post(id).await?;
+
+            println!("Пост возвращен из архива:");
+
+            println!("{}", post);
+        }
+        Commands::AddPostAuthor { id, user_id } => {
+            let post = client.add_post_author(id, user_id).await?;
+
+            println!("Соавтор добавлен:");
+
+            println!("{}", post);
+        }
+        Commands::SharePost { id, ttl } => {
+            let link = client.create_share_link(id, ttl).await?;
+
+            println!("Ссылка для совместного доступа:");
+
+            println!("{}", link);
+        }
+        Commands::GetSharedPost { token } => {
+            let post = client.get_shared_post(&token).await?;
+
+            println!("Полученный пост:");
+
+            println!("{}", post);
+        }
+        Commands::Upload { path, post_id } => {
+            let content = tokio::fs::read(&path).await?;
+            let total = content.len() as u64;
+            let show_progress = std::io::stderr().is_terminal();
+
+            let media = client
+                .upload_media(content, upload_progress(show_progress, total))
+                .await;
+
+            if show_progress {
+                eprintln!();
+            }
+
+            let media = media?;
+
+            println!("Загруженное изображение:");
+
+            println!("{}", media);
+
+            if let Some(post_id) = post_id {
+                client.set_cover_image(post_id, &media.url).await?;
+
+                println!("\nИзображение установлено обложкой поста {post_id}");
+            }
+        }
+        Commands::Admin {
+            command: AdminCommands::Stats { days },
+        } => {
+            let stats = client.get_admin_stats(days).await?;
+
+            println!("Статистика блога:\n");
+
+            print!("{}", stats);
+        }
+        // Обрабатываются до подключения к серверу.
+        Commands::Completions { .. } | Commands::Man { .. } | Commands::Bench { .. } => {
+            unreachable!()
+        }
     }
 
     Ok(())
 }
+
+/// Варианты автодополнения `--profile`: профили, для которых сохранялись токены.
+fn profile_candidates() -> Vec<CompletionCandidate> {
+    token_store::known_profiles()
+        .into_iter()
+        .map(CompletionCandidate::new)
+        .collect()
+}
+
+/// Обработчик хода загрузки, рисующий индикатор прогресса в stderr.
+fn upload_progress(show: bool, total: u64) -> UploadProgress {
+    Arc::new(move |sent| {
+        if !show {
+            return;
+        }
+
+        let percent = (sent * 100).checked_div(total).unwrap_or(100);
+        let filled = (percent as usize * PROGRESS_WIDTH) / 100;
+
+        eprint!(
+            "\r[{}{}] {percent:>3}% {}/{} КиБ",
+            "#".repeat(filled),
+            "-".repeat(PROGRESS_WIDTH - filled),
+            sent / 1024,
+            total / 1024,
+        );
+    })
+}
+
+/// Вывести список постов в виде таблицы.
+fn print_posts_table(posts: &[PostPreview]) {
+    println!(
+        "{:>6}  {:<40}  {:>8}  {:<16}  Описание",
+        "ID", "Заголовок", "Автор", "Создан"
+    );
+
+    for post in posts {
+        println!(
+            "{:>6}  {:<40}  {:>8}  {:<16}  {}",
+            post.id,
+            truncate(post.title.as_deref().unwrap_or("-"), 40),
+            post.author_id
+                .map(|id| id.to_string())
+                .unwrap_or_else(|| "-".into()),
+            post.created_at
+                .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_else(|| "-".into()),
+            truncate(post.excerpt.as_deref().unwrap_or(""), 60),
+        );
+    }
+}
+
+/// Получить слова поискового запроса для выделения в результатах.
+///
+/// Исключенные слова (`-слово`) и оператор `or` не выделяются.
+fn search_terms(query: &str) -> Vec<String> {
+    query
+        .split_whitespace()
+        .filter(|word| !word.starts_with('-'))
+        .flat_map(|word| word.split(|c: char| !c.is_alphanumeric()))
+        .filter(|term| !term.is_empty() && !term.eq_ignore_ascii_case("or"))
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Выделить цветом слова текста, совпадающие (без учета регистра) со словами запроса.
+fn highlight<'a>(text: &'a str, terms: &[String], color: bool) -> Cow<'a, str> {
+    if !color || terms.is_empty() {
+        return Cow::Borrowed(text);
+    }
+
+    let mut result = String::with_capacity(text.len());
+    let mut word_start = None;
+
+    // Завершающий пробел закрывает последнее слово текста.
+    for (i, c) in text.char_indices().chain([(text.len(), ' ')]) {
+        if c.is_alphanumeric() {
+            word_start.get_or_insert(i);
+            continue;
+        }
+
+        if let Some(start) = word_start.take() {
+            let word = &text[start..i];
+
+            if terms.contains(&word.to_lowercase()) {
+                result.push_str(HIGHLIGHT);
+                result.push_str(word);
+                result.push_str(RESET);
+            } else {
+                result.push_str(word);
+            }
+        }
+
+        if i < text.len() {
+            result.push(c);
+        }
+    }
+
+    Cow::Owned(result)
+}
+
+/// Обрезать строку до указанного количества символов.
+fn truncate(s: &str, max_chars: usize) -> Cow<'_, str> {
+    if s.chars().count() <= max_chars {
+        Cow::Borrowed(s)
+    } else {
+        let truncated: String = s.chars().take(max_chars - 1).collect();
+        Cow::Owned(format!("{truncated}…"))
+    }
+}
@@ -0,0 +1,151 @@
+//! Хранение JWT-токенов консольного клиента между запусками.
+//!
+//! Токены хранятся в системном хранилище ключей (Keychain, Credential Manager,
+//! Secret Service). Если оно недоступно, токены записываются в файл
+//! `blog-cli/tokens.json` в каталоге настроек пользователя (`~/.config` в Linux),
+//! доступный только владельцу.
+//!
+//! Имена профилей, для которых сохранялись токены, записываются в файл
+//! `blog-cli/profiles.json` для автодополнения аргумента `--profile`.
+
+use anyhow::Context;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Название сервиса, под которым токены сохраняются в системном хранилище ключей.
+const KEYRING_SERVICE: &str = "blog-cli";
+
+/// Имя файла с токенами внутри каталога настроек клиента.
+const TOKENS_FILE: &str = "tokens.json";
+
+/// Имя файла со списком профилей внутри каталога настроек клиента.
+const PROFILES_FILE: &str = "profiles.json";
+
+/// Хранилище токена для пары профиль - сервер.
+pub(crate) struct TokenStore {
+    /// Профиль.
+    profile: String,
+    /// Ключ токена: `<профиль>@<сервер>`.
+    key: String,
+}
+
+impl TokenStore {
+    /// Создать хранилище токена указанного профиля для указанного сервера.
+    pub(crate) fn new(profile: &str, server: &str) -> Self {
+        Self {
+            profile: profile.to_string(),
+            key: format!("{profile}@{server}"),
+        }
+    }
+
+    /// Загрузить сохраненный токен.
+    pub(crate) fn load(&self) -> anyhow::Result<Option<String>> {
+        if let Ok(token) = self.entry().and_then(|entry| entry.get_password()) {
+            return Ok(Some(token));
+        }
+
+        Ok(read_tokens()?.remove(&self.key))
+    }
+
+    /// Сохранить токен.
+    ///
+    /// Если системное хранилище ключей недоступно, токен записывается в файл.
+    pub(crate) fn save(&self, token: &str) -> anyhow::Result<()> {
+        match self.entry().and_then(|entry| entry.set_password(token)) {
+            Ok(()) => {
+                let mut tokens = read_tokens()?;
+
+                // Устаревший токен из файла больше не нужен.
+                if tokens.remove(&self.key).is_some() {
+                    write_tokens(&tokens)?;
+                }
+            }
+            Err(e) => {
+                let mut tokens = read_tokens()?;
+                tokens.insert(self.key.clone(), token.to_string());
+                write_tokens(&tokens)?;
+
+                eprintln!(
+                    "Системное хранилище ключей недоступно ({e}), токен сохранен в {}",
+                    config_path(TOKENS_FILE)?.display()
+                );
+            }
+        }
+
+        let mut profiles = known_profiles();
+
+        if !profiles.contains(&self.profile) {
+            profiles.push(self.profile.clone());
+            write_private(PROFILES_FILE, &serde_json::to_string_pretty(&profiles)?)?;
+        }
+
+        Ok(())
+    }
+
+    /// Запись системного хранилища ключей для токена.
+    fn entry(&self) -> keyring::Result<keyring::Entry> {
+        keyring::Entry::new(KEYRING_SERVICE, &self.key)
+    }
+}
+
+/// Профили, для которых сохранялись токены (пустой список, если файл не удалось прочитать).
+pub(crate) fn known_profiles() -> Vec<String> {
+    config_path(PROFILES_FILE)
+        .and_then(|path| Ok(std::fs::read_to_string(path)?))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Путь к файлу в каталоге настроек клиента.
+fn config_path(file: &str) -> anyhow::Result<PathBuf> {
+    let dir = dirs::config_dir().context("Не удалось определить каталог настроек пользователя")?;
+
+    Ok(dir.join(KEYRING_SERVICE).join(file))
+}
+
+/// Прочитать токены из файла (пустой набор, если файла нет).
+fn read_tokens() -> anyhow::Result<BTreeMap<String, String>> {
+    let path = config_path(TOKENS_FILE)?;
+
+    if !std::fs::exists(&path)? {
+        return Ok(BTreeMap::new());
+    }
+
+    let content = std::fs::read_to_string(&path)?;
+
+    serde_json::from_str(&content)
+        .with_context(|| format!("Некорректный файл с токенами {}", path.display()))
+}
+
+/// Записать токены в файл, доступный только владельцу.
+fn write_tokens(tokens: &BTreeMap<String, String>) -> anyhow::Result<()> {
+    write_private(TOKENS_FILE, &serde_json::to_string_pretty(tokens)?)
+}
+
+/// Записать файл в каталог настроек клиента с доступом только для владельца.
+fn write_private(file: &str, content: &str) -> anyhow::Result<()> {
+    let path = config_path(file)?;
+
+    if let Some(dir) = path.parent() {
+        let mut builder = std::fs::DirBuilder::new();
+        builder.recursive(true);
+
+        #[cfg(unix)]
+        std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+
+        builder.create(dir)?;
+    }
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    let mut file = options.open(&path)?;
+    file.write_all(content.as_bytes())?;
+
+    Ok(())
+}
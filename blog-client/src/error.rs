@@ -1,5 +1,6 @@
 //! Описание ошибок при взаимодействии с сервером блога.
 
+use crate::rate_limit::RateLimit;
 use thiserror::Error;
 
 /// Ошибки взаимодействия с сервером блога.
@@ -26,6 +27,9 @@ pub enum BlogClientError {
     #[error("Некорректные данные для регистрации!")]
     InvalidRegistrationCredentials,
 
+    #[error("Регистрация закрыта или требует действительного приглашения!")]
+    RegistrationClosed,
+
     #[error("Некорректное содержимое информации о пользователе!")]
     InvalidUser,
 
@@ -38,15 +42,123 @@ pub enum BlogClientError {
     #[error("Запрещено взаимодействие с данным постом!")]
     Forbidden,
 
+    #[error("Пост был изменен другим запросом!")]
+    Conflict,
+
+    #[error("Пост с таким заголовком уже создан недавно (id {0})!")]
+    DuplicatePost(i64),
+
+    #[error("Пост находится в архиве и доступен только для чтения!")]
+    PostArchived,
+
+    #[error("Некорректное или неподдерживаемое изображение!")]
+    InvalidMedia,
+
+    #[error("Изображение превышает допустимый размер!")]
+    MediaTooLarge,
+
+    #[error("Загрузка изображений отключена!")]
+    MediaUploadsDisabled,
+
+    #[error("Действие доступно только администраторам!")]
+    AdminRequired,
+
+    #[error("Некорректные параметры запроса!")]
+    InvalidQuery,
+
+    #[error(
+        "Некорректные параметры пагинации: количество постов должно быть от 1 до {}, смещение - неотрицательным (получено {limit} и {offset})",
+        crate::MAX_PAGE_LIMIT
+    )]
+    InvalidPagination { limit: i64, offset: i64 },
+
+    #[error("Превышен лимит частоты запросов к серверу!")]
+    RateLimited(RateLimit),
+
     #[error("Непредвиденная ошибка!")]
     Unexpected,
 
     #[error("Внутренняя ошибка HTTP протокола: {0}")]
     Http(#[from] reqwest::Error),
 
+    #[error("Не удалось закодировать запрос в MessagePack: {0}")]
+    MsgpackEncode(#[from] rmp_serde::encode::Error),
+
+    #[error("Не удалось декодировать ответ в формате MessagePack: {0}")]
+    MsgpackDecode(#[from] rmp_serde::decode::Error),
+
     #[error("Внутренняя ошибка gRPC протокола: {0}")]
     Grpc(#[from] tonic::transport::Error),
 
     #[error("Непредвиденный статус gRPC ответа: {0}")]
     GrpcStatus(#[from] tonic::Status),
 }
+
+impl BlogClientError {
+    /// Не удалось связаться с сервером: он не запущен, недоступен по сети или не ответил вовремя.
+    pub fn is_unreachable(&self) -> bool {
+        match self {
+            Self::Http(e) => e.is_connect() || e.is_timeout(),
+            Self::Grpc(_) => true,
+            Self::GrpcStatus(status) => matches!(
+                status.code(),
+                tonic::Code::Unavailable | tonic::Code::DeadlineExceeded
+            ),
+            _ => false,
+        }
+    }
+
+    /// Не удалось установить соединение с сервером, то есть запрос до него не дошел.
+    ///
+    /// В отличие от [`Self::is_unreachable`], истечение времени ожидания ответа сюда
+    /// не относится: сервер мог уже выполнить запрос. Статус `UNAVAILABLE`, полученный
+    /// от сервера (например, в режиме обслуживания), тоже не относится: запрос до сервера дошел.
+    pub fn is_connect_failure(&self) -> bool {
+        match self {
+            Self::Http(e) => e.is_connect(),
+            Self::Grpc(_) => true,
+            // Канал подключается лениво, поэтому ошибка подключения приходит как статус,
+            // источником которого является ошибка транспорта.
+            Self::GrpcStatus(status) => is_transport_connect_error(status),
+            _ => false,
+        }
+    }
+}
+
+/// Вызван ли статус ошибкой подключения транспорта gRPC к серверу.
+fn is_transport_connect_error(status: &tonic::Status) -> bool {
+    let mut source = std::error::Error::source(status);
+
+    while let Some(error) = source {
+        if error.is::<tonic::ConnectError>() {
+            return true;
+        }
+
+        source = error.source();
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+
+    #[test]
+    fn transport_connect_error_is_connect_failure() {
+        let connect =
+            tonic::ConnectError(Box::new(io::Error::from(io::ErrorKind::ConnectionRefused)));
+        let status = tonic::Status::from_error(Box::new(connect));
+
+        assert_eq!(status.code(), tonic::Code::Unavailable);
+        assert!(BlogClientError::GrpcStatus(status).is_connect_failure());
+    }
+
+    #[test]
+    fn unavailable_from_server_is_not_connect_failure() {
+        let status = tonic::Status::unavailable("Сервер находится на обслуживании");
+
+        assert!(!BlogClientError::GrpcStatus(status).is_connect_failure());
+    }
+}
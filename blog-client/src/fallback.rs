@@ -0,0 +1,277 @@
+//! Клиент, переключающийся на запасной транспорт при недоступности сервера.
+
+use crate::error::BlogClientError;
+use crate::request_log::RequestLog;
+use crate::{
+    AdminStats, AuthResponse, BulkDeleteResult, Client, ContentFormat, Media, Page, Post, PostPage,
+    PostPreview, PublicProfile, ShareLink, UploadProgress, User,
+};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tonic::async_trait;
+
+/// Выполнить запрос через активный транспорт, а если соединение с сервером не удалось
+/// установить - повторить его через другой транспорт.
+///
+/// Аргументы вычисляются заново для каждой попытки, поэтому владеющие значения
+/// передаются клонами.
+macro_rules! with_fallback {
+    ($self:ident.$method:ident($($arg:expr),* $(,)?)) => {{
+        let active = $self.active.load(Ordering::Relaxed);
+
+        match $self.transports[active].1.$method($($arg),*).await {
+            Err(e) if e.is_connect_failure() => {
+                let other = 1 - active;
+                let result = $self.transports[other].1.$method($($arg),*).await;
+
+                if !matches!(&result, Err(e) if e.is_connect_failure()) {
+                    $self.switch(active, other);
+                }
+
+                result
+            }
+            result => result,
+        }
+    }};
+}
+
+/// Клиент с основным и запасным транспортом.
+///
+/// Запросы выполняются через активный транспорт (сначала - основной). Если он не может
+/// подключиться к серверу, запрос повторяется через другой транспорт, и при удачном
+/// подключении тот становится активным. Повторяются только запросы, которые не дошли до
+/// сервера, поэтому повтор не приводит к повторному выполнению операции.
+pub(crate) struct FallbackClient {
+    /// Названия и клиенты основного и запасного транспортов.
+    transports: [(&'static str, Box<dyn Client<Error = BlogClientError>>); 2],
+
+    /// Индекс активного транспорта.
+    active: AtomicUsize,
+
+    /// Журналирование переключений транспорта.
+    #[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+    log: RequestLog,
+}
+
+impl FallbackClient {
+    /// Создать клиент из основного и запасного транспортов.
+    pub(crate) fn new(
+        primary: Box<dyn Client<Error = BlogClientError>>,
+        fallback: Box<dyn Client<Error = BlogClientError>>,
+        log: RequestLog,
+    ) -> Self {
+        Self {
+            transports: [
+                (primary.transport(), primary),
+                (fallback.transport(), fallback),
+            ],
+            active: AtomicUsize::new(0),
+            log,
+        }
+    }
+
+    /// Сделать транспорт `to` активным, если активным все еще остается `from`.
+    fn switch(&self, from: usize, to: usize) {
+        if self
+            .active
+            .compare_exchange(from, to, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok()
+        {
+            #[cfg(feature = "tracing")]
+            if self.log.enabled() {
+                self.log
+                    .fallback(self.transports[from].0, self.transports[to].0);
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Client for FallbackClient {
+    type Error = BlogClientError;
+
+    /// Название активного транспорта.
+    fn transport(&self) -> &'static str {
+        self.transports[self.active.load(Ordering::Relaxed)].0
+    }
+
+    /// Регистрация нового пользователя.
+    async fn register(
+        &self,
+        username: &str,
+        email: &str,
+        password: &str,
+        invite_code: Option<&str>,
+    ) -> Result<AuthResponse, Self::Error> {
+        with_fallback!(self.register(username, email, password, invite_code))
+    }
+
+    /// Авторизация пользователя.
+    async fn login(&self, username: &str, password: &str) -> Result<AuthResponse, Self::Error> {
+        with_fallback!(self.login(username, password))
+    }
+
+    /// Получить публичный профиль пользователя по имени пользователя.
+    async fn get_user(&self, username: &str) -> Result<PublicProfile, Self::Error> {
+        with_fallback!(self.get_user(username))
+    }
+
+    /// Получить информацию о пользователе, которому выдан токен.
+    async fn get_current_user(&self, token: &str) -> Result<User, Self::Error> {
+        with_fallback!(self.get_current_user(token))
+    }
+
+    /// Изменить профиль пользователя.
+    async fn update_profile(
+        &self,
+        token: &str,
+        email: Option<&str>,
+        remove_avatar: bool,
+    ) -> Result<User, Self::Error> {
+        with_fallback!(self.update_profile(token, email, remove_avatar))
+    }
+
+    /// Создать новый пост.
+    async fn create_post(
+        &self,
+        token: &str,
+        title: &str,
+        content: &str,
+        is_private: bool,
+        content_format: Option<ContentFormat>,
+    ) -> Result<Post, Self::Error> {
+        with_fallback!(self.create_post(token, title, content, is_private, content_format))
+    }
+
+    /// Получить пост по идентификатору.
+    async fn get_post(&self, id: i64) -> Result<Post, Self::Error> {
+        with_fallback!(self.get_post(id))
+    }
+
+    /// Получить посты по списку идентификаторов.
+    async fn get_posts_by_ids(&self, ids: &[i64]) -> Result<Vec<Post>, Self::Error> {
+        with_fallback!(self.get_posts_by_ids(ids))
+    }
+
+    /// Найти посты по поисковому запросу.
+    async fn search_posts(
+        &self,
+        query: &str,
+        author_id: Option<i64>,
+        page: Page,
+    ) -> Result<Vec<PostPreview>, Self::Error> {
+        with_fallback!(self.search_posts(query, author_id, page))
+    }
+
+    /// Получить список постов с пагинацией.
+    async fn get_posts(&self, page: Page, archived: Option<bool>) -> Result<PostPage, Self::Error> {
+        with_fallback!(self.get_posts(page, archived))
+    }
+
+    /// Получить список сокращенных представлений постов.
+    async fn get_post_previews(
+        &self,
+        page: Page,
+        fields: &[&str],
+        archived: Option<bool>,
+    ) -> Result<PostPage, Self::Error> {
+        with_fallback!(self.get_post_previews(page, fields, archived))
+    }
+
+    /// Обновить существующий пост.
+    async fn update_post(
+        &self,
+        token: &str,
+        id: i64,
+        title: Option<String>,
+        content: Option<String>,
+        content_format: Option<ContentFormat>,
+        expected_version: Option<i64>,
+    ) -> Result<Post, Self::Error> {
+        with_fallback!(self.update_post(
+            token,
+            id,
+            title.clone(),
+            content.clone(),
+            content_format,
+            expected_version,
+        ))
+    }
+
+    /// Удалить пост.
+    async fn delete_post(&self, token: &str, id: i64) -> Result<(), Self::Error> {
+        with_fallback!(self.delete_post(token, id))
+    }
+
+    /// Удалить несколько постов в одной транзакции.
+    async fn bulk_delete(
+        &self,
+        token: &str,
+        ids: &[i64],
+    ) -> Result<Vec<BulkDeleteResult>, Self::Error> {
+        with_fallback!(self.bulk_delete(token, ids))
+    }
+
+    /// Перевести пост в архив или вернуть его из архива.
+    async fn set_archived(
+        &self,
+        token: &str,
+        id: i64,
+        archived: bool,
+    ) -> Result<Post, Self::Error> {
+        with_fallback!(self.set_archived(token, id, archived))
+    }
+
+    /// Добавить соавтора поста.
+    async fn add_post_author(
+        &self,
+        token: &str,
+        id: i64,
+        user_id: i64,
+    ) -> Result<Post, Self::Error> {
+        with_fallback!(self.add_post_author(token, id, user_id))
+    }
+
+    /// Создать ссылку для совместного доступа к посту.
+    async fn create_share_link(
+        &self,
+        token: &str,
+        id: i64,
+        ttl_seconds: Option<u64>,
+    ) -> Result<ShareLink, Self::Error> {
+        with_fallback!(self.create_share_link(token, id, ttl_seconds))
+    }
+
+    /// Получить пост по токену ссылки для совместного доступа.
+    async fn get_shared_post(&self, share_token: &str) -> Result<Post, Self::Error> {
+        with_fallback!(self.get_shared_post(share_token))
+    }
+
+    /// Установить обложку поста.
+    async fn set_cover_image(
+        &self,
+        token: &str,
+        id: i64,
+        cover_image_url: &str,
+    ) -> Result<Post, Self::Error> {
+        with_fallback!(self.set_cover_image(token, id, cover_image_url))
+    }
+
+    /// Загрузить изображение.
+    async fn upload_media(
+        &self,
+        token: &str,
+        content: Vec<u8>,
+        progress: UploadProgress,
+    ) -> Result<Media, Self::Error> {
+        with_fallback!(self.upload_media(token, content.clone(), progress.clone()))
+    }
+
+    /// Получить статистику блога.
+    async fn get_admin_stats(
+        &self,
+        token: &str,
+        days: Option<i32>,
+    ) -> Result<AdminStats, Self::Error> {
+        with_fallback!(self.get_admin_stats(token, days))
+    }
+}
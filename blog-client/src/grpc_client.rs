@@ -2,13 +2,34 @@
 
 use crate::blog_grpc::blog_service_client::BlogServiceClient;
 use crate::blog_grpc::{
-    CreatePostRequest, CreateUserRequest, DeletePostRequest, GetPostRequest, GetPostsRequest,
-    LoginUserRequest, UpdatePostRequest,
+    AddPostAuthorRequest, ArchivePostRequest, BulkDeletePostsRequest, CreatePostRequest,
+    CreateShareLinkRequest, CreateUserRequest, DeletePostRequest, GetAdminStatsRequest,
+    GetCurrentUserRequest, GetPostRequest, GetPostsByIdsRequest, GetPostsRequest,
+    GetSharedPostRequest, GetUserRequest, LoginUserRequest, SearchPostsRequest, UpdatePostRequest,
+    UpdateProfileRequest, UploadMediaRequest,
 };
 use crate::error::BlogClientError;
-use crate::{AuthResponse, Client, Post};
-use std::net::SocketAddr;
-use tonic::{Request, async_trait};
+use crate::trace::{TRACEPARENT, TraceContext};
+use crate::{
+    AdminStats, AuthResponse, BulkDeleteResult, Client, ContentFormat, DEFAULT_LIST_FIELDS,
+    GrpcOptions, Media, Page, Post, PostPage, PostPreview, PublicProfile, ServerAddr, ShareLink,
+    UploadChunks, UploadProgress, User,
+};
+use hyper_util::rt::TokioIo;
+use std::io;
+use tokio::net::{TcpStream, UnixStream};
+use tokio::time::Instant;
+use tokio_stream::StreamExt;
+use tonic::service::interceptor::InterceptedService;
+use tonic::transport::{Channel, Endpoint, Uri};
+use tonic::{Request, Status, async_trait};
+use tower::service_fn;
+
+/// Ключ метаданных с идентификатором ранее созданного поста в ответе на создание дубликата.
+const DUPLICATE_POST_ID_METADATA: &str = "duplicate-post-id";
+
+/// Перехватчик вызовов, добавляющий контекст трассировки в метаданные.
+type TraceInterceptor = fn(Request<()>) -> Result<Request<()>, Status>;
 
 /// gRPC-клиент для взаимодействия с сервисом блога.
 #[derive(Clone)]
@@ -17,44 +38,179 @@ pub(crate) struct GrpcClient {
     #[allow(dead_code)]
     addr: String,
     /// Внутренний gRPC-клиент для отправки запросов.
-    inner: BlogServiceClient<tonic::transport::Channel>,
+    inner: BlogServiceClient<InterceptedService<Channel, TraceInterceptor>>,
 }
 
 impl GrpcClient {
-    /// Создать новый экземпляр gRPC-клиента и подключиться к серверу.
-    pub(crate) async fn new(addr: SocketAddr) -> Result<Self, BlogClientError> {
-        let addr = format!("http://{addr}");
-        let inner = BlogServiceClient::connect(addr.clone()).await?;
+    /// Создать новый экземпляр gRPC-клиента.
+    ///
+    /// Соединение устанавливается при первом запросе, поэтому клиент можно создать
+    /// до запуска сервера. При обрыве соединение восстанавливается так же - с
+    /// повторными попытками в пределах [`GrpcOptions::connect_timeout`].
+    pub(crate) async fn new(
+        addr: ServerAddr,
+        options: GrpcOptions,
+    ) -> Result<Self, BlogClientError> {
+        // При подключении через Unix-сокет адрес конечной точки используется только
+        // в заголовках HTTP/2, а соединение устанавливается с сокетом.
+        let endpoint = match &addr {
+            ServerAddr::Tcp(addr) => Endpoint::from_shared(format!("http://{addr}"))?,
+            ServerAddr::Unix(_) => Endpoint::from_static("http://localhost"),
+        };
+
+        let endpoint = match options.keepalive_interval {
+            Some(interval) => endpoint
+                .http2_keep_alive_interval(interval)
+                .keep_alive_timeout(options.keepalive_timeout)
+                .keep_alive_while_idle(true),
+            None => endpoint,
+        };
+
+        let channel = match &addr {
+            ServerAddr::Tcp(addr) => {
+                let addr = *addr;
+
+                endpoint.connect_with_connector_lazy(service_fn(move |_: Uri| {
+                    let options = options.clone();
+                    async move {
+                        let stream =
+                            connect_with_backoff(&options, || TcpStream::connect(addr)).await?;
+                        stream.set_nodelay(true)?;
+
+                        Ok::<_, io::Error>(TokioIo::new(stream))
+                    }
+                }))
+            }
+            ServerAddr::Unix(path) => {
+                let path = path.clone();
+
+                endpoint.connect_with_connector_lazy(service_fn(move |_: Uri| {
+                    let (path, options) = (path.clone(), options.clone());
+                    async move {
+                        let stream =
+                            connect_with_backoff(&options, || UnixStream::connect(&path)).await?;
+
+                        Ok::<_, io::Error>(TokioIo::new(stream))
+                    }
+                }))
+            }
+        };
+
+        Ok(Self {
+            addr: addr.to_string(),
+            inner: BlogServiceClient::with_interceptor(channel, inject_trace_context),
+        })
+    }
+
+    /// Загрузить посты целиком вместе с общим количеством постов.
+    async fn fetch_posts(
+        &self,
+        page: Page,
+        archived: Option<bool>,
+    ) -> Result<(Vec<Post>, i64), BlogClientError> {
+        let payload = Request::new(GetPostsRequest {
+            limit: page.limit(),
+            offset: page.offset(),
+            include: vec![],
+            archived,
+            created_after: None,
+            created_before: None,
+            updated_since: None,
+            sort: None,
+        });
+
+        let response = self.inner.clone().get_posts(payload).await?.into_inner();
+
+        let mut posts = vec![];
+
+        for post in response.posts {
+            let p = post.try_into()?;
+            posts.push(p);
+        }
 
-        Ok(Self { addr, inner })
+        Ok((posts, response.total))
     }
 }
 
+/// Добавить в метаданные вызова `traceparent` с новым спаном текущей операции.
+fn inject_trace_context(mut request: Request<()>) -> Result<Request<()>, Status> {
+    if let Ok(value) = TraceContext::outgoing().to_string().parse() {
+        request.metadata_mut().insert(TRACEPARENT, value);
+    }
+
+    Ok(request)
+}
+
 /// Реализация клиентского интерфейса для gRPC.
+/// Установить соединение, повторяя неудачные попытки с экспоненциально растущей паузой
+/// (от [`GrpcOptions::initial_backoff`] до [`GrpcOptions::max_backoff`]), пока не истечет
+/// [`GrpcOptions::connect_timeout`].
+async fn connect_with_backoff<S, F, Fut>(options: &GrpcOptions, connect: F) -> io::Result<S>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = io::Result<S>>,
+{
+    let deadline = Instant::now() + options.connect_timeout;
+    let mut backoff = options.initial_backoff;
+
+    loop {
+        match tokio::time::timeout_at(deadline, connect()).await {
+            Ok(Ok(stream)) => return Ok(stream),
+            Ok(Err(e)) if Instant::now() + backoff >= deadline => return Err(e),
+            Ok(Err(_)) => {
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(options.max_backoff);
+            }
+            Err(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    "Истекло время ожидания подключения к серверу",
+                ));
+            }
+        }
+    }
+}
+
 #[async_trait]
 impl Client for GrpcClient {
     type Error = BlogClientError;
 
+    /// Название транспорта.
+    fn transport(&self) -> &'static str {
+        "grpc"
+    }
+
     /// Регистрация нового пользователя.
     async fn register(
-        &mut self,
+        &self,
         username: &str,
         email: &str,
         password: &str,
+        invite_code: Option<&str>,
     ) -> Result<AuthResponse, Self::Error> {
         let payload = Request::new(CreateUserRequest {
             username: username.to_string(),
             email: email.to_string(),
             password: password.to_string(),
+            invite_code: invite_code.map(str::to_string),
         });
 
-        let response = self.inner.register(payload).await?.into_inner();
+        let response = self
+            .inner
+            .clone()
+            .register(payload)
+            .await
+            .map_err(|status| match status.code() {
+                tonic::Code::PermissionDenied => BlogClientError::RegistrationClosed,
+                _ => BlogClientError::GrpcStatus(status),
+            })?
+            .into_inner();
 
         Ok(response.try_into()?)
     }
 
     /// Авторизация пользователя.
-    async fn login(&mut self, username: &str, password: &str) -> Result<AuthResponse, Self::Error> {
+    async fn login(&self, username: &str, password: &str) -> Result<AuthResponse, Self::Error> {
         let payload = Request::new(LoginUserRequest {
             username: username.to_string(),
             password: password.to_string(),
@@ -62,6 +218,7 @@ impl Client for GrpcClient {
 
         let response = self
             .inner
+            .clone()
             .login(payload)
             .await
             .map_err(|status| {
@@ -77,16 +234,100 @@ impl Client for GrpcClient {
         Ok(response.try_into()?)
     }
 
+    /// Получить публичный профиль пользователя по имени пользователя.
+    async fn get_user(&self, username: &str) -> Result<PublicProfile, Self::Error> {
+        let payload = Request::new(GetUserRequest {
+            username: username.to_string(),
+        });
+
+        let response = self
+            .inner
+            .clone()
+            .get_user(payload)
+            .await
+            .map_err(check_user_err)?
+            .into_inner();
+
+        response
+            .profile
+            .ok_or(BlogClientError::UserNotFound)?
+            .try_into()
+    }
+
+    /// Получить информацию о пользователе, которому выдан токен.
+    async fn get_current_user(&self, token: &str) -> Result<User, Self::Error> {
+        let mut payload = Request::new(GetCurrentUserRequest {});
+
+        payload.metadata_mut().insert(
+            "authorization",
+            format!("Bearer {token}")
+                .parse()
+                .map_err(|_| BlogClientError::InvalidToken)?,
+        );
+
+        let response = self
+            .inner
+            .clone()
+            .get_current_user(payload)
+            .await
+            .map_err(check_user_err)?
+            .into_inner();
+
+        response
+            .user
+            .ok_or(BlogClientError::UserNotFound)?
+            .try_into()
+    }
+
+    /// Изменить профиль пользователя.
+    async fn update_profile(
+        &self,
+        token: &str,
+        email: Option<&str>,
+        remove_avatar: bool,
+    ) -> Result<User, Self::Error> {
+        let mut payload = Request::new(UpdateProfileRequest {
+            email: email.map(str::to_string),
+            remove_avatar,
+        });
+
+        payload.metadata_mut().insert(
+            "authorization",
+            format!("Bearer {token}")
+                .parse()
+                .map_err(|_| BlogClientError::InvalidToken)?,
+        );
+
+        let response = self
+            .inner
+            .clone()
+            .update_profile(payload)
+            .await
+            .map_err(check_user_err)?
+            .into_inner();
+
+        response
+            .user
+            .ok_or(BlogClientError::UserNotFound)?
+            .try_into()
+    }
+
     /// Создать новый пост.
     async fn create_post(
-        &mut self,
+        &self,
         token: &str,
         title: &str,
         content: &str,
+        is_private: bool,
+        content_format: Option<ContentFormat>,
     ) -> Result<Post, Self::Error> {
         let mut payload = Request::new(CreatePostRequest {
             title: title.to_string(),
             content: content.to_string(),
+            excerpt: None,
+            cover_image_url: None,
+            is_private,
+            content_format: content_format.map(|format| format.to_string()),
         });
 
         payload.metadata_mut().insert(
@@ -98,9 +339,10 @@ impl Client for GrpcClient {
 
         let response = self
             .inner
+            .clone()
             .create_post(payload)
             .await
-            .map_err(check_post_auth_err)?
+            .map_err(check_create_post_err)?
             .into_inner();
 
         let post = response
@@ -112,11 +354,15 @@ impl Client for GrpcClient {
     }
 
     /// Получить пост по идентификатору.
-    async fn get_post(&mut self, id: i64) -> Result<Post, Self::Error> {
-        let payload = Request::new(GetPostRequest { id });
+    async fn get_post(&self, id: i64) -> Result<Post, Self::Error> {
+        let payload = Request::new(GetPostRequest {
+            id,
+            include: vec![],
+        });
 
         let response = self
             .inner
+            .clone()
             .get_post(payload)
             .await
             .map_err(|status| {
@@ -136,31 +382,104 @@ impl Client for GrpcClient {
         Ok(post)
     }
 
-    /// Получить список постов с пагинацией.
-    async fn get_posts(&mut self, limit: i64, offset: i64) -> Result<Vec<Post>, Self::Error> {
-        let payload = Request::new(GetPostsRequest { limit, offset });
+    /// Получить посты по списку идентификаторов.
+    async fn get_posts_by_ids(&self, ids: &[i64]) -> Result<Vec<Post>, Self::Error> {
+        let payload = Request::new(GetPostsByIdsRequest { ids: ids.to_vec() });
 
-        let response = self.inner.get_posts(payload).await?.into_inner();
+        let response = self
+            .inner
+            .clone()
+            .get_posts_by_ids(payload)
+            .await
+            .map_err(|status| match status.code() {
+                tonic::Code::InvalidArgument => BlogClientError::InvalidQuery,
+                _ => BlogClientError::GrpcStatus(status),
+            })?
+            .into_inner();
 
         let mut posts = vec![];
 
         for post in response.posts {
-            let p = post.try_into()?;
-            posts.push(p);
+            posts.push(post.try_into()?);
         }
 
         Ok(posts)
     }
 
+    /// Найти посты по поисковому запросу.
+    async fn search_posts(
+        &self,
+        query: &str,
+        author_id: Option<i64>,
+        page: Page,
+    ) -> Result<Vec<PostPreview>, Self::Error> {
+        let payload = Request::new(SearchPostsRequest {
+            query: query.to_string(),
+            author_id,
+            limit: page.limit(),
+            offset: page.offset(),
+        });
+
+        let response = self.inner.clone().search_posts(payload).await?.into_inner();
+
+        let mut posts = vec![];
+
+        for post in response.posts {
+            posts.push(PostPreview::project(post.try_into()?, DEFAULT_LIST_FIELDS));
+        }
+
+        Ok(posts)
+    }
+
+    /// Получить список постов с пагинацией.
+    async fn get_posts(&self, page: Page, archived: Option<bool>) -> Result<PostPage, Self::Error> {
+        self.get_post_previews(page, DEFAULT_LIST_FIELDS, archived)
+            .await
+    }
+
+    /// Получить список сокращенных представлений постов.
+    ///
+    /// gRPC API не поддерживает выборку полей, поэтому посты загружаются целиком
+    /// и сокращаются на стороне клиента.
+    async fn get_post_previews(
+        &self,
+        page: Page,
+        fields: &[&str],
+        archived: Option<bool>,
+    ) -> Result<PostPage, Self::Error> {
+        let (posts, total) = self.fetch_posts(page, archived).await?;
+
+        Ok(PostPage {
+            posts: posts
+                .into_iter()
+                .map(|post| PostPreview::project(post, fields))
+                .collect(),
+            total,
+            limit: page.limit(),
+            offset: page.offset(),
+        })
+    }
+
     /// Обновить существующий пост.
     async fn update_post(
-        &mut self,
+        &self,
         token: &str,
         id: i64,
         title: Option<String>,
         content: Option<String>,
+        content_format: Option<ContentFormat>,
+        expected_version: Option<i64>,
     ) -> Result<Post, Self::Error> {
-        let mut payload = Request::new(UpdatePostRequest { id, title, content });
+        let mut payload = Request::new(UpdatePostRequest {
+            id,
+            title,
+            content,
+            expected_version,
+            excerpt: None,
+            cover_image_url: None,
+            is_private: None,
+            content_format: content_format.map(|format| format.to_string()),
+        });
 
         payload.metadata_mut().insert(
             "authorization",
@@ -171,6 +490,7 @@ impl Client for GrpcClient {
 
         let response = self
             .inner
+            .clone()
             .update_post(payload)
             .await
             .map_err(check_post_auth_err)?
@@ -185,7 +505,7 @@ impl Client for GrpcClient {
     }
 
     /// Удалить пост.
-    async fn delete_post(&mut self, token: &str, id: i64) -> Result<(), Self::Error> {
+    async fn delete_post(&self, token: &str, id: i64) -> Result<(), Self::Error> {
         let mut payload = Request::new(DeletePostRequest { id });
 
         payload.metadata_mut().insert(
@@ -196,6 +516,7 @@ impl Client for GrpcClient {
         );
 
         self.inner
+            .clone()
             .delete_post(payload)
             .await
             .map_err(check_post_auth_err)?
@@ -203,6 +524,295 @@ impl Client for GrpcClient {
 
         Ok(())
     }
+
+    /// Удалить несколько постов в одной транзакции.
+    async fn bulk_delete(
+        &self,
+        token: &str,
+        ids: &[i64],
+    ) -> Result<Vec<BulkDeleteResult>, Self::Error> {
+        let mut payload = Request::new(BulkDeletePostsRequest { ids: ids.to_vec() });
+
+        payload.metadata_mut().insert(
+            "authorization",
+            format!("Bearer {token}")
+                .parse()
+                .map_err(|_| BlogClientError::InvalidToken)?,
+        );
+
+        let response = self
+            .inner
+            .clone()
+            .bulk_delete_posts(payload)
+            .await
+            .map_err(|status| match status.code() {
+                tonic::Code::InvalidArgument => BlogClientError::InvalidQuery,
+                _ => check_post_auth_err(status),
+            })?
+            .into_inner();
+
+        Ok(response.results.into_iter().map(Into::into).collect())
+    }
+
+    /// Перевести пост в архив или вернуть его из архива.
+    async fn set_archived(
+        &self,
+        token: &str,
+        id: i64,
+        archived: bool,
+    ) -> Result<Post, Self::Error> {
+        let mut payload = Request::new(ArchivePostRequest { id });
+
+        payload.metadata_mut().insert(
+            "authorization",
+            format!("Bearer {token}")
+                .parse()
+                .map_err(|_| BlogClientError::InvalidToken)?,
+        );
+
+        let response = if archived {
+            self.inner.clone().archive_post(payload).await
+        } else {
+            self.inner.clone().unarchive_post(payload).await
+        }
+        .map_err(check_post_auth_err)?
+        .into_inner();
+
+        let post = response
+            .post
+            .ok_or(BlogClientError::PostNotFound)?
+            .try_into()?;
+
+        Ok(post)
+    }
+
+    /// Добавить соавтора поста.
+    async fn add_post_author(
+        &self,
+        token: &str,
+        id: i64,
+        user_id: i64,
+    ) -> Result<Post, Self::Error> {
+        let mut payload = Request::new(AddPostAuthorRequest { id, user_id });
+
+        payload.metadata_mut().insert(
+            "authorization",
+            format!("Bearer {token}")
+                .parse()
+                .map_err(|_| BlogClientError::InvalidToken)?,
+        );
+
+        let response = self
+            .inner
+            .clone()
+            .add_post_author(payload)
+            .await
+            .map_err(check_post_auth_err)?
+            .into_inner();
+
+        let post = response
+            .post
+            .ok_or(BlogClientError::PostNotFound)?
+            .try_into()?;
+
+        Ok(post)
+    }
+
+    /// Создать ссылку для совместного доступа к посту.
+    async fn create_share_link(
+        &self,
+        token: &str,
+        id: i64,
+        ttl_seconds: Option<u64>,
+    ) -> Result<ShareLink, Self::Error> {
+        let mut payload = Request::new(CreateShareLinkRequest { id, ttl_seconds });
+
+        payload.metadata_mut().insert(
+            "authorization",
+            format!("Bearer {token}")
+                .parse()
+                .map_err(|_| BlogClientError::InvalidToken)?,
+        );
+
+        let link = self
+            .inner
+            .clone()
+            .create_share_link(payload)
+            .await
+            .map_err(check_post_auth_err)?
+            .into_inner()
+            .try_into()?;
+
+        Ok(link)
+    }
+
+    /// Получить пост по токену ссылки для совместного доступа.
+    async fn get_shared_post(&self, share_token: &str) -> Result<Post, Self::Error> {
+        let payload = Request::new(GetSharedPostRequest {
+            token: share_token.to_string(),
+        });
+
+        let response = self
+            .inner
+            .clone()
+            .get_shared_post(payload)
+            .await
+            .map_err(check_post_auth_err)?
+            .into_inner();
+
+        let post = response
+            .post
+            .ok_or(BlogClientError::PostNotFound)?
+            .try_into()?;
+
+        Ok(post)
+    }
+
+    /// Установить обложку поста.
+    async fn set_cover_image(
+        &self,
+        token: &str,
+        id: i64,
+        cover_image_url: &str,
+    ) -> Result<Post, Self::Error> {
+        let mut payload = Request::new(UpdatePostRequest {
+            id,
+            title: None,
+            content: None,
+            expected_version: None,
+            excerpt: None,
+            cover_image_url: Some(cover_image_url.to_string()),
+            is_private: None,
+            content_format: None,
+        });
+
+        payload.metadata_mut().insert(
+            "authorization",
+            format!("Bearer {token}")
+                .parse()
+                .map_err(|_| BlogClientError::InvalidToken)?,
+        );
+
+        let response = self
+            .inner
+            .clone()
+            .update_post(payload)
+            .await
+            .map_err(check_post_auth_err)?
+            .into_inner();
+
+        let post = response
+            .post
+            .ok_or(BlogClientError::PostNotFound)?
+            .try_into()?;
+
+        Ok(post)
+    }
+
+    /// Загрузить изображение потоком фрагментов.
+    async fn upload_media(
+        &self,
+        token: &str,
+        content: Vec<u8>,
+        progress: UploadProgress,
+    ) -> Result<Media, Self::Error> {
+        let chunks = tokio_stream::iter(UploadChunks::new(content, progress))
+            .map(|chunk| UploadMediaRequest { chunk });
+
+        let mut payload = Request::new(chunks);
+
+        payload.metadata_mut().insert(
+            "authorization",
+            format!("Bearer {token}")
+                .parse()
+                .map_err(|_| BlogClientError::InvalidToken)?,
+        );
+
+        let media = self
+            .inner
+            .clone()
+            .upload_media(payload)
+            .await
+            .map_err(check_media_err)?
+            .into_inner()
+            .media
+            .ok_or(BlogClientError::Unexpected)?
+            .try_into()?;
+
+        Ok(media)
+    }
+
+    /// Получить статистику блога.
+    async fn get_admin_stats(
+        &self,
+        token: &str,
+        days: Option<i32>,
+    ) -> Result<AdminStats, Self::Error> {
+        let mut payload = Request::new(GetAdminStatsRequest { days });
+
+        payload.metadata_mut().insert(
+            "authorization",
+            format!("Bearer {token}")
+                .parse()
+                .map_err(|_| BlogClientError::InvalidToken)?,
+        );
+
+        let response = self
+            .inner
+            .clone()
+            .get_admin_stats(payload)
+            .await
+            .map_err(check_admin_err)?
+            .into_inner();
+
+        Ok(response.try_into()?)
+    }
+}
+
+/// Преобразовать ошибку gRPC при административном запросе в ошибку клиента.
+fn check_admin_err(status: tonic::Status) -> BlogClientError {
+    match status.code() {
+        tonic::Code::Unauthenticated => BlogClientError::UserUnauthorized,
+        tonic::Code::PermissionDenied => BlogClientError::AdminRequired,
+        tonic::Code::InvalidArgument => BlogClientError::InvalidQuery,
+        _ => BlogClientError::GrpcStatus(status),
+    }
+}
+
+/// Преобразовать ошибку gRPC при загрузке изображений в ошибку клиента.
+fn check_media_err(status: tonic::Status) -> BlogClientError {
+    match status.code() {
+        tonic::Code::Unauthenticated => BlogClientError::UserUnauthorized,
+        tonic::Code::PermissionDenied => BlogClientError::MediaUploadsDisabled,
+        tonic::Code::ResourceExhausted => BlogClientError::MediaTooLarge,
+        tonic::Code::InvalidArgument => BlogClientError::InvalidMedia,
+        _ => BlogClientError::GrpcStatus(status),
+    }
+}
+
+/// Преобразовать ошибку gRPC при работе с профилем пользователя в ошибку клиента.
+fn check_user_err(status: tonic::Status) -> BlogClientError {
+    match status.code() {
+        tonic::Code::Unauthenticated => BlogClientError::UserUnauthorized,
+        tonic::Code::NotFound => BlogClientError::UserNotFound,
+        tonic::Code::AlreadyExists => BlogClientError::UserAlreadyExists,
+        tonic::Code::InvalidArgument => BlogClientError::InvalidUser,
+        _ => BlogClientError::GrpcStatus(status),
+    }
+}
+
+/// Преобразовать ошибку gRPC при создании поста в ошибку клиента.
+fn check_create_post_err(status: tonic::Status) -> BlogClientError {
+    let duplicate = status
+        .metadata()
+        .get(DUPLICATE_POST_ID_METADATA)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok());
+
+    match (status.code(), duplicate) {
+        (tonic::Code::AlreadyExists, Some(post_id)) => BlogClientError::DuplicatePost(post_id),
+        _ => check_post_auth_err(status),
+    }
 }
 
 /// Преобразовать ошибку gRPC при работе с постами в ошибку клиента.
@@ -211,7 +821,36 @@ fn check_post_auth_err(status: tonic::Status) -> BlogClientError {
     match code {
         tonic::Code::Unauthenticated => BlogClientError::UserUnauthorized,
         tonic::Code::NotFound => BlogClientError::PostNotFound,
-        tonic::Code::InvalidArgument => BlogClientError::Forbidden,
+        tonic::Code::PermissionDenied => BlogClientError::Forbidden,
+        tonic::Code::Aborted => BlogClientError::Conflict,
+        tonic::Code::FailedPrecondition => BlogClientError::PostArchived,
+        tonic::Code::InvalidArgument => BlogClientError::InvalidPostContent,
         _ => BlogClientError::GrpcStatus(status),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_argument_is_invalid_post_content() {
+        let err = check_post_auth_err(tonic::Status::invalid_argument("title is empty"));
+
+        assert!(matches!(err, BlogClientError::InvalidPostContent));
+    }
+
+    #[test]
+    fn permission_denied_is_forbidden() {
+        let err = check_post_auth_err(tonic::Status::permission_denied("not an author"));
+
+        assert!(matches!(err, BlogClientError::Forbidden));
+    }
+
+    #[test]
+    fn create_post_validation_error_is_invalid_post_content() {
+        let err = check_create_post_err(tonic::Status::invalid_argument("title is empty"));
+
+        assert!(matches!(err, BlogClientError::InvalidPostContent));
+    }
+}
@@ -1,11 +1,54 @@
 //! HTTP-клиент для API сервиса блога.
 
 use crate::error::BlogClientError;
-use crate::{AuthResponse, Client, Post};
+use crate::rate_limit::RateLimit;
+use crate::request_log::RequestLog;
+use crate::trace::{TRACEPARENT, TraceContext};
+use crate::{
+    AdminStats, AuthResponse, BulkDeleteResult, Client, ContentFormat, Media, Page, Post, PostPage,
+    PostPreview, PublicProfile, ServerAddr, ShareLink, UploadChunks, UploadProgress, User,
+};
+use reqwest::header::{ACCEPT, CONTENT_LENGTH, CONTENT_TYPE, HeaderMap, HeaderValue};
+use reqwest::{Body, RequestBuilder, Response, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::net::SocketAddr;
+use std::time::Duration;
+use tokio_stream::StreamExt;
 use tonic::async_trait;
 
+/// MIME-тип MessagePack.
+const MSGPACK: &str = "application/msgpack";
+
+/// Максимальное количество повторов запроса, отклоненного из-за превышения лимита частоты.
+const MAX_RATE_LIMIT_RETRIES: u32 = 3;
+
+/// Минимальная пауза перед повтором.
+///
+/// Сервер сообщает время до снятия ограничения в целых секундах с округлением вниз,
+/// поэтому `Retry-After: 0` не означает, что запрос можно повторить сразу.
+const MIN_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Максимальная пауза перед повтором; при большем `Retry-After` запрос не повторяется.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// Тело ответа `409` на создание поста, совпадающего с недавним постом автора.
+#[derive(Debug, Deserialize)]
+struct DuplicatePostBody {
+    /// Идентификатор ранее созданного поста.
+    post_id: i64,
+}
+
+/// Формат тел запросов и ответов HTTP API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Encoding {
+    /// JSON.
+    Json,
+
+    /// MessagePack (компактнее JSON).
+    MessagePack,
+}
+
 /// HTTP-клиент для взаимодействия с сервисом блога.
 #[derive(Clone)]
 pub(crate) struct HttpClient {
@@ -13,66 +56,222 @@ pub(crate) struct HttpClient {
     addr: String,
     /// Внутренний HTTP-клиент для отправки запросов.
     inner: reqwest::Client,
+    /// Формат тел запросов и ответов.
+    encoding: Encoding,
+    /// Журналирование запросов.
+    log: RequestLog,
 }
 
 impl HttpClient {
     /// Создать новый экземпляр HTTP-клиента.
-    pub(crate) async fn new(addr: SocketAddr) -> Result<Self, BlogClientError> {
-        let inner = reqwest::Client::new();
+    pub(crate) async fn new(
+        addr: ServerAddr,
+        encoding: Encoding,
+        log: RequestLog,
+    ) -> Result<Self, BlogClientError> {
+        let mut headers = HeaderMap::new();
+
+        if encoding == Encoding::MessagePack {
+            headers.insert(ACCEPT, HeaderValue::from_static(MSGPACK));
+        }
+
+        let builder = reqwest::Client::builder().default_headers(headers);
+
+        // При подключении через Unix-сокет имя хоста в адресе запроса не используется.
+        let (addr, builder) = match addr {
+            ServerAddr::Tcp(addr) => (format!("http://{addr}"), builder),
+            ServerAddr::Unix(path) => ("http://localhost".to_string(), builder.unix_socket(path)),
+        };
 
         Ok(Self {
-            addr: format!("http://{addr}"),
-            inner,
+            addr,
+            inner: builder.build()?,
+            encoding,
+            log,
+        })
+    }
+}
+
+/// Сериализация тела запроса в выбранном формате.
+trait EncodeBody: Sized {
+    /// Добавить к запросу тело в указанном формате.
+    fn encode<T: Serialize + ?Sized>(
+        self,
+        encoding: Encoding,
+        payload: &T,
+    ) -> Result<Self, BlogClientError>;
+}
+
+impl EncodeBody for RequestBuilder {
+    fn encode<T: Serialize + ?Sized>(
+        self,
+        encoding: Encoding,
+        payload: &T,
+    ) -> Result<Self, BlogClientError> {
+        Ok(match encoding {
+            Encoding::Json => self.json(payload),
+            Encoding::MessagePack => self
+                .header(CONTENT_TYPE, MSGPACK)
+                .body(rmp_serde::to_vec_named(payload)?),
         })
     }
 }
 
+/// Десериализация тела ответа в выбранном формате.
+trait DecodeBody {
+    /// Прочитать тело ответа в указанном формате.
+    async fn decode<T: DeserializeOwned>(self, encoding: Encoding) -> Result<T, BlogClientError>;
+}
+
+impl DecodeBody for Response {
+    async fn decode<T: DeserializeOwned>(self, encoding: Encoding) -> Result<T, BlogClientError> {
+        Ok(match encoding {
+            Encoding::Json => self.json().await?,
+            Encoding::MessagePack => rmp_serde::from_slice(&self.bytes().await?)?,
+        })
+    }
+}
+
+/// Отправка запроса с повтором при превышении лимита частоты запросов.
+trait SendWithRetry {
+    /// Отправить запрос, повторяя его после паузы из `Retry-After`, пока сервер отвечает 429.
+    ///
+    /// Если попытки исчерпаны или сервер просит ждать дольше [`MAX_RETRY_DELAY`],
+    /// возвращается ошибка [`BlogClientError::RateLimited`]. Попытки записываются в `log`.
+    async fn send_with_retry(self, log: &RequestLog) -> Result<Response, BlogClientError>;
+}
+
+impl SendWithRetry for RequestBuilder {
+    async fn send_with_retry(self, log: &RequestLog) -> Result<Response, BlogClientError> {
+        let mut retries = 0;
+
+        loop {
+            // Запрос с потоковым телом нельзя повторить, поэтому он отправляется один раз.
+            let Some(request) = self.try_clone() else {
+                return send_logged(self.traced(), log, retries + 1).await;
+            };
+
+            let response = send_logged(request.traced(), log, retries + 1).await?;
+
+            if response.status() != StatusCode::TOO_MANY_REQUESTS {
+                return Ok(response);
+            }
+
+            let rate_limit = RateLimit::from_headers(response.headers());
+            let delay = rate_limit
+                .retry_after
+                .unwrap_or_default()
+                .max(MIN_RETRY_DELAY);
+
+            if retries >= MAX_RATE_LIMIT_RETRIES || delay > MAX_RETRY_DELAY {
+                return Err(BlogClientError::RateLimited(rate_limit));
+            }
+
+            #[cfg(feature = "tracing")]
+            if log.enabled()
+                && let Some(request) = self.try_clone().and_then(|r| r.build().ok())
+            {
+                log.retry(request.method(), request.url(), delay, retries + 2);
+            }
+
+            tokio::time::sleep(delay).await;
+            retries += 1;
+        }
+    }
+}
+
+/// Передача контекста трассировки серверу.
+trait Traced {
+    /// Добавить к запросу заголовок `traceparent` с новым спаном текущей операции.
+    fn traced(self) -> Self;
+}
+
+impl Traced for RequestBuilder {
+    fn traced(self) -> Self {
+        self.header(TRACEPARENT, TraceContext::outgoing().to_string())
+    }
+}
+
+/// Отправить запрос, записав его в журнал как попытку `attempt`, если журналирование включено.
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+async fn send_logged(
+    request: RequestBuilder,
+    log: &RequestLog,
+    attempt: u32,
+) -> Result<Response, BlogClientError> {
+    #[cfg(feature = "tracing")]
+    if log.enabled() {
+        let (client, request) = request.build_split();
+        let request = request?;
+        let (method, url) = (request.method().clone(), request.url().clone());
+
+        let started = std::time::Instant::now();
+        let response = client.execute(request).await?;
+        log.response(&method, &url, response.status(), started.elapsed(), attempt);
+
+        return Ok(response);
+    }
+
+    Ok(request.send().await?)
+}
+
 /// Реализация клиентского интерфейса для HTTP.
 #[async_trait]
 impl Client for HttpClient {
     type Error = BlogClientError;
 
+    /// Название транспорта.
+    fn transport(&self) -> &'static str {
+        match self.encoding {
+            Encoding::Json => "http",
+            Encoding::MessagePack => "http+msgpack",
+        }
+    }
+
     /// Регистрация нового пользователя.
     async fn register(
-        &mut self,
+        &self,
         username: &str,
         email: &str,
         password: &str,
+        invite_code: Option<&str>,
     ) -> Result<AuthResponse, Self::Error> {
-        let endpoint = format!("{}/api/auth/register", self.addr);
+        let endpoint = format!("{}/api/v1/auth/register", self.addr);
 
         let payload = serde_json::json!({
             "username": username,
             "email": email,
-            "password": password
+            "password": password,
+            "invite_code": invite_code
         });
 
         let response = self
             .inner
             .post(endpoint)
-            .json(&payload)
-            .send()
-            .await
-            .and_then(|resp| resp.error_for_status())
+            .encode(self.encoding, &payload)?
+            .send_with_retry(&self.log)
+            .await?
+            .error_for_status()
             .map_err(|err| match err.status() {
                 Some(status) => match status {
                     reqwest::StatusCode::BAD_REQUEST => {
                         BlogClientError::InvalidRegistrationCredentials
                     }
                     reqwest::StatusCode::CONFLICT => BlogClientError::UserAlreadyExists,
+                    reqwest::StatusCode::FORBIDDEN => BlogClientError::RegistrationClosed,
                     _ => BlogClientError::Http(err),
                 },
                 None => BlogClientError::Http(err),
             })?
-            .json::<AuthResponse>()
+            .decode::<AuthResponse>(self.encoding)
             .await?;
 
         Ok(response)
     }
 
     /// Авторизация пользователя.
-    async fn login(&mut self, username: &str, password: &str) -> Result<AuthResponse, Self::Error> {
-        let endpoint = format!("{}/api/auth/login", self.addr);
+    async fn login(&self, username: &str, password: &str) -> Result<AuthResponse, Self::Error> {
+        let endpoint = format!("{}/api/v1/auth/login", self.addr);
 
         let payload = serde_json::json!({
             "username": username,
@@ -82,11 +281,10 @@ impl Client for HttpClient {
         let response = self
             .inner
             .post(endpoint)
-            .header("Content-Type", "application/json")
-            .json(&payload)
-            .send()
-            .await
-            .and_then(|resp| resp.error_for_status())
+            .encode(self.encoding, &payload)?
+            .send_with_retry(&self.log)
+            .await?
+            .error_for_status()
             .map_err(|err| match err.status() {
                 Some(status) => match status {
                     reqwest::StatusCode::NOT_FOUND => BlogClientError::UserNotFound,
@@ -95,87 +293,240 @@ impl Client for HttpClient {
                 },
                 None => BlogClientError::Http(err),
             })?
-            .json::<AuthResponse>()
+            .decode::<AuthResponse>(self.encoding)
             .await?;
 
         Ok(response)
     }
 
+    /// Получить публичный профиль пользователя по имени пользователя.
+    async fn get_user(&self, username: &str) -> Result<PublicProfile, Self::Error> {
+        let endpoint = format!("{}/api/v1/users/by-name/{username}", self.addr);
+
+        let profile = self
+            .inner
+            .get(endpoint)
+            .send_with_retry(&self.log)
+            .await?
+            .error_for_status()
+            .map_err(check_user_err)?
+            .decode::<PublicProfile>(self.encoding)
+            .await?;
+
+        Ok(profile)
+    }
+
+    /// Получить информацию о пользователе, которому выдан токен.
+    async fn get_current_user(&self, token: &str) -> Result<User, Self::Error> {
+        let endpoint = format!("{}/api/v1/users/me", self.addr);
+
+        let user = self
+            .inner
+            .get(endpoint)
+            .header("Authorization", format!("Bearer {}", token))
+            .send_with_retry(&self.log)
+            .await?
+            .error_for_status()
+            .map_err(check_user_err)?
+            .decode::<User>(self.encoding)
+            .await?;
+
+        Ok(user)
+    }
+
+    /// Изменить профиль пользователя.
+    async fn update_profile(
+        &self,
+        token: &str,
+        email: Option<&str>,
+        remove_avatar: bool,
+    ) -> Result<User, Self::Error> {
+        let endpoint = format!("{}/api/v1/users/me", self.addr);
+
+        let payload = serde_json::json!({
+            "email": email,
+            "remove_avatar": remove_avatar,
+        });
+
+        let user = self
+            .inner
+            .patch(endpoint)
+            .header("Authorization", format!("Bearer {}", token))
+            .encode(self.encoding, &payload)?
+            .send_with_retry(&self.log)
+            .await?
+            .error_for_status()
+            .map_err(check_user_err)?
+            .decode::<User>(self.encoding)
+            .await?;
+
+        Ok(user)
+    }
+
     /// Создать новый пост.
     async fn create_post(
-        &mut self,
+        &self,
         token: &str,
         title: &str,
         content: &str,
+        is_private: bool,
+        content_format: Option<ContentFormat>,
     ) -> Result<Post, Self::Error> {
-        let endpoint = format!("{}/api/posts", self.addr);
+        let endpoint = format!("{}/api/v1/posts", self.addr);
 
         let payload = serde_json::json!({
             "title": title,
-            "content": content
+            "content": content,
+            "is_private": is_private,
+            "content_format": content_format.map(ContentFormat::as_str),
         });
 
-        let post = self
+        let response = self
             .inner
             .post(endpoint)
             .header("Authorization", format!("Bearer {}", token))
-            .json(&payload)
-            .send()
-            .await
-            .and_then(|resp| resp.error_for_status())
+            .encode(self.encoding, &payload)?
+            .send_with_retry(&self.log)
+            .await?;
+
+        if response.status() == StatusCode::CONFLICT {
+            let body = response.json::<DuplicatePostBody>().await?;
+            return Err(BlogClientError::DuplicatePost(body.post_id));
+        }
+
+        let post = response
+            .error_for_status()
             .map_err(check_post_auth_err)?
-            .json::<Post>()
+            .decode::<Post>(self.encoding)
             .await?;
 
         Ok(post)
     }
 
     /// Получить пост по идентификатору.
-    async fn get_post(&mut self, id: i64) -> Result<Post, Self::Error> {
-        let endpoint = format!("{}/api/posts/{id}", self.addr);
+    async fn get_post(&self, id: i64) -> Result<Post, Self::Error> {
+        let endpoint = format!("{}/api/v1/posts/{id}", self.addr);
 
         let post = self
             .inner
             .get(endpoint)
-            .send()
-            .await
-            .and_then(|resp| resp.error_for_status())
+            .send_with_retry(&self.log)
+            .await?
+            .error_for_status()
             .map_err(|err| match err.status() {
                 Some(reqwest::StatusCode::NOT_FOUND) => BlogClientError::PostNotFound,
                 _ => BlogClientError::Http(err),
             })?
-            .json::<Post>()
+            .decode::<Post>(self.encoding)
             .await?;
 
         Ok(post)
     }
 
-    /// Получить список постов с пагинацией.
-    async fn get_posts(&mut self, limit: i64, offset: i64) -> Result<Vec<Post>, Self::Error> {
-        let endpoint = format!("{}/api/posts", self.addr);
+    /// Получить посты по списку идентификаторов.
+    async fn get_posts_by_ids(&self, ids: &[i64]) -> Result<Vec<Post>, Self::Error> {
+        let endpoint = format!("{}/api/v1/posts/batch", self.addr);
+
+        let payload = serde_json::json!({
+            "ids": ids,
+        });
+
+        let posts = self
+            .inner
+            .post(endpoint)
+            .encode(self.encoding, &payload)?
+            .send_with_retry(&self.log)
+            .await?
+            .error_for_status()
+            .map_err(|err| match err.status() {
+                Some(reqwest::StatusCode::BAD_REQUEST) => BlogClientError::InvalidQuery,
+                _ => BlogClientError::Http(err),
+            })?
+            .decode::<Vec<Post>>(self.encoding)
+            .await?;
+
+        Ok(posts)
+    }
+
+    /// Найти посты по поисковому запросу.
+    async fn search_posts(
+        &self,
+        query: &str,
+        author_id: Option<i64>,
+        page: Page,
+    ) -> Result<Vec<PostPreview>, Self::Error> {
+        let endpoint = format!("{}/api/v1/posts/search", self.addr);
 
         let posts = self
             .inner
             .get(endpoint)
-            .query(&[("limit", limit), ("offset", offset)])
-            .send()
-            .await
-            .and_then(|resp| resp.error_for_status())?
-            .json::<Vec<Post>>()
+            .query(&[("q", query)])
+            .query(&[("author_id", author_id)])
+            .query(&[("limit", page.limit()), ("offset", page.offset())])
+            .send_with_retry(&self.log)
+            .await?
+            .error_for_status()?
+            .decode::<Vec<PostPreview>>(self.encoding)
             .await?;
 
         Ok(posts)
     }
 
+    /// Получить список постов с пагинацией.
+    async fn get_posts(&self, page: Page, archived: Option<bool>) -> Result<PostPage, Self::Error> {
+        let endpoint = format!("{}/api/v1/posts", self.addr);
+
+        let page = self
+            .inner
+            .get(endpoint)
+            .query(&[("limit", page.limit()), ("offset", page.offset())])
+            .query(&[("archived", archived)])
+            .send_with_retry(&self.log)
+            .await?
+            .error_for_status()?
+            .decode::<PostPage>(self.encoding)
+            .await?;
+
+        Ok(page)
+    }
+
+    /// Получить список сокращенных представлений постов.
+    ///
+    /// Выборка полей выполняется на стороне сервера (`?fields=...`).
+    async fn get_post_previews(
+        &self,
+        page: Page,
+        fields: &[&str],
+        archived: Option<bool>,
+    ) -> Result<PostPage, Self::Error> {
+        let endpoint = format!("{}/api/v1/posts", self.addr);
+
+        let page = self
+            .inner
+            .get(endpoint)
+            .query(&[("limit", page.limit()), ("offset", page.offset())])
+            .query(&[("fields", fields.join(","))])
+            .query(&[("archived", archived)])
+            .send_with_retry(&self.log)
+            .await?
+            .error_for_status()?
+            .decode::<PostPage>(self.encoding)
+            .await?;
+
+        Ok(page)
+    }
+
     /// Обновить существующий пост.
     async fn update_post(
-        &mut self,
+        &self,
         token: &str,
         id: i64,
         title: Option<String>,
         content: Option<String>,
+        content_format: Option<ContentFormat>,
+        expected_version: Option<i64>,
     ) -> Result<Post, Self::Error> {
-        let endpoint = format!("{}/api/posts/{id}", self.addr);
+        let endpoint = format!("{}/api/v1/posts/{id}", self.addr);
 
         let payload = {
             let mut payload = HashMap::new();
@@ -188,38 +539,295 @@ impl Client for HttpClient {
                 payload.insert("content", content);
             }
 
+            if let Some(content_format) = content_format {
+                payload.insert("content_format", content_format.to_string());
+            }
+
             serde_json::json!(payload)
         };
 
-        let post = self
+        let mut request = self
             .inner
             .put(endpoint)
-            .header("Authorization", format!("Bearer {}", token))
-            .json(&payload)
-            .send()
-            .await
-            .and_then(|resp| resp.error_for_status())
+            .header("Authorization", format!("Bearer {}", token));
+
+        if let Some(version) = expected_version {
+            request = request.header("If-Match", format!("\"{version}\""));
+        }
+
+        let post = request
+            .encode(self.encoding, &payload)?
+            .send_with_retry(&self.log)
+            .await?
+            .error_for_status()
             .map_err(check_post_auth_err)?
-            .json::<Post>()
+            .decode::<Post>(self.encoding)
             .await?;
 
         Ok(post)
     }
 
     /// Удалить пост.
-    async fn delete_post(&mut self, token: &str, id: i64) -> Result<(), Self::Error> {
-        let endpoint = format!("{}/api/posts/{id}", self.addr);
+    async fn delete_post(&self, token: &str, id: i64) -> Result<(), Self::Error> {
+        let endpoint = format!("{}/api/v1/posts/{id}", self.addr);
 
         self.inner
             .delete(endpoint)
             .header("Authorization", format!("Bearer {}", token))
-            .send()
-            .await
-            .and_then(|resp| resp.error_for_status())
+            .send_with_retry(&self.log)
+            .await?
+            .error_for_status()
             .map_err(check_post_auth_err)?;
 
         Ok(())
     }
+
+    /// Удалить несколько постов в одной транзакции.
+    async fn bulk_delete(
+        &self,
+        token: &str,
+        ids: &[i64],
+    ) -> Result<Vec<BulkDeleteResult>, Self::Error> {
+        let endpoint = format!("{}/api/v1/posts/bulk-delete", self.addr);
+
+        let payload = serde_json::json!({
+            "ids": ids,
+        });
+
+        let results = self
+            .inner
+            .post(endpoint)
+            .header("Authorization", format!("Bearer {}", token))
+            .encode(self.encoding, &payload)?
+            .send_with_retry(&self.log)
+            .await?
+            .error_for_status()
+            .map_err(|err| match err.status() {
+                Some(reqwest::StatusCode::UNAUTHORIZED) => BlogClientError::UserUnauthorized,
+                Some(reqwest::StatusCode::BAD_REQUEST) => BlogClientError::InvalidQuery,
+                _ => BlogClientError::Http(err),
+            })?
+            .decode::<Vec<BulkDeleteResult>>(self.encoding)
+            .await?;
+
+        Ok(results)
+    }
+
+    /// Перевести пост в архив или вернуть его из архива.
+    async fn set_archived(
+        &self,
+        token: &str,
+        id: i64,
+        archived: bool,
+    ) -> Result<Post, Self::Error> {
+        let action = if archived { "archive" } else { "unarchive" };
+        let endpoint = format!("{}/api/v1/posts/{id}/{action}", self.addr);
+
+        let post = self
+            .inner
+            .post(endpoint)
+            .header("Authorization", format!("Bearer {}", token))
+            .send_with_retry(&self.log)
+            .await?
+            .error_for_status()
+            .map_err(check_post_auth_err)?
+            .decode::<Post>(self.encoding)
+            .await?;
+
+        Ok(post)
+    }
+
+    /// Добавить соавтора поста.
+    async fn add_post_author(
+        &self,
+        token: &str,
+        id: i64,
+        user_id: i64,
+    ) -> Result<Post, Self::Error> {
+        let endpoint = format!("{}/api/v1/posts/{id}/authors", self.addr);
+
+        let post = self
+            .inner
+            .post(endpoint)
+            .header("Authorization", format!("Bearer {}", token))
+            .encode(self.encoding, &serde_json::json!({ "user_id": user_id }))?
+            .send_with_retry(&self.log)
+            .await?
+            .error_for_status()
+            .map_err(check_post_auth_err)?
+            .decode::<Post>(self.encoding)
+            .await?;
+
+        Ok(post)
+    }
+
+    /// Создать ссылку для совместного доступа к посту.
+    async fn create_share_link(
+        &self,
+        token: &str,
+        id: i64,
+        ttl_seconds: Option<u64>,
+    ) -> Result<ShareLink, Self::Error> {
+        let endpoint = format!("{}/api/v1/posts/{id}/share", self.addr);
+
+        let link = self
+            .inner
+            .post(endpoint)
+            .header("Authorization", format!("Bearer {}", token))
+            .encode(
+                self.encoding,
+                &serde_json::json!({ "ttl_seconds": ttl_seconds }),
+            )?
+            .send_with_retry(&self.log)
+            .await?
+            .error_for_status()
+            .map_err(check_post_auth_err)?
+            .decode::<ShareLink>(self.encoding)
+            .await?;
+
+        Ok(link)
+    }
+
+    /// Получить пост по токену ссылки для совместного доступа.
+    async fn get_shared_post(&self, share_token: &str) -> Result<Post, Self::Error> {
+        let endpoint = format!("{}/api/v1/posts/shared/{share_token}", self.addr);
+
+        let post = self
+            .inner
+            .get(endpoint)
+            .send_with_retry(&self.log)
+            .await?
+            .error_for_status()
+            .map_err(check_post_auth_err)?
+            .decode::<Post>(self.encoding)
+            .await?;
+
+        Ok(post)
+    }
+
+    /// Установить обложку поста.
+    async fn set_cover_image(
+        &self,
+        token: &str,
+        id: i64,
+        cover_image_url: &str,
+    ) -> Result<Post, Self::Error> {
+        let endpoint = format!("{}/api/v1/posts/{id}", self.addr);
+
+        let payload = serde_json::json!({ "cover_image_url": cover_image_url });
+
+        let post = self
+            .inner
+            .put(endpoint)
+            .header("Authorization", format!("Bearer {}", token))
+            .encode(self.encoding, &payload)?
+            .send_with_retry(&self.log)
+            .await?
+            .error_for_status()
+            .map_err(check_post_auth_err)?
+            .decode::<Post>(self.encoding)
+            .await?;
+
+        Ok(post)
+    }
+
+    /// Загрузить изображение потоковым телом запроса.
+    async fn upload_media(
+        &self,
+        token: &str,
+        content: Vec<u8>,
+        progress: UploadProgress,
+    ) -> Result<Media, Self::Error> {
+        let endpoint = format!("{}/api/v1/media", self.addr);
+        let content_length = content.len() as u64;
+
+        let chunks =
+            tokio_stream::iter(UploadChunks::new(content, progress)).map(Ok::<_, std::io::Error>);
+
+        let media = self
+            .inner
+            .post(endpoint)
+            .header("Authorization", format!("Bearer {}", token))
+            .header(CONTENT_TYPE, "application/octet-stream")
+            .header(CONTENT_LENGTH, content_length)
+            .body(Body::wrap_stream(chunks))
+            .send_with_retry(&self.log)
+            .await?
+            .error_for_status()
+            .map_err(check_media_err)?
+            .decode::<Media>(self.encoding)
+            .await?;
+
+        Ok(media)
+    }
+
+    /// Получить статистику блога.
+    async fn get_admin_stats(
+        &self,
+        token: &str,
+        days: Option<i32>,
+    ) -> Result<AdminStats, Self::Error> {
+        let endpoint = format!("{}/api/v1/admin/stats", self.addr);
+
+        let stats = self
+            .inner
+            .get(endpoint)
+            .header("Authorization", format!("Bearer {}", token))
+            .query(&[("days", days)])
+            .send_with_retry(&self.log)
+            .await?
+            .error_for_status()
+            .map_err(check_admin_err)?
+            .decode::<AdminStats>(self.encoding)
+            .await?;
+
+        Ok(stats)
+    }
+}
+
+/// Преобразовать ошибку HTTP при административном запросе в ошибку клиента.
+fn check_admin_err(err: reqwest::Error) -> BlogClientError {
+    match err.status() {
+        Some(status) => match status {
+            reqwest::StatusCode::UNAUTHORIZED => BlogClientError::UserUnauthorized,
+            reqwest::StatusCode::FORBIDDEN => BlogClientError::AdminRequired,
+            reqwest::StatusCode::BAD_REQUEST => BlogClientError::InvalidQuery,
+            _ => BlogClientError::Http(err),
+        },
+        None => BlogClientError::Http(err),
+    }
+}
+
+/// Преобразовать ошибку HTTP при загрузке изображений в ошибку клиента.
+fn check_media_err(err: reqwest::Error) -> BlogClientError {
+    match err.status() {
+        Some(status) => match status {
+            reqwest::StatusCode::UNAUTHORIZED => BlogClientError::UserUnauthorized,
+            reqwest::StatusCode::FORBIDDEN => BlogClientError::MediaUploadsDisabled,
+            reqwest::StatusCode::PAYLOAD_TOO_LARGE => BlogClientError::MediaTooLarge,
+            reqwest::StatusCode::UNSUPPORTED_MEDIA_TYPE
+            | reqwest::StatusCode::UNPROCESSABLE_ENTITY => BlogClientError::InvalidMedia,
+            _ => BlogClientError::Http(err),
+        },
+        None => BlogClientError::Http(err),
+    }
+}
+
+/// Преобразовать ошибку HTTP при работе с профилем пользователя в ошибку клиента.
+fn check_user_err(err: reqwest::Error) -> BlogClientError {
+    match err.status() {
+        Some(status) => match status {
+            reqwest::StatusCode::UNAUTHORIZED => BlogClientError::UserUnauthorized,
+            reqwest::StatusCode::FORBIDDEN => BlogClientError::Forbidden,
+            reqwest::StatusCode::NOT_FOUND => BlogClientError::UserNotFound,
+            reqwest::StatusCode::CONFLICT => BlogClientError::UserAlreadyExists,
+            reqwest::StatusCode::BAD_REQUEST | reqwest::StatusCode::UNPROCESSABLE_ENTITY => {
+                BlogClientError::InvalidUser
+            }
+            _ => BlogClientError::Http(err),
+        },
+        None => BlogClientError::Http(err),
+    }
 }
 
 /// Преобразовать ошибку HTTP при работе с постами в ошибку клиента.
@@ -229,6 +837,11 @@ fn check_post_auth_err(err: reqwest::Error) -> BlogClientError {
             reqwest::StatusCode::UNAUTHORIZED => BlogClientError::UserUnauthorized,
             reqwest::StatusCode::NOT_FOUND => BlogClientError::PostNotFound,
             reqwest::StatusCode::FORBIDDEN => BlogClientError::Forbidden,
+            reqwest::StatusCode::CONFLICT => BlogClientError::Conflict,
+            reqwest::StatusCode::LOCKED => BlogClientError::PostArchived,
+            reqwest::StatusCode::BAD_REQUEST
+            | reqwest::StatusCode::UNPROCESSABLE_ENTITY
+            | reqwest::StatusCode::PAYLOAD_TOO_LARGE => BlogClientError::InvalidPostContent,
             _ => BlogClientError::Http(err),
         },
         None => BlogClientError::Http(err),
@@ -3,14 +3,24 @@
 //! Предоставляет абстракцию для взаимодействия с серверной частью блога
 //! через различные транспортные протоколы (HTTP, gRPC).
 
-use chrono::{DateTime, Utc};
-use serde::Deserialize;
-use std::net::SocketAddr;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::net::{AddrParseError, SocketAddr};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::Duration;
 use tonic::async_trait;
 
 pub mod error;
+mod fallback;
 mod grpc_client;
 mod http_client;
+pub mod observer;
+pub mod rate_limit;
+mod request_log;
+pub mod trace;
 
 use error::BlogClientError;
 
@@ -18,13 +28,18 @@ mod blog_grpc {
     tonic::include_proto!("blog");
 }
 
+use crate::fallback::FallbackClient;
 use crate::grpc_client::GrpcClient;
-use crate::http_client::HttpClient;
+use crate::http_client::{Encoding, HttpClient};
+use crate::observer::{ClientObserver, Observers, RequestInfo};
+use crate::request_log::RequestLog;
+use crate::trace::TraceContext;
 
 /// Ответ сервера с JWT-токеном при авторизации.
 #[derive(Debug, Deserialize)]
 pub struct AuthResponse {
-    /// JWT-токен для последующих запросов.
+    /// JWT-токен для последующих запросов (пустой при авторизации через cookie).
+    #[serde(default)]
     pub token: String,
 
     /// Авторизованный пользователь.
@@ -59,11 +74,115 @@ impl TryFrom<blog_grpc::LoginUserResponse> for AuthResponse {
     }
 }
 
+/// Поля поста, возвращаемые сервером в списке постов по умолчанию.
+pub const DEFAULT_LIST_FIELDS: &[&str] = &[
+    "title",
+    "excerpt",
+    "cover_image_url",
+    "is_private",
+    "archived_at",
+    "author_id",
+    "created_at",
+    "updated_at",
+    "version",
+    "reading_time_minutes",
+];
+
+/// Адрес сервера блога.
+#[derive(Debug, Clone)]
+pub enum ServerAddr {
+    /// TCP-адрес.
+    Tcp(SocketAddr),
+
+    /// Путь к Unix-сокету (в строковом виде `unix:/path/to.sock`).
+    Unix(PathBuf),
+}
+
+impl From<SocketAddr> for ServerAddr {
+    fn from(addr: SocketAddr) -> Self {
+        Self::Tcp(addr)
+    }
+}
+
+impl FromStr for ServerAddr {
+    type Err = AddrParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix("unix:") {
+            Some(path) => Ok(Self::Unix(PathBuf::from(path))),
+            None => Ok(Self::Tcp(s.parse()?)),
+        }
+    }
+}
+
+impl fmt::Display for ServerAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp(addr) => write!(f, "{addr}"),
+            Self::Unix(path) => write!(f, "unix:{}", path.display()),
+        }
+    }
+}
+
 /// Протокол для взаимодействия.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Transport {
-    Http(SocketAddr),
-    Grpc(SocketAddr),
+    Http(ServerAddr),
+    /// HTTP с телами запросов и ответов в формате MessagePack вместо JSON.
+    HttpMsgpack(ServerAddr),
+    Grpc(ServerAddr),
+}
+
+/// Параметры подключения к серверу по gRPC.
+///
+/// Клиент подключается к серверу при первом запросе и восстанавливает соединение
+/// после обрыва. Неудачные попытки подключения повторяются с паузой, которая
+/// удваивается от `initial_backoff` до `max_backoff`, пока не истечет `connect_timeout`.
+#[derive(Debug, Clone)]
+pub struct GrpcOptions {
+    /// Максимальное время подключения к серверу с учетом повторных попыток.
+    pub connect_timeout: Duration,
+
+    /// Интервал HTTP/2 keepalive-пингов (`None` - пинги не отправляются).
+    pub keepalive_interval: Option<Duration>,
+
+    /// Время ожидания ответа на keepalive-пинг, после которого соединение считается разорванным.
+    pub keepalive_timeout: Duration,
+
+    /// Пауза перед первой повторной попыткой подключения.
+    pub initial_backoff: Duration,
+
+    /// Максимальная пауза между попытками подключения.
+    pub max_backoff: Duration,
+}
+
+impl Default for GrpcOptions {
+    fn default() -> Self {
+        Self {
+            connect_timeout: Duration::from_secs(10),
+            keepalive_interval: Some(Duration::from_secs(30)),
+            keepalive_timeout: Duration::from_secs(10),
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(2),
+        }
+    }
+}
+
+impl Transport {
+    /// Создать клиент, реализующий протокол.
+    async fn client(
+        self,
+        grpc: GrpcOptions,
+        log: RequestLog,
+    ) -> Result<Box<dyn Client<Error = BlogClientError>>, BlogClientError> {
+        Ok(match self {
+            Self::Http(addr) => Box::new(HttpClient::new(addr, Encoding::Json, log).await?),
+            Self::HttpMsgpack(addr) => {
+                Box::new(HttpClient::new(addr, Encoding::MessagePack, log).await?)
+            }
+            Self::Grpc(addr) => Box::new(GrpcClient::new(addr, grpc).await?),
+        })
+    }
 }
 
 /// Трейт для реализации клиентского взаимодействия.
@@ -71,160 +190,721 @@ pub enum Transport {
 /// Определяет минимальный набор операций, которые должны быть доступны
 /// для взаимодействия с сервером независимо от используемого транспорта.
 #[async_trait]
-pub trait Client {
+pub trait Client: Send + Sync {
     type Error;
 
+    /// Название протокола, передаваемое наблюдателям за запросами.
+    fn transport(&self) -> &'static str;
+
     /// Регистрация нового пользователя.
+    ///
+    /// Код приглашения требуется, если сервер разрешает регистрацию только по приглашениям.
     async fn register(
-        &mut self,
+        &self,
         username: &str,
         email: &str,
         password: &str,
+        invite_code: Option<&str>,
     ) -> Result<AuthResponse, Self::Error>;
 
     /// Авторизация пользователя.
-    async fn login(&mut self, username: &str, password: &str) -> Result<AuthResponse, Self::Error>;
+    async fn login(&self, username: &str, password: &str) -> Result<AuthResponse, Self::Error>;
+
+    /// Получить публичный профиль пользователя по имени пользователя.
+    async fn get_user(&self, username: &str) -> Result<PublicProfile, Self::Error>;
+
+    /// Получить информацию о пользователе, которому выдан токен.
+    async fn get_current_user(&self, token: &str) -> Result<User, Self::Error>;
+
+    /// Изменить профиль пользователя.
+    ///
+    /// Новый email-адрес вступает в силу после перехода по ссылке из письма, поэтому
+    /// в возвращаемом профиле остается прежний адрес.
+    async fn update_profile(
+        &self,
+        token: &str,
+        email: Option<&str>,
+        remove_avatar: bool,
+    ) -> Result<User, Self::Error>;
 
     /// Создать новый пост.
     async fn create_post(
-        &mut self,
+        &self,
         token: &str,
         title: &str,
         content: &str,
+        is_private: bool,
+        content_format: Option<ContentFormat>,
     ) -> Result<Post, Self::Error>;
 
     /// Получить пост по идентификатору.
-    async fn get_post(&mut self, id: i64) -> Result<Post, Self::Error>;
+    async fn get_post(&self, id: i64) -> Result<Post, Self::Error>;
+
+    /// Получить посты по списку идентификаторов за один запрос.
+    ///
+    /// Посты возвращаются в порядке запроса; отсутствующие посты пропускаются.
+    async fn get_posts_by_ids(&self, ids: &[i64]) -> Result<Vec<Post>, Self::Error>;
+
+    /// Найти посты по поисковому запросу (при указании `author_id` - только посты этого автора).
+    ///
+    /// Посты возвращаются в порядке релевантности и содержат поля [`DEFAULT_LIST_FIELDS`].
+    async fn search_posts(
+        &self,
+        query: &str,
+        author_id: Option<i64>,
+        page: Page,
+    ) -> Result<Vec<PostPreview>, Self::Error>;
 
     /// Получить список постов с пагинацией.
-    async fn get_posts(&mut self, limit: i64, offset: i64) -> Result<Vec<Post>, Self::Error>;
+    ///
+    /// Посты в списке содержат поля [`DEFAULT_LIST_FIELDS`] (без полного содержимого)
+    /// и возвращаются вместе с общим количеством постов.
+    /// При указании `archived` возвращаются только архивные либо только неархивные посты.
+    async fn get_posts(&self, page: Page, archived: Option<bool>) -> Result<PostPage, Self::Error>;
+
+    /// Получить список сокращенных представлений постов, содержащих только указанные поля.
+    async fn get_post_previews(
+        &self,
+        page: Page,
+        fields: &[&str],
+        archived: Option<bool>,
+    ) -> Result<PostPage, Self::Error>;
 
     /// Обновить существующий пост.
+    ///
+    /// При указании `expected_version` обновление отклоняется, если пост уже был изменен.
     async fn update_post(
-        &mut self,
+        &self,
         token: &str,
         id: i64,
         title: Option<String>,
         content: Option<String>,
+        content_format: Option<ContentFormat>,
+        expected_version: Option<i64>,
     ) -> Result<Post, Self::Error>;
 
     /// Удалить пост.
-    async fn delete_post(&mut self, token: &str, id: i64) -> Result<(), Self::Error>;
+    async fn delete_post(&self, token: &str, id: i64) -> Result<(), Self::Error>;
+
+    /// Удалить несколько постов в одной транзакции.
+    ///
+    /// Для каждого поста возвращается результат удаления в порядке запроса.
+    async fn bulk_delete(
+        &self,
+        token: &str,
+        ids: &[i64],
+    ) -> Result<Vec<BulkDeleteResult>, Self::Error>;
+
+    /// Перевести пост в архив (`archived = true`) или вернуть его из архива.
+    async fn set_archived(&self, token: &str, id: i64, archived: bool)
+    -> Result<Post, Self::Error>;
+
+    /// Добавить соавтора поста.
+    async fn add_post_author(
+        &self,
+        token: &str,
+        id: i64,
+        user_id: i64,
+    ) -> Result<Post, Self::Error>;
+
+    /// Создать ссылку для совместного доступа к посту.
+    async fn create_share_link(
+        &self,
+        token: &str,
+        id: i64,
+        ttl_seconds: Option<u64>,
+    ) -> Result<ShareLink, Self::Error>;
+
+    /// Получить пост по токену ссылки для совместного доступа.
+    async fn get_shared_post(&self, share_token: &str) -> Result<Post, Self::Error>;
+
+    /// Установить обложку поста.
+    ///
+    /// Ссылка может указывать на изображение, загруженное через [`Client::upload_media`].
+    async fn set_cover_image(
+        &self,
+        token: &str,
+        id: i64,
+        cover_image_url: &str,
+    ) -> Result<Post, Self::Error>;
+
+    /// Загрузить изображение, отправляя его содержимое потоком фрагментов.
+    ///
+    /// После отправки каждого фрагмента вызывается `progress` с количеством уже отправленных байт.
+    async fn upload_media(
+        &self,
+        token: &str,
+        content: Vec<u8>,
+        progress: UploadProgress,
+    ) -> Result<Media, Self::Error>;
+
+    /// Получить статистику блога (только для администраторов).
+    async fn get_admin_stats(
+        &self,
+        token: &str,
+        days: Option<i32>,
+    ) -> Result<AdminStats, Self::Error>;
+}
+
+/// Размер фрагмента, которыми содержимое файла отправляется на сервер при загрузке.
+pub(crate) const UPLOAD_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Обработчик хода загрузки файла: получает количество уже отправленных байт.
+pub type UploadProgress = Arc<dyn Fn(u64) + Send + Sync>;
+
+/// Фрагменты содержимого файла для потоковой отправки на сервер.
+///
+/// При выдаче каждого фрагмента сообщает обработчику хода загрузки,
+/// сколько байт уже отправлено.
+pub(crate) struct UploadChunks {
+    /// Содержимое файла.
+    content: Vec<u8>,
+    /// Количество уже выданных байт.
+    sent: usize,
+    /// Обработчик хода загрузки.
+    progress: UploadProgress,
+}
+
+impl UploadChunks {
+    /// Подготовить содержимое файла к отправке фрагментами.
+    pub(crate) fn new(content: Vec<u8>, progress: UploadProgress) -> Self {
+        Self {
+            content,
+            sent: 0,
+            progress,
+        }
+    }
+}
+
+impl Iterator for UploadChunks {
+    type Item = Vec<u8>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.sent >= self.content.len() {
+            return None;
+        }
+
+        let end = (self.sent + UPLOAD_CHUNK_SIZE).min(self.content.len());
+        let chunk = self.content[self.sent..end].to_vec();
+
+        self.sent = end;
+        (self.progress)(self.sent as u64);
+
+        Some(chunk)
+    }
 }
 
 /// Клиент для взаимодействия с серверной частью системы блога.
 ///
 /// Поддерживает несколько транспортных протоколов (HTTP, gRPC)
 /// и автоматически управляет JWT-токеном авторизации.
+///
+/// Все запросы принимают `&self`, поэтому один клиент можно разделить между
+/// задачами через `Arc<BlogClient>` и выполнять запросы параллельно.
 pub struct BlogClient {
     /// Внутренний клиент, реализующий конкретный протокол.
     inner: Box<dyn Client<Error = BlogClientError>>,
     /// Сохраненный JWT-токен для использования в защищенных запросах.
-    token: Option<String>,
+    token: RwLock<Option<String>>,
+    /// Наблюдатели за запросами.
+    observers: Observers,
+    /// Родительский контекст трассировки операций, заданный приложением.
+    trace: Option<TraceContext>,
+    /// Журналирование HTTP-запросов, общее с транспортом.
+    #[cfg(feature = "tracing")]
+    log: RequestLog,
 }
 
 impl BlogClient {
     /// Создать новый клиент с указанным транспортным протоколом.
     pub async fn new(transport: Transport) -> anyhow::Result<Self> {
-        let client = match transport {
-            Transport::Http(addr) => Self {
-                inner: Box::new(HttpClient::new(addr).await?),
-                token: None,
-            },
-            Transport::Grpc(addr) => Self {
-                inner: Box::new(GrpcClient::new(addr).await?),
-                token: None,
-            },
-        };
+        Self::connect(transport, GrpcOptions::default()).await
+    }
+
+    /// Создать новый клиент с указанными параметрами подключения по gRPC
+    /// (для HTTP-транспортов параметры не используются).
+    pub async fn connect(transport: Transport, grpc: GrpcOptions) -> anyhow::Result<Self> {
+        let log = RequestLog::default();
+        let inner = transport.client(grpc, log.clone()).await?;
+
+        Ok(Self::with_inner(inner, log))
+    }
+
+    /// Создать клиент с основным и запасным транспортом (например, gRPC и HTTP).
+    ///
+    /// Запросы выполняются через основной транспорт. Если подключиться к серверу через него
+    /// не удалось, запрос повторяется через запасной, и дальше используется он - до тех пор,
+    /// пока уже он не сможет подключиться. Запросы, дошедшие до сервера (в том числе
+    /// завершившиеся по таймауту), не повторяются. Текущий транспорт возвращает
+    /// [`Self::active_transport`].
+    pub async fn connect_with_fallback(
+        primary: Transport,
+        fallback: Transport,
+        grpc: GrpcOptions,
+    ) -> anyhow::Result<Self> {
+        let log = RequestLog::default();
+
+        let inner = Box::new(FallbackClient::new(
+            primary.client(grpc.clone(), log.clone()).await?,
+            fallback.client(grpc, log.clone()).await?,
+            log.clone(),
+        ));
 
-        Ok(client)
+        Ok(Self::with_inner(inner, log))
+    }
+
+    /// Создать клиент поверх клиента, реализующего протокол.
+    fn with_inner(inner: Box<dyn Client<Error = BlogClientError>>, log: RequestLog) -> Self {
+        #[cfg(not(feature = "tracing"))]
+        let _ = log;
+
+        Self {
+            inner,
+            token: RwLock::new(None),
+            observers: Observers::default(),
+            trace: None,
+            #[cfg(feature = "tracing")]
+            log,
+        }
+    }
+
+    /// Название транспорта, через который сейчас выполняются запросы (`http`,
+    /// `http+msgpack` или `grpc`).
+    pub fn active_transport(&self) -> &'static str {
+        self.inner.transport()
+    }
+
+    /// Подключить наблюдателя за запросами (логирование, метрики, трассировка).
+    ///
+    /// Наблюдатели оповещаются в порядке подключения.
+    pub fn with_observer(mut self, observer: impl ClientObserver + 'static) -> Self {
+        self.observers.push(Box::new(observer));
+        self
+    }
+
+    /// Связать операции клиента с внешней трассировкой приложения.
+    ///
+    /// Спаны операций становятся дочерними для `context`, если для задачи не задан
+    /// другой контекст через [`TraceContext::scope`]. Без родительского контекста
+    /// каждая операция начинает новую трассировку.
+    pub fn with_trace_context(mut self, context: TraceContext) -> Self {
+        self.trace = Some(context);
+        self
+    }
+
+    /// Включить журналирование запросов через `tracing` на уровне `debug`.
+    ///
+    /// Для каждой операции записываются транспорт, результат и длительность, а для
+    /// HTTP-транспорта - также метод, адрес, код ответа и повторы при превышении
+    /// лимита частоты запросов. Токены в журнал не попадают.
+    #[cfg(feature = "tracing")]
+    pub fn with_tracing(self) -> Self {
+        self.log.enable();
+        self.with_observer(crate::observer::TracingObserver)
+    }
+
+    /// Сведения о запросе для наблюдателей.
+    fn request_info(&self, operation: &'static str) -> RequestInfo {
+        RequestInfo {
+            operation,
+            transport: self.inner.transport(),
+            trace: TraceContext::current()
+                .or(self.trace)
+                .map_or_else(TraceContext::new_root, |parent| parent.child()),
+        }
     }
 
     /// Установить JWT-токен авторизации для последующих защищенных запросов.
-    pub fn set_token(&mut self, token: String) {
-        self.token = Some(token);
+    pub fn set_token(&self, token: String) {
+        *self.token.write().unwrap_or_else(PoisonError::into_inner) = Some(token);
     }
 
     /// Получить текущий JWT-токен авторизации.
     pub fn get_token(&self) -> Option<String> {
-        self.token.clone()
+        self.token
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
     }
 
     /// Зарегистрировать нового пользователя и сохранить токен авторизации.
     pub async fn register(
-        &mut self,
+        &self,
         username: &str,
         email: &str,
         password: &str,
+        invite_code: Option<&str>,
     ) -> Result<User, BlogClientError> {
-        let response = self.inner.register(username, email, password).await?;
+        let response = self
+            .observers
+            .observe(
+                self.request_info("register"),
+                self.inner.register(username, email, password, invite_code),
+            )
+            .await?;
         self.set_token(response.token);
 
         Ok(response.user)
     }
 
     /// Авторизовать пользователя и сохранить токен авторизации.
-    pub async fn login(&mut self, username: &str, password: &str) -> Result<User, BlogClientError> {
-        let response = self.inner.login(username, password).await?;
+    pub async fn login(&self, username: &str, password: &str) -> Result<User, BlogClientError> {
+        let response = self
+            .observers
+            .observe(
+                self.request_info("login"),
+                self.inner.login(username, password),
+            )
+            .await?;
 
         self.set_token(response.token);
 
         Ok(response.user)
     }
 
+    /// Получить публичный профиль пользователя по имени пользователя.
+    pub async fn get_user(&self, username: &str) -> Result<PublicProfile, BlogClientError> {
+        let profile = self
+            .observers
+            .observe(self.request_info("get_user"), self.inner.get_user(username))
+            .await?;
+
+        Ok(profile)
+    }
+
+    /// Получить информацию об авторизованном пользователе.
+    pub async fn get_current_user(&self) -> Result<User, BlogClientError> {
+        let token = self.get_token().ok_or(BlogClientError::TokenNotFound)?;
+
+        let user = self
+            .observers
+            .observe(
+                self.request_info("get_current_user"),
+                self.inner.get_current_user(&token),
+            )
+            .await?;
+
+        Ok(user)
+    }
+
+    /// Изменить профиль авторизованного пользователя: запросить смену email-адреса
+    /// и/или удалить загруженный аватар.
+    pub async fn update_profile(
+        &self,
+        email: Option<&str>,
+        remove_avatar: bool,
+    ) -> Result<User, BlogClientError> {
+        let token = self.get_token().ok_or(BlogClientError::TokenNotFound)?;
+
+        let user = self
+            .observers
+            .observe(
+                self.request_info("update_profile"),
+                self.inner.update_profile(&token, email, remove_avatar),
+            )
+            .await?;
+
+        Ok(user)
+    }
+
     /// Создать новый пост от имени авторизованного пользователя.
     pub async fn create_post(
-        &mut self,
+        &self,
         title: &str,
         content: &str,
+        is_private: bool,
+        content_format: Option<ContentFormat>,
     ) -> Result<Post, BlogClientError> {
         let token = self.get_token().ok_or(BlogClientError::TokenNotFound)?;
 
-        let post = self.inner.create_post(&token, title, content).await?;
+        let post = self
+            .observers
+            .observe(
+                self.request_info("create_post"),
+                self.inner
+                    .create_post(&token, title, content, is_private, content_format),
+            )
+            .await?;
 
         Ok(post)
     }
 
     /// Получить пост по идентификатору.
-    pub async fn get_post(&mut self, id: i64) -> Result<Post, BlogClientError> {
-        let post = self.inner.get_post(id).await?;
+    pub async fn get_post(&self, id: i64) -> Result<Post, BlogClientError> {
+        let post = self
+            .observers
+            .observe(self.request_info("get_post"), self.inner.get_post(id))
+            .await?;
 
         Ok(post)
     }
 
+    /// Получить посты по списку идентификаторов (не более 100 за запрос).
+    pub async fn get_posts_by_ids(&self, ids: &[i64]) -> Result<Vec<Post>, BlogClientError> {
+        let posts = self
+            .observers
+            .observe(
+                self.request_info("get_posts_by_ids"),
+                self.inner.get_posts_by_ids(ids),
+            )
+            .await?;
+
+        Ok(posts)
+    }
+
+    /// Найти посты по поисковому запросу.
+    pub async fn search_posts(
+        &self,
+        query: &str,
+        author_id: Option<i64>,
+        page: Page,
+    ) -> Result<Vec<PostPreview>, BlogClientError> {
+        let posts = self
+            .observers
+            .observe(
+                self.request_info("search_posts"),
+                self.inner.search_posts(query, author_id, page),
+            )
+            .await?;
+
+        Ok(posts)
+    }
+
     /// Получить список постов с пагинацией.
     pub async fn get_posts(
-        &mut self,
-        limit: i64,
-        offset: i64,
-    ) -> Result<Vec<Post>, BlogClientError> {
-        let posts = self.inner.get_posts(limit, offset).await?;
+        &self,
+        page: Page,
+        archived: Option<bool>,
+    ) -> Result<PostPage, BlogClientError> {
+        let posts = self
+            .observers
+            .observe(
+                self.request_info("get_posts"),
+                self.inner.get_posts(page, archived),
+            )
+            .await?;
+
+        Ok(posts)
+    }
+
+    /// Получить список постов с пагинацией, ограничив ответ указанными полями
+    /// (например, `["title", "created_at"]`). Идентификатор поста возвращается всегда.
+    pub async fn get_post_previews(
+        &self,
+        page: Page,
+        fields: &[&str],
+        archived: Option<bool>,
+    ) -> Result<PostPage, BlogClientError> {
+        let posts = self
+            .observers
+            .observe(
+                self.request_info("get_post_previews"),
+                self.inner.get_post_previews(page, fields, archived),
+            )
+            .await?;
 
         Ok(posts)
     }
 
     /// Обновить пост от имени авторизованного пользователя.
+    ///
+    /// При указании `expected_version` обновление отклоняется с ошибкой
+    /// [`BlogClientError::Conflict`], если пост уже был изменен.
     pub async fn update_post(
-        &mut self,
+        &self,
         id: i64,
         title: Option<String>,
         content: Option<String>,
+        content_format: Option<ContentFormat>,
+        expected_version: Option<i64>,
     ) -> Result<Post, BlogClientError> {
         let token = self.get_token().ok_or(BlogClientError::TokenNotFound)?;
 
-        let post = self.inner.update_post(&token, id, title, content).await?;
+        let post = self
+            .observers
+            .observe(
+                self.request_info("update_post"),
+                self.inner.update_post(
+                    &token,
+                    id,
+                    title,
+                    content,
+                    content_format,
+                    expected_version,
+                ),
+            )
+            .await?;
 
         Ok(post)
     }
 
     /// Удалить пост от имени авторизованного пользователя.
-    pub async fn delete_post(&mut self, id: i64) -> Result<(), BlogClientError> {
+    pub async fn delete_post(&self, id: i64) -> Result<(), BlogClientError> {
         let token = self.get_token().ok_or(BlogClientError::TokenNotFound)?;
 
-        self.inner.delete_post(&token, id).await?;
+        self.observers
+            .observe(
+                self.request_info("delete_post"),
+                self.inner.delete_post(&token, id),
+            )
+            .await?;
 
         Ok(())
     }
+
+    /// Удалить несколько постов авторизованного пользователя (не более 100 за запрос).
+    ///
+    /// Отсутствующие и чужие посты не прерывают удаление остальных: причина, по которой
+    /// пост не удален, указывается в его результате.
+    pub async fn bulk_delete(&self, ids: &[i64]) -> Result<Vec<BulkDeleteResult>, BlogClientError> {
+        let token = self.get_token().ok_or(BlogClientError::TokenNotFound)?;
+
+        let results = self
+            .observers
+            .observe(
+                self.request_info("bulk_delete"),
+                self.inner.bulk_delete(&token, ids),
+            )
+            .await?;
+
+        Ok(results)
+    }
+
+    /// Перевести пост авторизованного пользователя в архив.
+    ///
+    /// Архивный пост остается доступным для чтения, но изменить его нельзя.
+    pub async fn archive_post(&self, id: i64) -> Result<Post, BlogClientError> {
+        let token = self.get_token().ok_or(BlogClientError::TokenNotFound)?;
+
+        let post = self
+            .observers
+            .observe(
+                self.request_info("archive_post"),
+                self.inner.set_archived(&token, id, true),
+            )
+            .await?;
+
+        Ok(post)
+    }
+
+    /// Вернуть пост авторизованного пользователя из архива.
+    pub async fn unarchive_post(&self, id: i64) -> Result<Post, BlogClientError> {
+        let token = self.get_token().ok_or(BlogClientError::TokenNotFound)?;
+
+        let post = self
+            .observers
+            .observe(
+                self.request_info("unarchive_post"),
+                self.inner.set_archived(&token, id, false),
+            )
+            .await?;
+
+        Ok(post)
+    }
+
+    /// Добавить соавтора посту авторизованного пользователя.
+    ///
+    /// Соавтор может редактировать пост, но не может удалять или архивировать его.
+    pub async fn add_post_author(&self, id: i64, user_id: i64) -> Result<Post, BlogClientError> {
+        let token = self.get_token().ok_or(BlogClientError::TokenNotFound)?;
+
+        let post = self
+            .observers
+            .observe(
+                self.request_info("add_post_author"),
+                self.inner.add_post_author(&token, id, user_id),
+            )
+            .await?;
+
+        Ok(post)
+    }
+
+    /// Создать ссылку для совместного доступа к посту авторизованного пользователя.
+    pub async fn create_share_link(
+        &self,
+        id: i64,
+        ttl_seconds: Option<u64>,
+    ) -> Result<ShareLink, BlogClientError> {
+        let token = self.get_token().ok_or(BlogClientError::TokenNotFound)?;
+
+        let link = self
+            .observers
+            .observe(
+                self.request_info("create_share_link"),
+                self.inner.create_share_link(&token, id, ttl_seconds),
+            )
+            .await?;
+
+        Ok(link)
+    }
+
+    /// Получить пост по токену ссылки для совместного доступа.
+    pub async fn get_shared_post(&self, share_token: &str) -> Result<Post, BlogClientError> {
+        let post = self
+            .observers
+            .observe(
+                self.request_info("get_shared_post"),
+                self.inner.get_shared_post(share_token),
+            )
+            .await?;
+
+        Ok(post)
+    }
+
+    /// Установить обложку поста авторизованного пользователя.
+    pub async fn set_cover_image(
+        &self,
+        id: i64,
+        cover_image_url: &str,
+    ) -> Result<Post, BlogClientError> {
+        let token = self.get_token().ok_or(BlogClientError::TokenNotFound)?;
+
+        let post = self
+            .observers
+            .observe(
+                self.request_info("set_cover_image"),
+                self.inner.set_cover_image(&token, id, cover_image_url),
+            )
+            .await?;
+
+        Ok(post)
+    }
+
+    /// Загрузить изображение от имени авторизованного пользователя.
+    ///
+    /// Ход загрузки сообщается обработчику `progress` количеством уже отправленных байт.
+    pub async fn upload_media(
+        &self,
+        content: Vec<u8>,
+        progress: UploadProgress,
+    ) -> Result<Media, BlogClientError> {
+        let token = self.get_token().ok_or(BlogClientError::TokenNotFound)?;
+
+        let media = self
+            .observers
+            .observe(
+                self.request_info("upload_media"),
+                self.inner.upload_media(&token, content, progress),
+            )
+            .await?;
+
+        Ok(media)
+    }
+
+    /// Получить статистику блога за последние `days` дней (только для администраторов).
+    pub async fn get_admin_stats(&self, days: Option<i32>) -> Result<AdminStats, BlogClientError> {
+        let token = self.get_token().ok_or(BlogClientError::TokenNotFound)?;
+
+        let stats = self
+            .observers
+            .observe(
+                self.request_info("get_admin_stats"),
+                self.inner.get_admin_stats(&token, days),
+            )
+            .await?;
+
+        Ok(stats)
+    }
 }
 
 /// Информация о пользователе.
@@ -239,6 +919,10 @@ pub struct User {
     /// Email-адрес пользователя.
     pub email: String,
 
+    /// Ссылка на аватар пользователя.
+    #[serde(default)]
+    pub avatar_url: String,
+
     /// Время создания пользователя.
     pub created_at: DateTime<Utc>,
 }
@@ -250,9 +934,10 @@ impl std::fmt::Display for User {
             r#"Идентификатор пользователя: {}
 Имя пользователя: {}
 Email пользователя: {}
+Аватар пользователя: {}
 Время создания пользователя: {}
 "#,
-            self.id, self.username, self.email, self.created_at
+            self.id, self.username, self.email, self.avatar_url, self.created_at
         )
     }
 }
@@ -265,6 +950,7 @@ impl TryFrom<blog_grpc::User> for User {
             id: user.id,
             username: user.username,
             email: user.email,
+            avatar_url: user.avatar_url,
             created_at: user
                 .created_at
                 .parse()
@@ -273,6 +959,57 @@ impl TryFrom<blog_grpc::User> for User {
     }
 }
 
+/// Публичный профиль пользователя.
+#[derive(Debug, Deserialize)]
+pub struct PublicProfile {
+    /// Идентификатор пользователя.
+    pub id: i64,
+
+    /// Имя пользователя.
+    pub username: String,
+
+    /// Ссылка на аватар пользователя.
+    pub avatar_url: String,
+
+    /// Время регистрации пользователя.
+    pub created_at: DateTime<Utc>,
+
+    /// Количество публичных постов пользователя.
+    pub post_count: i64,
+}
+
+impl std::fmt::Display for PublicProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            r#"Идентификатор пользователя: {}
+Имя пользователя: {}
+Аватар пользователя: {}
+Время регистрации пользователя: {}
+Количество постов: {}
+"#,
+            self.id, self.username, self.avatar_url, self.created_at, self.post_count
+        )
+    }
+}
+
+impl TryFrom<blog_grpc::PublicProfile> for PublicProfile {
+    type Error = BlogClientError;
+
+    fn try_from(profile: blog_grpc::PublicProfile) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: profile.id,
+            username: profile.username,
+            avatar_url: profile.avatar_url,
+            created_at: profile
+                .created_at
+                .parse()
+                .map_err(|_| BlogClientError::InvalidUser)?,
+            post_count: profile.post_count,
+        })
+    }
+}
+
 /// Информация о посте.
 #[derive(Debug, Deserialize)]
 pub struct Post {
@@ -285,14 +1022,100 @@ pub struct Post {
     /// Содержимое поста.
     pub content: String,
 
+    /// Краткое описание поста.
+    pub excerpt: Option<String>,
+
+    /// Ссылка на изображение обложки поста.
+    pub cover_image_url: Option<String>,
+
+    /// Признак приватного поста.
+    #[serde(default)]
+    pub is_private: bool,
+
+    /// Время перевода поста в архив.
+    #[serde(default)]
+    pub archived_at: Option<DateTime<Utc>>,
+
     /// Идентификатор пользователя-автора поста.
     pub author_id: i64,
 
+    /// Идентификаторы всех авторов поста (владельца и соавторов).
+    #[serde(default)]
+    pub author_ids: Vec<i64>,
+
     /// Время создания поста.
     pub created_at: DateTime<Utc>,
 
     /// Время последнего обновления поста.
     pub updated_at: DateTime<Utc>,
+
+    /// Версия поста.
+    pub version: i64,
+
+    /// Оценка времени чтения поста (в минутах).
+    #[serde(default)]
+    pub reading_time_minutes: i32,
+
+    /// Формат содержимого поста.
+    #[serde(default)]
+    pub content_format: ContentFormat,
+
+    /// HTML-представление содержимого, сформированное сервером (только для Markdown).
+    #[serde(default)]
+    pub content_html: Option<String>,
+
+    /// Количество просмотров поста.
+    #[serde(default)]
+    pub view_count: i64,
+
+    /// Оценка популярности поста.
+    #[serde(default)]
+    pub trending_score: f64,
+}
+
+/// Формат содержимого поста.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentFormat {
+    /// Разметка Markdown.
+    Markdown,
+
+    /// Обычный текст.
+    #[default]
+    Plain,
+
+    /// HTML.
+    Html,
+}
+
+impl ContentFormat {
+    /// Значение формата в API.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ContentFormat::Markdown => "markdown",
+            ContentFormat::Plain => "plain",
+            ContentFormat::Html => "html",
+        }
+    }
+}
+
+impl fmt::Display for ContentFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ContentFormat {
+    type Err = BlogClientError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "markdown" => Ok(ContentFormat::Markdown),
+            "plain" => Ok(ContentFormat::Plain),
+            "html" => Ok(ContentFormat::Html),
+            _ => Err(BlogClientError::InvalidPostContent),
+        }
+    }
 }
 
 impl std::fmt::Display for Post {
@@ -305,8 +1128,267 @@ impl std::fmt::Display for Post {
 Идентификатор пользователя-автора поста: {}
 Время создания поста: {}
 Время последнего обновления поста: {}
+Версия поста: {}
 "#,
-            self.id, self.title, self.content, self.author_id, self.created_at, self.updated_at
+            self.id,
+            self.title,
+            self.content,
+            self.author_id,
+            self.created_at,
+            self.updated_at,
+            self.version
+        )?;
+
+        if let Some(excerpt) = &self.excerpt {
+            writeln!(f, "Краткое описание поста: {excerpt}")?;
+        }
+        if let Some(cover_image_url) = &self.cover_image_url {
+            writeln!(f, "Обложка поста: {cover_image_url}")?;
+        }
+        if self.is_private {
+            writeln!(f, "Приватный пост")?;
+        }
+        if let Some(archived_at) = self.archived_at {
+            writeln!(f, "Пост в архиве с: {archived_at}")?;
+        }
+        if self.reading_time_minutes > 0 {
+            writeln!(f, "Время чтения: {} мин.", self.reading_time_minutes)?;
+        }
+        if self.content_format != ContentFormat::Plain {
+            writeln!(f, "Формат содержимого: {}", self.content_format)?;
+        }
+        if self.author_ids.len() > 1 {
+            let author_ids: Vec<String> = self.author_ids.iter().map(i64::to_string).collect();
+            writeln!(f, "Авторы поста: {}", author_ids.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Сокращенное представление поста, содержащее только запрошенные поля.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PostPreview {
+    /// Идентификатор поста.
+    pub id: i64,
+
+    /// Заголовок поста.
+    pub title: Option<String>,
+
+    /// Содержимое поста.
+    pub content: Option<String>,
+
+    /// Краткое описание поста.
+    pub excerpt: Option<String>,
+
+    /// Ссылка на изображение обложки поста.
+    pub cover_image_url: Option<String>,
+
+    /// Признак приватного поста.
+    pub is_private: Option<bool>,
+
+    /// Время перевода поста в архив.
+    pub archived_at: Option<DateTime<Utc>>,
+
+    /// Идентификатор пользователя-автора поста.
+    pub author_id: Option<i64>,
+
+    /// Время создания поста.
+    pub created_at: Option<DateTime<Utc>>,
+
+    /// Время последнего обновления поста.
+    pub updated_at: Option<DateTime<Utc>>,
+
+    /// Версия поста.
+    pub version: Option<i64>,
+
+    /// Оценка времени чтения поста (в минутах).
+    pub reading_time_minutes: Option<i32>,
+
+    /// Количество просмотров поста.
+    pub view_count: Option<i64>,
+
+    /// Оценка популярности поста.
+    pub trending_score: Option<f64>,
+}
+
+impl PostPreview {
+    /// Оставить в посте только указанные поля.
+    pub(crate) fn project(post: Post, fields: &[&str]) -> Self {
+        let has = |field: &str| fields.contains(&field);
+
+        Self {
+            id: post.id,
+            title: has("title").then_some(post.title),
+            content: has("content").then_some(post.content),
+            excerpt: post.excerpt.filter(|_| has("excerpt")),
+            cover_image_url: post.cover_image_url.filter(|_| has("cover_image_url")),
+            is_private: has("is_private").then_some(post.is_private),
+            archived_at: post.archived_at.filter(|_| has("archived_at")),
+            author_id: has("author_id").then_some(post.author_id),
+            created_at: has("created_at").then_some(post.created_at),
+            updated_at: has("updated_at").then_some(post.updated_at),
+            version: has("version").then_some(post.version),
+            reading_time_minutes: has("reading_time_minutes").then_some(post.reading_time_minutes),
+            view_count: has("view_count").then_some(post.view_count),
+            trending_score: has("trending_score").then_some(post.trending_score),
+        }
+    }
+}
+
+impl std::fmt::Display for PostPreview {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Идентификатор поста: {}", self.id)?;
+
+        if let Some(title) = &self.title {
+            writeln!(f, "Заголовок поста: {title}")?;
+        }
+        if let Some(content) = &self.content {
+            writeln!(f, "Содержимое поста: {content}")?;
+        }
+        if let Some(excerpt) = &self.excerpt {
+            writeln!(f, "Краткое описание поста: {excerpt}")?;
+        }
+        if let Some(cover_image_url) = &self.cover_image_url {
+            writeln!(f, "Обложка поста: {cover_image_url}")?;
+        }
+        if let Some(is_private) = self.is_private {
+            writeln!(
+                f,
+                "Приватный пост: {}",
+                if is_private { "да" } else { "нет" }
+            )?;
+        }
+        if let Some(archived_at) = self.archived_at {
+            writeln!(f, "Пост в архиве с: {archived_at}")?;
+        }
+        if let Some(author_id) = self.author_id {
+            writeln!(f, "Идентификатор пользователя-автора поста: {author_id}")?;
+        }
+        if let Some(created_at) = self.created_at {
+            writeln!(f, "Время создания поста: {created_at}")?;
+        }
+        if let Some(updated_at) = self.updated_at {
+            writeln!(f, "Время последнего обновления поста: {updated_at}")?;
+        }
+        if let Some(version) = self.version {
+            writeln!(f, "Версия поста: {version}")?;
+        }
+        if let Some(reading_time_minutes) = self.reading_time_minutes {
+            writeln!(f, "Время чтения: {reading_time_minutes} мин.")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Максимальное количество постов на странице, которое принимает сервер.
+pub const MAX_PAGE_LIMIT: i64 = 100;
+
+/// Параметры пагинации: количество постов на странице и смещение от первого поста.
+///
+/// Границы проверяются при создании, поэтому некорректные параметры отклоняются
+/// до отправки запроса.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Page {
+    /// Максимальное количество постов на странице (от 1 до [`MAX_PAGE_LIMIT`]).
+    limit: i64,
+
+    /// Смещение от первого поста (неотрицательное).
+    offset: i64,
+}
+
+impl Page {
+    /// Создать параметры пагинации.
+    ///
+    /// Возвращает [`BlogClientError::InvalidPagination`], если `limit` не входит в
+    /// диапазон от 1 до [`MAX_PAGE_LIMIT`] или `offset` отрицательный.
+    pub fn new(limit: i64, offset: i64) -> Result<Self, BlogClientError> {
+        if !(1..=MAX_PAGE_LIMIT).contains(&limit) || offset < 0 {
+            return Err(BlogClientError::InvalidPagination { limit, offset });
+        }
+
+        Ok(Self { limit, offset })
+    }
+
+    /// Первая страница с указанным количеством постов.
+    pub fn first(limit: i64) -> Result<Self, BlogClientError> {
+        Self::new(limit, 0)
+    }
+
+    /// Максимальное количество постов на странице.
+    pub fn limit(&self) -> i64 {
+        self.limit
+    }
+
+    /// Смещение от первого поста.
+    pub fn offset(&self) -> i64 {
+        self.offset
+    }
+
+    /// Следующая страница того же размера.
+    pub fn next(&self) -> Self {
+        Self {
+            limit: self.limit,
+            offset: self.offset.saturating_add(self.limit),
+        }
+    }
+}
+
+/// Первая страница из [`MAX_PAGE_LIMIT`] постов.
+impl Default for Page {
+    fn default() -> Self {
+        Self {
+            limit: MAX_PAGE_LIMIT,
+            offset: 0,
+        }
+    }
+}
+
+/// Страница списка постов вместе с общим количеством постов.
+#[derive(Debug, Deserialize)]
+pub struct PostPage {
+    /// Посты на странице.
+    pub posts: Vec<PostPreview>,
+
+    /// Общее количество постов, удовлетворяющих условиям отбора.
+    pub total: i64,
+
+    /// Максимальное количество постов на странице.
+    pub limit: i64,
+
+    /// Смещение от первого поста.
+    pub offset: i64,
+}
+
+impl PostPage {
+    /// Проверить, что на странице нет постов.
+    pub fn is_empty(&self) -> bool {
+        self.posts.is_empty()
+    }
+}
+
+/// Сводка о странице (сами посты выводятся отдельно).
+impl std::fmt::Display for PostPage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.total == 0 {
+            return write!(f, "Постов не найдено (всего: 0)");
+        }
+
+        if self.is_empty() {
+            return write!(
+                f,
+                "На странице нет постов: смещение {} превышает их количество (всего: {})",
+                self.offset, self.total
+            );
+        }
+
+        write!(
+            f,
+            "Показаны посты {}-{} из {}",
+            self.offset + 1,
+            self.offset + self.posts.len() as i64,
+            self.total
         )
     }
 }
@@ -319,7 +1401,16 @@ impl TryFrom<blog_grpc::Post> for Post {
             id: post.id,
             title: post.title,
             content: post.content,
+            excerpt: post.excerpt,
+            cover_image_url: post.cover_image_url,
+            is_private: post.is_private,
+            archived_at: post
+                .archived_at
+                .map(|archived_at| archived_at.parse())
+                .transpose()
+                .map_err(|_| BlogClientError::InvalidPostContent)?,
             author_id: post.author_id,
+            author_ids: post.author_ids,
             created_at: post
                 .created_at
                 .parse()
@@ -328,6 +1419,280 @@ impl TryFrom<blog_grpc::Post> for Post {
                 .updated_at
                 .parse()
                 .map_err(|_| BlogClientError::InvalidPostContent)?,
+            version: post.version,
+            reading_time_minutes: post.reading_time_minutes,
+            content_format: post.content_format.parse()?,
+            content_html: post.content_html,
+            view_count: post.view_count,
+            trending_score: post.trending_score,
+        })
+    }
+}
+
+/// Результат удаления одного поста в составе пакетного удаления.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BulkDeleteResult {
+    /// Идентификатор поста.
+    pub id: i64,
+
+    /// Был ли пост удален.
+    pub deleted: bool,
+
+    /// Причина, по которой пост не был удален.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl std::fmt::Display for BulkDeleteResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.error {
+            None => write!(f, "{:>6}  удален", self.id),
+            Some(error) => write!(f, "{:>6}  не удален: {error}", self.id),
+        }
+    }
+}
+
+impl From<blog_grpc::BulkDeleteResult> for BulkDeleteResult {
+    fn from(result: blog_grpc::BulkDeleteResult) -> Self {
+        Self {
+            id: result.id,
+            deleted: result.deleted,
+            error: result.error,
+        }
+    }
+}
+
+/// Ссылка для совместного доступа к посту.
+#[derive(Debug, Deserialize)]
+pub struct ShareLink {
+    /// Токен доступа к посту.
+    pub token: String,
+
+    /// Время истечения ссылки.
+    pub expires_at: DateTime<Utc>,
+}
+
+impl std::fmt::Display for ShareLink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            r#"Токен доступа: {}
+Действует до: {}
+"#,
+            self.token, self.expires_at
+        )
+    }
+}
+
+impl TryFrom<blog_grpc::CreateShareLinkResponse> for ShareLink {
+    type Error = BlogClientError;
+
+    fn try_from(link: blog_grpc::CreateShareLinkResponse) -> Result<Self, Self::Error> {
+        Ok(Self {
+            token: link.token,
+            expires_at: link
+                .expires_at
+                .parse()
+                .map_err(|_| BlogClientError::InvalidPostContent)?,
+        })
+    }
+}
+
+/// Вариант загруженного изображения определенного размера.
+#[derive(Debug, Deserialize)]
+pub struct MediaVariant {
+    /// Размер варианта (`thumbnail`, `medium` или `original`).
+    pub size: String,
+
+    /// Ширина в пикселях.
+    pub width: i32,
+
+    /// Высота в пикселях.
+    pub height: i32,
+
+    /// Размер файла в байтах.
+    pub byte_size: i64,
+}
+
+/// Информация о загруженном изображении.
+#[derive(Debug, Deserialize)]
+pub struct Media {
+    /// Идентификатор изображения.
+    pub id: i64,
+
+    /// Идентификатор пользователя, загрузившего изображение.
+    pub owner_id: i64,
+
+    /// MIME-тип изображения.
+    pub content_type: String,
+
+    /// Ссылка на изображение относительно адреса сервера.
+    pub url: String,
+
+    /// Готовые варианты изображения.
+    pub variants: Vec<MediaVariant>,
+
+    /// Время загрузки изображения.
+    pub created_at: DateTime<Utc>,
+}
+
+impl std::fmt::Display for Media {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Идентификатор изображения: {}", self.id)?;
+        writeln!(f, "Ссылка на изображение: {}", self.url)?;
+        writeln!(f, "Тип изображения: {}", self.content_type)?;
+
+        for variant in &self.variants {
+            writeln!(
+                f,
+                "Вариант {}: {}x{} ({} байт)",
+                variant.size, variant.width, variant.height, variant.byte_size
+            )?;
+        }
+
+        write!(f, "Загружено: {}", self.created_at)
+    }
+}
+
+impl TryFrom<blog_grpc::Media> for Media {
+    type Error = BlogClientError;
+
+    fn try_from(media: blog_grpc::Media) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: media.id,
+            owner_id: media.owner_id,
+            content_type: media.content_type,
+            url: media.url,
+            variants: media
+                .variants
+                .into_iter()
+                .map(|variant| MediaVariant {
+                    size: variant.size,
+                    width: variant.width,
+                    height: variant.height,
+                    byte_size: variant.byte_size,
+                })
+                .collect(),
+            created_at: media
+                .created_at
+                .parse()
+                .map_err(|_| BlogClientError::Unexpected)?,
+        })
+    }
+}
+
+/// Общие показатели блога.
+#[derive(Debug, Deserialize)]
+pub struct StatsTotals {
+    /// Количество пользователей.
+    pub users: i64,
+
+    /// Количество постов.
+    pub posts: i64,
+
+    /// Количество загруженных изображений.
+    pub media: i64,
+
+    /// Количество пользователей, входивших за период.
+    pub active_users: i64,
+}
+
+/// Показатели блога за один день.
+#[derive(Debug, Deserialize)]
+pub struct DailyStats {
+    /// День (UTC).
+    pub date: NaiveDate,
+
+    /// Количество регистраций.
+    pub registrations: i64,
+
+    /// Количество созданных постов.
+    pub posts: i64,
+
+    /// Количество пользователей, входивших в этот день.
+    pub active_users: i64,
+}
+
+/// Статистика блога для администратора.
+#[derive(Debug, Deserialize)]
+pub struct AdminStats {
+    /// Период статистики в днях.
+    pub days: i32,
+
+    /// Общие показатели.
+    pub totals: StatsTotals,
+
+    /// Показатели по дням (от старых к новым).
+    pub daily: Vec<DailyStats>,
+
+    /// Время расчета статистики.
+    pub generated_at: DateTime<Utc>,
+}
+
+impl std::fmt::Display for AdminStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            r#"Пользователей: {}
+Постов: {}
+Изображений: {}
+Активных пользователей за {} дн.: {}
+Рассчитано: {}
+"#,
+            self.totals.users,
+            self.totals.posts,
+            self.totals.media,
+            self.days,
+            self.totals.active_users,
+            self.generated_at
+        )?;
+
+        writeln!(
+            f,
+            "{:<10}  {:>11}  {:>6}  {:>8}",
+            "День", "Регистраций", "Постов", "Активных"
+        )?;
+
+        for day in &self.daily {
+            writeln!(
+                f,
+                "{:<10}  {:>11}  {:>6}  {:>8}",
+                day.date, day.registrations, day.posts, day.active_users
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl TryFrom<blog_grpc::GetAdminStatsResponse> for AdminStats {
+    type Error = BlogClientError;
+
+    fn try_from(stats: blog_grpc::GetAdminStatsResponse) -> Result<Self, Self::Error> {
+        let mut daily = vec![];
+
+        for day in stats.daily {
+            daily.push(DailyStats {
+                date: day.date.parse().map_err(|_| BlogClientError::Unexpected)?,
+                registrations: day.registrations,
+                posts: day.posts,
+                active_users: day.active_users,
+            });
+        }
+
+        Ok(Self {
+            days: stats.days,
+            totals: StatsTotals {
+                users: stats.total_users,
+                posts: stats.total_posts,
+                media: stats.total_media,
+                active_users: stats.active_users,
+            },
+            daily,
+            generated_at: stats
+                .generated_at
+                .parse()
+                .map_err(|_| BlogClientError::Unexpected)?,
         })
     }
 }
@@ -0,0 +1,129 @@
+//! Наблюдатели за запросами клиента для подключения логирования, метрик и трассировки.
+
+use crate::error::BlogClientError;
+use crate::trace::TraceContext;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Сведения о запросе к серверу блога.
+#[derive(Debug, Clone, Copy)]
+pub struct RequestInfo {
+    /// Название операции клиента (например, `get_post`).
+    pub operation: &'static str,
+
+    /// Транспортный протокол (`http`, `http+msgpack` или `grpc`).
+    pub transport: &'static str,
+
+    /// Контекст трассировки операции; запросы к серверу передаются как его дочерние спаны.
+    pub trace: TraceContext,
+}
+
+/// Наблюдатель за запросами клиента.
+///
+/// Все методы имеют пустую реализацию по умолчанию, поэтому достаточно
+/// переопределить только нужные. Методы вызываются синхронно в задаче,
+/// выполняющей запрос, и не должны блокировать ее надолго.
+pub trait ClientObserver: Send + Sync {
+    /// Вызывается перед отправкой запроса.
+    fn on_request(&self, _request: &RequestInfo) {}
+
+    /// Вызывается после успешного ответа сервера.
+    fn on_response(&self, _request: &RequestInfo, _elapsed: Duration) {}
+
+    /// Вызывается, если запрос завершился ошибкой.
+    fn on_error(&self, _request: &RequestInfo, _error: &BlogClientError, _elapsed: Duration) {}
+}
+
+impl<T: ClientObserver + ?Sized> ClientObserver for Arc<T> {
+    fn on_request(&self, request: &RequestInfo) {
+        (**self).on_request(request);
+    }
+
+    fn on_response(&self, request: &RequestInfo, elapsed: Duration) {
+        (**self).on_response(request, elapsed);
+    }
+
+    fn on_error(&self, request: &RequestInfo, error: &BlogClientError, elapsed: Duration) {
+        (**self).on_error(request, error, elapsed);
+    }
+}
+
+/// Наблюдатели, подключенные к клиенту.
+#[derive(Default)]
+pub(crate) struct Observers(Vec<Box<dyn ClientObserver>>);
+
+impl Observers {
+    /// Подключить наблюдателя.
+    pub(crate) fn push(&mut self, observer: Box<dyn ClientObserver>) {
+        self.0.push(observer);
+    }
+
+    /// Выполнить запрос, оповестив наблюдателей о его начале, длительности и результате.
+    pub(crate) async fn observe<T>(
+        &self,
+        request: RequestInfo,
+        future: impl Future<Output = Result<T, BlogClientError>>,
+    ) -> Result<T, BlogClientError> {
+        let future = request.trace.scope(future);
+
+        if self.0.is_empty() {
+            return future.await;
+        }
+
+        for observer in &self.0 {
+            observer.on_request(&request);
+        }
+
+        let started = Instant::now();
+        let result = future.await;
+        let elapsed = started.elapsed();
+
+        for observer in &self.0 {
+            match &result {
+                Ok(_) => observer.on_response(&request, elapsed),
+                Err(e) => observer.on_error(&request, e, elapsed),
+            }
+        }
+
+        result
+    }
+}
+
+/// Наблюдатель, записывающий операции клиента в `tracing` ([`crate::BlogClient::with_tracing`]).
+#[cfg(feature = "tracing")]
+pub(crate) struct TracingObserver;
+
+#[cfg(feature = "tracing")]
+impl ClientObserver for TracingObserver {
+    fn on_response(&self, request: &RequestInfo, elapsed: Duration) {
+        tracing::debug!(
+            target: "blog_client",
+            operation = request.operation,
+            transport = request.transport,
+            trace_id = %request.trace.trace_id(),
+            elapsed_ms = elapsed.as_millis() as u64,
+            "Запрос выполнен"
+        );
+    }
+
+    fn on_error(&self, request: &RequestInfo, error: &BlogClientError, elapsed: Duration) {
+        let mut message = error.to_string();
+
+        // Ошибки reqwest содержат адрес запроса, в котором может быть токен.
+        if let BlogClientError::Http(e) = error
+            && let Some(url) = e.url()
+        {
+            message = message.replace(url.as_str(), &crate::request_log::redact(url));
+        }
+
+        tracing::debug!(
+            target: "blog_client",
+            operation = request.operation,
+            transport = request.transport,
+            trace_id = %request.trace.trace_id(),
+            elapsed_ms = elapsed.as_millis() as u64,
+            error = %message,
+            "Запрос завершился ошибкой"
+        );
+    }
+}
@@ -0,0 +1,64 @@
+//! Сведения об ограничении частоты запросов из заголовков ответа сервера.
+
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use std::time::Duration;
+
+/// Заголовок с максимальным количеством запросов в окне ограничения.
+const RATE_LIMIT_LIMIT: &str = "x-ratelimit-limit";
+
+/// Заголовок с количеством оставшихся в окне ограничения запросов.
+const RATE_LIMIT_REMAINING: &str = "x-ratelimit-remaining";
+
+/// Заголовок с количеством секунд до снятия ограничения.
+const RATE_LIMIT_AFTER: &str = "x-ratelimit-after";
+
+/// Состояние ограничения частоты запросов, сообщенное сервером.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Максимальное количество запросов (`X-RateLimit-Limit`).
+    pub limit: Option<u64>,
+
+    /// Количество оставшихся запросов (`X-RateLimit-Remaining`).
+    pub remaining: Option<u64>,
+
+    /// Время до снятия ограничения (`Retry-After` или `X-RateLimit-After`).
+    pub retry_after: Option<Duration>,
+}
+
+impl RateLimit {
+    /// Разобрать заголовки ограничения частоты запросов.
+    ///
+    /// `Retry-After` может содержать как количество секунд, так и дату (RFC 9110).
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+        let number = |name: &str| header(name).and_then(|value| value.trim().parse().ok());
+
+        let retry_after = header(RETRY_AFTER.as_str())
+            .and_then(parse_retry_after)
+            .or_else(|| number(RATE_LIMIT_AFTER).map(Duration::from_secs));
+
+        Self {
+            limit: number(RATE_LIMIT_LIMIT),
+            remaining: number(RATE_LIMIT_REMAINING),
+            retry_after,
+        }
+    }
+}
+
+/// Разобрать значение заголовка `Retry-After`.
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+
+    if let Ok(seconds) = value.parse() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+
+    Some(
+        (date.with_timezone(&Utc) - Utc::now())
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
@@ -0,0 +1,126 @@
+//! Журналирование HTTP-запросов клиента через `tracing` (возможность `tracing`).
+//!
+//! Записываются метод, адрес, код ответа, длительность и номер попытки каждого
+//! запроса на уровне `debug`. Токены из адресов (ссылки для совместного доступа,
+//! параметры `token`) заменяются на `***`, заголовки запросов не записываются.
+//! Переключения на запасной транспорт записываются на уровне `warn`.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "tracing")]
+use std::time::Duration;
+
+/// Переключатель журналирования HTTP-запросов, общий для клиента и его транспорта.
+///
+/// Включается [`crate::BlogClient::with_tracing`]; без возможности `tracing` ничего не записывает.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RequestLog(Arc<AtomicBool>);
+
+impl RequestLog {
+    /// Включить журналирование.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn enable(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Включено ли журналирование.
+    #[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+    pub(crate) fn enabled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Записать ответ сервера на попытку запроса (начиная с 1).
+    #[cfg(feature = "tracing")]
+    pub(crate) fn response(
+        &self,
+        method: &reqwest::Method,
+        url: &reqwest::Url,
+        status: reqwest::StatusCode,
+        elapsed: Duration,
+        attempt: u32,
+    ) {
+        tracing::debug!(
+            target: "blog_client::http",
+            %method,
+            endpoint = %redact(url),
+            status = status.as_u16(),
+            elapsed_ms = elapsed.as_millis() as u64,
+            attempt,
+            "HTTP-запрос выполнен"
+        );
+    }
+
+    /// Записать повтор запроса после паузы.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn retry(
+        &self,
+        method: &reqwest::Method,
+        url: &reqwest::Url,
+        delay: Duration,
+        attempt: u32,
+    ) {
+        tracing::debug!(
+            target: "blog_client::http",
+            %method,
+            endpoint = %redact(url),
+            delay_ms = delay.as_millis() as u64,
+            attempt,
+            "Превышен лимит частоты запросов, запрос будет повторен"
+        );
+    }
+
+    /// Записать переключение на другой транспорт после неудачного подключения.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn fallback(&self, from: &'static str, to: &'static str) {
+        tracing::warn!(
+            target: "blog_client::transport",
+            from,
+            to,
+            "Сервер недоступен, запросы переключены на другой транспорт"
+        );
+    }
+}
+
+/// Адрес запроса без токенов.
+#[cfg(feature = "tracing")]
+pub(crate) fn redact(url: &reqwest::Url) -> String {
+    let mut url = url.clone();
+
+    let segments: Vec<String> = url
+        .path_segments()
+        .map(|segments| segments.map(str::to_string).collect())
+        .unwrap_or_default();
+
+    let path: Vec<&str> = segments
+        .iter()
+        .enumerate()
+        .map(|(i, segment)| {
+            if i > 0 && segments[i - 1] == "shared" {
+                "***"
+            } else {
+                segment.as_str()
+            }
+        })
+        .collect();
+
+    url.set_path(&path.join("/"));
+
+    if url.query().is_some() {
+        let query: Vec<(String, String)> = url
+            .query_pairs()
+            .map(|(key, value)| {
+                let value = if key.contains("token") {
+                    "***".to_string()
+                } else {
+                    value.into_owned()
+                };
+
+                (key.into_owned(), value)
+            })
+            .collect();
+
+        url.query_pairs_mut().clear().extend_pairs(query);
+    }
+
+    url.to_string()
+}
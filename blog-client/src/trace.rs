@@ -0,0 +1,183 @@
+//! Распространение контекста трассировки W3C Trace Context.
+//!
+//! Каждая операция клиента получает собственный спан, а каждая попытка запроса к серверу -
+//! дочерний спан операции, который передается в заголовке `traceparent` (HTTP) или
+//! в метаданных вызова (gRPC). Сервер записывает идентификатор трассировки и родительского
+//! спана в свой спан запроса, поэтому запросы клиента и сервера связываются в одну трассировку.
+//!
+//! Родительский контекст операции берется из [`TraceContext::scope`], затем из
+//! [`crate::BlogClient::with_trace_context`]; если он не задан, операция начинает новую
+//! трассировку.
+
+use std::fmt;
+use std::hash::{BuildHasher, Hasher, RandomState};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Название заголовка (и ключа метаданных gRPC) с контекстом трассировки.
+pub const TRACEPARENT: &str = "traceparent";
+
+/// Поддерживаемая версия формата `traceparent`.
+const VERSION: u8 = 0;
+
+/// Флаг `sampled` в поле `trace-flags`.
+const FLAG_SAMPLED: u8 = 0x01;
+
+tokio::task_local! {
+    /// Контекст трассировки, заданный для текущей задачи.
+    static CURRENT: TraceContext;
+}
+
+/// Контекст трассировки: идентификаторы трассировки и текущего спана.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceContext {
+    /// Идентификатор трассировки.
+    trace_id: u128,
+
+    /// Идентификатор спана.
+    span_id: u64,
+
+    /// Записывается ли трассировка.
+    sampled: bool,
+}
+
+/// Ошибка разбора заголовка `traceparent`.
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("Некорректный заголовок traceparent: {0}")]
+pub struct InvalidTraceParent(String);
+
+impl TraceContext {
+    /// Начать новую трассировку.
+    pub fn new_root() -> Self {
+        Self {
+            trace_id: (u128::from(random_u64()) << 64 | u128::from(random_u64())).max(1),
+            span_id: random_u64().max(1),
+            sampled: true,
+        }
+    }
+
+    /// Контекст дочернего спана в той же трассировке.
+    pub fn child(&self) -> Self {
+        Self {
+            span_id: random_u64().max(1),
+            ..*self
+        }
+    }
+
+    /// Контекст, заданный для текущей задачи через [`TraceContext::scope`].
+    pub fn current() -> Option<Self> {
+        CURRENT.try_with(|context| *context).ok()
+    }
+
+    /// Контекст очередной попытки запроса к серверу: дочерний спан текущей операции.
+    pub(crate) fn outgoing() -> Self {
+        Self::current().map_or_else(Self::new_root, |context| context.child())
+    }
+
+    /// Выполнить `future`, используя этот контекст как родительский для запросов клиента.
+    ///
+    /// Позволяет связать запросы с трассировкой приложения, например с входящим запросом,
+    /// который оно обрабатывает.
+    pub async fn scope<F: Future>(self, future: F) -> F::Output {
+        CURRENT.scope(self, future).await
+    }
+
+    /// Идентификатор трассировки (32 шестнадцатеричных символа).
+    pub fn trace_id(&self) -> String {
+        format!("{:032x}", self.trace_id)
+    }
+
+    /// Идентификатор спана (16 шестнадцатеричных символов).
+    pub fn span_id(&self) -> String {
+        format!("{:016x}", self.span_id)
+    }
+
+    /// Записывается ли трассировка.
+    pub fn is_sampled(&self) -> bool {
+        self.sampled
+    }
+}
+
+/// Значение заголовка `traceparent`.
+impl fmt::Display for TraceContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let flags = if self.sampled { FLAG_SAMPLED } else { 0 };
+
+        write!(
+            f,
+            "{VERSION:02x}-{:032x}-{:016x}-{flags:02x}",
+            self.trace_id, self.span_id
+        )
+    }
+}
+
+/// Разбор заголовка `traceparent`.
+///
+/// Версии новее `00` принимаются, если начало значения соответствует формату версии `00`.
+impl FromStr for TraceContext {
+    type Err = InvalidTraceParent;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidTraceParent(value.to_string());
+        let mut parts = value.trim().split('-');
+
+        let (Some(version), Some(trace_id), Some(span_id), Some(flags)) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+
+        let version = parse_hex(version, 2, u8::from_str_radix).ok_or_else(invalid)?;
+
+        if version == 0xff || (version == VERSION && parts.next().is_some()) {
+            return Err(invalid());
+        }
+
+        let trace_id = parse_hex(trace_id, 32, u128::from_str_radix).ok_or_else(invalid)?;
+        let span_id = parse_hex(span_id, 16, u64::from_str_radix).ok_or_else(invalid)?;
+        let flags = parse_hex(flags, 2, u8::from_str_radix).ok_or_else(invalid)?;
+
+        if trace_id == 0 || span_id == 0 {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            trace_id,
+            span_id,
+            sampled: flags & FLAG_SAMPLED != 0,
+        })
+    }
+}
+
+/// Разобрать шестнадцатеричное поле фиксированной длины (только строчные символы).
+fn parse_hex<T>(
+    value: &str,
+    len: usize,
+    parse: fn(&str, u32) -> Result<T, std::num::ParseIntError>,
+) -> Option<T> {
+    let valid = value.len() == len
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b));
+
+    valid.then(|| parse(value, 16).ok()).flatten()
+}
+
+/// Случайное число для идентификаторов трассировки и спанов.
+///
+/// Хешер [`RandomState`] инициализируется случайными ключами, а счетчик и время
+/// гарантируют разные значения при повторных вызовах.
+fn random_u64() -> u64 {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+    hasher.write_u128(nanos);
+    hasher.finish()
+}
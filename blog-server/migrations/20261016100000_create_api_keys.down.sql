@@ -0,0 +1,5 @@
+DROP INDEX IF EXISTS idx_api_keys_user_id;
+
+DROP TABLE IF EXISTS api_keys;
+
+DROP TYPE IF EXISTS api_key_scope;
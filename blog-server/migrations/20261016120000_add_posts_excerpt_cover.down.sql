@@ -0,0 +1,2 @@
+ALTER TABLE posts DROP COLUMN IF EXISTS cover_image_url;
+ALTER TABLE posts DROP COLUMN IF EXISTS excerpt;
@@ -0,0 +1 @@
+DROP INDEX IF EXISTS posts_title_trgm_idx;
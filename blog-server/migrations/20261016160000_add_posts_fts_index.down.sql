@@ -0,0 +1 @@
+DROP INDEX IF EXISTS posts_fts_idx;
@@ -0,0 +1 @@
+CREATE INDEX IF NOT EXISTS posts_fts_idx ON posts USING GIN (to_tsvector('simple', title || ' ' || content));
@@ -0,0 +1,7 @@
+DROP TABLE IF EXISTS media_variants;
+
+DROP INDEX IF EXISTS idx_media_owner_id;
+
+DROP TABLE IF EXISTS media;
+
+DROP TYPE IF EXISTS media_size;
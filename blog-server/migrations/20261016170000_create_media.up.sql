@@ -0,0 +1,20 @@
+CREATE TYPE media_size AS ENUM ('thumbnail', 'medium', 'original');
+
+CREATE TABLE IF NOT EXISTS media (
+    id BIGSERIAL PRIMARY KEY,
+    owner_id BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    content_type VARCHAR NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_media_owner_id ON media(owner_id);
+
+CREATE TABLE IF NOT EXISTS media_variants (
+    media_id BIGINT NOT NULL REFERENCES media(id) ON DELETE CASCADE,
+    size media_size NOT NULL,
+    width INTEGER NOT NULL,
+    height INTEGER NOT NULL,
+    byte_size BIGINT NOT NULL,
+    path VARCHAR NOT NULL,
+    PRIMARY KEY (media_id, size)
+);
@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS post_authors (
+    post_id BIGINT NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    user_id BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    added_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (post_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_post_authors_user_id ON post_authors(user_id);
+
+INSERT INTO post_authors (post_id, user_id, added_at)
+SELECT id, author_id, created_at FROM posts
+ON CONFLICT DO NOTHING;
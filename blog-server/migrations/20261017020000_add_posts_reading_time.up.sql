@@ -0,0 +1,7 @@
+ALTER TABLE posts ADD COLUMN IF NOT EXISTS reading_time_minutes INTEGER NOT NULL DEFAULT 1;
+
+UPDATE posts
+SET reading_time_minutes = GREATEST(
+    1,
+    CEIL(COALESCE(array_length(regexp_split_to_array(btrim(content), '\s+'), 1), 0) / 200.0)
+)::INTEGER;
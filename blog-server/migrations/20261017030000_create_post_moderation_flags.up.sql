@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS post_moderation_flags (
+    post_id BIGINT PRIMARY KEY REFERENCES posts(id) ON DELETE CASCADE,
+    reason TEXT NOT NULL,
+    flagged_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS posts_tenant_trending_idx;
+
+ALTER TABLE posts DROP COLUMN IF EXISTS trending_score;
+ALTER TABLE posts DROP COLUMN IF EXISTS view_count;
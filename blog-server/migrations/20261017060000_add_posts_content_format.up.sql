@@ -0,0 +1,3 @@
+ALTER TABLE posts ADD COLUMN IF NOT EXISTS content_format VARCHAR(16) NOT NULL DEFAULT 'plain'
+    CHECK (content_format IN ('markdown', 'plain', 'html'));
+ALTER TABLE posts ADD COLUMN IF NOT EXISTS content_html TEXT;
@@ -0,0 +1,188 @@
+//! Сервис администрирования.
+
+use crate::data::admin_repository::AdminRepository;
+use crate::data::invite_repository::InviteRepository;
+use crate::domain::admin::{
+    AdminStats, ContentReport, DEFAULT_STATS_CACHE_TTL_SECS, MigrationStatus,
+};
+use crate::domain::error::AdminError;
+use crate::domain::feature::{Feature, FeatureFlag};
+use crate::domain::invite::{
+    CreateInviteRequest, DEFAULT_INVITE_TTL_SECS, Invite, hash_invite_code,
+};
+use crate::domain::post::PostModerationFlag;
+use crate::infrastructure::cache::TtlCache;
+use crate::infrastructure::features::FeatureFlags;
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{TimeDelta, Utc};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Сервис для административных функций блога.
+#[derive(Debug)]
+pub(crate) struct AdminService {
+    /// Репозиторий для административных запросов.
+    admin_repository: Arc<AdminRepository>,
+
+    /// Кэш статистики по блогу и периоду в днях.
+    stats_cache: TtlCache<(i64, i32), AdminStats>,
+
+    /// Флаги функциональности.
+    features: Arc<FeatureFlags>,
+
+    /// Репозиторий для работы с приглашениями.
+    invite_repository: Arc<InviteRepository>,
+}
+
+impl AdminService {
+    /// Создать новый экземпляр сервиса администрирования.
+    pub(crate) fn new(
+        admin_repository: Arc<AdminRepository>,
+        features: Arc<FeatureFlags>,
+        invite_repository: Arc<InviteRepository>,
+    ) -> Self {
+        Self {
+            admin_repository,
+            stats_cache: TtlCache::new(Duration::from_secs(DEFAULT_STATS_CACHE_TTL_SECS)),
+            features,
+            invite_repository,
+        }
+    }
+
+    /// Задать время жизни кэша статистики.
+    pub(crate) fn with_stats_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.stats_cache = TtlCache::new(ttl);
+        self
+    }
+
+    /// Проверить, что пользователь является администратором.
+    pub(crate) async fn ensure_admin(&self, user_id: i64) -> Result<(), AdminError> {
+        if !self.admin_repository.is_admin(user_id).await? {
+            return Err(AdminError::Forbidden);
+        }
+
+        Ok(())
+    }
+
+    /// Получить посты блога, отмеченные фильтром содержимого для проверки модератором.
+    pub(crate) async fn get_flagged_posts(
+        &self,
+        tenant_id: i64,
+    ) -> Result<Vec<PostModerationFlag>, AdminError> {
+        self.admin_repository
+            .get_post_moderation_flags(tenant_id)
+            .await
+    }
+
+    /// Получить статистику блога за последние `days` дней.
+    ///
+    /// Статистика кэшируется, поэтому может отставать от текущего состояния.
+    pub(crate) async fn get_stats(
+        &self,
+        tenant_id: i64,
+        days: i32,
+    ) -> Result<AdminStats, AdminError> {
+        if let Some(stats) = self.stats_cache.get(&(tenant_id, days)) {
+            return Ok(stats);
+        }
+
+        let stats = AdminStats {
+            days,
+            totals: self.admin_repository.get_totals(tenant_id, days).await?,
+            daily: self
+                .admin_repository
+                .get_daily_stats(tenant_id, days)
+                .await?,
+            generated_at: Utc::now(),
+        };
+
+        self.stats_cache.insert((tenant_id, days), stats.clone());
+
+        Ok(stats)
+    }
+
+    /// Составить отчет о содержимом блога; частота публикаций считается за последние `days` дней.
+    pub(crate) async fn get_content_report(
+        &self,
+        tenant_id: i64,
+        days: i32,
+    ) -> Result<ContentReport, AdminError> {
+        Ok(ContentReport {
+            days,
+            totals: self.admin_repository.get_content_totals(tenant_id).await?,
+            authors: self
+                .admin_repository
+                .get_author_content_stats(tenant_id, days)
+                .await?,
+            generated_at: Utc::now(),
+        })
+    }
+
+    /// Получить состояние миграций БД.
+    pub(crate) async fn get_migration_status(&self) -> Result<MigrationStatus, AdminError> {
+        self.admin_repository.get_migration_status().await
+    }
+
+    /// Получить состояние всех флагов функциональности.
+    pub(crate) fn get_features(&self) -> Vec<FeatureFlag> {
+        self.features.list()
+    }
+
+    /// Включить или отключить функцию от имени администратора `admin_id`.
+    pub(crate) async fn set_feature(
+        &self,
+        name: &str,
+        enabled: bool,
+        admin_id: i64,
+    ) -> Result<FeatureFlag, AdminError> {
+        let feature = Feature::from_name(name).ok_or(AdminError::FeatureNotFound)?;
+
+        tracing::info!(
+            "Администратор {admin_id} {} функцию {feature}",
+            if enabled {
+                "включил"
+            } else {
+                "отключил"
+            }
+        );
+
+        Ok(self.features.set(feature, enabled).await?)
+    }
+
+    /// Вернуть флагу функциональности значение из конфигурации.
+    pub(crate) async fn reset_feature(
+        &self,
+        name: &str,
+        admin_id: i64,
+    ) -> Result<FeatureFlag, AdminError> {
+        let feature = Feature::from_name(name).ok_or(AdminError::FeatureNotFound)?;
+
+        tracing::info!("Администратор {admin_id} сбросил флаг функции {feature}");
+
+        Ok(self.features.reset(feature).await?)
+    }
+
+    /// Создать одноразовое приглашение для регистрации от имени администратора `admin_id`.
+    ///
+    /// В базе данных хранится только хеш кода, поэтому код возвращается один раз.
+    pub(crate) async fn create_invite(
+        &self,
+        request: CreateInviteRequest,
+        admin_id: i64,
+    ) -> Result<Invite, AdminError> {
+        let mut bytes = [0u8; 16];
+        OsRng.fill_bytes(&mut bytes);
+
+        let code = URL_SAFE_NO_PAD.encode(bytes);
+        let ttl = request.ttl_seconds.unwrap_or(DEFAULT_INVITE_TTL_SECS);
+        let expires_at = Utc::now() + TimeDelta::seconds(ttl as i64);
+
+        self.invite_repository
+            .create_invite(&hash_invite_code(&code), admin_id, expires_at)
+            .await?;
+
+        Ok(Invite { code, expires_at })
+    }
+}
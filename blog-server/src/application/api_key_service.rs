@@ -0,0 +1,70 @@
+//! Сервис персональных API-ключей.
+
+use crate::data::api_key_repository::ApiKeyRepository;
+use crate::domain::api_key::{ApiKey, ApiKeyOwner, CreateApiKeyRequest, CreateApiKeyResponse};
+use crate::domain::error::ApiKeyError;
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+/// Префикс значения API-ключа.
+const API_KEY_PREFIX: &str = "blog_";
+
+/// Сервис для управления персональными API-ключами пользователей.
+#[derive(Debug)]
+pub(crate) struct ApiKeyService {
+    /// Репозиторий для работы с API-ключами.
+    api_key_repository: Arc<ApiKeyRepository>,
+}
+
+impl ApiKeyService {
+    /// Создать новый экземпляр сервиса API-ключей.
+    pub(crate) fn new(api_key_repository: Arc<ApiKeyRepository>) -> Self {
+        Self { api_key_repository }
+    }
+
+    /// Выпустить новый API-ключ. В базе данных сохраняется только хеш ключа.
+    pub(crate) async fn create_api_key(
+        &self,
+        request: CreateApiKeyRequest,
+        user_id: i64,
+    ) -> Result<CreateApiKeyResponse, ApiKeyError> {
+        let mut bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut bytes);
+
+        let key = format!("{API_KEY_PREFIX}{}", URL_SAFE_NO_PAD.encode(bytes));
+
+        let api_key = self
+            .api_key_repository
+            .create_api_key(user_id, &request.name, &hash_key(&key), request.scope)
+            .await?;
+
+        Ok(CreateApiKeyResponse { key, api_key })
+    }
+
+    /// Получить список API-ключей пользователя.
+    pub(crate) async fn get_api_keys(&self, user_id: i64) -> Result<Vec<ApiKey>, ApiKeyError> {
+        self.api_key_repository.get_api_keys(user_id).await
+    }
+
+    /// Отозвать API-ключ пользователя.
+    pub(crate) async fn revoke_api_key(&self, id: i64, user_id: i64) -> Result<(), ApiKeyError> {
+        self.api_key_repository.delete_api_key(id, user_id).await
+    }
+
+    /// Найти владельца по значению API-ключа.
+    pub(crate) async fn authenticate(&self, key: &str) -> Result<ApiKeyOwner, ApiKeyError> {
+        if !key.starts_with(API_KEY_PREFIX) {
+            return Err(ApiKeyError::ApiKeyNotFound);
+        }
+
+        self.api_key_repository.get_owner(&hash_key(key)).await
+    }
+}
+
+/// Вычислить хеш значения API-ключа.
+fn hash_key(key: &str) -> String {
+    format!("{:x}", Sha256::digest(key.as_bytes()))
+}
@@ -1,12 +1,21 @@
 //! Сервис авторизации.
 
+use crate::application::login_history_service::LoginHistoryService;
 use crate::domain::error::UserError;
+use crate::domain::event::DomainEvent;
+use crate::domain::feature::Feature;
+use crate::domain::invite::hash_invite_code;
+use crate::domain::login::{LoginContext, LoginRecord};
 use crate::domain::user::{
-    CreateUserRequest, CreateUserResponse, LoginUserRequest, LoginUserResponse,
+    CreateUserRequest, CreateUserResponse, LoginUserRequest, LoginUserResponse, PasswordPolicy,
+    User,
 };
 use std::sync::Arc;
 
+use crate::data::invite_repository::InviteRepository;
 use crate::data::user_repository::UserRepository;
+use crate::infrastructure::events::EventBus;
+use crate::infrastructure::features::FeatureFlags;
 use crate::infrastructure::jwt::JwtService;
 use argon2::{Argon2, PasswordVerifier, password_hash::PasswordHash};
 
@@ -18,38 +27,129 @@ pub(crate) struct AuthService {
 
     /// Репозиторий для работы с пользователями.
     user_repository: Arc<UserRepository>,
+
+    /// Политика требований к паролям.
+    password_policy: PasswordPolicy,
+
+    /// Сервис истории входов.
+    login_history_service: Arc<LoginHistoryService>,
+
+    /// Флаги функциональности.
+    features: Arc<FeatureFlags>,
+
+    /// Репозиторий для работы с приглашениями.
+    invite_repository: Arc<InviteRepository>,
+
+    /// Шина доменных событий.
+    events: Arc<EventBus>,
+
+    /// Регистрация возможна только по приглашениям.
+    invite_only: bool,
 }
 
 impl AuthService {
     /// Создать новый экземпляр сервиса авторизации.
-    pub(crate) fn new(jwt_service: Arc<JwtService>, user_repository: Arc<UserRepository>) -> Self {
+    pub(crate) fn new(
+        jwt_service: Arc<JwtService>,
+        user_repository: Arc<UserRepository>,
+        password_policy: PasswordPolicy,
+        login_history_service: Arc<LoginHistoryService>,
+        features: Arc<FeatureFlags>,
+        invite_repository: Arc<InviteRepository>,
+        events: Arc<EventBus>,
+    ) -> Self {
         Self {
             jwt_service,
             user_repository,
+            password_policy,
+            login_history_service,
+            features,
+            invite_repository,
+            events,
+            invite_only: false,
         }
     }
 
-    /// Зарегистрировать нового пользователя.
+    /// Разрешить регистрацию только по приглашениям.
+    pub(crate) fn with_invite_only(mut self, invite_only: bool) -> Self {
+        self.invite_only = invite_only;
+        self
+    }
+
+    /// Зарегистрировать нового пользователя блога.
+    ///
+    /// Регистрация возможна, только пока включен флаг [`Feature::RegistrationOpen`].
+    /// В режиме регистрации по приглашениям код приглашения проверяется и погашается
+    /// в одной транзакции с созданием пользователя.
     pub(crate) async fn register(
         &self,
-        user: CreateUserRequest,
+        tenant_id: i64,
+        mut user: CreateUserRequest,
     ) -> Result<CreateUserResponse, UserError> {
-        let user = self.user_repository.create_user(user.try_into()?).await?;
+        if !self.features.is_enabled(Feature::RegistrationOpen) {
+            return Err(UserError::FeatureDisabled(Feature::RegistrationOpen));
+        }
+
+        self.password_policy.check(&user.password)?;
+
+        let invite_code = user.invite_code.take();
+
+        let user = User {
+            tenant_id,
+            ..user.try_into()?
+        };
+
+        let user = if self.invite_only {
+            let code = invite_code.ok_or(UserError::InvalidInvite)?;
+
+            let mut tx = self.user_repository.begin_transaction().await?;
+
+            let user = self
+                .user_repository
+                .create_user_with_tx(user, &mut *tx)
+                .await?;
+
+            if !self
+                .invite_repository
+                .use_invite_with_tx(&hash_invite_code(&code), user.id, &mut *tx)
+                .await?
+            {
+                return Err(UserError::InvalidInvite);
+            }
+
+            tx.commit().await?;
+
+            user
+        } else {
+            self.user_repository.create_user(user).await?
+        };
 
         let token = self
             .jwt_service
-            .generate_token(user.id, &user.username)
+            .generate_token(user.id, &user.username, user.tenant_id)
             .map_err(|e| UserError::CreateJwtToken(e.to_string()))?;
 
+        self.events.publish(DomainEvent::UserRegistered {
+            user_id: user.id,
+            username: user.username.clone(),
+        });
+
         Ok(CreateUserResponse { token, user })
     }
 
-    /// Авторизовать пользователя.
+    /// Авторизовать пользователя блога.
+    ///
+    /// Успешный вход записывается в историю входов пользователя.
     pub(crate) async fn login(
         &self,
+        tenant_id: i64,
         request: LoginUserRequest,
+        context: LoginContext,
     ) -> Result<LoginUserResponse, UserError> {
-        let user = self.user_repository.get_user(&request.username).await?;
+        let user = self
+            .user_repository
+            .get_user(tenant_id, &request.username)
+            .await?;
 
         let parsed_hash = PasswordHash::new(&user.password_hash)?;
 
@@ -59,9 +159,25 @@ impl AuthService {
 
         let token = self
             .jwt_service
-            .generate_token(user.id, &user.username)
+            .generate_token(user.id, &user.username, user.tenant_id)
             .map_err(|e| UserError::CreateJwtToken(e.to_string()))?;
 
+        self.login_history_service
+            .record_login(&user, &context)
+            .await;
+
         Ok(LoginUserResponse { token, user })
     }
+
+    /// Получить историю входов пользователя.
+    pub(crate) async fn get_logins(
+        &self,
+        user_id: i64,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<LoginRecord>, UserError> {
+        self.login_history_service
+            .get_logins(user_id, limit, offset)
+            .await
+    }
 }
@@ -1,92 +1,798 @@
 use crate::data::post_repository::PostRepository;
 use crate::domain::error::PostError;
-use crate::domain::post::{CreatePostRequest, Post, UpdatePostRequest};
+use crate::domain::event::DomainEvent;
+use crate::domain::feature::Feature;
+use crate::domain::post::{
+    AddPostAuthorRequest, BulkDeleteResult, CreatePostRequest, CreateShareLinkRequest,
+    DEFAULT_RELATED_POSTS_CACHE_TTL_SECS, DEFAULT_SHARE_LINK_TTL_SECS, DuplicatePostCheck,
+    MAX_BATCH_POSTS, MAX_RELATED_POSTS, MAX_SUGGEST_QUERY_LENGTH, MAX_SUGGESTIONS, Post, PostField,
+    PostFilter, PostMeta, PostPage, PostSuggestion, PostWithAuthor, ShareLink, TRENDING_GRAVITY,
+    UpdatePostRequest, normalize_cover_image_url, render_content_html,
+};
+use crate::infrastructure::cache::TtlCache;
+use crate::infrastructure::content_filter::{self, ContentFilter, ContentFilterAction};
+use crate::infrastructure::database::retry_transaction;
+use crate::infrastructure::events::EventBus;
+use crate::infrastructure::features::FeatureFlags;
+use crate::infrastructure::jwt::JwtService;
+use crate::infrastructure::search::SearchIndex;
 use sqlx::{Postgres, Transaction};
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 /// Сервис для управления постами блога.
 #[derive(Debug)]
 pub(crate) struct BlogService {
     /// Репозиторий для работы с постами.
     post_repository: Arc<PostRepository>,
+
+    /// Сервис для подписи ссылок совместного доступа.
+    jwt_service: Arc<JwtService>,
+
+    /// Поисковый индекс постов.
+    search_index: Arc<dyn SearchIndex>,
+
+    /// Флаги функциональности.
+    features: Arc<FeatureFlags>,
+
+    /// Шина доменных событий.
+    events: Arc<EventBus>,
+
+    /// Кэш идентификаторов похожих постов.
+    related_posts_cache: TtlCache<i64, Vec<i64>>,
+
+    /// Фильтр недопустимого содержимого и действие при его срабатывании.
+    content_filter: Option<(Arc<dyn ContentFilter>, ContentFilterAction)>,
+
+    /// Просмотры постов, еще не записанные в базу данных.
+    pending_views: Mutex<HashMap<i64, i64>>,
+
+    /// Проверка создаваемых постов на дубликаты (`None` - не проверять).
+    duplicate_check: Option<DuplicatePostCheck>,
 }
 
 impl BlogService {
     /// Создать новый экземпляр сервиса блога.
-    pub(crate) fn new(post_repository: Arc<PostRepository>) -> Self {
-        Self { post_repository }
+    pub(crate) fn new(
+        post_repository: Arc<PostRepository>,
+        jwt_service: Arc<JwtService>,
+        search_index: Arc<dyn SearchIndex>,
+        features: Arc<FeatureFlags>,
+        events: Arc<EventBus>,
+    ) -> Self {
+        Self {
+            post_repository,
+            jwt_service,
+            search_index,
+            features,
+            events,
+            related_posts_cache: TtlCache::new(Duration::from_secs(
+                DEFAULT_RELATED_POSTS_CACHE_TTL_SECS,
+            )),
+            content_filter: None,
+            pending_views: Mutex::default(),
+            duplicate_check: None,
+        }
+    }
+
+    /// Задать время жизни кэша похожих постов.
+    pub(crate) fn with_related_posts_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.related_posts_cache = TtlCache::new(ttl);
+        self
+    }
+
+    /// Отклонять создание поста, если у автора недавно был создан пост с тем же
+    /// или похожим заголовком.
+    pub(crate) fn with_duplicate_check(mut self, check: DuplicatePostCheck) -> Self {
+        self.duplicate_check = Some(check);
+        self
+    }
+
+    /// Проверять заголовок, описание и содержимое постов фильтром содержимого.
+    pub(crate) fn with_content_filter(
+        mut self,
+        filter: Arc<dyn ContentFilter>,
+        action: ContentFilterAction,
+    ) -> Self {
+        self.content_filter = Some((filter, action));
+        self
     }
 
-    /// Создать новый пост.
+    /// Создать новый пост в блоге.
+    ///
+    /// Ссылка на обложку сохраняется в каноническом виде (см. [`normalize_cover_image_url`]).
     pub(crate) async fn create_post(
         &self,
-        post: CreatePostRequest,
+        tenant_id: i64,
+        mut post: CreatePostRequest,
         author_id: i64,
     ) -> Result<Post, PostError> {
+        post.cover_image_url = post
+            .cover_image_url
+            .as_deref()
+            .and_then(normalize_cover_image_url);
+
+        let mut fields = vec![&mut post.title, &mut post.content];
+        fields.extend(post.excerpt.as_mut());
+        let flag_reason = self.filter_content(fields).await?;
+
+        let post = Post {
+            tenant_id,
+            ..post.into()
+        };
+
+        let post = self
+            .post_repository
+            .create_post(post, author_id, self.duplicate_check)
+            .await?;
+
+        if let Some(reason) = flag_reason {
+            self.post_repository.flag_post(post.id, &reason).await?;
+        }
+
+        self.events
+            .publish(DomainEvent::PostCreated(Arc::new(post.clone())));
+
+        Ok(post)
+    }
+
+    /// Получить пост по идентификатору.
+    ///
+    /// Приватный пост доступен только автору (`viewer_id`), для остальных он не существует.
+    pub(crate) async fn get_post(
+        &self,
+        tenant_id: i64,
+        id: i64,
+        viewer_id: Option<i64>,
+    ) -> Result<Post, PostError> {
+        let post = self.post_repository.get_post(tenant_id, id).await?;
+
+        if !post.is_visible_to(viewer_id) {
+            return Err(PostError::PostNotFound);
+        }
+
+        Ok(post)
+    }
+
+    /// Учесть просмотр поста.
+    ///
+    /// Просмотры накапливаются в памяти и записываются в базу данных при очередном
+    /// пересчете популярности, поэтому чтение поста не требует записи в базу данных.
+    pub(crate) fn record_view(&self, id: i64) {
+        let mut views = self.pending_views.lock().unwrap_or_else(|e| e.into_inner());
+
+        *views.entry(id).or_default() += 1;
+    }
+
+    /// Записать накопленные просмотры и пересчитать оценки популярности постов.
+    ///
+    /// Возвращает количество постов с обновленной оценкой.
+    pub(crate) async fn update_trending(&self) -> Result<u64, PostError> {
+        let views =
+            std::mem::take(&mut *self.pending_views.lock().unwrap_or_else(|e| e.into_inner()));
+
+        if !views.is_empty() {
+            let (ids, counts): (Vec<i64>, Vec<i64>) = views.into_iter().unzip();
+
+            if let Err(e) = self.post_repository.add_post_views(&ids, &counts).await {
+                let mut views = self.pending_views.lock().unwrap_or_else(|e| e.into_inner());
+
+                for (id, count) in ids.into_iter().zip(counts) {
+                    *views.entry(id).or_default() += count;
+                }
+
+                return Err(e);
+            }
+        }
+
         self.post_repository
-            .create_post(post.into(), author_id)
+            .update_trending_scores(TRENDING_GRAVITY)
             .await
     }
 
-    /// Получить пост по идентификатору.
-    pub(crate) async fn get_post(&self, id: i64) -> Result<Post, PostError> {
-        self.post_repository.get_post(id).await
+    /// Запустить периодический пересчет популярности постов с указанным интервалом.
+    pub(crate) fn spawn_trending_updates(self: &Arc<Self>, interval: Duration) {
+        let service = self.clone();
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(interval);
+
+            loop {
+                interval.tick().await;
+
+                match service.update_trending().await {
+                    Ok(posts) => tracing::debug!(posts, "Оценки популярности постов пересчитаны"),
+                    Err(e) => tracing::warn!("Не удалось пересчитать популярность постов: {e}"),
+                }
+            }
+        });
+    }
+
+    /// Получить сведения о посте без его содержимого (для запросов `HEAD`).
+    pub(crate) async fn get_post_meta(
+        &self,
+        tenant_id: i64,
+        id: i64,
+        viewer_id: Option<i64>,
+    ) -> Result<PostMeta, PostError> {
+        let post = self.post_repository.get_post_meta(tenant_id, id).await?;
+
+        if !post.is_visible_to(viewer_id) {
+            return Err(PostError::PostNotFound);
+        }
+
+        Ok(post)
+    }
+
+    /// Получить пост вместе с автором по идентификатору.
+    pub(crate) async fn get_post_with_author(
+        &self,
+        tenant_id: i64,
+        id: i64,
+        viewer_id: Option<i64>,
+    ) -> Result<PostWithAuthor, PostError> {
+        let post = self
+            .post_repository
+            .get_post_with_author(tenant_id, id)
+            .await?;
+
+        if !post.post.is_visible_to(viewer_id) {
+            return Err(PostError::PostNotFound);
+        }
+
+        Ok(post)
     }
 
     /// Получить список постов с пагинацией.
-    pub(crate) async fn get_posts(&self, limit: i64, offset: i64) -> Result<Vec<Post>, PostError> {
-        self.post_repository.get_posts(limit, offset).await
+    pub(crate) async fn get_posts(
+        &self,
+        filter: &PostFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Post>, PostError> {
+        self.post_repository.get_posts(filter, limit, offset).await
+    }
+
+    /// Получить количество постов, удовлетворяющих условиям отбора.
+    pub(crate) async fn count_posts(&self, filter: &PostFilter) -> Result<i64, PostError> {
+        self.post_repository.count_posts(filter).await
+    }
+
+    /// Получить посты по списку идентификаторов.
+    ///
+    /// Посты возвращаются в порядке запроса; несуществующие и недоступные пользователю
+    /// (`viewer_id`) приватные посты пропускаются. За один запрос можно получить не более
+    /// [`MAX_BATCH_POSTS`] постов.
+    pub(crate) async fn get_posts_by_ids(
+        &self,
+        tenant_id: i64,
+        ids: &[i64],
+        viewer_id: Option<i64>,
+    ) -> Result<Vec<Post>, PostError> {
+        if ids.is_empty() || ids.len() > MAX_BATCH_POSTS {
+            return Err(PostError::InvalidQuery(format!(
+                "Можно запросить от 1 до {MAX_BATCH_POSTS} постов!"
+            )));
+        }
+
+        let mut posts = self
+            .post_repository
+            .get_posts_by_ids(tenant_id, ids, viewer_id)
+            .await?;
+
+        posts.sort_by_key(|post| ids.iter().position(|id| *id == post.id));
+
+        Ok(posts)
+    }
+
+    /// Получить список постов вместе с авторами с пагинацией.
+    pub(crate) async fn get_posts_with_author(
+        &self,
+        filter: &PostFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<PostWithAuthor>, PostError> {
+        self.post_repository
+            .get_posts_with_author(filter, limit, offset)
+            .await
+    }
+
+    /// Получить страницу сокращенных представлений постов вместе с общим количеством постов.
+    pub(crate) async fn get_post_previews(
+        &self,
+        fields: &[PostField],
+        with_author: bool,
+        filter: &PostFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<PostPage, PostError> {
+        let (posts, total) = tokio::try_join!(
+            self.post_repository
+                .get_post_previews(fields, with_author, filter, limit, offset),
+            self.post_repository.count_posts(filter),
+        )?;
+
+        Ok(PostPage {
+            posts,
+            total,
+            limit,
+            offset,
+        })
+    }
+
+    /// Получить посты, похожие на указанный (для блока «читайте также»).
+    ///
+    /// Подборка кэшируется; видимость постов проверяется при каждом запросе.
+    pub(crate) async fn get_related_posts(
+        &self,
+        tenant_id: i64,
+        id: i64,
+        viewer_id: Option<i64>,
+    ) -> Result<Vec<Post>, PostError> {
+        if !self
+            .post_repository
+            .exists(tenant_id, id, viewer_id)
+            .await?
+        {
+            return Err(PostError::PostNotFound);
+        }
+
+        let ids = match self.related_posts_cache.get(&id) {
+            Some(ids) => ids,
+            None => {
+                let ids = self
+                    .post_repository
+                    .get_related_post_ids(id, MAX_RELATED_POSTS)
+                    .await?;
+
+                self.related_posts_cache.insert(id, ids.clone());
+
+                ids
+            }
+        };
+
+        let mut posts = self
+            .post_repository
+            .get_posts_by_ids(tenant_id, &ids, viewer_id)
+            .await?;
+
+        posts.sort_by_key(|post| ids.iter().position(|id| *id == post.id));
+
+        Ok(posts)
+    }
+
+    /// Найти посты по поисковому запросу (при указании `author_id` - только посты этого автора).
+    ///
+    /// Посты возвращаются в порядке релевантности.
+    pub(crate) async fn search_posts(
+        &self,
+        tenant_id: i64,
+        query: &str,
+        author_id: Option<i64>,
+        viewer_id: Option<i64>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Post>, PostError> {
+        let query = query.trim();
+
+        if query.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let ids = self
+            .search_index
+            .search(tenant_id, query, author_id, viewer_id, limit, offset)
+            .await?;
+
+        let mut posts = self
+            .post_repository
+            .get_posts_by_ids(tenant_id, &ids, viewer_id)
+            .await?;
+
+        posts.sort_by_key(|post| ids.iter().position(|id| *id == post.id));
+
+        Ok(posts)
+    }
+
+    /// Получить подсказки для строки поиска постов.
+    ///
+    /// Пустой запрос возвращает пустой список, слишком длинный - ошибку.
+    pub(crate) async fn suggest_posts(
+        &self,
+        tenant_id: i64,
+        query: &str,
+        viewer_id: Option<i64>,
+    ) -> Result<Vec<PostSuggestion>, PostError> {
+        let query = query.trim();
+
+        if query.is_empty() {
+            return Ok(vec![]);
+        }
+
+        if query.chars().count() > MAX_SUGGEST_QUERY_LENGTH {
+            return Err(PostError::InvalidQuery(format!(
+                "Поисковый запрос длиннее {MAX_SUGGEST_QUERY_LENGTH} символов!"
+            )));
+        }
+
+        self.post_repository
+            .suggest_posts(tenant_id, query, viewer_id, MAX_SUGGESTIONS)
+            .await
+    }
+
+    /// Создать ссылку для совместного доступа к посту.
+    ///
+    /// Ссылку может создать только автор поста.
+    pub(crate) async fn create_share_link(
+        &self,
+        tenant_id: i64,
+        id: i64,
+        request: CreateShareLinkRequest,
+        user_id: i64,
+    ) -> Result<ShareLink, PostError> {
+        self.ensure_share_links_enabled()?;
+
+        let post = self.post_repository.get_post_meta(tenant_id, id).await?;
+
+        if post.author_id != user_id {
+            return Err(PostError::Forbidden);
+        }
+
+        let ttl = Duration::from_secs(request.ttl_seconds.unwrap_or(DEFAULT_SHARE_LINK_TTL_SECS));
+
+        let (token, expires_at) = self
+            .jwt_service
+            .generate_share_token(post.id, ttl)
+            .map_err(|e| PostError::CreateShareLink(e.to_string()))?;
+
+        Ok(ShareLink { token, expires_at })
+    }
+
+    /// Получить пост блога по ссылке для совместного доступа (в том числе приватный).
+    pub(crate) async fn get_shared_post(
+        &self,
+        tenant_id: i64,
+        token: &str,
+    ) -> Result<Post, PostError> {
+        self.ensure_share_links_enabled()?;
+
+        let id = self
+            .jwt_service
+            .verify_share_token(token)
+            .map_err(|_| PostError::InvalidShareLink)?;
+
+        self.post_repository.get_post(tenant_id, id).await
     }
 
     /// Обновить существующий пост.
+    ///
+    /// Ссылка на обложку сохраняется в каноническом виде (см. [`normalize_cover_image_url`]).
     pub(crate) async fn update_post(
         &self,
-        post: UpdatePostRequest,
+        tenant_id: i64,
+        mut post: UpdatePostRequest,
         user_id: i64,
     ) -> Result<Post, PostError> {
-        let mut tx = self.post_repository.begin_transaction().await?;
+        post.cover_image_url = post
+            .cover_image_url
+            .as_deref()
+            .and_then(normalize_cover_image_url);
 
-        if !self.is_author(post.id, user_id, &mut tx).await? {
-            return Err(PostError::Forbidden);
-        }
+        let fields = [&mut post.title, &mut post.content, &mut post.excerpt]
+            .into_iter()
+            .flat_map(Option::as_mut)
+            .collect();
+        let flag_reason = self.filter_content(fields).await?;
+
+        let flag_reason = flag_reason.as_deref();
+
+        let post = retry_transaction(|| {
+            let post = post.clone();
+
+            async move {
+                let mut tx = self.post_repository.begin_transaction().await?;
+
+                let current = self
+                    .get_authored_post(tenant_id, post.id, user_id, &mut tx)
+                    .await?;
+
+                if current.is_archived() {
+                    return Err(PostError::Archived);
+                }
+
+                let content_changed = post.content.is_some() || post.content_format.is_some();
+
+                let mut post = self
+                    .post_repository
+                    .update_post_with_tx(post, &mut *tx)
+                    .await?;
+
+                if content_changed {
+                    post.content_html = render_content_html(post.content_format, &post.content);
+
+                    self.post_repository
+                        .set_content_html_with_tx(post.id, post.content_html.as_deref(), &mut *tx)
+                        .await?;
+                }
+
+                if let Some(reason) = flag_reason {
+                    self.post_repository
+                        .flag_post_with_tx(post.id, reason, &mut *tx)
+                        .await?;
+                }
+
+                tx.commit().await?;
 
-        let post = self.post_repository.update_post(post).await?;
+                Ok::<_, PostError>(post)
+            }
+        })
+        .await?;
 
-        tx.commit().await?;
+        self.events
+            .publish(DomainEvent::PostUpdated(Arc::new(post.clone())));
+
+        Ok(post)
+    }
+
+    /// Перевести пост в архив или вернуть его из архива.
+    ///
+    /// Архивный пост доступен для чтения, но не может быть изменен.
+    pub(crate) async fn set_archived(
+        &self,
+        tenant_id: i64,
+        id: i64,
+        archived: bool,
+        user_id: i64,
+    ) -> Result<Post, PostError> {
+        let post = retry_transaction(|| async move {
+            let mut tx = self.post_repository.begin_transaction().await?;
+
+            self.get_owned_post(tenant_id, id, user_id, &mut tx).await?;
+
+            let post = self
+                .post_repository
+                .set_archived_with_tx(id, archived, &mut *tx)
+                .await?;
+
+            tx.commit().await?;
+
+            Ok::<_, PostError>(post)
+        })
+        .await?;
+
+        self.events
+            .publish(DomainEvent::PostUpdated(Arc::new(post.clone())));
+
+        Ok(post)
+    }
+
+    /// Добавить соавтора поста.
+    ///
+    /// Добавлять соавторов может только владелец поста. Соавтор может
+    /// редактировать пост, но не может удалять или архивировать его.
+    pub(crate) async fn add_post_author(
+        &self,
+        tenant_id: i64,
+        id: i64,
+        request: AddPostAuthorRequest,
+        user_id: i64,
+    ) -> Result<Post, PostError> {
+        let post = retry_transaction(|| async move {
+            let mut tx = self.post_repository.begin_transaction().await?;
+
+            let post = self.get_owned_post(tenant_id, id, user_id, &mut tx).await?;
+
+            if post.is_archived() {
+                return Err(PostError::Archived);
+            }
+
+            self.post_repository
+                .add_post_author_with_tx(id, request.user_id, &mut *tx)
+                .await?;
+
+            let post = self
+                .post_repository
+                .get_post_with_tx(tenant_id, id, &mut *tx)
+                .await?;
+
+            tx.commit().await?;
+
+            Ok::<_, PostError>(post)
+        })
+        .await?;
+
+        self.events
+            .publish(DomainEvent::PostUpdated(Arc::new(post.clone())));
 
         Ok(post)
     }
 
     /// Удалить пост.
-    pub(crate) async fn delete_post(&self, id: i64, user_id: i64) -> Result<(), PostError> {
-        let mut tx = self.post_repository.begin_transaction().await?;
+    ///
+    /// Удалить пост может только его владелец.
+    pub(crate) async fn delete_post(
+        &self,
+        tenant_id: i64,
+        id: i64,
+        user_id: i64,
+    ) -> Result<(), PostError> {
+        let post = retry_transaction(|| async move {
+            let mut tx = self.post_repository.begin_transaction().await?;
+
+            let post = self.get_owned_post(tenant_id, id, user_id, &mut tx).await?;
+
+            self.post_repository
+                .delete_post_with_tx(id, &mut *tx)
+                .await?;
 
-        if !self.is_author(id, user_id, &mut tx).await? {
+            tx.commit().await?;
+
+            Ok::<_, PostError>(post)
+        })
+        .await?;
+
+        self.related_posts_cache.invalidate(&id);
+
+        self.events.publish(DomainEvent::PostDeleted {
+            id,
+            author_id: post.author_id,
+        });
+
+        Ok(())
+    }
+
+    /// Удалить несколько постов пользователя в одной транзакции.
+    ///
+    /// Отсутствующие и чужие посты пропускаются с указанием причины в результате,
+    /// остальные удаляются вместе. Результаты возвращаются в порядке запроса (повторы
+    /// идентификаторов отбрасываются). За один запрос можно удалить не более
+    /// [`MAX_BATCH_POSTS`] постов.
+    pub(crate) async fn bulk_delete_posts(
+        &self,
+        tenant_id: i64,
+        ids: &[i64],
+        user_id: i64,
+    ) -> Result<Vec<BulkDeleteResult>, PostError> {
+        if ids.is_empty() || ids.len() > MAX_BATCH_POSTS {
+            return Err(PostError::InvalidQuery(format!(
+                "Можно удалить от 1 до {MAX_BATCH_POSTS} постов!"
+            )));
+        }
+
+        let mut seen = HashSet::new();
+        let ids: Vec<i64> = ids.iter().copied().filter(|id| seen.insert(*id)).collect();
+        let ids = &ids;
+
+        let (results, deleted) = retry_transaction(|| async move {
+            let mut tx = self.post_repository.begin_transaction().await?;
+            let mut results = Vec::with_capacity(ids.len());
+            let mut deleted = Vec::new();
+
+            for &id in ids {
+                match self.get_owned_post(tenant_id, id, user_id, &mut tx).await {
+                    Ok(post) => {
+                        self.post_repository
+                            .delete_post_with_tx(id, &mut *tx)
+                            .await?;
+
+                        results.push(BulkDeleteResult::deleted(id));
+                        deleted.push(post);
+                    }
+                    Err(e @ (PostError::PostNotFound | PostError::Forbidden)) => {
+                        results.push(BulkDeleteResult::failed(id, &e));
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+
+            tx.commit().await?;
+
+            Ok::<_, PostError>((results, deleted))
+        })
+        .await?;
+
+        for post in deleted {
+            self.related_posts_cache.invalidate(&post.id);
+
+            self.events.publish(DomainEvent::PostDeleted {
+                id: post.id,
+                author_id: post.author_id,
+            });
+        }
+
+        Ok(results)
+    }
+
+    /// Проверить текстовые поля поста фильтром содержимого.
+    ///
+    /// В зависимости от настроенного действия запрос отклоняется, недопустимые
+    /// фрагменты маскируются на месте, либо возвращается причина, по которой пост
+    /// нужно отметить для проверки модератором.
+    async fn filter_content(&self, fields: Vec<&mut String>) -> Result<Option<String>, PostError> {
+        let Some((filter, action)) = &self.content_filter else {
+            return Ok(None);
+        };
+
+        let mut flagged = Vec::new();
+
+        for field in fields {
+            let fragments = filter.check(field).await?;
+
+            if fragments.is_empty() {
+                continue;
+            }
+
+            match action {
+                ContentFilterAction::Reject => {
+                    return Err(PostError::ContentRejected(fragments.join(", ")));
+                }
+                ContentFilterAction::Mask => *field = content_filter::mask(field, &fragments),
+                ContentFilterAction::Flag => flagged.extend(fragments),
+            }
+        }
+
+        Ok((!flagged.is_empty()).then(|| flagged.join(", ")))
+    }
+
+    /// Проверить, что ссылки совместного доступа не отключены.
+    fn ensure_share_links_enabled(&self) -> Result<(), PostError> {
+        if !self.features.is_enabled(Feature::ShareLinks) {
+            return Err(PostError::FeatureDisabled(Feature::ShareLinks));
+        }
+
+        Ok(())
+    }
+
+    /// Получить сведения о посте, проверив авторство (владение или соавторство) пользователя.
+    async fn get_authored_post(
+        &self,
+        tenant_id: i64,
+        post_id: i64,
+        user_id: i64,
+        tx: &mut Transaction<'static, Postgres>,
+    ) -> Result<PostMeta, PostError> {
+        let post = self
+            .post_repository
+            .get_post_meta_with_tx(tenant_id, post_id, &mut **tx)
+            .await?;
+
+        if !self.is_author(post_id, user_id, tx).await? {
             return Err(PostError::Forbidden);
         }
 
-        self.post_repository
-            .delete_post_with_tx(id, &mut *tx)
+        Ok(post)
+    }
+
+    /// Получить сведения о посте, проверив, что пользователь является его владельцем.
+    async fn get_owned_post(
+        &self,
+        tenant_id: i64,
+        post_id: i64,
+        user_id: i64,
+        tx: &mut Transaction<'static, Postgres>,
+    ) -> Result<PostMeta, PostError> {
+        let post = self
+            .post_repository
+            .get_post_meta_with_tx(tenant_id, post_id, &mut **tx)
             .await?;
 
-        tx.commit().await?;
+        if post.author_id != user_id {
+            return Err(PostError::Forbidden);
+        }
 
-        Ok(())
+        Ok(post)
     }
 
-    /// Проверить авторство пользователя для данного поста.
+    /// Проверить, является ли пользователь автором (владельцем или соавтором) поста.
     async fn is_author(
         &self,
         post_id: i64,
         user_id: i64,
         tx: &mut Transaction<'static, Postgres>,
     ) -> Result<bool, PostError> {
-        let author_id = self
-            .post_repository
-            .get_post_with_tx(post_id, &mut **tx)
-            .await?
-            .author_id;
-
-        Ok(user_id == author_id)
+        self.post_repository
+            .is_post_author_with_tx(post_id, user_id, &mut **tx)
+            .await
     }
 }
@@ -0,0 +1,118 @@
+//! Сервис периодической очистки устаревших данных.
+
+use crate::data::cleanup_repository::CleanupRepository;
+use crate::domain::error::CleanupError;
+use crate::infrastructure::media_storage::MediaStorage;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Минимальный возраст каталога изображения, после которого он может считаться потерянным.
+///
+/// Файл оригинала сохраняется до фиксации транзакции с записью об изображении,
+/// поэтому свежие каталоги без записи в базе данных не удаляются.
+pub(crate) const ORPHAN_MEDIA_MIN_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// Количество удаленных за один проход объектов.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct CleanupReport {
+    /// Истекшие запросы на смену email-адреса.
+    pub email_changes: u64,
+
+    /// Истекшие неиспользованные приглашения.
+    pub invites: u64,
+
+    /// Каталоги изображений без записи в базе данных.
+    pub orphaned_media: u64,
+}
+
+/// Сервис для удаления устаревших данных.
+#[derive(Debug)]
+pub(crate) struct CleanupService {
+    /// Репозиторий для удаления устаревших данных.
+    cleanup_repository: Arc<CleanupRepository>,
+
+    /// Хранилище файлов изображений.
+    media_storage: Arc<MediaStorage>,
+}
+
+impl CleanupService {
+    /// Создать новый экземпляр сервиса очистки.
+    pub(crate) fn new(
+        cleanup_repository: Arc<CleanupRepository>,
+        media_storage: Arc<MediaStorage>,
+    ) -> Self {
+        Self {
+            cleanup_repository,
+            media_storage,
+        }
+    }
+
+    /// Выполнить один проход очистки.
+    ///
+    /// Ошибка в одной из категорий записывается в лог и не прерывает очистку остальных.
+    pub(crate) async fn run(&self) -> CleanupReport {
+        let mut report = CleanupReport::default();
+
+        match self.cleanup_repository.delete_expired_email_changes().await {
+            Ok(count) => report.email_changes = count,
+            Err(e) => tracing::warn!("Не удалось удалить истекшие запросы на смену email: {e}"),
+        }
+
+        match self.cleanup_repository.delete_expired_invites().await {
+            Ok(count) => report.invites = count,
+            Err(e) => tracing::warn!("Не удалось удалить истекшие приглашения: {e}"),
+        }
+
+        match self.remove_orphaned_media().await {
+            Ok(count) => report.orphaned_media = count,
+            Err(e) => tracing::warn!("Не удалось удалить потерянные изображения: {e}"),
+        }
+
+        report
+    }
+
+    /// Удалить каталоги изображений, записи о которых уже удалены из базы данных.
+    async fn remove_orphaned_media(&self) -> Result<u64, CleanupError> {
+        let ids = self
+            .media_storage
+            .list_media_dirs(ORPHAN_MEDIA_MIN_AGE)
+            .await?;
+
+        if ids.is_empty() {
+            return Ok(0);
+        }
+
+        let mut removed = 0;
+
+        for id in self.cleanup_repository.get_missing_media_ids(&ids).await? {
+            match self.media_storage.remove_media_dir(id).await {
+                Ok(()) => removed += 1,
+                Err(e) => tracing::warn!("Не удалось удалить файлы изображения {id}: {e}"),
+            }
+        }
+
+        Ok(removed)
+    }
+
+    /// Запустить периодическую очистку с указанным интервалом.
+    pub(crate) fn spawn(self: &Arc<Self>, interval: Duration) {
+        let service = self.clone();
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(interval);
+
+            loop {
+                interval.tick().await;
+
+                let report = service.run().await;
+
+                tracing::info!(
+                    email_changes = report.email_changes,
+                    invites = report.invites,
+                    orphaned_media = report.orphaned_media,
+                    "Очистка устаревших данных завершена"
+                );
+            }
+        });
+    }
+}
@@ -0,0 +1,234 @@
+//! Сервис выгрузки данных пользователя.
+
+use crate::data::api_key_repository::ApiKeyRepository;
+use crate::data::export_repository::ExportRepository;
+use crate::data::media_repository::MediaRepository;
+use crate::data::post_repository::PostRepository;
+use crate::data::user_repository::UserRepository;
+use crate::domain::error::ExportError;
+use crate::domain::export::{DataExport, DataExportStatus, EXPORT_JOB_TIMEOUT_SECS};
+use crate::domain::feature::Feature;
+use crate::domain::media::MediaSize;
+use crate::domain::user::User;
+use crate::infrastructure::features::FeatureFlags;
+use crate::infrastructure::mailer::Mailer;
+use crate::infrastructure::media_storage::MediaStorage;
+use chrono::{TimeDelta, Utc};
+use serde::Serialize;
+use std::io::{Cursor, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// Источники данных пользователя, попадающих в выгрузку.
+#[derive(Debug, Clone)]
+pub(crate) struct ExportSources {
+    /// Репозиторий для работы с пользователями.
+    pub user_repository: Arc<UserRepository>,
+
+    /// Репозиторий для работы с постами.
+    pub post_repository: Arc<PostRepository>,
+
+    /// Репозиторий для работы с изображениями.
+    pub media_repository: Arc<MediaRepository>,
+
+    /// Репозиторий для работы с API-ключами.
+    pub api_key_repository: Arc<ApiKeyRepository>,
+
+    /// Хранилище файлов изображений.
+    pub media_storage: Arc<MediaStorage>,
+}
+
+/// Сервис для подготовки архивов с данными пользователей.
+///
+/// Архив собирается в фоновой задаче; по готовности пользователю отправляется письмо.
+#[derive(Debug, Clone)]
+pub(crate) struct ExportService {
+    /// Репозиторий для работы с выгрузками.
+    export_repository: Arc<ExportRepository>,
+
+    /// Источники данных пользователя.
+    sources: ExportSources,
+
+    /// Отправитель писем.
+    mailer: Arc<dyn Mailer>,
+
+    /// Каталог для хранения готовых архивов.
+    export_dir: PathBuf,
+
+    /// Публичный адрес сервера, по которому строятся ссылки в письмах.
+    public_url: String,
+
+    /// Флаги функциональности.
+    features: Arc<FeatureFlags>,
+}
+
+impl ExportService {
+    /// Создать новый экземпляр сервиса выгрузки.
+    pub(crate) fn new(
+        export_repository: Arc<ExportRepository>,
+        sources: ExportSources,
+        mailer: Arc<dyn Mailer>,
+        export_dir: impl Into<PathBuf>,
+        public_url: &str,
+        features: Arc<FeatureFlags>,
+    ) -> Self {
+        Self {
+            export_repository,
+            sources,
+            mailer,
+            export_dir: export_dir.into(),
+            public_url: public_url.trim_end_matches('/').to_string(),
+            features,
+        }
+    }
+
+    /// Запросить выгрузку данных пользователя.
+    ///
+    /// Если выгрузка уже готовится, новая не запускается и возвращается текущая.
+    pub(crate) async fn request_export(&self, user_id: i64) -> Result<DataExport, ExportError> {
+        if !self.features.is_enabled(Feature::DataExports) {
+            return Err(ExportError::FeatureDisabled(Feature::DataExports));
+        }
+
+        let stale_before = Utc::now() - TimeDelta::seconds(EXPORT_JOB_TIMEOUT_SECS);
+
+        match self
+            .export_repository
+            .start_export(user_id, stale_before)
+            .await?
+        {
+            Some(export) => {
+                tokio::spawn(self.clone().run_export(user_id));
+                Ok(export)
+            }
+            None => self.export_repository.get_export(user_id).await,
+        }
+    }
+
+    /// Получить состояние выгрузки данных пользователя.
+    pub(crate) async fn get_export(&self, user_id: i64) -> Result<DataExport, ExportError> {
+        self.export_repository.get_export(user_id).await
+    }
+
+    /// Открыть готовый архив с данными пользователя.
+    pub(crate) async fn open_archive(&self, user_id: i64) -> Result<tokio::fs::File, ExportError> {
+        Ok(tokio::fs::File::open(self.archive_path(user_id)).await?)
+    }
+
+    /// Подготовить архив и уведомить пользователя о готовности.
+    async fn run_export(self, user_id: i64) {
+        let (status, user) = match self.build_archive(user_id).await {
+            Ok(user) => (DataExportStatus::Ready, Some(user)),
+            Err(e) => {
+                tracing::warn!(
+                    "Не удалось подготовить выгрузку данных пользователя {user_id}: {e}"
+                );
+                (DataExportStatus::Failed, None)
+            }
+        };
+
+        if let Err(e) = self.export_repository.finish_export(user_id, status).await {
+            tracing::warn!("Не удалось сохранить статус выгрузки пользователя {user_id}: {e}");
+            return;
+        }
+
+        let Some(user) = user else {
+            return;
+        };
+
+        let body = format!(
+            "Архив с вашими данными готов и доступен для скачивания по ссылке:\n\
+            {}/api/v1/users/me/export\n\n\
+            Для скачивания требуется авторизация.",
+            self.public_url
+        );
+
+        if let Err(e) = self
+            .mailer
+            .send(&user.email, "Архив с вашими данными готов", &body)
+            .await
+        {
+            tracing::warn!(
+                "Не удалось уведомить пользователя {user_id} о готовности выгрузки: {e}"
+            );
+        }
+    }
+
+    /// Собрать данные пользователя и сохранить архив.
+    async fn build_archive(&self, user_id: i64) -> Result<User, ExportError> {
+        let sources = &self.sources;
+
+        let user = sources.user_repository.get_user_by_id(user_id).await?;
+        let posts = sources.post_repository.get_posts_by_author(user_id).await?;
+        let api_keys = sources.api_key_repository.get_api_keys(user_id).await?;
+
+        let mut media = vec![];
+        let mut media_files = vec![];
+
+        for id in sources
+            .media_repository
+            .get_media_ids_by_owner(user_id)
+            .await?
+        {
+            let item = sources
+                .media_repository
+                .get_media(user.tenant_id, id)
+                .await?;
+
+            if let Some(original) = item.variant(MediaSize::Original) {
+                let bytes = sources.media_storage.read(&original.path).await?;
+                media_files.push((format!("media/{}", original.path), bytes));
+            }
+
+            media.push(item);
+        }
+
+        let mut entries = vec![
+            json_entry("profile.json", &user)?,
+            json_entry("posts.json", &posts)?,
+            json_entry("media.json", &media)?,
+            json_entry("api_keys.json", &api_keys)?,
+        ];
+        entries.extend(media_files);
+
+        let archive = tokio::task::spawn_blocking(move || write_archive(entries))
+            .await
+            .map_err(std::io::Error::other)??;
+
+        tokio::fs::create_dir_all(&self.export_dir).await?;
+
+        // Архив записывается во временный файл, чтобы не отдать недописанный.
+        let path = self.archive_path(user_id);
+        let tmp_path = path.with_extension("zip.tmp");
+
+        tokio::fs::write(&tmp_path, archive).await?;
+        tokio::fs::rename(tmp_path, path).await?;
+
+        Ok(user)
+    }
+
+    /// Путь к архиву с данными пользователя.
+    fn archive_path(&self, user_id: i64) -> PathBuf {
+        self.export_dir.join(format!("{user_id}.zip"))
+    }
+}
+
+/// Сериализовать данные в JSON-файл архива.
+fn json_entry<T: Serialize>(name: &str, data: &T) -> Result<(String, Vec<u8>), ExportError> {
+    Ok((name.to_string(), serde_json::to_vec_pretty(data)?))
+}
+
+/// Упаковать файлы в zip-архив.
+fn write_archive(entries: Vec<(String, Vec<u8>)>) -> Result<Vec<u8>, ExportError> {
+    let mut zip = ZipWriter::new(Cursor::new(vec![]));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    for (name, bytes) in entries {
+        zip.start_file(name, options)?;
+        zip.write_all(&bytes)?;
+    }
+
+    Ok(zip.finish()?.into_inner())
+}
@@ -0,0 +1,142 @@
+//! Сервис истории входов пользователя.
+
+use crate::data::login_history_repository::LoginHistoryRepository;
+use crate::data::settings_repository::SettingsRepository;
+use crate::domain::error::UserError;
+use crate::domain::login::{LoginContext, LoginRecord};
+use crate::domain::user::User;
+use crate::infrastructure::mailer::Mailer;
+use std::sync::Arc;
+
+/// Сервис для учета входов пользователей и оповещения о подозрительных входах.
+#[derive(Debug)]
+pub(crate) struct LoginHistoryService {
+    /// Репозиторий для работы с историей входов.
+    login_history_repository: Arc<LoginHistoryRepository>,
+
+    /// Репозиторий настроек пользователей (оповещения можно отключить в настройках).
+    settings_repository: Arc<SettingsRepository>,
+
+    /// Отправитель писем.
+    mailer: Arc<dyn Mailer>,
+
+    /// Оповещать пользователя по email о входе с нового устройства или из новой страны.
+    notify: bool,
+}
+
+impl LoginHistoryService {
+    /// Создать новый экземпляр сервиса истории входов.
+    pub(crate) fn new(
+        login_history_repository: Arc<LoginHistoryRepository>,
+        settings_repository: Arc<SettingsRepository>,
+        mailer: Arc<dyn Mailer>,
+    ) -> Self {
+        Self {
+            login_history_repository,
+            settings_repository,
+            mailer,
+            notify: false,
+        }
+    }
+
+    /// Включить оповещения о подозрительных входах по email.
+    pub(crate) fn with_notifications(mut self, notify: bool) -> Self {
+        self.notify = notify;
+        self
+    }
+
+    /// Записать вход пользователя.
+    ///
+    /// Ошибки записи не препятствуют входу и только записываются в лог.
+    pub(crate) async fn record_login(&self, user: &User, context: &LoginContext) {
+        let record = match self
+            .login_history_repository
+            .add_login(user.id, context)
+            .await
+        {
+            Ok(record) => record,
+            Err(e) => {
+                tracing::warn!("Не удалось записать вход пользователя {}: {e}", user.id);
+                return;
+            }
+        };
+
+        if !record.is_anomalous() {
+            return;
+        }
+
+        let description = describe_login(&record);
+
+        tracing::warn!(
+            "Подозрительный вход пользователя {} ({}): {description}",
+            user.id,
+            user.username
+        );
+
+        if !self.notify {
+            return;
+        }
+
+        match self.settings_repository.get_settings(user.id).await {
+            Ok(settings) if !settings.email_notifications.login_alerts => return,
+            Ok(_) => {}
+            Err(e) => {
+                tracing::warn!(
+                    "Не удалось получить настройки пользователя {}: {e}",
+                    user.id
+                );
+            }
+        }
+
+        let body = format!(
+            "В ваш аккаунт {} выполнен вход {description}.\n\n\
+            Если это были не вы, смените пароль и отзовите API-ключи.",
+            user.username
+        );
+
+        if let Err(e) = self
+            .mailer
+            .send(&user.email, "Подозрительный вход в аккаунт", &body)
+            .await
+        {
+            tracing::warn!(
+                "Не удалось оповестить пользователя {} о входе: {e}",
+                user.id
+            );
+        }
+    }
+
+    /// Получить историю входов пользователя.
+    pub(crate) async fn get_logins(
+        &self,
+        user_id: i64,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<LoginRecord>, UserError> {
+        self.login_history_repository
+            .get_logins(user_id, limit, offset)
+            .await
+    }
+}
+
+/// Описать вход для лога и письма пользователю.
+fn describe_login(record: &LoginRecord) -> String {
+    let mut reasons = vec![];
+
+    if record.new_device {
+        reasons.push("с нового устройства");
+    }
+
+    if record.new_country {
+        reasons.push("из новой страны");
+    }
+
+    format!(
+        "{} (время: {}, IP: {}, устройство: {}, страна: {})",
+        reasons.join(" и "),
+        record.created_at,
+        record.ip.as_deref().unwrap_or("неизвестен"),
+        record.user_agent.as_deref().unwrap_or("неизвестно"),
+        record.country.as_deref().unwrap_or("неизвестна"),
+    )
+}
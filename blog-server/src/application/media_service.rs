@@ -0,0 +1,221 @@
+//! Сервис загрузки изображений.
+
+use crate::data::media_repository::MediaRepository;
+use crate::domain::error::MediaError;
+use crate::domain::feature::Feature;
+use crate::domain::media::{
+    MAX_IMAGE_DIMENSION, Media, MediaSize, MediaVariant, SUPPORTED_FORMATS,
+};
+use crate::infrastructure::features::FeatureFlags;
+use crate::infrastructure::media_storage::MediaStorage;
+use image::{DynamicImage, ImageFormat, ImageReader, Limits};
+use std::io::Cursor;
+use std::sync::Arc;
+
+/// Сервис для загрузки изображений и подготовки их уменьшенных вариантов.
+#[derive(Debug)]
+pub(crate) struct MediaService {
+    /// Репозиторий для работы с изображениями.
+    media_repository: Arc<MediaRepository>,
+
+    /// Хранилище файлов изображений.
+    storage: Arc<MediaStorage>,
+
+    /// Флаги функциональности.
+    features: Arc<FeatureFlags>,
+}
+
+impl MediaService {
+    /// Создать новый экземпляр сервиса изображений.
+    pub(crate) fn new(
+        media_repository: Arc<MediaRepository>,
+        storage: Arc<MediaStorage>,
+        features: Arc<FeatureFlags>,
+    ) -> Self {
+        Self {
+            media_repository,
+            storage,
+            features,
+        }
+    }
+
+    /// Загрузить изображение.
+    ///
+    /// Исходное изображение сохраняется сразу, уменьшенные варианты генерируются
+    /// в фоновой задаче и становятся доступны по мере готовности.
+    pub(crate) async fn upload(&self, owner_id: i64, bytes: Vec<u8>) -> Result<Media, MediaError> {
+        if !self.features.is_enabled(Feature::MediaUploads) {
+            return Err(MediaError::FeatureDisabled(Feature::MediaUploads));
+        }
+
+        let (format, image, bytes) = tokio::task::spawn_blocking(move || {
+            decode(&bytes).map(|(format, image)| (format, image, bytes))
+        })
+        .await
+        .map_err(std::io::Error::other)??;
+
+        let mut tx = self.media_repository.begin_transaction().await?;
+
+        let mut media = self
+            .media_repository
+            .create_media_with_tx(owner_id, format.to_mime_type(), &mut *tx)
+            .await?;
+
+        let original = MediaVariant {
+            size: MediaSize::Original,
+            width: image.width() as i32,
+            height: image.height() as i32,
+            byte_size: bytes.len() as i64,
+            path: variant_path(media.id, MediaSize::Original, format),
+        };
+
+        self.storage.save(&original.path, &bytes).await?;
+
+        self.media_repository
+            .add_variant_with_tx(media.id, &original, &mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        media.variants.push(original);
+
+        tokio::spawn(generate_variants(
+            self.media_repository.clone(),
+            self.storage.clone(),
+            media.id,
+            format,
+            image,
+        ));
+
+        Ok(media)
+    }
+
+    /// Получить информацию об изображении блога.
+    pub(crate) async fn get_media(&self, tenant_id: i64, id: i64) -> Result<Media, MediaError> {
+        self.media_repository.get_media(tenant_id, id).await
+    }
+
+    /// Получить содержимое изображения нужного размера вместе с его MIME-типом.
+    pub(crate) async fn get_image(
+        &self,
+        tenant_id: i64,
+        id: i64,
+        size: MediaSize,
+    ) -> Result<(String, Vec<u8>), MediaError> {
+        let media = self.media_repository.get_media(tenant_id, id).await?;
+
+        let variant = media.variant(size).ok_or(MediaError::MediaNotFound)?;
+
+        let bytes = self.storage.read(&variant.path).await?;
+
+        Ok((media.content_type, bytes))
+    }
+}
+
+/// Определить формат и декодировать изображение, проверив его размеры.
+fn decode(bytes: &[u8]) -> Result<(ImageFormat, DynamicImage), MediaError> {
+    let format = image::guess_format(bytes).map_err(|_| MediaError::UnsupportedMediaType)?;
+
+    if !SUPPORTED_FORMATS.contains(&format) {
+        return Err(MediaError::UnsupportedMediaType);
+    }
+
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_IMAGE_DIMENSION);
+    limits.max_image_height = Some(MAX_IMAGE_DIMENSION);
+
+    let mut reader = ImageReader::with_format(Cursor::new(bytes), format);
+    reader.limits(limits);
+
+    let image = reader
+        .decode()
+        .map_err(|e| MediaError::InvalidImage(e.to_string()))?;
+
+    Ok((format, image))
+}
+
+/// Уменьшить изображение и закодировать его в исходном формате.
+fn resize(
+    image: &DynamicImage,
+    max_dimension: u32,
+    format: ImageFormat,
+) -> image::ImageResult<(DynamicImage, Vec<u8>)> {
+    let resized = image.thumbnail(max_dimension, max_dimension);
+
+    // JPEG не поддерживает альфа-канал.
+    let encoded_image = match format {
+        ImageFormat::Jpeg => DynamicImage::ImageRgb8(resized.to_rgb8()),
+        _ => resized.clone(),
+    };
+
+    let mut bytes = vec![];
+    encoded_image.write_to(&mut Cursor::new(&mut bytes), format)?;
+
+    Ok((resized, bytes))
+}
+
+/// Путь к файлу варианта изображения в хранилище.
+fn variant_path(media_id: i64, size: MediaSize, format: ImageFormat) -> String {
+    let extension = format.extensions_str().first().copied().unwrap_or("img");
+
+    format!("{media_id}/{}.{extension}", size.as_str())
+}
+
+/// Сгенерировать уменьшенные варианты изображения.
+///
+/// Варианты, не меньшие исходного изображения, не создаются.
+async fn generate_variants(
+    media_repository: Arc<MediaRepository>,
+    storage: Arc<MediaStorage>,
+    media_id: i64,
+    format: ImageFormat,
+    image: DynamicImage,
+) {
+    let image = Arc::new(image);
+
+    for &size in MediaSize::RESIZED {
+        let Some(max_dimension) = size.max_dimension() else {
+            continue;
+        };
+
+        if image.width() <= max_dimension && image.height() <= max_dimension {
+            continue;
+        }
+
+        let source = image.clone();
+        let resized =
+            tokio::task::spawn_blocking(move || resize(&source, max_dimension, format)).await;
+
+        let (resized, bytes) = match resized {
+            Ok(Ok(resized)) => resized,
+            Ok(Err(e)) => {
+                tracing::warn!("Не удалось уменьшить изображение {media_id}: {e}");
+                continue;
+            }
+            Err(e) => {
+                tracing::warn!("Не удалось уменьшить изображение {media_id}: {e}");
+                continue;
+            }
+        };
+
+        let variant = MediaVariant {
+            size,
+            width: resized.width() as i32,
+            height: resized.height() as i32,
+            byte_size: bytes.len() as i64,
+            path: variant_path(media_id, size, format),
+        };
+
+        let saved = match storage.save(&variant.path, &bytes).await {
+            Ok(()) => media_repository.add_variant(media_id, &variant).await,
+            Err(e) => Err(e.into()),
+        };
+
+        if let Err(e) = saved {
+            tracing::warn!(
+                "Не удалось сохранить вариант {} изображения {media_id}: {e}",
+                size.as_str()
+            );
+        }
+    }
+}
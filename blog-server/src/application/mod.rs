@@ -1,2 +1,10 @@
+pub(crate) mod admin_service;
+pub(crate) mod api_key_service;
 pub(crate) mod auth_service;
 pub(crate) mod blog_service;
+pub(crate) mod cleanup_service;
+pub(crate) mod export_service;
+pub(crate) mod login_history_service;
+pub(crate) mod media_service;
+pub(crate) mod tenant_service;
+pub(crate) mod user_service;
@@ -0,0 +1,117 @@
+//! Сервис блогов (арендаторов) инсталляции.
+
+use crate::data::tenant_repository::TenantRepository;
+use crate::domain::error::TenantError;
+use crate::domain::tenant::{
+    CreateTenantRequest, DEFAULT_TENANT_ID, TENANT_CACHE_TTL_SECS, Tenant, normalize_hostname,
+};
+use crate::infrastructure::cache::TtlCache;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Сервис для определения блога запроса и управления блогами.
+#[derive(Debug)]
+pub(crate) struct TenantService {
+    /// Репозиторий для работы с блогами.
+    tenant_repository: Arc<TenantRepository>,
+
+    /// Кэш идентификаторов блогов по короткому имени.
+    slugs: TtlCache<String, i64>,
+
+    /// Кэш идентификаторов блогов по доменному имени (`None` - домен не привязан к блогу).
+    hostnames: TtlCache<String, Option<i64>>,
+}
+
+impl TenantService {
+    /// Создать новый экземпляр сервиса блогов.
+    pub(crate) fn new(tenant_repository: Arc<TenantRepository>) -> Self {
+        let ttl = Duration::from_secs(TENANT_CACHE_TTL_SECS);
+
+        Self {
+            tenant_repository,
+            slugs: TtlCache::new(ttl),
+            hostnames: TtlCache::new(ttl),
+        }
+    }
+
+    /// Определить блог запроса.
+    ///
+    /// Короткое имя блога из заголовка запроса имеет приоритет и должно существовать;
+    /// иначе блог ищется по доменному имени, а если домен не привязан ни к одному
+    /// блогу, запрос относится к основному блогу.
+    pub(crate) async fn resolve(
+        &self,
+        slug: Option<&str>,
+        host: Option<&str>,
+    ) -> Result<i64, TenantError> {
+        if let Some(slug) = slug.map(str::trim).filter(|slug| !slug.is_empty()) {
+            if let Some(id) = self.slugs.get(&slug.to_string()) {
+                return Ok(id);
+            }
+
+            let tenant = self.tenant_repository.get_tenant_by_slug(slug).await?;
+            self.slugs.insert(tenant.slug, tenant.id);
+
+            return Ok(tenant.id);
+        }
+
+        let Some(hostname) = host.map(normalize_hostname) else {
+            return Ok(DEFAULT_TENANT_ID);
+        };
+
+        let id = match self.hostnames.get(&hostname) {
+            Some(id) => id,
+            None => {
+                let id = self
+                    .tenant_repository
+                    .find_tenant_by_hostname(&hostname)
+                    .await?
+                    .map(|tenant| tenant.id);
+
+                self.hostnames.insert(hostname, id);
+
+                id
+            }
+        };
+
+        Ok(id.unwrap_or(DEFAULT_TENANT_ID))
+    }
+
+    /// Получить список всех блогов.
+    pub(crate) async fn get_tenants(&self) -> Result<Vec<Tenant>, TenantError> {
+        self.tenant_repository.get_tenants().await
+    }
+
+    /// Создать новый блог.
+    pub(crate) async fn create_tenant(
+        &self,
+        request: CreateTenantRequest,
+    ) -> Result<Tenant, TenantError> {
+        let tenant = self.tenant_repository.create_tenant(&request).await?;
+
+        if let Some(hostname) = &tenant.hostname {
+            self.hostnames.invalidate(hostname);
+        }
+
+        Ok(tenant)
+    }
+
+    /// Удалить пустой блог.
+    ///
+    /// Основной блог удалить нельзя.
+    pub(crate) async fn delete_tenant(&self, id: i64) -> Result<(), TenantError> {
+        if id == DEFAULT_TENANT_ID {
+            return Err(TenantError::DefaultTenant);
+        }
+
+        let tenant = self.tenant_repository.delete_tenant(id).await?;
+
+        self.slugs.invalidate(&tenant.slug);
+
+        if let Some(hostname) = &tenant.hostname {
+            self.hostnames.invalidate(hostname);
+        }
+
+        Ok(())
+    }
+}
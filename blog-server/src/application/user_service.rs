@@ -0,0 +1,209 @@
+//! Сервис профиля пользователя.
+
+use crate::data::settings_repository::SettingsRepository;
+use crate::data::user_repository::UserRepository;
+use crate::domain::error::UserError;
+use crate::domain::media::{Media, MediaSize};
+use crate::domain::settings::UserSettings;
+use crate::domain::user::{
+    ChangeEmailRequest, EMAIL_CHANGE_COOLDOWN_SECS, EMAIL_CONFIRMATION_TTL_SECS, PublicProfile,
+    UpdateProfileRequest, User,
+};
+use crate::infrastructure::mailer::Mailer;
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{TimeDelta, Utc};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+/// Сервис для управления профилем пользователя.
+#[derive(Debug)]
+pub(crate) struct UserService {
+    /// Репозиторий для работы с пользователями.
+    user_repository: Arc<UserRepository>,
+
+    /// Репозиторий для работы с настройками пользователей.
+    settings_repository: Arc<SettingsRepository>,
+
+    /// Отправитель писем.
+    mailer: Arc<dyn Mailer>,
+
+    /// Публичный адрес сервера, по которому строятся ссылки в письмах.
+    public_url: String,
+}
+
+impl UserService {
+    /// Создать новый экземпляр сервиса профиля.
+    pub(crate) fn new(
+        user_repository: Arc<UserRepository>,
+        settings_repository: Arc<SettingsRepository>,
+        mailer: Arc<dyn Mailer>,
+        public_url: &str,
+    ) -> Self {
+        Self {
+            user_repository,
+            settings_repository,
+            mailer,
+            public_url: public_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Установить загруженное изображение аватаром пользователя.
+    pub(crate) async fn set_avatar(&self, user_id: i64, media: &Media) -> Result<User, UserError> {
+        let avatar_url = format!("{}?size={}", media.url, MediaSize::Thumbnail.as_str());
+
+        self.user_repository
+            .set_avatar_url(user_id, Some(&avatar_url))
+            .await
+    }
+
+    /// Удалить аватар пользователя (будет использоваться Gravatar).
+    pub(crate) async fn remove_avatar(&self, user_id: i64) -> Result<User, UserError> {
+        self.user_repository.set_avatar_url(user_id, None).await
+    }
+
+    /// Получить публичный профиль пользователя блога по имени пользователя.
+    pub(crate) async fn get_public_profile(
+        &self,
+        tenant_id: i64,
+        username: &str,
+    ) -> Result<PublicProfile, UserError> {
+        self.user_repository
+            .get_public_profile(tenant_id, username)
+            .await
+    }
+
+    /// Получить информацию о пользователе по идентификатору.
+    pub(crate) async fn get_user(&self, user_id: i64) -> Result<User, UserError> {
+        self.user_repository.get_user_by_id(user_id).await
+    }
+
+    /// Изменить профиль пользователя.
+    ///
+    /// Новый email-адрес, как и при [`Self::request_email_change`], вступает в силу только
+    /// после подтверждения, поэтому в возвращаемом профиле остается прежний адрес.
+    pub(crate) async fn update_profile(
+        &self,
+        user_id: i64,
+        request: UpdateProfileRequest,
+    ) -> Result<User, UserError> {
+        if let Some(email) = request.email {
+            self.request_email_change(user_id, ChangeEmailRequest { email })
+                .await?;
+        }
+
+        if request.remove_avatar {
+            return self.remove_avatar(user_id).await;
+        }
+
+        self.get_user(user_id).await
+    }
+
+    /// Получить настройки пользователя.
+    pub(crate) async fn get_settings(&self, user_id: i64) -> Result<UserSettings, UserError> {
+        self.settings_repository.get_settings(user_id).await
+    }
+
+    /// Сохранить настройки пользователя (заменяют ранее сохраненные).
+    pub(crate) async fn update_settings(
+        &self,
+        user_id: i64,
+        settings: UserSettings,
+    ) -> Result<UserSettings, UserError> {
+        self.settings_repository
+            .save_settings(user_id, &settings)
+            .await
+    }
+
+    /// Запросить смену email-адреса.
+    ///
+    /// На новый адрес отправляется ссылка подтверждения; до перехода по ней действует
+    /// прежний адрес. Повторный запрос возможен не чаще раза в
+    /// [`EMAIL_CHANGE_COOLDOWN_SECS`] секунд и заменяет предыдущий.
+    pub(crate) async fn request_email_change(
+        &self,
+        user_id: i64,
+        request: ChangeEmailRequest,
+    ) -> Result<(), UserError> {
+        let now = Utc::now();
+
+        let mut tx = self.user_repository.begin_transaction().await?;
+
+        if self
+            .user_repository
+            .email_exists_with_tx(user_id, &request.email, &mut *tx)
+            .await?
+        {
+            return Err(UserError::UserAlreadyExists);
+        }
+
+        let mut bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut bytes);
+
+        let token = URL_SAFE_NO_PAD.encode(bytes);
+
+        let saved = self
+            .user_repository
+            .save_email_change_with_tx(
+                user_id,
+                &request.email,
+                &hash_token(&token),
+                now + TimeDelta::seconds(EMAIL_CONFIRMATION_TTL_SECS),
+                now - TimeDelta::seconds(EMAIL_CHANGE_COOLDOWN_SECS),
+                &mut *tx,
+            )
+            .await?;
+
+        if !saved {
+            return Err(UserError::TooManyRequests);
+        }
+
+        let body = format!(
+            "Для подтверждения нового email-адреса перейдите по ссылке:\n\
+            {}/api/v1/auth/confirm-email?token={token}\n\n\
+            Ссылка действительна {} ч. Если вы не запрашивали смену адреса, \
+            просто проигнорируйте это письмо.",
+            self.public_url,
+            EMAIL_CONFIRMATION_TTL_SECS / 3600
+        );
+
+        self.mailer
+            .send(&request.email, "Подтверждение email-адреса", &body)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Подтвердить смену email-адреса по токену из письма.
+    pub(crate) async fn confirm_email(&self, token: &str) -> Result<User, UserError> {
+        let mut tx = self.user_repository.begin_transaction().await?;
+
+        let change = self
+            .user_repository
+            .take_email_change_with_tx(&hash_token(token), &mut *tx)
+            .await?
+            .ok_or(UserError::InvalidEmailConfirmation)?;
+
+        if change.expires_at < Utc::now() {
+            tx.commit().await?;
+            return Err(UserError::InvalidEmailConfirmation);
+        }
+
+        let user = self
+            .user_repository
+            .update_email_with_tx(change.user_id, &change.new_email, &mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(user)
+    }
+}
+
+/// Вычислить хеш токена подтверждения.
+fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
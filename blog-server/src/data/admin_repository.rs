@@ -0,0 +1,243 @@
+//! Репозиторий для административных запросов к базе данных.
+
+use crate::domain::admin::{
+    AuthorContentStats, ContentTotals, DailyStats, MigrationStatus, StatsTotals,
+};
+use crate::domain::error::AdminError;
+use crate::domain::media::MEDIA_URL_PREFIX;
+use crate::domain::post::PostModerationFlag;
+use crate::infrastructure::database;
+use sqlx::PgPool;
+
+/// Репозиторий для административных запросов к базе данных.
+#[derive(Debug)]
+pub(crate) struct AdminRepository {
+    /// Пул соединений с базой данных PostgreSQL.
+    pool: PgPool,
+}
+
+impl AdminRepository {
+    pub(crate) fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Назначить пользователя блога администратором.
+    ///
+    /// Возвращает `false`, если пользователь не найден.
+    pub(crate) async fn grant_admin(
+        &self,
+        tenant_id: i64,
+        username: &str,
+    ) -> Result<bool, AdminError> {
+        let result = sqlx::query!(
+            "UPDATE users SET is_admin = TRUE WHERE tenant_id = $1 AND username = $2",
+            tenant_id,
+            username
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Проверить, является ли пользователь администратором.
+    pub(crate) async fn is_admin(&self, user_id: i64) -> Result<bool, AdminError> {
+        let is_admin = sqlx::query_scalar!("SELECT is_admin FROM users WHERE id = $1", user_id)
+            .fetch_optional(&self.pool)
+            .await?
+            .unwrap_or(false);
+
+        Ok(is_admin)
+    }
+
+    /// Получить состояние миграций БД.
+    pub(crate) async fn get_migration_status(&self) -> Result<MigrationStatus, AdminError> {
+        Ok(database::migration_status(&self.pool).await?)
+    }
+
+    /// Получить посты блога, отмеченные фильтром содержимого для проверки (сначала новые).
+    pub(crate) async fn get_post_moderation_flags(
+        &self,
+        tenant_id: i64,
+    ) -> Result<Vec<PostModerationFlag>, AdminError> {
+        let flags = sqlx::query_as!(
+            PostModerationFlag,
+            "SELECT f.post_id, f.reason, f.flagged_at
+            FROM post_moderation_flags f
+            JOIN posts p ON p.id = f.post_id
+            WHERE p.tenant_id = $1
+            ORDER BY f.flagged_at DESC, f.post_id DESC",
+            tenant_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(flags)
+    }
+
+    /// Получить общие показатели блога; активные пользователи считаются за последние `days` дней.
+    pub(crate) async fn get_totals(
+        &self,
+        tenant_id: i64,
+        days: i32,
+    ) -> Result<StatsTotals, AdminError> {
+        let totals = sqlx::query_as!(
+            StatsTotals,
+            r#"SELECT
+                (SELECT COUNT(*) FROM users WHERE tenant_id = $2) AS "users!",
+                (SELECT COUNT(*) FROM posts WHERE tenant_id = $2) AS "posts!",
+                (
+                    SELECT COUNT(*) FROM media m
+                    JOIN users u ON u.id = m.owner_id
+                    WHERE u.tenant_id = $2
+                ) AS "media!",
+                (
+                    SELECT COUNT(DISTINCT l.user_id) FROM login_history l
+                    JOIN users u ON u.id = l.user_id
+                    WHERE u.tenant_id = $2 AND l.created_at >= CURRENT_DATE - ($1::INT - 1)
+                ) AS "active_users!""#,
+            days,
+            tenant_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(totals)
+    }
+
+    /// Получить сводные показатели содержимого блога.
+    ///
+    /// Изображение считается неиспользуемым, если ссылка на него не встречается ни в обложке
+    /// или содержимом поста блога, ни в аватаре пользователя.
+    pub(crate) async fn get_content_totals(
+        &self,
+        tenant_id: i64,
+    ) -> Result<ContentTotals, AdminError> {
+        let totals = sqlx::query_as!(
+            ContentTotals,
+            r#"SELECT
+                (SELECT COUNT(*) FROM posts WHERE tenant_id = $1) AS "posts!",
+                (SELECT COUNT(DISTINCT author_id) FROM posts WHERE tenant_id = $1) AS "authors!",
+                (
+                    SELECT COALESCE(AVG(
+                        array_length(regexp_split_to_array(btrim(content), '\s+'), 1)
+                    ), 0)::FLOAT8
+                    FROM posts WHERE tenant_id = $1
+                ) AS "average_words!",
+                (
+                    SELECT COALESCE(AVG(char_length(content)), 0)::FLOAT8
+                    FROM posts WHERE tenant_id = $1
+                ) AS "average_length!",
+                (
+                    SELECT COUNT(*) FROM media m
+                    JOIN users u ON u.id = m.owner_id
+                    WHERE u.tenant_id = $1
+                        AND NOT EXISTS (
+                            SELECT 1 FROM posts p
+                            WHERE p.tenant_id = $1
+                                AND (
+                                    p.cover_image_url ~ ($2 || m.id || '([^0-9]|$)')
+                                    OR p.content ~ ($2 || m.id || '([^0-9]|$)')
+                                )
+                        )
+                        AND NOT EXISTS (
+                            SELECT 1 FROM users a
+                            WHERE a.avatar_url ~ ($2 || m.id || '([^0-9]|$)')
+                        )
+                ) AS "orphaned_media!""#,
+            tenant_id,
+            MEDIA_URL_PREFIX
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(totals)
+    }
+
+    /// Получить показатели содержимого по авторам блога; частота публикаций считается
+    /// за последние `days` дней (включая текущий).
+    pub(crate) async fn get_author_content_stats(
+        &self,
+        tenant_id: i64,
+        days: i32,
+    ) -> Result<Vec<AuthorContentStats>, AdminError> {
+        let authors = sqlx::query_as!(
+            AuthorContentStats,
+            r#"SELECT
+                u.id AS "author_id!",
+                u.username AS "username!",
+                s.posts AS "posts!",
+                s.recent_posts AS "recent_posts!",
+                (s.recent_posts * 7.0 / $1::INT)::FLOAT8 AS "posts_per_week!",
+                s.words AS "words!",
+                (s.words::FLOAT8 / s.posts) AS "average_words!",
+                s.average_length AS "average_length!",
+                s.last_post_at AS "last_post_at!"
+            FROM (
+                SELECT
+                    author_id,
+                    COUNT(*) AS posts,
+                    COUNT(*) FILTER (
+                        WHERE created_at >= CURRENT_DATE - ($1::INT - 1)
+                    ) AS recent_posts,
+                    COALESCE(SUM(
+                        array_length(regexp_split_to_array(btrim(content), '\s+'), 1)
+                    ), 0)::BIGINT AS words,
+                    AVG(char_length(content))::FLOAT8 AS average_length,
+                    MAX(created_at) AS last_post_at
+                FROM posts
+                WHERE tenant_id = $2
+                GROUP BY author_id
+            ) s
+            JOIN users u ON u.id = s.author_id
+            ORDER BY s.posts DESC, u.id"#,
+            days,
+            tenant_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(authors)
+    }
+
+    /// Получить показатели блога по дням за последние `days` дней (включая текущий).
+    pub(crate) async fn get_daily_stats(
+        &self,
+        tenant_id: i64,
+        days: i32,
+    ) -> Result<Vec<DailyStats>, AdminError> {
+        let daily = sqlx::query_as!(
+            DailyStats,
+            r#"SELECT
+                day::DATE AS "date!",
+                (
+                    SELECT COUNT(*) FROM users
+                    WHERE tenant_id = $2
+                        AND created_at >= day AND created_at < day + INTERVAL '1 day'
+                ) AS "registrations!",
+                (
+                    SELECT COUNT(*) FROM posts
+                    WHERE tenant_id = $2
+                        AND created_at >= day AND created_at < day + INTERVAL '1 day'
+                ) AS "posts!",
+                (
+                    SELECT COUNT(DISTINCT l.user_id) FROM login_history l
+                    JOIN users u ON u.id = l.user_id
+                    WHERE u.tenant_id = $2
+                        AND l.created_at >= day AND l.created_at < day + INTERVAL '1 day'
+                ) AS "active_users!"
+            FROM generate_series(
+                (CURRENT_DATE - ($1::INT - 1))::TIMESTAMPTZ,
+                CURRENT_DATE::TIMESTAMPTZ,
+                INTERVAL '1 day'
+            ) AS day
+            ORDER BY day"#,
+            days,
+            tenant_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(daily)
+    }
+}
@@ -0,0 +1,92 @@
+//! Репозиторий для работы с API-ключами в базе данных.
+
+use crate::domain::api_key::{ApiKey, ApiKeyOwner, ApiKeyScope};
+use crate::domain::error::ApiKeyError;
+use sqlx::PgPool;
+
+/// Репозиторий для работы с API-ключами в базе данных.
+#[derive(Debug)]
+pub(crate) struct ApiKeyRepository {
+    /// Пул соединений с базой данных PostgreSQL.
+    pool: PgPool,
+}
+
+impl ApiKeyRepository {
+    pub(crate) fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Сохранить новый API-ключ по его хешу.
+    pub(crate) async fn create_api_key(
+        &self,
+        user_id: i64,
+        name: &str,
+        key_hash: &str,
+        scope: ApiKeyScope,
+    ) -> Result<ApiKey, ApiKeyError> {
+        let api_key = sqlx::query_as!(
+            ApiKey,
+            r#"INSERT INTO api_keys (user_id, name, key_hash, scope) VALUES ($1, $2, $3, $4)
+            RETURNING id, user_id, name, scope AS "scope: ApiKeyScope", created_at, last_used_at"#,
+            user_id,
+            name,
+            key_hash,
+            scope as ApiKeyScope
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(api_key)
+    }
+
+    /// Получить список API-ключей пользователя.
+    pub(crate) async fn get_api_keys(&self, user_id: i64) -> Result<Vec<ApiKey>, ApiKeyError> {
+        let api_keys = sqlx::query_as!(
+            ApiKey,
+            r#"SELECT id, user_id, name, scope AS "scope: ApiKeyScope", created_at, last_used_at
+            FROM api_keys WHERE user_id = $1 ORDER BY id"#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(api_keys)
+    }
+
+    /// Удалить API-ключ пользователя.
+    pub(crate) async fn delete_api_key(&self, id: i64, user_id: i64) -> Result<(), ApiKeyError> {
+        let result = sqlx::query!(
+            "DELETE FROM api_keys WHERE id = $1 AND user_id = $2",
+            id,
+            user_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(ApiKeyError::ApiKeyNotFound);
+        }
+
+        Ok(())
+    }
+
+    /// Найти владельца API-ключа по хешу и отметить использование ключа.
+    pub(crate) async fn get_owner(&self, key_hash: &str) -> Result<ApiKeyOwner, ApiKeyError> {
+        let owner = sqlx::query_as!(
+            ApiKeyOwner,
+            r#"WITH api_key AS (
+                UPDATE api_keys SET last_used_at = NOW() WHERE key_hash = $1
+                RETURNING user_id, scope
+            )
+            SELECT api_key.user_id AS "user_id!", users.tenant_id,
+                api_key.scope AS "scope!: ApiKeyScope"
+            FROM api_key JOIN users ON users.id = api_key.user_id"#,
+            key_hash
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(ApiKeyError::ApiKeyNotFound)?;
+
+        Ok(owner)
+    }
+}
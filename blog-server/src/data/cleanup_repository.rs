@@ -0,0 +1,52 @@
+//! Репозиторий для удаления устаревших данных из базы данных.
+
+use sqlx::PgPool;
+
+/// Репозиторий для удаления устаревших данных из базы данных.
+#[derive(Debug)]
+pub(crate) struct CleanupRepository {
+    /// Пул соединений с базой данных PostgreSQL.
+    pool: PgPool,
+}
+
+impl CleanupRepository {
+    pub(crate) fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Удалить истекшие запросы на смену email-адреса.
+    ///
+    /// Возвращает количество удаленных строк.
+    pub(crate) async fn delete_expired_email_changes(&self) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM email_changes WHERE expires_at <= NOW()")
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Удалить истекшие неиспользованные приглашения.
+    ///
+    /// Использованные приглашения сохраняются, чтобы было видно, кто кого пригласил.
+    pub(crate) async fn delete_expired_invites(&self) -> Result<u64, sqlx::Error> {
+        let result =
+            sqlx::query!("DELETE FROM invites WHERE used_at IS NULL AND expires_at <= NOW()")
+                .execute(&self.pool)
+                .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Отобрать из списка идентификаторы изображений, которых нет в базе данных.
+    pub(crate) async fn get_missing_media_ids(&self, ids: &[i64]) -> Result<Vec<i64>, sqlx::Error> {
+        let missing = sqlx::query_scalar!(
+            r#"SELECT candidate.id AS "id!" FROM UNNEST($1::BIGINT[]) AS candidate(id)
+            WHERE NOT EXISTS (SELECT 1 FROM media m WHERE m.id = candidate.id)"#,
+            ids
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(missing)
+    }
+}
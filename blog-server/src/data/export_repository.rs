@@ -0,0 +1,76 @@
+//! Репозиторий для работы с выгрузками данных пользователей в базе данных.
+
+use crate::domain::error::ExportError;
+use crate::domain::export::{DataExport, DataExportStatus};
+use sqlx::PgPool;
+use sqlx::types::chrono::{DateTime, Utc};
+
+/// Репозиторий для работы с выгрузками данных пользователей в базе данных.
+#[derive(Debug)]
+pub(crate) struct ExportRepository {
+    /// Пул соединений с базой данных PostgreSQL.
+    pool: PgPool,
+}
+
+impl ExportRepository {
+    pub(crate) fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Начать новую выгрузку данных пользователя.
+    ///
+    /// Предыдущая выгрузка заменяется, если она завершена или была начата не позже
+    /// `stale_before`; иначе возвращается `None`.
+    pub(crate) async fn start_export(
+        &self,
+        user_id: i64,
+        stale_before: DateTime<Utc>,
+    ) -> Result<Option<DataExport>, ExportError> {
+        let export = sqlx::query_as!(
+            DataExport,
+            r#"INSERT INTO data_exports (user_id) VALUES ($1)
+            ON CONFLICT (user_id) DO UPDATE
+                SET status = 'pending', requested_at = NOW(), completed_at = NULL
+                WHERE data_exports.status <> 'pending' OR data_exports.requested_at <= $2
+            RETURNING status AS "status: DataExportStatus", requested_at, completed_at"#,
+            user_id,
+            stale_before
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(export)
+    }
+
+    /// Завершить выгрузку данных пользователя с указанным статусом.
+    pub(crate) async fn finish_export(
+        &self,
+        user_id: i64,
+        status: DataExportStatus,
+    ) -> Result<(), ExportError> {
+        sqlx::query!(
+            "UPDATE data_exports SET status = $2, completed_at = NOW() WHERE user_id = $1",
+            user_id,
+            status as DataExportStatus
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Получить выгрузку данных пользователя.
+    pub(crate) async fn get_export(&self, user_id: i64) -> Result<DataExport, ExportError> {
+        let export = sqlx::query_as!(
+            DataExport,
+            r#"SELECT status AS "status: DataExportStatus", requested_at, completed_at
+            FROM data_exports WHERE user_id = $1"#,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(ExportError::ExportNotFound)?;
+
+        Ok(export)
+    }
+}
@@ -0,0 +1,63 @@
+//! Репозиторий для работы с приглашениями в базе данных.
+
+use sqlx::types::chrono::{DateTime, Utc};
+use sqlx::{Executor, PgPool, Postgres};
+
+/// Репозиторий для работы с приглашениями в базе данных.
+#[derive(Debug)]
+pub(crate) struct InviteRepository {
+    /// Пул соединений с базой данных PostgreSQL.
+    pool: PgPool,
+}
+
+impl InviteRepository {
+    pub(crate) fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Сохранить новое приглашение.
+    pub(crate) async fn create_invite(
+        &self,
+        code_hash: &str,
+        created_by: i64,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "INSERT INTO invites (code_hash, created_by, expires_at) VALUES ($1, $2, $3)",
+            code_hash,
+            created_by,
+            expires_at
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Использовать приглашение для регистрации пользователя в рамках транзакции.
+    ///
+    /// Возвращает `false`, если приглашение не найдено, истекло, уже использовано
+    /// или создано администратором другого блога.
+    pub(crate) async fn use_invite_with_tx<'e, E>(
+        &self,
+        code_hash: &str,
+        user_id: i64,
+        executor: E,
+    ) -> Result<bool, sqlx::Error>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let result = sqlx::query!(
+            "UPDATE invites SET used_by = $2, used_at = NOW()
+            WHERE code_hash = $1 AND used_at IS NULL AND expires_at > NOW()
+                AND (SELECT tenant_id FROM users WHERE id = created_by)
+                    = (SELECT tenant_id FROM users WHERE id = $2)",
+            code_hash,
+            user_id
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+}
@@ -0,0 +1,76 @@
+//! Репозиторий для работы с историей входов в базе данных.
+
+use crate::domain::error::UserError;
+use crate::domain::login::{LoginContext, LoginRecord};
+use sqlx::PgPool;
+
+/// Репозиторий для работы с историей входов в базе данных.
+#[derive(Debug)]
+pub(crate) struct LoginHistoryRepository {
+    /// Пул соединений с базой данных PostgreSQL.
+    pool: PgPool,
+}
+
+impl LoginHistoryRepository {
+    pub(crate) fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Записать вход пользователя.
+    ///
+    /// Вход отмечается как выполненный с нового устройства (User-Agent) или из новой страны,
+    /// если у пользователя уже были входы, но ни одного с такими же значениями.
+    pub(crate) async fn add_login(
+        &self,
+        user_id: i64,
+        context: &LoginContext,
+    ) -> Result<LoginRecord, UserError> {
+        let record = sqlx::query_as!(
+            LoginRecord,
+            r#"WITH previous AS (
+                SELECT
+                    COUNT(*) AS total,
+                    COUNT(*) FILTER (WHERE user_agent IS NOT DISTINCT FROM $3) AS same_device,
+                    COUNT(*) FILTER (WHERE country = $4) AS same_country
+                FROM login_history WHERE user_id = $1
+            )
+            INSERT INTO login_history (user_id, ip, user_agent, country, new_device, new_country)
+            SELECT $1, $2, $3, $4,
+                total > 0 AND same_device = 0,
+                total > 0 AND $4::VARCHAR IS NOT NULL AND same_country = 0
+            FROM previous
+            RETURNING id, ip, user_agent, country, new_device, new_country, created_at"#,
+            user_id,
+            context.ip,
+            context.user_agent,
+            context.country
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Получить историю входов пользователя (сначала последние).
+    pub(crate) async fn get_logins(
+        &self,
+        user_id: i64,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<LoginRecord>, UserError> {
+        let records = sqlx::query_as!(
+            LoginRecord,
+            "SELECT id, ip, user_agent, country, new_device, new_country, created_at
+            FROM login_history WHERE user_id = $1
+            ORDER BY created_at DESC, id DESC
+            LIMIT $2 OFFSET $3",
+            user_id,
+            limit,
+            offset
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(records)
+    }
+}
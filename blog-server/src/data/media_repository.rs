@@ -0,0 +1,143 @@
+//! Репозиторий для работы с загруженными изображениями в базе данных.
+
+use crate::domain::error::MediaError;
+use crate::domain::media::{Media, MediaSize, MediaVariant};
+use sqlx::{Executor, PgPool, Postgres, Transaction};
+
+/// Репозиторий для работы с загруженными изображениями в базе данных.
+#[derive(Debug)]
+pub(crate) struct MediaRepository {
+    /// Пул соединений с базой данных PostgreSQL.
+    pool: PgPool,
+}
+
+impl MediaRepository {
+    pub(crate) fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Начать транзакцию.
+    pub(crate) async fn begin_transaction(
+        &self,
+    ) -> Result<Transaction<'static, Postgres>, MediaError> {
+        Ok(self.pool.begin().await?)
+    }
+
+    /// Сохранить запись о новом изображении (без вариантов).
+    pub(crate) async fn create_media_with_tx<'e, E>(
+        &self,
+        owner_id: i64,
+        content_type: &str,
+        executor: E,
+    ) -> Result<Media, MediaError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let row = sqlx::query!(
+            "INSERT INTO media (owner_id, content_type) VALUES ($1, $2)
+            RETURNING id, owner_id, content_type, created_at",
+            owner_id,
+            content_type
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(Media {
+            id: row.id,
+            owner_id: row.owner_id,
+            content_type: row.content_type,
+            url: Media::url(row.id),
+            variants: vec![],
+            created_at: row.created_at,
+        })
+    }
+
+    /// Сохранить вариант изображения.
+    pub(crate) async fn add_variant(
+        &self,
+        media_id: i64,
+        variant: &MediaVariant,
+    ) -> Result<(), MediaError> {
+        self.add_variant_with_tx(media_id, variant, &self.pool)
+            .await
+    }
+
+    /// Сохранить вариант изображения в рамках транзакции.
+    pub(crate) async fn add_variant_with_tx<'e, E>(
+        &self,
+        media_id: i64,
+        variant: &MediaVariant,
+        executor: E,
+    ) -> Result<(), MediaError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query!(
+            "INSERT INTO media_variants (media_id, size, width, height, byte_size, path)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (media_id, size) DO UPDATE SET
+                width = EXCLUDED.width,
+                height = EXCLUDED.height,
+                byte_size = EXCLUDED.byte_size,
+                path = EXCLUDED.path",
+            media_id,
+            variant.size as MediaSize,
+            variant.width,
+            variant.height,
+            variant.byte_size,
+            variant.path
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Получить идентификаторы изображений пользователя.
+    pub(crate) async fn get_media_ids_by_owner(
+        &self,
+        owner_id: i64,
+    ) -> Result<Vec<i64>, MediaError> {
+        let ids = sqlx::query_scalar!(
+            "SELECT id FROM media WHERE owner_id = $1 ORDER BY id",
+            owner_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(ids)
+    }
+
+    /// Получить изображение, загруженное пользователем блога, вместе с готовыми вариантами.
+    pub(crate) async fn get_media(&self, tenant_id: i64, id: i64) -> Result<Media, MediaError> {
+        let row = sqlx::query!(
+            "SELECT m.id, m.owner_id, m.content_type, m.created_at
+            FROM media m
+            JOIN users u ON u.id = m.owner_id
+            WHERE m.id = $1 AND u.tenant_id = $2",
+            id,
+            tenant_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(MediaError::MediaNotFound)?;
+
+        let variants = sqlx::query_as!(
+            MediaVariant,
+            r#"SELECT size AS "size: MediaSize", width, height, byte_size, path
+            FROM media_variants WHERE media_id = $1 ORDER BY size"#,
+            id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(Media {
+            id: row.id,
+            owner_id: row.owner_id,
+            content_type: row.content_type,
+            url: Media::url(row.id),
+            variants,
+            created_at: row.created_at,
+        })
+    }
+}
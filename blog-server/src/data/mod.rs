@@ -1,2 +1,11 @@
+pub(crate) mod admin_repository;
+pub(crate) mod api_key_repository;
+pub(crate) mod cleanup_repository;
+pub(crate) mod export_repository;
+pub(crate) mod invite_repository;
+pub(crate) mod login_history_repository;
+pub(crate) mod media_repository;
 pub(crate) mod post_repository;
+pub(crate) mod settings_repository;
+pub(crate) mod tenant_repository;
 pub(crate) mod user_repository;
@@ -1,7 +1,12 @@
 //! Репозиторий для работы с постами в базе данных.
 
 use crate::domain::error::PostError;
-use crate::domain::post::{Post, UpdatePostRequest};
+use crate::domain::post::{
+    DuplicatePostCheck, Post, PostAuthor, PostField, PostFilter, PostMeta, PostPreview, PostSort,
+    PostSuggestion, PostWithAuthor, UpdatePostRequest, estimate_reading_time,
+};
+use crate::infrastructure::database::ReadPool;
+use sqlx::types::chrono::{DateTime, Utc};
 use sqlx::{Executor, PgPool, Postgres, QueryBuilder, Transaction};
 
 /// Репозиторий для работы с постами в базе данных.
@@ -9,11 +14,23 @@ use sqlx::{Executor, PgPool, Postgres, QueryBuilder, Transaction};
 pub(crate) struct PostRepository {
     /// Пул соединений с базой данных PostgreSQL.
     pool: PgPool,
+
+    /// Пул соединений для запросов только на чтение (реплика или основная БД).
+    read_pool: ReadPool,
 }
 
 impl PostRepository {
     pub(crate) fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self {
+            read_pool: ReadPool::new(pool.clone(), None),
+            pool,
+        }
+    }
+
+    /// Направлять запросы на чтение постов вне транзакций в указанный пул.
+    pub(crate) fn with_read_pool(mut self, read_pool: ReadPool) -> Self {
+        self.read_pool = read_pool;
+        self
     }
 
     /// Начать новую транзакцию.
@@ -33,8 +50,73 @@ impl PostRepository {
     }
 
     /// Создать новый пост.
-    pub(crate) async fn create_post(&self, post: Post, author_id: i64) -> Result<Post, PostError> {
-        self.create_post_with_tx(post, author_id, &self.pool).await
+    ///
+    /// Владелец поста сразу же записывается в список его авторов.
+    ///
+    /// При заданной проверке `duplicates` создание постов автора выполняется последовательно,
+    /// и при найденном дубликате возвращается [`PostError::Duplicate`].
+    pub(crate) async fn create_post(
+        &self,
+        post: Post,
+        author_id: i64,
+        duplicates: Option<DuplicatePostCheck>,
+    ) -> Result<Post, PostError> {
+        let mut tx = self.begin_transaction().await?;
+
+        if let Some(check) = duplicates {
+            // Блокировка до конца транзакции не дает одновременным повторам запроса
+            // создать несколько постов до того, как первый из них будет записан.
+            sqlx::query!("SELECT pg_advisory_xact_lock($1)", author_id)
+                .execute(&mut *tx)
+                .await?;
+
+            if let Some(id) = self
+                .find_duplicate_post_with_tx(&post, author_id, check, &mut *tx)
+                .await?
+            {
+                return Err(PostError::Duplicate(id));
+            }
+        }
+
+        let post = self.create_post_with_tx(post, author_id, &mut *tx).await?;
+        self.add_post_author_with_tx(post.id, author_id, &mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(post)
+    }
+
+    /// Найти недавний пост автора с тем же или похожим заголовком в рамках транзакции.
+    async fn find_duplicate_post_with_tx<'e, E>(
+        &self,
+        post: &Post,
+        author_id: i64,
+        check: DuplicatePostCheck,
+        executor: E,
+    ) -> Result<Option<i64>, PostError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let since = Utc::now() - check.window;
+
+        let id = sqlx::query_scalar!(
+            r#"SELECT id FROM posts
+            WHERE author_id = $1 AND tenant_id = $2 AND created_at >= $3
+                AND (lower(trim(title)) = lower(trim($4))
+                    OR ($5::real IS NOT NULL AND similarity(title, $4) >= $5))
+            ORDER BY lower(trim(title)) = lower(trim($4)) DESC, similarity(title, $4) DESC, id DESC
+            LIMIT 1"#,
+            author_id,
+            post.tenant_id,
+            since,
+            post.title,
+            check.similarity
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(id)
     }
 
     /// Создать новый пост в рамках транзакции.
@@ -49,10 +131,20 @@ impl PostRepository {
     {
         let post = sqlx::query_as!(
             Post,
-            "INSERT INTO posts (title, content, author_id) VALUES ($1, $2, $3) RETURNING *",
+            r#"INSERT INTO posts
+                (title, content, excerpt, cover_image_url, is_private, author_id, reading_time_minutes, tenant_id,
+                content_format, content_html)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) RETURNING *, ARRAY[author_id] AS "author_ids!""#,
             post.title,
             post.content,
-            author_id
+            post.excerpt,
+            post.cover_image_url,
+            post.is_private,
+            author_id,
+            post.reading_time_minutes,
+            post.tenant_id,
+            post.content_format.as_str(),
+            post.content_html
         )
         .fetch_one(executor)
         .await?;
@@ -60,36 +152,172 @@ impl PostRepository {
         Ok(post)
     }
 
-    /// Получить пост по идентификатору.
-    pub(crate) async fn get_post(&self, id: i64) -> Result<Post, PostError> {
-        self.get_post_with_tx(id, &self.pool).await
+    /// Получить пост блога по идентификатору.
+    pub(crate) async fn get_post(&self, tenant_id: i64, id: i64) -> Result<Post, PostError> {
+        self.get_post_with_tx(tenant_id, id, self.read_pool.get())
+            .await
     }
 
-    /// Получить пост по идентификатору в рамках транзакции.
+    /// Получить пост блога по идентификатору в рамках транзакции.
     pub(crate) async fn get_post_with_tx<'e, E>(
         &self,
+        tenant_id: i64,
         id: i64,
         executor: E,
     ) -> Result<Post, PostError>
     where
         E: Executor<'e, Database = Postgres>,
     {
-        let post = sqlx::query_as!(Post, "SELECT * FROM posts WHERE id = $1", id)
-            .fetch_optional(executor)
-            .await?
-            .ok_or(PostError::PostNotFound)?;
+        let post = sqlx::query_as!(
+            Post,
+            r#"SELECT *, ARRAY(SELECT pa.user_id FROM post_authors pa WHERE pa.post_id = posts.id ORDER BY pa.added_at, pa.user_id) AS "author_ids!"
+            FROM posts WHERE id = $1 AND tenant_id = $2"#,
+            id,
+            tenant_id
+        )
+        .fetch_optional(executor)
+        .await?
+        .ok_or(PostError::PostNotFound)?;
 
         Ok(post)
     }
 
+    /// Получить сведения о посте блога без его содержимого.
+    pub(crate) async fn get_post_meta(
+        &self,
+        tenant_id: i64,
+        id: i64,
+    ) -> Result<PostMeta, PostError> {
+        self.get_post_meta_with_tx(tenant_id, id, self.read_pool.get())
+            .await
+    }
+
+    /// Получить сведения о посте блога без его содержимого в рамках транзакции.
+    pub(crate) async fn get_post_meta_with_tx<'e, E>(
+        &self,
+        tenant_id: i64,
+        id: i64,
+        executor: E,
+    ) -> Result<PostMeta, PostError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let meta = sqlx::query_as!(
+            PostMeta,
+            r#"SELECT id, author_id, is_private, updated_at, version, archived_at,
+                ARRAY(SELECT pa.user_id FROM post_authors pa WHERE pa.post_id = posts.id ORDER BY pa.added_at, pa.user_id) AS "author_ids!"
+            FROM posts WHERE id = $1 AND tenant_id = $2"#,
+            id,
+            tenant_id
+        )
+        .fetch_optional(executor)
+        .await?
+        .ok_or(PostError::PostNotFound)?;
+
+        Ok(meta)
+    }
+
+    /// Проверить, существует ли пост блога, доступный пользователю.
+    ///
+    /// Приватный пост считается существующим только для его авторов.
+    pub(crate) async fn exists(
+        &self,
+        tenant_id: i64,
+        id: i64,
+        viewer_id: Option<i64>,
+    ) -> Result<bool, PostError> {
+        self.exists_with_tx(tenant_id, id, viewer_id, self.read_pool.get())
+            .await
+    }
+
+    /// Проверить, существует ли пост блога, доступный пользователю, в рамках транзакции.
+    pub(crate) async fn exists_with_tx<'e, E>(
+        &self,
+        tenant_id: i64,
+        id: i64,
+        viewer_id: Option<i64>,
+        executor: E,
+    ) -> Result<bool, PostError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let exists = sqlx::query_scalar!(
+            r#"SELECT EXISTS(
+                SELECT 1 FROM posts p
+                WHERE p.id = $1 AND p.tenant_id = $2
+                    AND (NOT p.is_private OR EXISTS(
+                        SELECT 1 FROM post_authors pa WHERE pa.post_id = p.id AND pa.user_id = $3
+                    ))
+            ) AS "exists!""#,
+            id,
+            tenant_id,
+            viewer_id
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(exists)
+    }
+
+    /// Получить пост блога вместе с автором по идентификатору.
+    pub(crate) async fn get_post_with_author(
+        &self,
+        tenant_id: i64,
+        id: i64,
+    ) -> Result<PostWithAuthor, PostError> {
+        self.get_post_with_author_with_tx(tenant_id, id, self.read_pool.get())
+            .await
+    }
+
+    /// Получить пост блога вместе с автором по идентификатору в рамках транзакции.
+    pub(crate) async fn get_post_with_author_with_tx<'e, E>(
+        &self,
+        tenant_id: i64,
+        id: i64,
+        executor: E,
+    ) -> Result<PostWithAuthor, PostError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let post = sqlx::query_as!(
+            PostWithAuthorRow,
+            r#"SELECT p.id, p.title, p.content, p.excerpt, p.cover_image_url, p.is_private,
+                p.author_id, p.created_at, p.updated_at, p.version, p.archived_at,
+                p.reading_time_minutes, p.content_format, p.content_html, p.tenant_id, p.view_count,
+                p.trending_score,
+                u.username AS author_username,
+                ARRAY(SELECT pa.user_id FROM post_authors pa WHERE pa.post_id = p.id ORDER BY pa.added_at, pa.user_id) AS "author_ids!"
+            FROM posts p
+            JOIN users u ON u.id = p.author_id
+            WHERE p.id = $1 AND p.tenant_id = $2"#,
+            id,
+            tenant_id
+        )
+        .fetch_optional(executor)
+        .await?
+        .ok_or(PostError::PostNotFound)?;
+
+        Ok(post.into())
+    }
+
     /// Получить список постов с пагинацией.
-    pub(crate) async fn get_posts(&self, limit: i64, offset: i64) -> Result<Vec<Post>, PostError> {
-        self.get_posts_with_tx(limit, offset, &self.pool).await
+    ///
+    /// Приватные посты включаются только для их авторов - владельца и соавторов
+    /// (`filter.viewer_id`).
+    pub(crate) async fn get_posts(
+        &self,
+        filter: &PostFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Post>, PostError> {
+        self.get_posts_with_tx(filter, limit, offset, self.read_pool.get())
+            .await
     }
 
     /// Получить список постов с пагинацией в рамках транзакции.
     pub(crate) async fn get_posts_with_tx<'e, E>(
         &self,
+        filter: &PostFilter,
         limit: i64,
         offset: i64,
         executor: E,
@@ -99,9 +327,112 @@ impl PostRepository {
     {
         let posts = sqlx::query_as!(
             Post,
-            "SELECT * FROM posts ORDER BY id DESC LIMIT $1 OFFSET $2",
+            r#"SELECT *, ARRAY(SELECT pa.user_id FROM post_authors pa WHERE pa.post_id = posts.id ORDER BY pa.added_at, pa.user_id) AS "author_ids!"
+            FROM posts
+            WHERE tenant_id = $8
+                AND (NOT is_private OR EXISTS(
+                    SELECT 1 FROM post_authors pa WHERE pa.post_id = posts.id AND pa.user_id = $3
+                ))
+                AND ($4::BOOLEAN IS NULL OR (archived_at IS NOT NULL) = $4)
+                AND ($5::TIMESTAMPTZ IS NULL OR created_at > $5)
+                AND ($6::TIMESTAMPTZ IS NULL OR created_at < $6)
+                AND ($7::TIMESTAMPTZ IS NULL OR updated_at >= $7)
+                AND ($10::BIGINT IS NULL OR author_id = $10)
+            ORDER BY CASE WHEN $9 THEN trending_score ELSE 0 END DESC, id DESC
+            LIMIT $1 OFFSET $2"#,
             limit,
-            offset
+            offset,
+            filter.viewer_id,
+            filter.archived,
+            filter.created_after,
+            filter.created_before,
+            filter.updated_since,
+            filter.tenant_id,
+            filter.sort == PostSort::Trending,
+            filter.author_id
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(posts)
+    }
+
+    /// Получить количество постов, удовлетворяющих условиям отбора.
+    pub(crate) async fn count_posts(&self, filter: &PostFilter) -> Result<i64, PostError> {
+        self.count_posts_with_tx(filter, self.read_pool.get()).await
+    }
+
+    /// Получить количество постов, удовлетворяющих условиям отбора, в рамках транзакции.
+    pub(crate) async fn count_posts_with_tx<'e, E>(
+        &self,
+        filter: &PostFilter,
+        executor: E,
+    ) -> Result<i64, PostError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let total = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) AS "total!"
+            FROM posts
+            WHERE tenant_id = $1
+                AND (NOT is_private OR EXISTS(
+                    SELECT 1 FROM post_authors pa WHERE pa.post_id = posts.id AND pa.user_id = $2
+                ))
+                AND ($3::BOOLEAN IS NULL OR (archived_at IS NOT NULL) = $3)
+                AND ($4::TIMESTAMPTZ IS NULL OR created_at > $4)
+                AND ($5::TIMESTAMPTZ IS NULL OR created_at < $5)
+                AND ($6::TIMESTAMPTZ IS NULL OR updated_at >= $6)
+                AND ($7::BIGINT IS NULL OR author_id = $7)"#,
+            filter.tenant_id,
+            filter.viewer_id,
+            filter.archived,
+            filter.created_after,
+            filter.created_before,
+            filter.updated_since,
+            filter.author_id
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(total)
+    }
+
+    /// Получить посты блога по списку идентификаторов.
+    ///
+    /// Приватные посты включаются только для их авторов (владельца и соавторов);
+    /// порядок постов не гарантируется.
+    pub(crate) async fn get_posts_by_ids(
+        &self,
+        tenant_id: i64,
+        ids: &[i64],
+        viewer_id: Option<i64>,
+    ) -> Result<Vec<Post>, PostError> {
+        self.get_posts_by_ids_with_tx(tenant_id, ids, viewer_id, self.read_pool.get())
+            .await
+    }
+
+    /// Получить посты блога по списку идентификаторов в рамках транзакции.
+    pub(crate) async fn get_posts_by_ids_with_tx<'e, E>(
+        &self,
+        tenant_id: i64,
+        ids: &[i64],
+        viewer_id: Option<i64>,
+        executor: E,
+    ) -> Result<Vec<Post>, PostError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let posts = sqlx::query_as!(
+            Post,
+            r#"SELECT *, ARRAY(SELECT pa.user_id FROM post_authors pa WHERE pa.post_id = posts.id ORDER BY pa.added_at, pa.user_id) AS "author_ids!"
+            FROM posts
+            WHERE id = ANY($1) AND tenant_id = $3
+                AND (NOT is_private OR EXISTS(
+                    SELECT 1 FROM post_authors pa WHERE pa.post_id = posts.id AND pa.user_id = $2
+                ))"#,
+            ids,
+            viewer_id,
+            tenant_id
         )
         .fetch_all(executor)
         .await?;
@@ -109,9 +440,296 @@ impl PostRepository {
         Ok(posts)
     }
 
-    /// Обновить существующий пост.
-    pub(crate) async fn update_post(&self, post: UpdatePostRequest) -> Result<Post, PostError> {
-        self.update_post_with_tx(post, &self.pool).await
+    /// Получить все посты автора (включая приватные и архивные).
+    pub(crate) async fn get_posts_by_author(&self, author_id: i64) -> Result<Vec<Post>, PostError> {
+        let posts = sqlx::query_as!(
+            Post,
+            r#"SELECT *, ARRAY(SELECT pa.user_id FROM post_authors pa WHERE pa.post_id = posts.id ORDER BY pa.added_at, pa.user_id) AS "author_ids!"
+            FROM posts WHERE author_id = $1 ORDER BY id"#,
+            author_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(posts)
+    }
+
+    /// Получить идентификаторы публичных постов того же блога с похожими заголовками (`pg_trgm`).
+    pub(crate) async fn get_related_post_ids(
+        &self,
+        id: i64,
+        limit: i64,
+    ) -> Result<Vec<i64>, PostError> {
+        self.get_related_post_ids_with_tx(id, limit, self.read_pool.get())
+            .await
+    }
+
+    /// Получить идентификаторы постов с похожими заголовками в рамках транзакции.
+    pub(crate) async fn get_related_post_ids_with_tx<'e, E>(
+        &self,
+        id: i64,
+        limit: i64,
+        executor: E,
+    ) -> Result<Vec<i64>, PostError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let ids = sqlx::query_scalar!(
+            "SELECT p.id FROM posts p
+            JOIN posts source ON source.id = $1
+            WHERE p.id <> source.id AND p.tenant_id = source.tenant_id
+                AND NOT p.is_private AND p.title % source.title
+            ORDER BY similarity(p.title, source.title) DESC, p.id DESC
+            LIMIT $2",
+            id,
+            limit
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(ids)
+    }
+
+    /// Получить подсказки для строки поиска по постам блога.
+    ///
+    /// В начале списка идут посты, заголовок которых начинается с запроса,
+    /// затем - наиболее похожие по триграммам (`pg_trgm`).
+    pub(crate) async fn suggest_posts(
+        &self,
+        tenant_id: i64,
+        query: &str,
+        viewer_id: Option<i64>,
+        limit: i64,
+    ) -> Result<Vec<PostSuggestion>, PostError> {
+        self.suggest_posts_with_tx(tenant_id, query, viewer_id, limit, self.read_pool.get())
+            .await
+    }
+
+    /// Получить подсказки для строки поиска по постам блога в рамках транзакции.
+    pub(crate) async fn suggest_posts_with_tx<'e, E>(
+        &self,
+        tenant_id: i64,
+        query: &str,
+        viewer_id: Option<i64>,
+        limit: i64,
+        executor: E,
+    ) -> Result<Vec<PostSuggestion>, PostError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let prefix = format!("{}%", escape_like(query));
+
+        let suggestions = sqlx::query_as!(
+            PostSuggestion,
+            "SELECT id, title FROM posts
+            WHERE tenant_id = $5
+                AND (NOT is_private OR EXISTS(
+                    SELECT 1 FROM post_authors pa WHERE pa.post_id = posts.id AND pa.user_id = $3
+                ))
+                AND (title ILIKE $1 OR title % $2)
+            ORDER BY title ILIKE $1 DESC, similarity(title, $2) DESC, id DESC
+            LIMIT $4",
+            prefix,
+            query,
+            viewer_id,
+            limit,
+            tenant_id
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(suggestions)
+    }
+
+    /// Получить список постов вместе с авторами с пагинацией.
+    pub(crate) async fn get_posts_with_author(
+        &self,
+        filter: &PostFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<PostWithAuthor>, PostError> {
+        self.get_posts_with_author_with_tx(filter, limit, offset, self.read_pool.get())
+            .await
+    }
+
+    /// Получить список постов вместе с авторами с пагинацией в рамках транзакции.
+    pub(crate) async fn get_posts_with_author_with_tx<'e, E>(
+        &self,
+        filter: &PostFilter,
+        limit: i64,
+        offset: i64,
+        executor: E,
+    ) -> Result<Vec<PostWithAuthor>, PostError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let posts = sqlx::query_as!(
+            PostWithAuthorRow,
+            r#"SELECT p.id, p.title, p.content, p.excerpt, p.cover_image_url, p.is_private,
+                p.author_id, p.created_at, p.updated_at, p.version, p.archived_at,
+                p.reading_time_minutes, p.content_format, p.content_html, p.tenant_id, p.view_count,
+                p.trending_score,
+                u.username AS author_username,
+                ARRAY(SELECT pa.user_id FROM post_authors pa WHERE pa.post_id = p.id ORDER BY pa.added_at, pa.user_id) AS "author_ids!"
+            FROM posts p
+            JOIN users u ON u.id = p.author_id
+            WHERE p.tenant_id = $8
+                AND (NOT p.is_private OR EXISTS(
+                    SELECT 1 FROM post_authors pa WHERE pa.post_id = p.id AND pa.user_id = $3
+                ))
+                AND ($4::BOOLEAN IS NULL OR (p.archived_at IS NOT NULL) = $4)
+                AND ($5::TIMESTAMPTZ IS NULL OR p.created_at > $5)
+                AND ($6::TIMESTAMPTZ IS NULL OR p.created_at < $6)
+                AND ($7::TIMESTAMPTZ IS NULL OR p.updated_at >= $7)
+                AND ($10::BIGINT IS NULL OR p.author_id = $10)
+            ORDER BY CASE WHEN $9 THEN p.trending_score ELSE 0 END DESC, p.id DESC
+            LIMIT $1 OFFSET $2"#,
+            limit,
+            offset,
+            filter.viewer_id,
+            filter.archived,
+            filter.created_after,
+            filter.created_before,
+            filter.updated_since,
+            filter.tenant_id,
+            filter.sort == PostSort::Trending,
+            filter.author_id
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(posts.into_iter().map(Into::into).collect())
+    }
+
+    /// Получить список сокращенных представлений постов с пагинацией.
+    ///
+    /// Из базы данных выбираются только указанные поля, а при `with_author`
+    /// данные автора подгружаются тем же запросом.
+    pub(crate) async fn get_post_previews(
+        &self,
+        fields: &[PostField],
+        with_author: bool,
+        filter: &PostFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<PostPreview>, PostError> {
+        self.get_post_previews_with_tx(
+            fields,
+            with_author,
+            filter,
+            limit,
+            offset,
+            self.read_pool.get(),
+        )
+        .await
+    }
+
+    /// Получить список сокращенных представлений постов с пагинацией в рамках транзакции.
+    pub(crate) async fn get_post_previews_with_tx<'e, E>(
+        &self,
+        fields: &[PostField],
+        with_author: bool,
+        filter: &PostFilter,
+        limit: i64,
+        offset: i64,
+        executor: E,
+    ) -> Result<Vec<PostPreview>, PostError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let mut query_builder = QueryBuilder::new("SELECT ");
+
+        let mut columns = query_builder.separated(", ");
+        for field in fields {
+            columns.push(format_args!("p.{}", field.column()));
+        }
+        if with_author {
+            columns.push("u.id AS author_user_id, u.username AS author_username");
+        }
+
+        query_builder.push(" FROM posts p");
+        if with_author {
+            query_builder.push(" JOIN users u ON u.id = p.author_id");
+        }
+
+        query_builder.push(" WHERE p.tenant_id = ");
+        query_builder.push_bind(filter.tenant_id);
+
+        query_builder.push(
+            " AND (NOT p.is_private OR EXISTS(\
+             SELECT 1 FROM post_authors pa WHERE pa.post_id = p.id AND pa.user_id = ",
+        );
+        query_builder.push_bind(filter.viewer_id);
+        query_builder.push("))");
+
+        if let Some(author_id) = filter.author_id {
+            query_builder.push(" AND p.author_id = ");
+            query_builder.push_bind(author_id);
+        }
+
+        if let Some(archived) = filter.archived {
+            query_builder.push(" AND (p.archived_at IS NOT NULL) = ");
+            query_builder.push_bind(archived);
+        }
+
+        if let Some(created_after) = filter.created_after {
+            query_builder.push(" AND p.created_at > ");
+            query_builder.push_bind(created_after);
+        }
+
+        if let Some(created_before) = filter.created_before {
+            query_builder.push(" AND p.created_at < ");
+            query_builder.push_bind(created_before);
+        }
+
+        if let Some(updated_since) = filter.updated_since {
+            query_builder.push(" AND p.updated_at >= ");
+            query_builder.push_bind(updated_since);
+        }
+
+        query_builder.push(match filter.sort {
+            PostSort::Newest => " ORDER BY p.id DESC LIMIT ",
+            PostSort::Trending => " ORDER BY p.trending_score DESC, p.id DESC LIMIT ",
+        });
+        query_builder.push_bind(limit);
+        query_builder.push(" OFFSET ");
+        query_builder.push_bind(offset);
+
+        let posts = query_builder
+            .build_query_as::<PostPreviewRow>()
+            .fetch_all(executor)
+            .await?;
+
+        Ok(posts.into_iter().map(Into::into).collect())
+    }
+
+    /// Перевести пост в архив или вернуть из архива в рамках транзакции.
+    ///
+    /// Изменение состояния увеличивает версию поста.
+    pub(crate) async fn set_archived_with_tx<'e, E>(
+        &self,
+        id: i64,
+        archived: bool,
+        executor: E,
+    ) -> Result<Post, PostError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let post = sqlx::query_as!(
+            Post,
+            r#"UPDATE posts
+            SET archived_at = CASE WHEN $2 THEN COALESCE(archived_at, NOW()) END,
+                version = version + 1
+            WHERE id = $1
+            RETURNING *, ARRAY(SELECT pa.user_id FROM post_authors pa WHERE pa.post_id = posts.id ORDER BY pa.added_at, pa.user_id) AS "author_ids!""#,
+            id,
+            archived
+        )
+        .fetch_optional(executor)
+        .await?
+        .ok_or(PostError::PostNotFound)?;
+
+        Ok(post)
     }
 
     /// Обновить существующий пост в рамках транзакции.
@@ -142,6 +760,44 @@ impl PostRepository {
             }
             query_builder.push("content = ");
             query_builder.push_bind(content);
+            query_builder.push(", reading_time_minutes = ");
+            query_builder.push_bind(estimate_reading_time(content));
+            has_fields = true;
+        }
+
+        if let Some(excerpt) = &post.excerpt {
+            if has_fields {
+                query_builder.push(", ");
+            }
+            query_builder.push("excerpt = ");
+            query_builder.push_bind(excerpt);
+            has_fields = true;
+        }
+
+        if let Some(cover_image_url) = &post.cover_image_url {
+            if has_fields {
+                query_builder.push(", ");
+            }
+            query_builder.push("cover_image_url = ");
+            query_builder.push_bind(cover_image_url);
+            has_fields = true;
+        }
+
+        if let Some(is_private) = post.is_private {
+            if has_fields {
+                query_builder.push(", ");
+            }
+            query_builder.push("is_private = ");
+            query_builder.push_bind(is_private);
+            has_fields = true;
+        }
+
+        if let Some(content_format) = &post.content_format {
+            if has_fields {
+                query_builder.push(", ");
+            }
+            query_builder.push("content_format = ");
+            query_builder.push_bind(content_format);
             has_fields = true;
         }
 
@@ -149,19 +805,210 @@ impl PostRepository {
             query_builder.push(", ");
         }
 
-        query_builder.push("updated_at = NOW() WHERE id = ");
+        query_builder.push("updated_at = NOW(), version = version + 1 WHERE id = ");
         query_builder.push_bind(post.id);
-        query_builder.push(" RETURNING *");
+
+        if let Some(expected_version) = post.expected_version {
+            query_builder.push(" AND version = ");
+            query_builder.push_bind(expected_version);
+        }
+
+        query_builder.push(
+            " RETURNING *, ARRAY(SELECT pa.user_id FROM post_authors pa \
+            WHERE pa.post_id = posts.id ORDER BY pa.added_at, pa.user_id) AS author_ids",
+        );
 
         let updated_post = query_builder
             .build_query_as::<Post>()
             .fetch_optional(executor)
             .await?
-            .ok_or(PostError::PostNotFound)?;
+            .ok_or(match post.expected_version {
+                Some(_) => PostError::Conflict,
+                None => PostError::PostNotFound,
+            })?;
 
         Ok(updated_post)
     }
 
+    /// Сохранить HTML-представление содержимого поста в рамках транзакции.
+    ///
+    /// Версия поста не меняется: представление обновляется вместе с содержимым.
+    pub(crate) async fn set_content_html_with_tx<'e, E>(
+        &self,
+        id: i64,
+        content_html: Option<&str>,
+        executor: E,
+    ) -> Result<(), PostError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query!(
+            "UPDATE posts SET content_html = $2 WHERE id = $1",
+            id,
+            content_html
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Добавить пользователя в авторы поста в рамках транзакции.
+    ///
+    /// Автором может стать только пользователь того же блога, что и пост.
+    /// Повторное добавление уже существующего автора ничего не меняет.
+    pub(crate) async fn add_post_author_with_tx<'e, E>(
+        &self,
+        post_id: i64,
+        user_id: i64,
+        executor: E,
+    ) -> Result<(), PostError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let found = sqlx::query_scalar!(
+            r#"WITH candidate AS (
+                SELECT u.id FROM users u
+                JOIN posts p ON p.tenant_id = u.tenant_id
+                WHERE p.id = $1 AND u.id = $2
+            ), inserted AS (
+                INSERT INTO post_authors (post_id, user_id)
+                SELECT $1, id FROM candidate
+                ON CONFLICT DO NOTHING
+            )
+            SELECT EXISTS(SELECT 1 FROM candidate) AS "found!""#,
+            post_id,
+            user_id
+        )
+        .fetch_one(executor)
+        .await?;
+
+        if !found {
+            return Err(PostError::CoAuthorNotFound);
+        }
+
+        Ok(())
+    }
+
+    /// Проверить, является ли пользователь автором (владельцем или соавтором) поста,
+    /// в рамках транзакции.
+    pub(crate) async fn is_post_author_with_tx<'e, E>(
+        &self,
+        post_id: i64,
+        user_id: i64,
+        executor: E,
+    ) -> Result<bool, PostError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let is_author = sqlx::query_scalar!(
+            r#"SELECT EXISTS(
+                SELECT 1 FROM post_authors WHERE post_id = $1 AND user_id = $2
+            ) AS "is_author!""#,
+            post_id,
+            user_id
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(is_author)
+    }
+
+    /// Отметить пост для проверки модератором.
+    pub(crate) async fn flag_post(&self, post_id: i64, reason: &str) -> Result<(), PostError> {
+        self.flag_post_with_tx(post_id, reason, &self.pool).await
+    }
+
+    /// Отметить пост для проверки модератором в рамках транзакции.
+    ///
+    /// Повторная отметка заменяет причину и время предыдущей.
+    pub(crate) async fn flag_post_with_tx<'e, E>(
+        &self,
+        post_id: i64,
+        reason: &str,
+        executor: E,
+    ) -> Result<(), PostError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query!(
+            "INSERT INTO post_moderation_flags (post_id, reason) VALUES ($1, $2)
+            ON CONFLICT (post_id) DO UPDATE SET reason = EXCLUDED.reason, flagged_at = NOW()",
+            post_id,
+            reason
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Добавить накопленные просмотры постов.
+    ///
+    /// Просмотры уже удаленных постов пропускаются.
+    pub(crate) async fn add_post_views(
+        &self,
+        ids: &[i64],
+        counts: &[i64],
+    ) -> Result<(), PostError> {
+        self.add_post_views_with_tx(ids, counts, &self.pool).await
+    }
+
+    /// Добавить накопленные просмотры постов в рамках транзакции.
+    pub(crate) async fn add_post_views_with_tx<'e, E>(
+        &self,
+        ids: &[i64],
+        counts: &[i64],
+        executor: E,
+    ) -> Result<(), PostError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query!(
+            "UPDATE posts p SET view_count = p.view_count + v.count
+            FROM UNNEST($1::BIGINT[], $2::BIGINT[]) AS v(id, count)
+            WHERE p.id = v.id",
+            ids,
+            counts
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Пересчитать оценки популярности просмотренных постов.
+    ///
+    /// Возвращает количество обновленных постов.
+    pub(crate) async fn update_trending_scores(&self, gravity: f64) -> Result<u64, PostError> {
+        self.update_trending_scores_with_tx(gravity, &self.pool)
+            .await
+    }
+
+    /// Пересчитать оценки популярности просмотренных постов в рамках транзакции.
+    pub(crate) async fn update_trending_scores_with_tx<'e, E>(
+        &self,
+        gravity: f64,
+        executor: E,
+    ) -> Result<u64, PostError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let result = sqlx::query!(
+            "UPDATE posts
+            SET trending_score = view_count / POWER(
+                GREATEST(EXTRACT(EPOCH FROM NOW() - created_at)::DOUBLE PRECISION, 0) / 3600 + 2,
+                $1
+            )
+            WHERE view_count > 0",
+            gravity
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
     /// Удалить пост по идентификатору.
     #[allow(dead_code)]
     pub(crate) async fn delete_post(&self, id: i64) -> Result<(), PostError> {
@@ -184,3 +1031,203 @@ impl PostRepository {
         Ok(())
     }
 }
+
+/// Строка выборки поста, объединенная с данными автора.
+struct PostWithAuthorRow {
+    id: i64,
+    title: String,
+    content: String,
+    excerpt: Option<String>,
+    cover_image_url: Option<String>,
+    is_private: bool,
+    author_id: i64,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    version: i64,
+    archived_at: Option<DateTime<Utc>>,
+    reading_time_minutes: i32,
+    content_format: String,
+    content_html: Option<String>,
+    tenant_id: i64,
+    view_count: i64,
+    trending_score: f64,
+    author_username: String,
+    author_ids: Vec<i64>,
+}
+
+impl From<PostWithAuthorRow> for PostWithAuthor {
+    fn from(row: PostWithAuthorRow) -> Self {
+        Self {
+            post: Post {
+                id: row.id,
+                title: row.title,
+                content: row.content,
+                excerpt: row.excerpt,
+                cover_image_url: row.cover_image_url,
+                is_private: row.is_private,
+                author_id: row.author_id,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+                version: row.version,
+                archived_at: row.archived_at,
+                reading_time_minutes: row.reading_time_minutes,
+                content_format: row.content_format.into(),
+                content_html: row.content_html,
+                author_ids: row.author_ids,
+                tenant_id: row.tenant_id,
+                view_count: row.view_count,
+                trending_score: row.trending_score,
+            },
+            author: PostAuthor {
+                id: row.author_id,
+                username: row.author_username,
+            },
+        }
+    }
+}
+
+/// Строка выборки сокращенного представления поста с необязательными данными автора.
+#[derive(sqlx::FromRow)]
+struct PostPreviewRow {
+    #[sqlx(flatten)]
+    preview: PostPreview,
+
+    #[sqlx(default)]
+    author_user_id: Option<i64>,
+
+    #[sqlx(default)]
+    author_username: Option<String>,
+}
+
+impl From<PostPreviewRow> for PostPreview {
+    fn from(row: PostPreviewRow) -> Self {
+        let author = row
+            .author_user_id
+            .zip(row.author_username)
+            .map(|(id, username)| PostAuthor { id, username });
+
+        Self {
+            author,
+            ..row.preview
+        }
+    }
+}
+
+/// Экранировать спецсимволы шаблона `LIKE` в пользовательском запросе.
+fn escape_like(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::post::CreatePostRequest;
+
+    /// Создать пользователя блога по умолчанию.
+    async fn create_user(pool: &PgPool, username: &str) -> i64 {
+        sqlx::query_scalar(
+            "INSERT INTO users (username, email, password_hash, tenant_id)
+            VALUES ($1, $1 || '@example.com', '', 1) RETURNING id",
+        )
+        .bind(username)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    /// Условия отбора постов блога по умолчанию для пользователя.
+    fn filter(viewer_id: i64) -> PostFilter {
+        PostFilter {
+            tenant_id: 1,
+            viewer_id: Some(viewer_id),
+            author_id: None,
+            archived: None,
+            created_after: None,
+            created_before: None,
+            updated_since: None,
+            sort: PostSort::Newest,
+        }
+    }
+
+    #[sqlx::test(migrator = "crate::infrastructure::database::MIGRATOR")]
+    #[ignore = "требуется PostgreSQL (DATABASE_URL)"]
+    async fn co_author_sees_private_post_in_lists(pool: PgPool) {
+        let repository = PostRepository::new(pool.clone());
+        let owner = create_user(&pool, "owner").await;
+        let co_author = create_user(&pool, "co_author").await;
+        let stranger = create_user(&pool, "stranger").await;
+
+        let post = Post::from(CreatePostRequest {
+            title: "Черновик".into(),
+            content: "Текст".into(),
+            excerpt: None,
+            cover_image_url: None,
+            is_private: true,
+            content_format: None,
+        });
+        let post = repository
+            .create_post(
+                Post {
+                    tenant_id: 1,
+                    ..post
+                },
+                owner,
+                None,
+            )
+            .await
+            .unwrap();
+        repository
+            .add_post_author_with_tx(post.id, co_author, &pool)
+            .await
+            .unwrap();
+
+        for (viewer, visible) in [(owner, true), (co_author, true), (stranger, false)] {
+            let filter = filter(viewer);
+            let expected = if visible { vec![post.id] } else { vec![] };
+
+            let posts = repository.get_posts(&filter, 10, 0).await.unwrap();
+            assert_eq!(posts.iter().map(|p| p.id).collect::<Vec<_>>(), expected);
+
+            let total = repository.count_posts(&filter).await.unwrap();
+            assert_eq!(total, expected.len() as i64);
+
+            let posts = repository
+                .get_posts_by_ids(1, &[post.id], Some(viewer))
+                .await
+                .unwrap();
+            assert_eq!(posts.iter().map(|p| p.id).collect::<Vec<_>>(), expected);
+
+            let posts = repository
+                .get_posts_with_author(&filter, 10, 0)
+                .await
+                .unwrap();
+            assert_eq!(posts.len(), expected.len());
+
+            let previews = repository
+                .get_post_previews(&[PostField::Id], false, &filter, 10, 0)
+                .await
+                .unwrap();
+            assert_eq!(previews.iter().map(|p| p.id).collect::<Vec<_>>(), expected);
+
+            let suggestions = repository
+                .suggest_posts(1, "Черн", Some(viewer), 10)
+                .await
+                .unwrap();
+            assert_eq!(suggestions.len(), expected.len());
+
+            assert_eq!(
+                repository.exists(1, post.id, Some(viewer)).await.unwrap(),
+                visible
+            );
+        }
+    }
+}
@@ -0,0 +1,50 @@
+//! Репозиторий для работы с настройками пользователей в базе данных.
+
+use crate::domain::error::UserError;
+use crate::domain::settings::UserSettings;
+use sqlx::PgPool;
+use sqlx::types::Json;
+
+/// Репозиторий для работы с настройками пользователей в базе данных.
+#[derive(Debug)]
+pub(crate) struct SettingsRepository {
+    /// Пул соединений с базой данных PostgreSQL.
+    pool: PgPool,
+}
+
+impl SettingsRepository {
+    pub(crate) fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Получить настройки пользователя (значения по умолчанию, если настройки не сохранялись).
+    pub(crate) async fn get_settings(&self, user_id: i64) -> Result<UserSettings, UserError> {
+        let settings = sqlx::query_scalar!(
+            r#"SELECT settings AS "settings: Json<UserSettings>" FROM user_settings WHERE user_id = $1"#,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(settings.map(|Json(settings)| settings).unwrap_or_default())
+    }
+
+    /// Сохранить настройки пользователя.
+    pub(crate) async fn save_settings(
+        &self,
+        user_id: i64,
+        settings: &UserSettings,
+    ) -> Result<UserSettings, UserError> {
+        let Json(settings) = sqlx::query_scalar!(
+            r#"INSERT INTO user_settings (user_id, settings) VALUES ($1, $2)
+            ON CONFLICT (user_id) DO UPDATE SET settings = EXCLUDED.settings, updated_at = NOW()
+            RETURNING settings AS "settings: Json<UserSettings>""#,
+            user_id,
+            Json(settings) as _
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(settings)
+    }
+}
@@ -0,0 +1,99 @@
+//! Репозиторий для работы с блогами (арендаторами) в базе данных.
+
+use crate::domain::error::TenantError;
+use crate::domain::tenant::{CreateTenantRequest, Tenant};
+use sqlx::PgPool;
+
+/// Репозиторий для работы с блогами (арендаторами) в базе данных.
+#[derive(Debug)]
+pub(crate) struct TenantRepository {
+    /// Пул соединений с базой данных PostgreSQL.
+    pool: PgPool,
+}
+
+impl TenantRepository {
+    pub(crate) fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Создать новый блог.
+    pub(crate) async fn create_tenant(
+        &self,
+        request: &CreateTenantRequest,
+    ) -> Result<Tenant, TenantError> {
+        let tenant = sqlx::query_as!(
+            Tenant,
+            "INSERT INTO tenants (slug, name, hostname) VALUES ($1, $2, $3) RETURNING *",
+            request.slug,
+            request.name,
+            request.hostname
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db_err)
+                if db_err.code() == Some(std::borrow::Cow::Borrowed("23505")) =>
+            {
+                TenantError::TenantAlreadyExists
+            }
+            _ => TenantError::Database(e),
+        })?;
+
+        Ok(tenant)
+    }
+
+    /// Получить список всех блогов.
+    pub(crate) async fn get_tenants(&self) -> Result<Vec<Tenant>, TenantError> {
+        let tenants = sqlx::query_as!(Tenant, "SELECT * FROM tenants ORDER BY id")
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(tenants)
+    }
+
+    /// Получить блог по короткому имени.
+    pub(crate) async fn get_tenant_by_slug(&self, slug: &str) -> Result<Tenant, TenantError> {
+        let tenant = sqlx::query_as!(Tenant, "SELECT * FROM tenants WHERE slug = $1", slug)
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or(TenantError::TenantNotFound)?;
+
+        Ok(tenant)
+    }
+
+    /// Найти блог по доменному имени.
+    pub(crate) async fn find_tenant_by_hostname(
+        &self,
+        hostname: &str,
+    ) -> Result<Option<Tenant>, TenantError> {
+        let tenant = sqlx::query_as!(
+            Tenant,
+            "SELECT * FROM tenants WHERE hostname = $1",
+            hostname
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(tenant)
+    }
+
+    /// Удалить блог, в котором нет пользователей и постов.
+    ///
+    /// Возвращает удаленный блог.
+    pub(crate) async fn delete_tenant(&self, id: i64) -> Result<Tenant, TenantError> {
+        let tenant = sqlx::query_as!(Tenant, "DELETE FROM tenants WHERE id = $1 RETURNING *", id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::Database(db_err)
+                    if db_err.code() == Some(std::borrow::Cow::Borrowed("23503")) =>
+                {
+                    TenantError::TenantNotEmpty
+                }
+                _ => TenantError::Database(e),
+            })?
+            .ok_or(TenantError::TenantNotFound)?;
+
+        Ok(tenant)
+    }
+}
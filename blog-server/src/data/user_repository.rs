@@ -1,8 +1,9 @@
 //! Репозиторий для работы с пользователями в базе данных.
 
 use crate::domain::error::UserError;
-use crate::domain::user::User;
-use sqlx::PgPool;
+use crate::domain::user::{EmailChange, PublicProfile, User};
+use sqlx::types::chrono::{DateTime, Utc};
+use sqlx::{Executor, PgPool, Postgres, Transaction};
 
 /// Репозиторий для работы с пользователями в базе данных.
 #[derive(Debug)]
@@ -16,16 +17,37 @@ impl UserRepository {
         Self { pool }
     }
 
+    /// Начать новую транзакцию.
+    pub(crate) async fn begin_transaction(
+        &self,
+    ) -> Result<Transaction<'static, Postgres>, sqlx::Error> {
+        self.pool.begin().await
+    }
+
     /// Создать нового пользователя.
     pub(crate) async fn create_user(&self, user: User) -> Result<User, UserError> {
-        let post = sqlx::query_as!(
+        self.create_user_with_tx(user, &self.pool).await
+    }
+
+    /// Создать нового пользователя в рамках транзакции.
+    pub(crate) async fn create_user_with_tx<'e, E>(
+        &self,
+        user: User,
+        executor: E,
+    ) -> Result<User, UserError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let user = sqlx::query_as!(
             User,
-            "INSERT INTO users (username, email, password_hash) VALUES ($1, $2, $3) RETURNING *",
+            "INSERT INTO users (username, email, password_hash, tenant_id)
+            VALUES ($1, $2, $3, $4) RETURNING *",
             user.username,
             user.email,
-            user.password_hash
+            user.password_hash,
+            user.tenant_id
         )
-        .fetch_one(&self.pool)
+        .fetch_one(executor)
         .await
         .map_err(|e| match e {
             sqlx::Error::Database(db_err)
@@ -36,16 +58,200 @@ impl UserRepository {
             _ => UserError::Database(e),
         })?;
 
-        Ok(post)
+        Ok(user)
+    }
+
+    /// Получить пользователя блога по имени пользователя.
+    pub(crate) async fn get_user(&self, tenant_id: i64, username: &str) -> Result<User, UserError> {
+        let user = sqlx::query_as!(
+            User,
+            "SELECT * FROM users WHERE tenant_id = $1 AND username = $2",
+            tenant_id,
+            username
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(UserError::UserNotFound)?;
+
+        Ok(user)
+    }
+
+    /// Получить публичный профиль пользователя блога по имени пользователя.
+    ///
+    /// Количество публичных постов считается в том же запросе.
+    pub(crate) async fn get_public_profile(
+        &self,
+        tenant_id: i64,
+        username: &str,
+    ) -> Result<PublicProfile, UserError> {
+        let row = sqlx::query!(
+            r#"SELECT u.*,
+                (SELECT COUNT(*) FROM posts p WHERE p.author_id = u.id AND NOT p.is_private)
+                    AS "post_count!"
+            FROM users u
+            WHERE u.tenant_id = $1 AND u.username = $2"#,
+            tenant_id,
+            username
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(UserError::UserNotFound)?;
+
+        let user = User {
+            id: row.id,
+            username: row.username,
+            email: row.email,
+            password_hash: row.password_hash,
+            created_at: row.created_at,
+            avatar_url: row.avatar_url,
+            is_admin: row.is_admin,
+            tenant_id: row.tenant_id,
+        };
+
+        Ok(PublicProfile::new(user, row.post_count))
     }
 
-    /// Получить пользователя по имени пользователя.
-    pub(crate) async fn get_user(&self, username: &str) -> Result<User, UserError> {
-        let user = sqlx::query_as!(User, "SELECT * FROM users WHERE username = $1", username)
+    /// Получить пользователя по идентификатору.
+    pub(crate) async fn get_user_by_id(&self, id: i64) -> Result<User, UserError> {
+        let user = sqlx::query_as!(User, "SELECT * FROM users WHERE id = $1", id)
             .fetch_optional(&self.pool)
             .await?
             .ok_or(UserError::UserNotFound)?;
 
         Ok(user)
     }
+
+    /// Сохранить ссылку на аватар пользователя.
+    pub(crate) async fn set_avatar_url(
+        &self,
+        id: i64,
+        avatar_url: Option<&str>,
+    ) -> Result<User, UserError> {
+        let user = sqlx::query_as!(
+            User,
+            "UPDATE users SET avatar_url = $2 WHERE id = $1 RETURNING *",
+            id,
+            avatar_url
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(UserError::UserNotFound)?;
+
+        Ok(user)
+    }
+
+    /// Проверить, занят ли email-адрес в блоге пользователя `user_id`, в рамках транзакции.
+    pub(crate) async fn email_exists_with_tx<'e, E>(
+        &self,
+        user_id: i64,
+        email: &str,
+        executor: E,
+    ) -> Result<bool, UserError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let exists = sqlx::query_scalar!(
+            r#"SELECT EXISTS(
+                SELECT 1 FROM users
+                WHERE email = $2 AND tenant_id = (SELECT tenant_id FROM users WHERE id = $1)
+            ) AS "exists!""#,
+            user_id,
+            email
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(exists)
+    }
+
+    /// Сохранить запрос на смену email-адреса в рамках транзакции.
+    ///
+    /// Предыдущий запрос пользователя заменяется, только если он был сделан не позже
+    /// `not_after`; в противном случае возвращается `false`.
+    pub(crate) async fn save_email_change_with_tx<'e, E>(
+        &self,
+        user_id: i64,
+        new_email: &str,
+        token_hash: &str,
+        expires_at: DateTime<Utc>,
+        not_after: DateTime<Utc>,
+        executor: E,
+    ) -> Result<bool, UserError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let saved = sqlx::query_scalar!(
+            "INSERT INTO email_changes (user_id, new_email, token_hash, expires_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (user_id) DO UPDATE
+                SET new_email = EXCLUDED.new_email,
+                    token_hash = EXCLUDED.token_hash,
+                    requested_at = NOW(),
+                    expires_at = EXCLUDED.expires_at
+                WHERE email_changes.requested_at <= $5
+            RETURNING user_id",
+            user_id,
+            new_email,
+            token_hash,
+            expires_at,
+            not_after
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(saved.is_some())
+    }
+
+    /// Извлечь (удалить и вернуть) запрос на смену email-адреса по хешу токена
+    /// в рамках транзакции.
+    pub(crate) async fn take_email_change_with_tx<'e, E>(
+        &self,
+        token_hash: &str,
+        executor: E,
+    ) -> Result<Option<EmailChange>, UserError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let change = sqlx::query_as!(
+            EmailChange,
+            "DELETE FROM email_changes WHERE token_hash = $1
+            RETURNING user_id, new_email, expires_at",
+            token_hash
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(change)
+    }
+
+    /// Изменить email-адрес пользователя в рамках транзакции.
+    pub(crate) async fn update_email_with_tx<'e, E>(
+        &self,
+        id: i64,
+        email: &str,
+        executor: E,
+    ) -> Result<User, UserError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let user = sqlx::query_as!(
+            User,
+            "UPDATE users SET email = $2 WHERE id = $1 RETURNING *",
+            id,
+            email
+        )
+        .fetch_optional(executor)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db_err)
+                if db_err.code() == Some(std::borrow::Cow::Borrowed("23505")) =>
+            {
+                UserError::UserAlreadyExists
+            }
+            _ => UserError::Database(e),
+        })?
+        .ok_or(UserError::UserNotFound)?;
+
+        Ok(user)
+    }
 }
@@ -0,0 +1,305 @@
+//! Доменные модели администрирования.
+
+use crate::domain::error::AdminError;
+use crate::impl_json_response;
+use axum::http::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+use axum::response::{IntoResponse, Response};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sqlx::types::chrono::{DateTime, Utc};
+use std::fmt::Write;
+
+/// Период статистики по умолчанию (в днях).
+pub(crate) const DEFAULT_STATS_DAYS: i32 = 30;
+
+/// Максимальный период статистики (в днях).
+pub(crate) const MAX_STATS_DAYS: i32 = 365;
+
+/// Время жизни кэша статистики по умолчанию (в секундах).
+pub(crate) const DEFAULT_STATS_CACHE_TTL_SECS: u64 = 60;
+
+/// Параметры запроса статистики.
+#[derive(Debug, Deserialize)]
+pub struct StatsParams {
+    /// Период статистики в днях (включая текущий день).
+    pub days: Option<i32>,
+}
+
+impl StatsParams {
+    /// Получить период статистики, проверив его допустимость.
+    pub(crate) fn days(&self) -> Result<i32, AdminError> {
+        match self.days {
+            None => Ok(DEFAULT_STATS_DAYS),
+            Some(days @ 1..=MAX_STATS_DAYS) => Ok(days),
+            Some(_) => Err(AdminError::InvalidQuery(format!(
+                "Период статистики должен быть от 1 до {MAX_STATS_DAYS} дней!"
+            ))),
+        }
+    }
+}
+
+/// Формат отчета о содержимом блога.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ReportFormat {
+    /// JSON со сводными показателями и показателями по авторам.
+    #[default]
+    Json,
+
+    /// CSV с показателями по авторам (для анализа в электронных таблицах).
+    Csv,
+}
+
+/// Параметры запроса отчета о содержимом блога.
+#[derive(Debug, Deserialize)]
+pub struct ContentReportParams {
+    /// Период в днях, за который считается частота публикаций.
+    pub days: Option<i32>,
+
+    /// Формат отчета.
+    #[serde(default)]
+    pub format: ReportFormat,
+}
+
+impl ContentReportParams {
+    /// Получить период отчета, проверив его допустимость.
+    pub(crate) fn days(&self) -> Result<i32, AdminError> {
+        StatsParams { days: self.days }.days()
+    }
+}
+
+/// Показатели содержимого одного автора.
+#[derive(Debug, Clone, Serialize)]
+pub struct AuthorContentStats {
+    /// Идентификатор автора.
+    pub author_id: i64,
+
+    /// Имя автора.
+    pub username: String,
+
+    /// Количество постов автора.
+    pub posts: i64,
+
+    /// Количество постов, созданных за период отчета.
+    pub recent_posts: i64,
+
+    /// Среднее количество постов в неделю за период отчета.
+    pub posts_per_week: f64,
+
+    /// Общее количество слов в постах автора.
+    pub words: i64,
+
+    /// Среднее количество слов в посте.
+    pub average_words: f64,
+
+    /// Средняя длина поста в символах.
+    pub average_length: f64,
+
+    /// Время создания последнего поста.
+    pub last_post_at: DateTime<Utc>,
+}
+
+/// Сводные показатели содержимого блога.
+#[derive(Debug, Clone, Serialize)]
+pub struct ContentTotals {
+    /// Количество постов.
+    pub posts: i64,
+
+    /// Количество авторов, у которых есть посты.
+    pub authors: i64,
+
+    /// Среднее количество слов в посте.
+    pub average_words: f64,
+
+    /// Средняя длина поста в символах.
+    pub average_length: f64,
+
+    /// Количество изображений, на которые не ссылается ни один пост и ни один аватар.
+    pub orphaned_media: i64,
+}
+
+/// Отчет о содержимом блога для администратора.
+#[derive(Debug, Clone, Serialize)]
+pub struct ContentReport {
+    /// Период в днях, за который считается частота публикаций.
+    pub days: i32,
+
+    /// Сводные показатели.
+    pub totals: ContentTotals,
+
+    /// Показатели по авторам (сначала авторы с большим количеством постов).
+    pub authors: Vec<AuthorContentStats>,
+
+    /// Время расчета отчета.
+    pub generated_at: DateTime<Utc>,
+}
+
+impl_json_response!(ContentReport);
+
+impl ContentReport {
+    /// Представить показатели по авторам в формате CSV (с заголовком).
+    pub(crate) fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "author_id,username,posts,recent_posts,posts_per_week,words,average_words,average_length,last_post_at\n",
+        );
+
+        for author in &self.authors {
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{:.2},{},{:.1},{:.1},{}",
+                author.author_id,
+                csv_field(&author.username),
+                author.posts,
+                author.recent_posts,
+                author.posts_per_week,
+                author.words,
+                author.average_words,
+                author.average_length,
+                author.last_post_at.to_rfc3339(),
+            );
+        }
+
+        csv
+    }
+
+    /// Ответ с отчетом в указанном формате.
+    pub(crate) fn into_response_as(self, format: ReportFormat) -> Response {
+        match format {
+            ReportFormat::Json => self.into_response(),
+            ReportFormat::Csv => (
+                [
+                    (CONTENT_TYPE, "text/csv; charset=utf-8"),
+                    (
+                        CONTENT_DISPOSITION,
+                        "attachment; filename=\"content-report.csv\"",
+                    ),
+                ],
+                self.to_csv(),
+            )
+                .into_response(),
+        }
+    }
+}
+
+/// Экранировать значение поля CSV (RFC 4180).
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Общие показатели блога.
+#[derive(Debug, Clone, Serialize)]
+pub struct StatsTotals {
+    /// Количество пользователей.
+    pub users: i64,
+
+    /// Количество постов.
+    pub posts: i64,
+
+    /// Количество загруженных изображений.
+    pub media: i64,
+
+    /// Количество пользователей, входивших за период.
+    pub active_users: i64,
+}
+
+/// Показатели блога за один день.
+#[derive(Debug, Clone, Serialize)]
+pub struct DailyStats {
+    /// День (UTC).
+    pub date: NaiveDate,
+
+    /// Количество регистраций.
+    pub registrations: i64,
+
+    /// Количество созданных постов.
+    pub posts: i64,
+
+    /// Количество пользователей, входивших в этот день.
+    pub active_users: i64,
+}
+
+/// Статистика блога для администратора.
+#[derive(Debug, Clone, Serialize)]
+pub struct AdminStats {
+    /// Период статистики в днях.
+    pub days: i32,
+
+    /// Общие показатели.
+    pub totals: StatsTotals,
+
+    /// Показатели по дням (от старых к новым).
+    pub daily: Vec<DailyStats>,
+
+    /// Время расчета статистики.
+    pub generated_at: DateTime<Utc>,
+}
+
+impl_json_response!(AdminStats);
+
+impl From<AdminStats> for crate::blog_grpc::GetAdminStatsResponse {
+    fn from(stats: AdminStats) -> Self {
+        Self {
+            days: stats.days,
+            total_users: stats.totals.users,
+            total_posts: stats.totals.posts,
+            total_media: stats.totals.media,
+            active_users: stats.totals.active_users,
+            daily: stats
+                .daily
+                .into_iter()
+                .map(|day| crate::blog_grpc::DailyStats {
+                    date: day.date.to_string(),
+                    registrations: day.registrations,
+                    posts: day.posts,
+                    active_users: day.active_users,
+                })
+                .collect(),
+            generated_at: stats.generated_at.to_string(),
+        }
+    }
+}
+
+/// Примененная миграция БД.
+#[derive(Debug, Clone, Serialize)]
+pub struct AppliedMigration {
+    /// Версия миграции.
+    pub version: i64,
+
+    /// Описание миграции.
+    pub description: String,
+
+    /// Время применения миграции.
+    pub installed_on: DateTime<Utc>,
+
+    /// Длительность применения миграции в миллисекундах.
+    pub execution_time_ms: i64,
+
+    /// Миграция была изменена после применения (контрольная сумма не совпадает со встроенной).
+    pub modified: bool,
+}
+
+/// Миграция БД, которая еще не применена.
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingMigration {
+    /// Версия миграции.
+    pub version: i64,
+
+    /// Описание миграции.
+    pub description: String,
+}
+
+/// Состояние миграций БД относительно миграций, встроенных в сервер.
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationStatus {
+    /// Примененные миграции (от старых к новым).
+    pub applied: Vec<AppliedMigration>,
+
+    /// Непримененные миграции (в порядке применения).
+    pub pending: Vec<PendingMigration>,
+}
+
+impl_json_response!(MigrationStatus);
@@ -0,0 +1,77 @@
+//! Доменные модели персональных API-ключей.
+
+use serde::{Deserialize, Serialize};
+use sqlx::types::chrono::{DateTime, Utc};
+use validator::Validate;
+
+/// Область действия API-ключа.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "api_key_scope", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyScope {
+    /// Только чтение.
+    Read,
+
+    /// Чтение и изменение данных.
+    ReadWrite,
+}
+
+/// Информация об API-ключе (без самого ключа).
+#[derive(Debug, Serialize)]
+pub struct ApiKey {
+    /// Идентификатор ключа.
+    pub id: i64,
+
+    /// Идентификатор пользователя-владельца ключа.
+    pub user_id: i64,
+
+    /// Название ключа.
+    pub name: String,
+
+    /// Область действия ключа.
+    pub scope: ApiKeyScope,
+
+    /// Время создания ключа.
+    pub created_at: DateTime<Utc>,
+
+    /// Время последнего использования ключа.
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+crate::impl_json_response!(ApiKey);
+
+/// Владелец API-ключа, найденный при авторизации.
+#[derive(Debug)]
+pub struct ApiKeyOwner {
+    /// Идентификатор пользователя.
+    pub user_id: i64,
+
+    /// Идентификатор блога пользователя.
+    pub tenant_id: i64,
+
+    /// Область действия ключа.
+    pub scope: ApiKeyScope,
+}
+
+/// Данные о запросе на создание API-ключа.
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateApiKeyRequest {
+    /// Название ключа.
+    #[validate(length(min = 1, max = 64))]
+    pub name: String,
+
+    /// Область действия ключа.
+    pub scope: ApiKeyScope,
+}
+
+/// Данные об ответе на создание API-ключа.
+#[derive(Debug, Serialize)]
+pub struct CreateApiKeyResponse {
+    /// Значение ключа. Возвращается только один раз при создании.
+    pub key: String,
+
+    /// Созданный ключ.
+    pub api_key: ApiKey,
+}
+
+crate::impl_json_response!(CreateApiKeyResponse);
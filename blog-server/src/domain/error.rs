@@ -1,10 +1,60 @@
 //! Описание ошибок при взаимодействии с данными.
 
-use axum::response::IntoResponse;
+use crate::domain::feature::Feature;
+use crate::infrastructure::database::{RetryableError, is_connection_error};
+use axum::Json;
+use axum::http::header::RETRY_AFTER;
+use axum::response::{IntoResponse, Response};
+use serde::Serialize;
 use thiserror::Error;
+use validator::ValidationErrors;
 
 use axum::http::StatusCode;
 
+/// Структурированное описание ошибки валидации запроса.
+#[derive(Debug, Serialize)]
+struct ValidationErrorBody<'a> {
+    /// Описание ошибки.
+    error: &'a str,
+
+    /// Ошибки валидации по полям запроса.
+    fields: &'a ValidationErrors,
+}
+
+/// Сформировать ответ с описанием ошибок валидации.
+fn validation_error_response(
+    status_code: StatusCode,
+    error: &str,
+    fields: &ValidationErrors,
+) -> Response {
+    (status_code, Json(ValidationErrorBody { error, fields })).into_response()
+}
+
+/// Описание ошибки создания дубликата поста.
+#[derive(Debug, Serialize)]
+struct DuplicatePostBody<'a> {
+    /// Описание ошибки.
+    error: &'a str,
+
+    /// Идентификатор ранее созданного поста.
+    post_id: i64,
+}
+
+/// Ключ метаданных gRPC с идентификатором ранее созданного поста при создании дубликата.
+pub(crate) const DUPLICATE_POST_ID_METADATA: &str = "duplicate-post-id";
+
+/// Через сколько секунд клиенту следует повторить запрос, если БД недоступна.
+const DATABASE_RETRY_AFTER_SECS: u64 = 5;
+
+/// Сформировать ответ о временной недоступности БД (503 с заголовком `Retry-After`).
+fn database_unavailable_response() -> Response {
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        [(RETRY_AFTER, DATABASE_RETRY_AFTER_SECS.to_string())],
+    )
+        .into_response()
+}
+
 /// Ошибка взаимодействия с данными пользователя.
 #[derive(Debug, Error)]
 pub enum UserError {
@@ -20,6 +70,30 @@ pub enum UserError {
     #[error("Некорректные данные для регистрации: {0}")]
     InvalidRegistrationCredentials(#[from] validator::ValidationErrors),
 
+    #[error("Пароль не соответствует требованиям: {0}")]
+    WeakPassword(String),
+
+    #[error("Некорректный email-адрес: {0}")]
+    InvalidEmail(validator::ValidationErrors),
+
+    #[error("Ссылка подтверждения email-адреса недействительна!")]
+    InvalidEmailConfirmation,
+
+    #[error("Некорректные настройки: {0}")]
+    InvalidSettings(validator::ValidationErrors),
+
+    #[error("Регистрация возможна только по действительному приглашению!")]
+    InvalidInvite,
+
+    #[error("Слишком частые запросы, повторите попытку позже!")]
+    TooManyRequests,
+
+    #[error("Функция {0} отключена!")]
+    FeatureDisabled(Feature),
+
+    #[error("Не удалось отправить письмо ({0})")]
+    Mail(#[from] MailError),
+
     #[error("Ошибка хеширования пароля")]
     PasswordHashing(#[from] argon2::password_hash::Error),
 
@@ -34,11 +108,49 @@ impl IntoResponse for UserError {
     fn into_response(self) -> axum::response::Response {
         tracing::error!("Ошибка при взаимодействии с пользователями: {self}");
 
+        if let UserError::Database(e) = &self
+            && is_connection_error(e)
+        {
+            return database_unavailable_response();
+        }
+
+        if let UserError::InvalidRegistrationCredentials(errors) = &self {
+            return validation_error_response(
+                StatusCode::BAD_REQUEST,
+                "Некорректные данные для регистрации",
+                errors,
+            );
+        }
+
+        if let UserError::InvalidEmail(errors) = &self {
+            return validation_error_response(
+                StatusCode::BAD_REQUEST,
+                "Некорректный email-адрес",
+                errors,
+            );
+        }
+
+        if let UserError::InvalidSettings(errors) = &self {
+            return validation_error_response(
+                StatusCode::BAD_REQUEST,
+                "Некорректные настройки",
+                errors,
+            );
+        }
+
         let status_code = match self {
             UserError::UserNotFound => StatusCode::NOT_FOUND,
             UserError::UserAlreadyExists => StatusCode::CONFLICT,
             UserError::InvalidCredentials => StatusCode::UNAUTHORIZED,
             UserError::InvalidRegistrationCredentials(_) => StatusCode::BAD_REQUEST,
+            UserError::WeakPassword(_) => StatusCode::BAD_REQUEST,
+            UserError::InvalidEmail(_) => StatusCode::BAD_REQUEST,
+            UserError::InvalidEmailConfirmation => StatusCode::BAD_REQUEST,
+            UserError::InvalidSettings(_) => StatusCode::BAD_REQUEST,
+            UserError::InvalidInvite => StatusCode::FORBIDDEN,
+            UserError::TooManyRequests => StatusCode::TOO_MANY_REQUESTS,
+            UserError::FeatureDisabled(_) => StatusCode::FORBIDDEN,
+            UserError::Mail(_) => StatusCode::SERVICE_UNAVAILABLE,
             UserError::PasswordHashing(_) => StatusCode::INTERNAL_SERVER_ERROR,
             UserError::CreateJwtToken(_) => StatusCode::INTERNAL_SERVER_ERROR,
             UserError::Database(_) => StatusCode::INTERNAL_SERVER_ERROR,
@@ -57,8 +169,17 @@ impl From<UserError> for tonic::Status {
             UserError::UserAlreadyExists => Self::already_exists,
             UserError::InvalidCredentials => Self::invalid_argument,
             UserError::InvalidRegistrationCredentials(_) => Self::invalid_argument,
+            UserError::WeakPassword(_) => Self::invalid_argument,
+            UserError::InvalidEmail(_) => Self::invalid_argument,
+            UserError::InvalidEmailConfirmation => Self::invalid_argument,
+            UserError::InvalidSettings(_) => Self::invalid_argument,
+            UserError::InvalidInvite => Self::permission_denied,
+            UserError::TooManyRequests => Self::resource_exhausted,
+            UserError::FeatureDisabled(_) => Self::permission_denied,
+            UserError::Mail(_) => Self::unavailable,
             UserError::PasswordHashing(_) => Self::internal,
             UserError::CreateJwtToken(_) => Self::internal,
+            UserError::Database(ref e) if is_connection_error(e) => Self::unavailable,
             UserError::Database(_) => Self::internal,
         };
 
@@ -75,17 +196,98 @@ pub enum PostError {
     #[error("Запрещено взаимодействие с данным постом!")]
     Forbidden,
 
+    #[error("Пользователь, добавляемый в соавторы, не найден!")]
+    CoAuthorNotFound,
+
+    #[error("Пост был изменен другим запросом!")]
+    Conflict,
+
+    #[error("Пост находится в архиве и доступен только для чтения!")]
+    Archived,
+
+    #[error("Пост с таким заголовком уже создан недавно (id {0})!")]
+    Duplicate(i64),
+
+    #[error("Некорректное содержимое поста: {0}")]
+    InvalidPostContent(#[from] validator::ValidationErrors),
+
+    #[error("Некорректные параметры запроса: {0}")]
+    InvalidQuery(String),
+
+    #[error("Ссылка для совместного доступа недействительна!")]
+    InvalidShareLink,
+
+    #[error("Функция {0} отключена!")]
+    FeatureDisabled(Feature),
+
+    #[error("Не удалось создать ссылку для совместного доступа ({0})")]
+    CreateShareLink(String),
+
+    #[error("Поиск временно недоступен ({0})")]
+    Search(#[from] SearchError),
+
+    #[error("Содержимое поста отклонено фильтром: {0}")]
+    ContentRejected(String),
+
+    #[error("Проверка содержимого временно недоступна ({0})")]
+    ContentFilter(#[from] ContentFilterError),
+
     #[error("Внутренняя ошибка со стороны базы данных ({0})")]
     Database(#[from] sqlx::Error),
 }
 
+impl RetryableError for PostError {
+    fn is_retryable(&self) -> bool {
+        matches!(self, PostError::Database(e) if e.is_retryable())
+    }
+}
+
 impl IntoResponse for PostError {
     fn into_response(self) -> axum::response::Response {
         tracing::error!("Ошибка при взаимодействии с постами: {self}");
 
+        if let PostError::Database(e) = &self
+            && is_connection_error(e)
+        {
+            return database_unavailable_response();
+        }
+
+        if let PostError::InvalidPostContent(errors) = &self {
+            return validation_error_response(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "Некорректное содержимое поста",
+                errors,
+            );
+        }
+
+        if let PostError::Duplicate(post_id) = self {
+            let error = self.to_string();
+
+            return (
+                StatusCode::CONFLICT,
+                Json(DuplicatePostBody {
+                    error: &error,
+                    post_id,
+                }),
+            )
+                .into_response();
+        }
+
         let status_code = match self {
             PostError::PostNotFound => StatusCode::NOT_FOUND,
             PostError::Forbidden => StatusCode::FORBIDDEN,
+            PostError::CoAuthorNotFound => StatusCode::NOT_FOUND,
+            PostError::Conflict => StatusCode::CONFLICT,
+            PostError::Archived => StatusCode::LOCKED,
+            PostError::Duplicate(_) => StatusCode::CONFLICT,
+            PostError::InvalidPostContent(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            PostError::InvalidQuery(_) => StatusCode::BAD_REQUEST,
+            PostError::InvalidShareLink => StatusCode::NOT_FOUND,
+            PostError::FeatureDisabled(_) => StatusCode::FORBIDDEN,
+            PostError::CreateShareLink(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            PostError::Search(_) => StatusCode::SERVICE_UNAVAILABLE,
+            PostError::ContentRejected(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            PostError::ContentFilter(_) => StatusCode::SERVICE_UNAVAILABLE,
             PostError::Database(_) => StatusCode::INTERNAL_SERVER_ERROR,
         };
 
@@ -99,10 +301,404 @@ impl From<PostError> for tonic::Status {
 
         let status = match e {
             PostError::PostNotFound => Self::not_found,
-            PostError::Forbidden => Self::invalid_argument,
-            PostError::Database(_) => Self::invalid_argument,
+            PostError::Forbidden => Self::permission_denied,
+            PostError::CoAuthorNotFound => Self::not_found,
+            PostError::Conflict => Self::aborted,
+            PostError::Archived => Self::failed_precondition,
+            PostError::Duplicate(_) => Self::already_exists,
+            PostError::InvalidPostContent(_) => Self::invalid_argument,
+            PostError::InvalidQuery(_) => Self::invalid_argument,
+            PostError::InvalidShareLink => Self::not_found,
+            PostError::FeatureDisabled(_) => Self::permission_denied,
+            PostError::CreateShareLink(_) => Self::internal,
+            PostError::Search(_) => Self::unavailable,
+            PostError::ContentRejected(_) => Self::invalid_argument,
+            PostError::ContentFilter(_) => Self::unavailable,
+            PostError::Database(ref e) if is_connection_error(e) => Self::unavailable,
+            PostError::Database(_) => Self::internal,
+        };
+
+        let mut status = status(e.to_string());
+
+        if let PostError::Duplicate(post_id) = e {
+            status
+                .metadata_mut()
+                .insert(DUPLICATE_POST_ID_METADATA, post_id.into());
+        }
+
+        status
+    }
+}
+
+/// Ошибка взаимодействия с поисковым индексом.
+#[derive(Debug, Error)]
+pub enum SearchError {
+    #[error("Ошибка запроса к поисковому движку: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("Внутренняя ошибка со стороны базы данных ({0})")]
+    Database(#[from] sqlx::Error),
+}
+
+/// Ошибка проверки содержимого фильтром.
+#[derive(Debug, Error)]
+pub enum ContentFilterError {
+    #[error("Ошибка запроса к API модерации: {0}")]
+    Http(#[from] reqwest::Error),
+}
+
+/// Ошибка очистки устаревших данных.
+#[derive(Debug, Error)]
+pub enum CleanupError {
+    #[error("Ошибка файлового хранилища: {0}")]
+    Storage(#[from] std::io::Error),
+
+    #[error("Внутренняя ошибка со стороны базы данных ({0})")]
+    Database(#[from] sqlx::Error),
+}
+
+/// Ошибка отправки письма.
+#[derive(Debug, Error)]
+pub enum MailError {
+    #[error("Некорректный email-адрес: {0}")]
+    Address(#[from] lettre::address::AddressError),
+
+    #[error("Не удалось сформировать письмо: {0}")]
+    Message(#[from] lettre::error::Error),
+
+    #[error("Ошибка SMTP-сервера: {0}")]
+    Smtp(#[from] lettre::transport::smtp::Error),
+}
+
+/// Ошибка взаимодействия с API-ключами.
+#[derive(Debug, Error)]
+pub enum ApiKeyError {
+    #[error("API-ключ не найден!")]
+    ApiKeyNotFound,
+
+    #[error("Управление API-ключами доступно только при авторизации JWT-токеном!")]
+    Forbidden,
+
+    #[error("Некорректные данные API-ключа: {0}")]
+    InvalidApiKey(#[from] validator::ValidationErrors),
+
+    #[error("Внутренняя ошибка со стороны базы данных ({0})")]
+    Database(#[from] sqlx::Error),
+}
+
+impl IntoResponse for ApiKeyError {
+    fn into_response(self) -> axum::response::Response {
+        tracing::error!("Ошибка при взаимодействии с API-ключами: {self}");
+
+        if let ApiKeyError::Database(e) = &self
+            && is_connection_error(e)
+        {
+            return database_unavailable_response();
+        }
+
+        if let ApiKeyError::InvalidApiKey(errors) = &self {
+            return validation_error_response(
+                StatusCode::BAD_REQUEST,
+                "Некорректные данные API-ключа",
+                errors,
+            );
+        }
+
+        let status_code = match self {
+            ApiKeyError::ApiKeyNotFound => StatusCode::NOT_FOUND,
+            ApiKeyError::Forbidden => StatusCode::FORBIDDEN,
+            ApiKeyError::InvalidApiKey(_) => StatusCode::BAD_REQUEST,
+            ApiKeyError::Database(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        status_code.into_response()
+    }
+}
+
+/// Ошибка взаимодействия с загруженными изображениями.
+#[derive(Debug, Error)]
+pub enum MediaError {
+    #[error("Изображение не найдено!")]
+    MediaNotFound,
+
+    #[error("Неподдерживаемый формат изображения!")]
+    UnsupportedMediaType,
+
+    #[error("Некорректное изображение: {0}")]
+    InvalidImage(String),
+
+    #[error("Размер изображения превышает {0} байт!")]
+    TooLarge(usize),
+
+    #[error("Функция {0} отключена!")]
+    FeatureDisabled(Feature),
+
+    #[error("Ошибка хранилища изображений ({0})")]
+    Storage(#[from] std::io::Error),
+
+    #[error("Внутренняя ошибка со стороны базы данных ({0})")]
+    Database(#[from] sqlx::Error),
+}
+
+impl IntoResponse for MediaError {
+    fn into_response(self) -> axum::response::Response {
+        tracing::error!("Ошибка при взаимодействии с изображениями: {self}");
+
+        if let MediaError::Database(e) = &self
+            && is_connection_error(e)
+        {
+            return database_unavailable_response();
+        }
+
+        let status_code = match self {
+            MediaError::MediaNotFound => StatusCode::NOT_FOUND,
+            MediaError::UnsupportedMediaType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            MediaError::InvalidImage(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            MediaError::TooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            MediaError::FeatureDisabled(_) => StatusCode::FORBIDDEN,
+            MediaError::Storage(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            MediaError::Database(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        status_code.into_response()
+    }
+}
+
+impl From<MediaError> for tonic::Status {
+    fn from(e: MediaError) -> Self {
+        tracing::error!("Ошибка при взаимодействии с изображениями: {e}");
+
+        let status = match e {
+            MediaError::MediaNotFound => Self::not_found,
+            MediaError::UnsupportedMediaType => Self::invalid_argument,
+            MediaError::InvalidImage(_) => Self::invalid_argument,
+            MediaError::TooLarge(_) => Self::resource_exhausted,
+            MediaError::FeatureDisabled(_) => Self::permission_denied,
+            MediaError::Storage(_) => Self::internal,
+            MediaError::Database(ref e) if is_connection_error(e) => Self::unavailable,
+            MediaError::Database(_) => Self::internal,
         };
 
         status(e.to_string())
     }
 }
+
+/// Ошибка выгрузки данных пользователя.
+#[derive(Debug, Error)]
+pub enum ExportError {
+    #[error("Выгрузка данных не запрашивалась!")]
+    ExportNotFound,
+
+    #[error("Не удалось подготовить архив с данными, запросите выгрузку повторно!")]
+    ExportFailed,
+
+    #[error("Функция {0} отключена!")]
+    FeatureDisabled(Feature),
+
+    #[error("Не удалось получить данные пользователя ({0})")]
+    User(#[from] UserError),
+
+    #[error("Не удалось получить посты пользователя ({0})")]
+    Post(#[from] PostError),
+
+    #[error("Не удалось получить изображения пользователя ({0})")]
+    Media(#[from] MediaError),
+
+    #[error("Не удалось получить API-ключи пользователя ({0})")]
+    ApiKey(#[from] ApiKeyError),
+
+    #[error("Ошибка сериализации данных ({0})")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("Ошибка формирования архива ({0})")]
+    Archive(#[from] zip::result::ZipError),
+
+    #[error("Ошибка хранилища архивов ({0})")]
+    Storage(#[from] std::io::Error),
+
+    #[error("Внутренняя ошибка со стороны базы данных ({0})")]
+    Database(#[from] sqlx::Error),
+}
+
+impl IntoResponse for ExportError {
+    fn into_response(self) -> axum::response::Response {
+        tracing::error!("Ошибка при выгрузке данных пользователя: {self}");
+
+        if let ExportError::Database(e) = &self
+            && is_connection_error(e)
+        {
+            return database_unavailable_response();
+        }
+
+        let status_code = match self {
+            ExportError::ExportNotFound => StatusCode::NOT_FOUND,
+            ExportError::ExportFailed => StatusCode::INTERNAL_SERVER_ERROR,
+            ExportError::FeatureDisabled(_) => StatusCode::FORBIDDEN,
+            ExportError::User(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ExportError::Post(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ExportError::Media(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ExportError::ApiKey(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ExportError::Serialization(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ExportError::Archive(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ExportError::Storage(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ExportError::Database(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        status_code.into_response()
+    }
+}
+
+/// Ошибка администрирования.
+#[derive(Debug, Error)]
+pub enum AdminError {
+    #[error("Действие доступно только администраторам!")]
+    Forbidden,
+
+    #[error("Флаг функциональности не найден!")]
+    FeatureNotFound,
+
+    #[error("Некорректные параметры запроса: {0}")]
+    InvalidQuery(String),
+
+    #[error("Внутренняя ошибка со стороны базы данных ({0})")]
+    Database(#[from] sqlx::Error),
+}
+
+impl IntoResponse for AdminError {
+    fn into_response(self) -> axum::response::Response {
+        tracing::error!("Ошибка при администрировании: {self}");
+
+        if let AdminError::Database(e) = &self
+            && is_connection_error(e)
+        {
+            return database_unavailable_response();
+        }
+
+        let status_code = match self {
+            AdminError::Forbidden => StatusCode::FORBIDDEN,
+            AdminError::FeatureNotFound => StatusCode::NOT_FOUND,
+            AdminError::InvalidQuery(_) => StatusCode::BAD_REQUEST,
+            AdminError::Database(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        status_code.into_response()
+    }
+}
+
+impl From<AdminError> for tonic::Status {
+    fn from(e: AdminError) -> Self {
+        tracing::error!("Ошибка при администрировании: {e}");
+
+        let status = match e {
+            AdminError::Forbidden => Self::permission_denied,
+            AdminError::FeatureNotFound => Self::not_found,
+            AdminError::InvalidQuery(_) => Self::invalid_argument,
+            AdminError::Database(ref e) if is_connection_error(e) => Self::unavailable,
+            AdminError::Database(_) => Self::internal,
+        };
+
+        status(e.to_string())
+    }
+}
+
+/// Ошибка взаимодействия с блогами (арендаторами).
+#[derive(Debug, Error)]
+pub enum TenantError {
+    #[error("Блог не найден!")]
+    TenantNotFound,
+
+    #[error("Блог с таким именем или доменом уже существует!")]
+    TenantAlreadyExists,
+
+    #[error("Блог содержит пользователей или посты и не может быть удален!")]
+    TenantNotEmpty,
+
+    #[error("Основной блог не может быть удален!")]
+    DefaultTenant,
+
+    #[error("Некорректные данные блога: {0}")]
+    InvalidTenant(#[from] validator::ValidationErrors),
+
+    #[error("Внутренняя ошибка со стороны базы данных ({0})")]
+    Database(#[from] sqlx::Error),
+}
+
+impl IntoResponse for TenantError {
+    fn into_response(self) -> axum::response::Response {
+        tracing::error!("Ошибка при взаимодействии с блогами: {self}");
+
+        if let TenantError::Database(e) = &self
+            && is_connection_error(e)
+        {
+            return database_unavailable_response();
+        }
+
+        if let TenantError::InvalidTenant(errors) = &self {
+            return validation_error_response(
+                StatusCode::BAD_REQUEST,
+                "Некорректные данные блога",
+                errors,
+            );
+        }
+
+        let status_code = match self {
+            TenantError::TenantNotFound => StatusCode::NOT_FOUND,
+            TenantError::TenantAlreadyExists => StatusCode::CONFLICT,
+            TenantError::TenantNotEmpty => StatusCode::CONFLICT,
+            TenantError::DefaultTenant => StatusCode::CONFLICT,
+            TenantError::InvalidTenant(_) => StatusCode::BAD_REQUEST,
+            TenantError::Database(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        status_code.into_response()
+    }
+}
+
+impl From<TenantError> for tonic::Status {
+    fn from(e: TenantError) -> Self {
+        tracing::error!("Ошибка при взаимодействии с блогами: {e}");
+
+        let status = match e {
+            TenantError::TenantNotFound => Self::not_found,
+            TenantError::TenantAlreadyExists => Self::already_exists,
+            TenantError::TenantNotEmpty => Self::failed_precondition,
+            TenantError::DefaultTenant => Self::failed_precondition,
+            TenantError::InvalidTenant(_) => Self::invalid_argument,
+            TenantError::Database(ref e) if is_connection_error(e) => Self::unavailable,
+            TenantError::Database(_) => Self::internal,
+        };
+
+        status(e.to_string())
+    }
+}
+
+/// Ошибка параметров пагинации списка.
+#[derive(Debug, Error)]
+pub enum PaginationError {
+    #[error("Некорректные параметры пагинации: {0}")]
+    OutOfRange(ValidationErrors),
+}
+
+impl IntoResponse for PaginationError {
+    fn into_response(self) -> axum::response::Response {
+        tracing::warn!("Ошибка параметров пагинации: {self}");
+
+        match &self {
+            PaginationError::OutOfRange(errors) => validation_error_response(
+                StatusCode::BAD_REQUEST,
+                "Некорректные параметры пагинации",
+                errors,
+            ),
+        }
+    }
+}
+
+impl From<PaginationError> for tonic::Status {
+    fn from(e: PaginationError) -> Self {
+        tracing::warn!("Ошибка параметров пагинации: {e}");
+
+        match e {
+            PaginationError::OutOfRange(_) => Self::invalid_argument(e.to_string()),
+        }
+    }
+}
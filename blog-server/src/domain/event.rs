@@ -0,0 +1,23 @@
+//! Доменные события.
+
+use crate::domain::post::Post;
+use std::sync::Arc;
+
+/// Событие, публикуемое сервисами после успешного изменения данных.
+///
+/// Подписчики (например, поисковый индекс) реагируют на события асинхронно,
+/// поэтому основные сервисы не зависят от них напрямую.
+#[derive(Debug, Clone)]
+pub(crate) enum DomainEvent {
+    /// Зарегистрирован новый пользователь.
+    UserRegistered { user_id: i64, username: String },
+
+    /// Создан пост.
+    PostCreated(Arc<Post>),
+
+    /// Изменен пост (в том числе переведен в архив, возвращен из него или получил соавтора).
+    PostUpdated(Arc<Post>),
+
+    /// Удален пост.
+    PostDeleted { id: i64, author_id: i64 },
+}
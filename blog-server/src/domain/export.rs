@@ -0,0 +1,39 @@
+//! Доменные модели выгрузки данных пользователя.
+
+use crate::impl_json_response;
+use serde::Serialize;
+use sqlx::types::chrono::{DateTime, Utc};
+
+/// Время в секундах, после которого незавершенная выгрузка считается прерванной
+/// и может быть запрошена повторно.
+pub(crate) const EXPORT_JOB_TIMEOUT_SECS: i64 = 60 * 60;
+
+/// Статус подготовки архива с данными пользователя.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, sqlx::Type)]
+#[sqlx(type_name = "data_export_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum DataExportStatus {
+    /// Архив готовится.
+    Pending,
+
+    /// Архив готов к скачиванию.
+    Ready,
+
+    /// Не удалось подготовить архив.
+    Failed,
+}
+
+/// Выгрузка данных пользователя.
+#[derive(Debug, Serialize)]
+pub struct DataExport {
+    /// Статус подготовки архива.
+    pub status: DataExportStatus,
+
+    /// Время запроса выгрузки.
+    pub requested_at: DateTime<Utc>,
+
+    /// Время завершения подготовки архива.
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl_json_response!(DataExport);
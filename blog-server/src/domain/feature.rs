@@ -0,0 +1,92 @@
+//! Доменные модели флагов функциональности.
+
+use crate::impl_json_response;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Функция или режим работы блога, которые можно переключить без перезапуска сервера.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+#[value(rename_all = "snake_case")]
+pub enum Feature {
+    /// Регистрация новых пользователей.
+    RegistrationOpen,
+
+    /// Загрузка изображений.
+    MediaUploads,
+
+    /// Ссылки совместного доступа к постам.
+    ShareLinks,
+
+    /// Выгрузка данных пользователей.
+    DataExports,
+
+    /// Режим обслуживания: изменяющие запросы отклоняются, чтение остается доступным.
+    ///
+    /// Включается аргументом `--maintenance`, а не `--disable-feature`.
+    #[value(skip)]
+    MaintenanceMode,
+}
+
+impl Feature {
+    /// Все функции, управляемые флагами.
+    pub(crate) const ALL: &'static [Feature] = &[
+        Feature::RegistrationOpen,
+        Feature::MediaUploads,
+        Feature::ShareLinks,
+        Feature::DataExports,
+        Feature::MaintenanceMode,
+    ];
+
+    /// Название флага в хранилище и API.
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Feature::RegistrationOpen => "registration_open",
+            Feature::MediaUploads => "media_uploads",
+            Feature::ShareLinks => "share_links",
+            Feature::DataExports => "data_exports",
+            Feature::MaintenanceMode => "maintenance_mode",
+        }
+    }
+
+    /// Значение флага, если оно не задано в конфигурации и не переопределено администратором.
+    pub(crate) fn enabled_by_default(&self) -> bool {
+        !matches!(self, Feature::MaintenanceMode)
+    }
+
+    /// Найти функцию по названию флага.
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|feature| feature.as_str() == name)
+    }
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Состояние флага функциональности.
+#[derive(Debug, Clone, Serialize)]
+pub struct FeatureFlag {
+    /// Функция.
+    pub name: Feature,
+
+    /// Включена ли функция.
+    pub enabled: bool,
+
+    /// Переопределено ли значение из конфигурации администратором.
+    pub overridden: bool,
+}
+
+impl_json_response!(FeatureFlag);
+
+/// Запрос на изменение флага функциональности.
+#[derive(Debug, Deserialize)]
+pub struct SetFeatureRequest {
+    /// Включить или отключить функцию.
+    pub enabled: bool,
+}
@@ -0,0 +1,38 @@
+//! Доменные модели приглашений.
+
+use crate::impl_json_response;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::types::chrono::{DateTime, Utc};
+use validator::Validate;
+
+/// Время жизни приглашения по умолчанию (в секундах).
+pub(crate) const DEFAULT_INVITE_TTL_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Максимальное время жизни приглашения (в секундах).
+pub(crate) const MAX_INVITE_TTL_SECS: u64 = 90 * 24 * 60 * 60;
+
+/// Данные о запросе на создание приглашения.
+#[derive(Debug, Default, Deserialize, Validate)]
+pub(crate) struct CreateInviteRequest {
+    /// Время жизни приглашения в секундах.
+    #[validate(range(min = 1, max = MAX_INVITE_TTL_SECS))]
+    pub ttl_seconds: Option<u64>,
+}
+
+/// Приглашение для регистрации.
+#[derive(Debug, Serialize)]
+pub struct Invite {
+    /// Код приглашения (показывается только при создании).
+    pub code: String,
+
+    /// Время истечения приглашения.
+    pub expires_at: DateTime<Utc>,
+}
+
+impl_json_response!(Invite);
+
+/// Вычислить хеш кода приглашения, под которым оно хранится в базе данных.
+pub(crate) fn hash_invite_code(code: &str) -> String {
+    format!("{:x}", Sha256::digest(code.trim().as_bytes()))
+}
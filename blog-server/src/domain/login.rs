@@ -0,0 +1,49 @@
+//! Доменные модели истории входов пользователя.
+
+use serde::Serialize;
+use sqlx::types::chrono::{DateTime, Utc};
+
+/// Сведения о клиенте, выполняющем вход.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LoginContext {
+    /// IP-адрес клиента.
+    pub ip: Option<String>,
+
+    /// User-Agent клиента.
+    pub user_agent: Option<String>,
+
+    /// Код страны клиента (определяется обратным прокси).
+    pub country: Option<String>,
+}
+
+/// Запись истории входов пользователя.
+#[derive(Debug, Serialize)]
+pub struct LoginRecord {
+    /// Идентификатор записи.
+    pub id: i64,
+
+    /// IP-адрес клиента.
+    pub ip: Option<String>,
+
+    /// User-Agent клиента.
+    pub user_agent: Option<String>,
+
+    /// Код страны клиента.
+    pub country: Option<String>,
+
+    /// Вход выполнен с устройства, с которого пользователь ранее не входил.
+    pub new_device: bool,
+
+    /// Вход выполнен из страны, из которой пользователь ранее не входил.
+    pub new_country: bool,
+
+    /// Время входа.
+    pub created_at: DateTime<Utc>,
+}
+
+impl LoginRecord {
+    /// Вход выглядит подозрительно (новое устройство или страна).
+    pub(crate) fn is_anomalous(&self) -> bool {
+        self.new_device || self.new_country
+    }
+}
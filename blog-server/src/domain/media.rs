@@ -0,0 +1,161 @@
+//! Доменные модели загруженных изображений.
+
+use serde::{Deserialize, Serialize};
+use sqlx::types::chrono::{DateTime, Utc};
+
+/// Форматы изображений, которые можно загрузить.
+pub(crate) const SUPPORTED_FORMATS: &[image::ImageFormat] = &[
+    image::ImageFormat::Png,
+    image::ImageFormat::Jpeg,
+    image::ImageFormat::Gif,
+    image::ImageFormat::WebP,
+];
+
+/// Максимальная ширина и высота загружаемого изображения (в пикселях).
+pub(crate) const MAX_IMAGE_DIMENSION: u32 = 10_000;
+
+/// Начало ссылки на загруженное изображение.
+pub(crate) const MEDIA_URL_PREFIX: &str = "/api/v1/media/";
+
+/// Размер (вариант) изображения.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "media_size", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum MediaSize {
+    /// Миниатюра для списков.
+    Thumbnail,
+
+    /// Средний размер для страницы поста.
+    Medium,
+
+    /// Исходное изображение.
+    #[default]
+    Original,
+}
+
+impl MediaSize {
+    /// Уменьшенные варианты, которые генерируются после загрузки изображения.
+    pub(crate) const RESIZED: &[MediaSize] = &[MediaSize::Thumbnail, MediaSize::Medium];
+
+    /// Максимальная ширина и высота варианта (для исходного изображения не ограничена).
+    pub(crate) fn max_dimension(self) -> Option<u32> {
+        match self {
+            MediaSize::Thumbnail => Some(200),
+            MediaSize::Medium => Some(800),
+            MediaSize::Original => None,
+        }
+    }
+
+    /// Название варианта в имени файла.
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            MediaSize::Thumbnail => "thumbnail",
+            MediaSize::Medium => "medium",
+            MediaSize::Original => "original",
+        }
+    }
+}
+
+/// Вариант изображения определенного размера.
+#[derive(Debug, Clone, Serialize)]
+pub struct MediaVariant {
+    /// Размер варианта.
+    pub size: MediaSize,
+
+    /// Ширина в пикселях.
+    pub width: i32,
+
+    /// Высота в пикселях.
+    pub height: i32,
+
+    /// Размер файла в байтах.
+    pub byte_size: i64,
+
+    /// Путь к файлу внутри хранилища.
+    #[serde(skip)]
+    pub path: String,
+}
+
+/// Информация о загруженном изображении.
+#[derive(Debug, Serialize)]
+pub struct Media {
+    /// Идентификатор изображения.
+    pub id: i64,
+
+    /// Идентификатор пользователя, загрузившего изображение.
+    pub owner_id: i64,
+
+    /// MIME-тип изображения.
+    pub content_type: String,
+
+    /// Ссылка на изображение (размер выбирается параметром `?size=`).
+    pub url: String,
+
+    /// Готовые варианты изображения.
+    pub variants: Vec<MediaVariant>,
+
+    /// Время загрузки изображения.
+    pub created_at: DateTime<Utc>,
+}
+
+crate::impl_json_response!(Media);
+
+impl Media {
+    /// Ссылка на изображение с указанным идентификатором.
+    pub(crate) fn url(id: i64) -> String {
+        format!("{MEDIA_URL_PREFIX}{id}")
+    }
+
+    /// Извлечь идентификатор изображения из ссылки вида [`Media::url`].
+    pub(crate) fn parse_url(url: &str) -> Option<i64> {
+        url.strip_prefix(MEDIA_URL_PREFIX)?.parse().ok()
+    }
+
+    /// Выбрать вариант изображения нужного размера.
+    ///
+    /// Пока уменьшенный вариант не готов (или исходное изображение меньше него),
+    /// используется ближайший больший вариант.
+    pub(crate) fn variant(&self, size: MediaSize) -> Option<&MediaVariant> {
+        let fallback: &[MediaSize] = match size {
+            MediaSize::Thumbnail => &[MediaSize::Thumbnail, MediaSize::Medium, MediaSize::Original],
+            MediaSize::Medium => &[MediaSize::Medium, MediaSize::Original],
+            MediaSize::Original => &[MediaSize::Original],
+        };
+
+        fallback
+            .iter()
+            .find_map(|size| self.variants.iter().find(|v| v.size == *size))
+    }
+}
+
+impl From<MediaVariant> for crate::blog_grpc::MediaVariant {
+    fn from(variant: MediaVariant) -> Self {
+        Self {
+            size: variant.size.as_str().to_string(),
+            width: variant.width,
+            height: variant.height,
+            byte_size: variant.byte_size,
+        }
+    }
+}
+
+impl From<Media> for crate::blog_grpc::Media {
+    fn from(media: Media) -> Self {
+        Self {
+            id: media.id,
+            owner_id: media.owner_id,
+            content_type: media.content_type,
+            url: media.url,
+            variants: media.variants.into_iter().map(Into::into).collect(),
+            created_at: media.created_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Параметры запроса изображения.
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct MediaSizeParams {
+    /// Требуемый размер изображения.
+    #[serde(default)]
+    pub size: MediaSize,
+}
@@ -1,4 +1,15 @@
+pub(crate) mod admin;
+pub(crate) mod api_key;
 pub(crate) mod error;
+pub(crate) mod event;
+pub(crate) mod export;
+pub(crate) mod feature;
+pub(crate) mod invite;
+pub(crate) mod login;
 pub(crate) mod macros;
+pub(crate) mod media;
+pub(crate) mod pagination;
 pub(crate) mod post;
+pub(crate) mod settings;
+pub(crate) mod tenant;
 pub(crate) mod user;
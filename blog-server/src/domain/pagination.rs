@@ -0,0 +1,101 @@
+//! Параметры пагинации списков.
+
+use crate::domain::error::PaginationError;
+use validator::{ValidationError, ValidationErrors};
+
+/// Проверенные параметры пагинации.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Pagination {
+    /// Максимальное количество элементов на странице.
+    pub limit: i64,
+
+    /// Смещение от первого элемента.
+    pub offset: i64,
+}
+
+impl Pagination {
+    /// Проверить параметры пагинации.
+    ///
+    /// `limit` должен быть от 1 до `max_limit`, а `offset` - неотрицательным; иначе
+    /// возвращается ошибка с описанием каждого некорректного параметра.
+    pub(crate) fn new(limit: i64, offset: i64, max_limit: i64) -> Result<Self, PaginationError> {
+        let mut errors = ValidationErrors::new();
+
+        if !(1..=max_limit).contains(&limit) {
+            let mut error = ValidationError::new("range").with_message(
+                format!("Количество элементов на странице должно быть от 1 до {max_limit}").into(),
+            );
+            error.add_param("min".into(), &1);
+            error.add_param("max".into(), &max_limit);
+            error.add_param("value".into(), &limit);
+
+            errors.add("limit", error);
+        }
+
+        if offset < 0 {
+            let mut error = ValidationError::new("range")
+                .with_message("Смещение не может быть отрицательным".into());
+            error.add_param("min".into(), &0);
+            error.add_param("value".into(), &offset);
+
+            errors.add("offset", error);
+        }
+
+        if !errors.is_empty() {
+            return Err(PaginationError::OutOfRange(errors));
+        }
+
+        Ok(Self { limit, offset })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Получить поля, для которых `Pagination::new` вернул ошибку.
+    fn invalid_fields(limit: i64, offset: i64, max_limit: i64) -> Vec<String> {
+        let Err(PaginationError::OutOfRange(errors)) = Pagination::new(limit, offset, max_limit)
+        else {
+            panic!("параметры limit={limit}, offset={offset} должны быть отклонены");
+        };
+
+        let mut fields: Vec<_> = errors
+            .field_errors()
+            .into_keys()
+            .map(|field| field.to_string())
+            .collect();
+        fields.sort();
+        fields
+    }
+
+    #[test]
+    fn accepts_limit_up_to_max_page_size() {
+        let pagination = Pagination::new(100, 20, 100).unwrap();
+
+        assert_eq!(pagination.limit, 100);
+        assert_eq!(pagination.offset, 20);
+        assert_eq!(Pagination::new(1, 0, 100).unwrap().limit, 1);
+    }
+
+    #[test]
+    fn rejects_limit_above_max_page_size() {
+        assert_eq!(invalid_fields(101, 0, 100), ["limit"]);
+    }
+
+    #[test]
+    fn rejects_zero_or_negative_limit() {
+        assert_eq!(invalid_fields(0, 0, 100), ["limit"]);
+        assert_eq!(invalid_fields(-1, 0, 100), ["limit"]);
+    }
+
+    #[test]
+    fn rejects_negative_offset() {
+        assert_eq!(invalid_fields(10, -1, 100), ["offset"]);
+    }
+
+    #[test]
+    fn reports_every_invalid_parameter() {
+        assert_eq!(invalid_fields(0, -5, 100), ["limit", "offset"]);
+    }
+}
@@ -1,10 +1,194 @@
 //! Доменные модели поста.
 
+use crate::domain::error::PostError;
+use crate::domain::media::Media;
 use serde::{Deserialize, Serialize};
 use sqlx::types::chrono::{DateTime, Utc};
+use std::str::FromStr;
+use url::Url;
+use validator::{Validate, ValidationError};
+
+/// Максимальная длина заголовка поста (в символах).
+pub(crate) const MAX_TITLE_LENGTH: u64 = 256;
+
+/// Максимальная длина содержимого поста (в символах).
+pub(crate) const MAX_CONTENT_LENGTH: u64 = 100_000;
+
+/// Максимальная длина краткого описания поста (в символах).
+pub(crate) const MAX_EXCERPT_LENGTH: u64 = 500;
+
+/// Максимальная длина ссылки на обложку поста (в символах).
+pub(crate) const MAX_COVER_IMAGE_URL_LENGTH: u64 = 2048;
+
+/// Максимальное количество подсказок при поиске.
+pub(crate) const MAX_SUGGESTIONS: i64 = 10;
+
+/// Максимальное количество постов в пакетном запросе по идентификаторам.
+pub(crate) const MAX_BATCH_POSTS: usize = 100;
+
+/// Максимальное количество похожих постов в рекомендациях.
+pub(crate) const MAX_RELATED_POSTS: i64 = 5;
+
+/// Время жизни кэша похожих постов по умолчанию (в секундах).
+pub(crate) const DEFAULT_RELATED_POSTS_CACHE_TTL_SECS: u64 = 5 * 60;
+
+/// Окно по умолчанию (в секундах), в течение которого пост автора с тем же заголовком
+/// считается дубликатом.
+pub(crate) const DEFAULT_DUPLICATE_POST_WINDOW_SECS: u64 = 10 * 60;
+
+/// Проверка создаваемых постов на дубликаты.
+///
+/// Пост считается дубликатом, если у автора за последние `window` уже есть пост с тем же
+/// заголовком (без учета регистра и пробелов по краям) или, при заданном `similarity`,
+/// с заголовком, сходство которого по триграммам не меньше порога.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct DuplicatePostCheck {
+    /// Окно, в течение которого ищутся дубликаты.
+    pub(crate) window: std::time::Duration,
+
+    /// Минимальное сходство заголовков от 0 до 1 (`None` - только точное совпадение).
+    pub(crate) similarity: Option<f32>,
+}
+
+/// Максимальная длина поискового запроса для подсказок.
+pub(crate) const MAX_SUGGEST_QUERY_LENGTH: usize = 100;
+
+/// Степень затухания оценки популярности поста с возрастом.
+///
+/// Оценка равна `просмотры / (возраст в часах + 2) ^ TRENDING_GRAVITY`: чем больше
+/// степень, тем быстрее старые посты уступают место новым.
+pub(crate) const TRENDING_GRAVITY: f64 = 1.5;
+
+/// Время жизни ссылки для совместного доступа по умолчанию (в секундах).
+pub(crate) const DEFAULT_SHARE_LINK_TTL_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Максимальное время жизни ссылки для совместного доступа (в секундах).
+pub(crate) const MAX_SHARE_LINK_TTL_SECS: u64 = 30 * 24 * 60 * 60;
+
+/// Средняя скорость чтения (слов в минуту) для оценки времени чтения поста.
+pub(crate) const READING_WORDS_PER_MINUTE: usize = 200;
+
+/// Максимальная длина описания в превью ссылки, составленного из содержимого поста (в символах).
+pub(crate) const PREVIEW_DESCRIPTION_LENGTH: usize = 200;
+
+/// Оценить время чтения текста в минутах по количеству слов (не менее одной минуты).
+pub(crate) fn estimate_reading_time(content: &str) -> i32 {
+    let words = content.split_whitespace().count();
+    let minutes = words.div_ceil(READING_WORDS_PER_MINUTE).max(1);
+
+    i32::try_from(minutes).unwrap_or(i32::MAX)
+}
+
+/// Формат содержимого поста.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentFormat {
+    /// Разметка Markdown (CommonMark); HTML-представление формируется сервером.
+    Markdown,
+
+    /// Обычный текст.
+    #[default]
+    Plain,
+
+    /// HTML, подготовленный редактором.
+    Html,
+}
+
+impl ContentFormat {
+    /// Значение формата в базе данных и API.
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            ContentFormat::Markdown => "markdown",
+            ContentFormat::Plain => "plain",
+            ContentFormat::Html => "html",
+        }
+    }
+}
+
+impl FromStr for ContentFormat {
+    type Err = PostError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "markdown" => Ok(ContentFormat::Markdown),
+            "plain" => Ok(ContentFormat::Plain),
+            "html" => Ok(ContentFormat::Html),
+            _ => Err(PostError::InvalidQuery(format!(
+                "неизвестный формат содержимого `{s}`"
+            ))),
+        }
+    }
+}
+
+/// Значение из базы данных (допустимые значения гарантируются ограничением столбца).
+impl From<String> for ContentFormat {
+    fn from(value: String) -> Self {
+        value.parse().unwrap_or_default()
+    }
+}
+
+/// Сформировать HTML-представление содержимого поста.
+///
+/// Формируется только для Markdown; опасные теги и атрибуты (в том числе из
+/// встроенного в Markdown HTML) удаляются.
+pub(crate) fn render_content_html(format: ContentFormat, content: &str) -> Option<String> {
+    if format != ContentFormat::Markdown {
+        return None;
+    }
+
+    let parser = pulldown_cmark::Parser::new_ext(content, pulldown_cmark::Options::ENABLE_TABLES);
+
+    let mut html = String::with_capacity(content.len() * 3 / 2);
+    pulldown_cmark::html::push_html(&mut html, parser);
+
+    Some(ammonia::clean(&html))
+}
+
+/// Проверить, что формат содержимого поста известен.
+fn validate_content_format(format: &str) -> Result<(), ValidationError> {
+    format
+        .parse::<ContentFormat>()
+        .map(|_| ())
+        .map_err(|_| ValidationError::new("content_format"))
+}
+
+/// Проверить, что ссылка на обложку использует схему `http` или `https`
+/// либо указывает на изображение, загруженное на сервер блога.
+fn validate_cover_image_url(url: &str) -> Result<(), ValidationError> {
+    normalize_cover_image_url(url)
+        .map(|_| ())
+        .ok_or_else(|| ValidationError::new("url_scheme"))
+}
+
+/// Привести ссылку на обложку к каноническому виду, в котором она сохраняется.
+///
+/// Внешняя ссылка разбирается как URL со схемой `http` или `https` и сохраняется
+/// в сериализованном (percent-encoded) виде. Ссылки с кавычками, пробельными символами
+/// и `<>` отклоняются: обложка выводится в HTML-атрибуте. Возвращает `None`,
+/// если ссылка недопустима.
+pub(crate) fn normalize_cover_image_url(url: &str) -> Option<String> {
+    let unsafe_char =
+        |c: char| c.is_whitespace() || c.is_control() || matches!(c, '"' | '\'' | '`' | '<' | '>');
+
+    if url.contains(unsafe_char) {
+        return None;
+    }
+
+    if Media::parse_url(url).is_some() {
+        return Some(url.to_string());
+    }
+
+    let parsed = Url::parse(url).ok()?;
+
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+        return None;
+    }
+
+    Some(parsed.into())
+}
 
 /// Информация о посте.
-#[derive(Debug, Serialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
 pub struct Post {
     /// Идентификатор поста.
     pub id: i64,
@@ -15,6 +199,15 @@ pub struct Post {
     /// Содержимое поста.
     pub content: String,
 
+    /// Краткое описание поста.
+    pub excerpt: Option<String>,
+
+    /// Ссылка на изображение обложки поста.
+    pub cover_image_url: Option<String>,
+
+    /// Приватный пост доступен только автору и по ссылке для совместного доступа.
+    pub is_private: bool,
+
     /// Идентификатор пользователя-автора поста.
     pub author_id: i64,
 
@@ -23,6 +216,36 @@ pub struct Post {
 
     /// Время последнего обновления поста.
     pub updated_at: DateTime<Utc>,
+
+    /// Версия поста, увеличивается при каждом обновлении.
+    pub version: i64,
+
+    /// Время перевода поста в архив (архивный пост доступен только для чтения).
+    pub archived_at: Option<DateTime<Utc>>,
+
+    /// Оценка времени чтения поста (в минутах).
+    pub reading_time_minutes: i32,
+
+    /// Формат содержимого поста.
+    #[sqlx(try_from = "String")]
+    pub content_format: ContentFormat,
+
+    /// HTML-представление содержимого (только для Markdown).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_html: Option<String>,
+
+    /// Идентификаторы всех авторов поста: владельца и соавторов.
+    pub author_ids: Vec<i64>,
+
+    /// Идентификатор блога, к которому относится пост.
+    #[serde(skip)]
+    pub tenant_id: i64,
+
+    /// Количество просмотров поста (без учета еще не записанных в базу данных).
+    pub view_count: i64,
+
+    /// Оценка популярности поста с учетом его возраста (пересчитывается периодически).
+    pub trending_score: f64,
 }
 
 crate::impl_json_response!(Post);
@@ -36,18 +259,520 @@ impl From<Post> for crate::blog_grpc::Post {
             author_id: post.author_id,
             created_at: post.created_at.to_rfc3339(),
             updated_at: post.updated_at.to_rfc3339(),
+            version: post.version,
+            author: None,
+            excerpt: post.excerpt,
+            cover_image_url: post.cover_image_url,
+            is_private: post.is_private,
+            archived_at: post.archived_at.map(|t| t.to_rfc3339()),
+            reading_time_minutes: post.reading_time_minutes,
+            author_ids: post.author_ids,
+            content_format: post.content_format.as_str().to_string(),
+            content_html: post.content_html,
+            view_count: post.view_count,
+            trending_score: post.trending_score,
         }
     }
 }
 
+impl Post {
+    /// Проверить, может ли пользователь просматривать пост.
+    ///
+    /// Приватный пост доступен владельцу и соавторам.
+    pub(crate) fn is_visible_to(&self, viewer_id: Option<i64>) -> bool {
+        !self.is_private
+            || viewer_id.is_some_and(|id| id == self.author_id || self.author_ids.contains(&id))
+    }
+}
+
+/// Сведения о посте без его содержимого.
+///
+/// Используются для проверок доступа и ответов на условные запросы,
+/// которым не нужен текст поста.
+#[derive(Debug, Clone)]
+pub(crate) struct PostMeta {
+    /// Идентификатор поста.
+    pub id: i64,
+
+    /// Идентификатор пользователя-владельца поста.
+    pub author_id: i64,
+
+    /// Приватный пост доступен только авторам и по ссылке для совместного доступа.
+    pub is_private: bool,
+
+    /// Время последнего обновления поста.
+    pub updated_at: DateTime<Utc>,
+
+    /// Версия поста.
+    pub version: i64,
+
+    /// Время перевода поста в архив.
+    pub archived_at: Option<DateTime<Utc>>,
+
+    /// Идентификаторы всех авторов поста: владельца и соавторов.
+    pub author_ids: Vec<i64>,
+}
+
+impl PostMeta {
+    /// Проверить, может ли пользователь просматривать пост.
+    ///
+    /// Приватный пост доступен владельцу и соавторам.
+    pub(crate) fn is_visible_to(&self, viewer_id: Option<i64>) -> bool {
+        !self.is_private
+            || viewer_id.is_some_and(|id| id == self.author_id || self.author_ids.contains(&id))
+    }
+
+    /// Находится ли пост в архиве.
+    pub(crate) fn is_archived(&self) -> bool {
+        self.archived_at.is_some()
+    }
+}
+
+/// Порядок постов в списке.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum PostSort {
+    /// Сначала новые посты.
+    #[default]
+    Newest,
+
+    /// Сначала популярные посты (по оценке популярности с учетом возраста).
+    Trending,
+}
+
+impl FromStr for PostSort {
+    type Err = PostError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "newest" => Ok(PostSort::Newest),
+            "trending" => Ok(PostSort::Trending),
+            _ => Err(PostError::InvalidQuery(format!(
+                "неизвестный порядок сортировки `{s}`"
+            ))),
+        }
+    }
+}
+
+/// Условия отбора и порядок постов в списке.
+#[derive(Debug, Clone)]
+pub(crate) struct PostFilter {
+    /// Блог, посты которого выбираются.
+    pub tenant_id: i64,
+
+    /// Пользователь, запрашивающий список (видит также свои приватные посты).
+    pub viewer_id: Option<i64>,
+
+    /// Только посты указанного автора (`None` - посты всех авторов).
+    pub author_id: Option<i64>,
+
+    /// Отбор по состоянию архивации (`None` - все посты).
+    pub archived: Option<bool>,
+
+    /// Только посты, созданные позже указанного времени.
+    pub created_after: Option<DateTime<Utc>>,
+
+    /// Только посты, созданные раньше указанного времени.
+    pub created_before: Option<DateTime<Utc>>,
+
+    /// Только посты, измененные начиная с указанного времени (включительно).
+    pub updated_since: Option<DateTime<Utc>>,
+
+    /// Порядок постов в списке.
+    pub sort: PostSort,
+}
+
+/// Поле поста, доступное для выборки через параметр `fields`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PostField {
+    Id,
+    Title,
+    Content,
+    Excerpt,
+    CoverImageUrl,
+    IsPrivate,
+    AuthorId,
+    CreatedAt,
+    UpdatedAt,
+    Version,
+    ArchivedAt,
+    ReadingTimeMinutes,
+    ViewCount,
+    TrendingScore,
+}
+
+impl PostField {
+    /// Поля, возвращаемые в списке постов по умолчанию (без полного содержимого).
+    pub(crate) const DEFAULT_LIST: &[PostField] = &[
+        PostField::Id,
+        PostField::Title,
+        PostField::Excerpt,
+        PostField::CoverImageUrl,
+        PostField::IsPrivate,
+        PostField::AuthorId,
+        PostField::CreatedAt,
+        PostField::UpdatedAt,
+        PostField::Version,
+        PostField::ArchivedAt,
+        PostField::ReadingTimeMinutes,
+    ];
+
+    /// Поля, возвращаемые по умолчанию в списке популярных постов: поля списка
+    /// по умолчанию, количество просмотров и оценка популярности.
+    pub(crate) const TRENDING_LIST: &[PostField] = &[
+        PostField::Id,
+        PostField::Title,
+        PostField::Excerpt,
+        PostField::CoverImageUrl,
+        PostField::IsPrivate,
+        PostField::AuthorId,
+        PostField::CreatedAt,
+        PostField::UpdatedAt,
+        PostField::Version,
+        PostField::ArchivedAt,
+        PostField::ReadingTimeMinutes,
+        PostField::ViewCount,
+        PostField::TrendingScore,
+    ];
+
+    /// Имя столбца таблицы `posts`, соответствующего полю.
+    pub(crate) fn column(self) -> &'static str {
+        match self {
+            PostField::Id => "id",
+            PostField::Title => "title",
+            PostField::Content => "content",
+            PostField::Excerpt => "excerpt",
+            PostField::CoverImageUrl => "cover_image_url",
+            PostField::IsPrivate => "is_private",
+            PostField::AuthorId => "author_id",
+            PostField::CreatedAt => "created_at",
+            PostField::UpdatedAt => "updated_at",
+            PostField::Version => "version",
+            PostField::ArchivedAt => "archived_at",
+            PostField::ReadingTimeMinutes => "reading_time_minutes",
+            PostField::ViewCount => "view_count",
+            PostField::TrendingScore => "trending_score",
+        }
+    }
+
+    /// Разобрать список полей, перечисленных через запятую.
+    ///
+    /// Идентификатор поста включается в выборку всегда.
+    pub(crate) fn parse_list(fields: &str) -> Result<Vec<PostField>, PostError> {
+        let mut parsed = vec![PostField::Id];
+
+        for field in fields.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+            let field = field.parse()?;
+
+            if !parsed.contains(&field) {
+                parsed.push(field);
+            }
+        }
+
+        Ok(parsed)
+    }
+}
+
+impl FromStr for PostField {
+    type Err = PostError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "id" => Ok(PostField::Id),
+            "title" => Ok(PostField::Title),
+            "content" => Ok(PostField::Content),
+            "excerpt" => Ok(PostField::Excerpt),
+            "cover_image_url" => Ok(PostField::CoverImageUrl),
+            "is_private" => Ok(PostField::IsPrivate),
+            "author_id" => Ok(PostField::AuthorId),
+            "created_at" => Ok(PostField::CreatedAt),
+            "updated_at" => Ok(PostField::UpdatedAt),
+            "version" => Ok(PostField::Version),
+            "archived_at" => Ok(PostField::ArchivedAt),
+            "reading_time_minutes" => Ok(PostField::ReadingTimeMinutes),
+            "view_count" => Ok(PostField::ViewCount),
+            "trending_score" => Ok(PostField::TrendingScore),
+            _ => Err(PostError::InvalidQuery(format!(
+                "неизвестное поле поста `{s}`"
+            ))),
+        }
+    }
+}
+
+/// Сокращенное представление поста, содержащее только запрошенные поля.
+#[derive(Debug, Default, Serialize, sqlx::FromRow)]
+#[sqlx(default)]
+pub struct PostPreview {
+    /// Идентификатор поста.
+    pub id: i64,
+
+    /// Заголовок поста.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    /// Содержимое поста.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+
+    /// Краткое описание поста.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub excerpt: Option<String>,
+
+    /// Ссылка на изображение обложки поста.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cover_image_url: Option<String>,
+
+    /// Признак приватного поста.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_private: Option<bool>,
+
+    /// Идентификатор пользователя-автора поста.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author_id: Option<i64>,
+
+    /// Время создания поста.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+
+    /// Время последнего обновления поста.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
+
+    /// Версия поста.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<i64>,
+
+    /// Время перевода поста в архив.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived_at: Option<DateTime<Utc>>,
+
+    /// Оценка времени чтения поста (в минутах).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reading_time_minutes: Option<i32>,
+
+    /// Количество просмотров поста.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub view_count: Option<i64>,
+
+    /// Оценка популярности поста.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trending_score: Option<f64>,
+
+    /// Автор поста (`?include=author`).
+    #[sqlx(skip)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<PostAuthor>,
+}
+
+impl From<Post> for PostPreview {
+    /// Сокращенное представление с полями списка постов по умолчанию (без содержимого).
+    fn from(post: Post) -> Self {
+        Self {
+            id: post.id,
+            title: Some(post.title),
+            content: None,
+            excerpt: post.excerpt,
+            cover_image_url: post.cover_image_url,
+            is_private: Some(post.is_private),
+            author_id: Some(post.author_id),
+            created_at: Some(post.created_at),
+            updated_at: Some(post.updated_at),
+            version: Some(post.version),
+            archived_at: post.archived_at,
+            reading_time_minutes: Some(post.reading_time_minutes),
+            view_count: None,
+            trending_score: None,
+            author: None,
+        }
+    }
+}
+
+/// Страница списка постов.
+///
+/// Список всегда возвращается вместе с общим количеством постов, поэтому пустая
+/// страница (`total = 0` или смещение за пределами списка) однозначно отличается от ошибки.
+#[derive(Debug, Serialize)]
+pub struct PostPage {
+    /// Посты на странице.
+    pub posts: Vec<PostPreview>,
+
+    /// Общее количество постов, удовлетворяющих условиям отбора.
+    pub total: i64,
+
+    /// Максимальное количество постов на странице.
+    pub limit: i64,
+
+    /// Смещение от первого поста.
+    pub offset: i64,
+}
+
+/// Отметка поста для проверки модератором.
+#[derive(Debug, Serialize)]
+pub struct PostModerationFlag {
+    /// Идентификатор поста.
+    pub post_id: i64,
+
+    /// Причина отметки (недопустимые фрагменты, найденные фильтром содержимого).
+    pub reason: String,
+
+    /// Время отметки.
+    pub flagged_at: DateTime<Utc>,
+}
+
+/// Подсказка для строки поиска постов.
+#[derive(Debug, Serialize)]
+pub struct PostSuggestion {
+    /// Идентификатор поста.
+    pub id: i64,
+
+    /// Заголовок поста.
+    pub title: String,
+}
+
+/// Значение параметра `include`, добавляющее в ответ информацию об авторе поста.
+pub(crate) const INCLUDE_AUTHOR: &str = "author";
+
+/// Проверить, запрошено ли включение автора в ответ.
+pub(crate) fn includes_author<S: AsRef<str>>(include: impl IntoIterator<Item = S>) -> bool {
+    include
+        .into_iter()
+        .any(|value| value.as_ref().trim() == INCLUDE_AUTHOR)
+}
+
+/// Краткая информация об авторе поста.
+#[derive(Debug, Serialize)]
+pub struct PostAuthor {
+    /// Идентификатор пользователя.
+    pub id: i64,
+
+    /// Имя пользователя.
+    pub username: String,
+}
+
+impl From<PostAuthor> for crate::blog_grpc::PostAuthor {
+    fn from(author: PostAuthor) -> Self {
+        Self {
+            id: author.id,
+            username: author.username,
+        }
+    }
+}
+
+/// Информация о посте вместе с данными об авторе (`?include=author`).
+#[derive(Debug, Serialize)]
+pub struct PostWithAuthor {
+    /// Информация о посте.
+    #[serde(flatten)]
+    pub post: Post,
+
+    /// Автор поста.
+    pub author: PostAuthor,
+}
+
+crate::impl_json_response!(PostWithAuthor);
+
+impl From<PostWithAuthor> for crate::blog_grpc::Post {
+    fn from(post: PostWithAuthor) -> Self {
+        Self {
+            author: Some(post.author.into()),
+            ..post.post.into()
+        }
+    }
+}
+
+/// Превью поста для отображения ссылки на него (Open Graph, мессенджеры).
+#[derive(Debug, Serialize)]
+pub struct LinkPreview {
+    /// Идентификатор поста.
+    pub id: i64,
+
+    /// Заголовок поста.
+    pub title: String,
+
+    /// Краткое описание поста, а при его отсутствии - начало содержимого.
+    pub excerpt: String,
+
+    /// Ссылка на изображение обложки поста.
+    pub cover_image_url: Option<String>,
+
+    /// Автор поста.
+    pub author: PostAuthor,
+}
+
+crate::impl_json_response!(LinkPreview);
+
+impl From<PostWithAuthor> for LinkPreview {
+    fn from(post: PostWithAuthor) -> Self {
+        let excerpt = post
+            .post
+            .excerpt
+            .unwrap_or_else(|| truncate_words(&post.post.content, PREVIEW_DESCRIPTION_LENGTH));
+
+        Self {
+            id: post.post.id,
+            title: post.post.title,
+            excerpt,
+            cover_image_url: post.post.cover_image_url,
+            author: post.author,
+        }
+    }
+}
+
+/// Сократить текст до `max_chars` символов по границе слова, схлопнув пробельные символы.
+fn truncate_words(text: &str, max_chars: usize) -> String {
+    let mut result = String::new();
+
+    for word in text.split_whitespace() {
+        let len = result.chars().count() + word.chars().count() + 1;
+
+        if len > max_chars {
+            if result.is_empty() {
+                result.extend(word.chars().take(max_chars));
+            }
+
+            result.push('…');
+            break;
+        }
+
+        if !result.is_empty() {
+            result.push(' ');
+        }
+
+        result.push_str(word);
+    }
+
+    result
+}
+
 /// Данные о запросе на создание нового поста.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate)]
 pub struct CreatePostRequest {
     /// Заголовок поста.
+    #[validate(length(min = 1, max = MAX_TITLE_LENGTH))]
     pub title: String,
 
     /// Содержимое поста.
+    #[validate(length(min = 1, max = MAX_CONTENT_LENGTH))]
     pub content: String,
+
+    /// Краткое описание поста.
+    #[validate(length(min = 1, max = MAX_EXCERPT_LENGTH))]
+    pub excerpt: Option<String>,
+
+    /// Ссылка на изображение обложки поста (`http`, `https` или загруженное изображение).
+    #[validate(
+        length(max = MAX_COVER_IMAGE_URL_LENGTH),
+        custom(function = "validate_cover_image_url")
+    )]
+    pub cover_image_url: Option<String>,
+
+    /// Сделать пост приватным.
+    #[serde(default)]
+    pub is_private: bool,
+
+    /// Формат содержимого (`markdown`, `plain` или `html`, по умолчанию `plain`).
+    #[validate(custom(function = "validate_content_format"))]
+    pub content_format: Option<String>,
 }
 
 impl From<crate::blog_grpc::CreatePostRequest> for CreatePostRequest {
@@ -55,35 +780,81 @@ impl From<crate::blog_grpc::CreatePostRequest> for CreatePostRequest {
         Self {
             title: req.title,
             content: req.content,
+            excerpt: req.excerpt,
+            cover_image_url: req.cover_image_url,
+            is_private: req.is_private,
+            content_format: req.content_format,
         }
     }
 }
 
 impl From<CreatePostRequest> for Post {
     fn from(post: CreatePostRequest) -> Self {
+        let reading_time_minutes = estimate_reading_time(&post.content);
+        let content_format = post
+            .content_format
+            .map(ContentFormat::from)
+            .unwrap_or_default();
+        let content_html = render_content_html(content_format, &post.content);
+
         Self {
             id: -1,
             title: post.title,
             content: post.content,
+            excerpt: post.excerpt,
+            cover_image_url: post.cover_image_url,
+            is_private: post.is_private,
             author_id: -1,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            version: 1,
+            archived_at: None,
+            reading_time_minutes,
+            content_format,
+            content_html,
+            author_ids: vec![],
+            tenant_id: -1,
+            view_count: 0,
+            trending_score: 0.0,
         }
     }
 }
 
 /// Данные о запросе на обновление поста.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Validate)]
 pub(crate) struct UpdatePostRequest {
     /// Идентификатор поста.
     #[serde(skip)]
     pub id: i64,
 
     /// Заголовок поста.
+    #[validate(length(min = 1, max = MAX_TITLE_LENGTH))]
     pub title: Option<String>,
 
     /// Содержимое поста.
+    #[validate(length(min = 1, max = MAX_CONTENT_LENGTH))]
     pub content: Option<String>,
+
+    /// Краткое описание поста.
+    #[validate(length(min = 1, max = MAX_EXCERPT_LENGTH))]
+    pub excerpt: Option<String>,
+
+    /// Ссылка на изображение обложки поста (`http`, `https` или загруженное изображение).
+    #[validate(
+        length(max = MAX_COVER_IMAGE_URL_LENGTH),
+        custom(function = "validate_cover_image_url")
+    )]
+    pub cover_image_url: Option<String>,
+
+    /// Сделать пост приватным или публичным.
+    pub is_private: Option<bool>,
+
+    /// Формат содержимого (`markdown`, `plain` или `html`).
+    #[validate(custom(function = "validate_content_format"))]
+    pub content_format: Option<String>,
+
+    /// Ожидаемая версия поста. Обновление отклоняется, если пост уже был изменен.
+    pub expected_version: Option<i64>,
 }
 
 impl From<crate::blog_grpc::UpdatePostRequest> for UpdatePostRequest {
@@ -92,6 +863,174 @@ impl From<crate::blog_grpc::UpdatePostRequest> for UpdatePostRequest {
             id: req.id,
             title: req.title,
             content: req.content,
+            excerpt: req.excerpt,
+            cover_image_url: req.cover_image_url,
+            is_private: req.is_private,
+            content_format: req.content_format,
+            expected_version: req.expected_version,
+        }
+    }
+}
+
+/// Данные о запросе на получение постов по списку идентификаторов.
+#[derive(Debug, Deserialize)]
+pub(crate) struct GetPostsByIdsRequest {
+    /// Идентификаторы постов.
+    pub ids: Vec<i64>,
+}
+
+/// Данные о запросе на удаление нескольких постов.
+#[derive(Debug, Deserialize)]
+pub(crate) struct BulkDeleteRequest {
+    /// Идентификаторы удаляемых постов.
+    pub ids: Vec<i64>,
+}
+
+/// Результат удаления одного поста в составе пакетного удаления.
+#[derive(Debug, Serialize)]
+pub struct BulkDeleteResult {
+    /// Идентификатор поста.
+    pub id: i64,
+
+    /// Был ли пост удален.
+    pub deleted: bool,
+
+    /// Причина, по которой пост не был удален.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl BulkDeleteResult {
+    /// Пост удален.
+    pub(crate) fn deleted(id: i64) -> Self {
+        Self {
+            id,
+            deleted: true,
+            error: None,
+        }
+    }
+
+    /// Пост не удален по причине `error`.
+    pub(crate) fn failed(id: i64, error: &PostError) -> Self {
+        Self {
+            id,
+            deleted: false,
+            error: Some(error.to_string()),
+        }
+    }
+}
+
+impl From<BulkDeleteResult> for crate::blog_grpc::BulkDeleteResult {
+    fn from(result: BulkDeleteResult) -> Self {
+        Self {
+            id: result.id,
+            deleted: result.deleted,
+            error: result.error,
         }
     }
 }
+
+/// Данные о запросе на добавление соавтора поста.
+#[derive(Debug, Deserialize)]
+pub(crate) struct AddPostAuthorRequest {
+    /// Идентификатор пользователя-соавтора.
+    pub user_id: i64,
+}
+
+impl From<crate::blog_grpc::AddPostAuthorRequest> for AddPostAuthorRequest {
+    fn from(req: crate::blog_grpc::AddPostAuthorRequest) -> Self {
+        Self {
+            user_id: req.user_id,
+        }
+    }
+}
+
+/// Данные о запросе на создание ссылки для совместного доступа к посту.
+#[derive(Debug, Default, Deserialize, Validate)]
+pub(crate) struct CreateShareLinkRequest {
+    /// Время жизни ссылки в секундах.
+    #[validate(range(min = 1, max = MAX_SHARE_LINK_TTL_SECS))]
+    pub ttl_seconds: Option<u64>,
+}
+
+impl From<crate::blog_grpc::CreateShareLinkRequest> for CreateShareLinkRequest {
+    fn from(req: crate::blog_grpc::CreateShareLinkRequest) -> Self {
+        Self {
+            ttl_seconds: req.ttl_seconds,
+        }
+    }
+}
+
+/// Ссылка для совместного доступа к посту.
+#[derive(Debug, Serialize)]
+pub struct ShareLink {
+    /// Подписанный токен доступа.
+    pub token: String,
+
+    /// Время истечения ссылки.
+    pub expires_at: DateTime<Utc>,
+}
+
+crate::impl_json_response!(ShareLink);
+
+impl From<ShareLink> for crate::blog_grpc::CreateShareLinkResponse {
+    fn from(link: ShareLink) -> Self {
+        Self {
+            token: link.token,
+            expires_at: link.expires_at.to_rfc3339(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cover_image_url_is_serialized() {
+        assert_eq!(
+            normalize_cover_image_url("https://Example.com/a/b.png?x=1").as_deref(),
+            Some("https://example.com/a/b.png?x=1")
+        );
+        assert_eq!(
+            normalize_cover_image_url("http://example.com").as_deref(),
+            Some("http://example.com/")
+        );
+        assert_eq!(
+            normalize_cover_image_url("https://example.com/обложка.png").as_deref(),
+            Some("https://example.com/%D0%BE%D0%B1%D0%BB%D0%BE%D0%B6%D0%BA%D0%B0.png")
+        );
+    }
+
+    #[test]
+    fn uploaded_cover_image_url_is_kept() {
+        assert_eq!(
+            normalize_cover_image_url("/api/v1/media/42").as_deref(),
+            Some("/api/v1/media/42")
+        );
+    }
+
+    #[test]
+    fn cover_image_url_rejects_attribute_injection() {
+        assert_eq!(
+            normalize_cover_image_url(r#"https://x.com/a" onerror="alert(1)"#),
+            None
+        );
+        assert_eq!(normalize_cover_image_url("https://x.com/a'b"), None);
+        assert_eq!(normalize_cover_image_url("https://x.com/<script>"), None);
+        assert_eq!(normalize_cover_image_url("https://x.com/a\tb"), None);
+        assert!(validate_cover_image_url(r#"https://x.com/a" onerror="alert(1)"#).is_err());
+    }
+
+    #[test]
+    fn cover_image_url_requires_http_scheme() {
+        assert_eq!(normalize_cover_image_url("javascript:alert(1)"), None);
+        assert_eq!(
+            normalize_cover_image_url("data:image/png;base64,AAAA"),
+            None
+        );
+        assert_eq!(normalize_cover_image_url("ftp://example.com/a.png"), None);
+        assert_eq!(normalize_cover_image_url("/api/v1/media/abc"), None);
+        assert_eq!(normalize_cover_image_url("example.com/a.png"), None);
+    }
+}
@@ -0,0 +1,72 @@
+//! Доменные модели пользовательских настроек.
+
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// Количество постов на странице по умолчанию.
+pub(crate) const DEFAULT_POSTS_PER_PAGE: i64 = 10;
+
+/// Максимальное количество постов на странице.
+pub(crate) const MAX_POSTS_PER_PAGE: i64 = 100;
+
+/// Язык интерфейса и писем.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    /// Русский.
+    #[default]
+    Ru,
+
+    /// Английский.
+    En,
+}
+
+/// Настройки email-уведомлений.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EmailNotifications {
+    /// Оповещать о входе в аккаунт с нового устройства или из новой страны.
+    pub login_alerts: bool,
+
+    /// Получать дайджест новых постов.
+    pub digest: bool,
+}
+
+impl Default for EmailNotifications {
+    fn default() -> Self {
+        Self {
+            login_alerts: true,
+            digest: false,
+        }
+    }
+}
+
+/// Настройки пользователя.
+///
+/// Хранятся в базе данных в виде JSON: отсутствующие ключи принимают значения
+/// по умолчанию, а неизвестные ключи игнорируются.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[serde(default)]
+pub struct UserSettings {
+    /// Настройки email-уведомлений.
+    pub email_notifications: EmailNotifications,
+
+    /// Язык интерфейса и писем.
+    pub language: Language,
+
+    /// Количество постов на странице.
+    #[validate(range(min = 1, max = MAX_POSTS_PER_PAGE))]
+    pub posts_per_page: i64,
+}
+
+impl Default for UserSettings {
+    fn default() -> Self {
+        Self {
+            email_notifications: EmailNotifications::default(),
+            language: Language::default(),
+            posts_per_page: DEFAULT_POSTS_PER_PAGE,
+        }
+    }
+}
+
+crate::impl_json_response!(UserSettings);
@@ -0,0 +1,101 @@
+//! Доменные модели блогов (арендаторов) одной инсталляции сервера.
+
+use crate::impl_json_response;
+use serde::{Deserialize, Serialize};
+use sqlx::types::chrono::{DateTime, Utc};
+use validator::{Validate, ValidationError};
+
+/// Идентификатор основного блога.
+///
+/// Запросы, для которых блог не удалось определить, относятся к основному блогу;
+/// администраторы основного блога управляют остальными блогами и флагами функциональности.
+pub(crate) const DEFAULT_TENANT_ID: i64 = 1;
+
+/// Время жизни кэша соответствия заголовков запроса блогам (в секундах).
+pub(crate) const TENANT_CACHE_TTL_SECS: u64 = 60;
+
+/// Максимальная длина короткого имени блога.
+pub(crate) const MAX_TENANT_SLUG_LENGTH: u64 = 63;
+
+/// Максимальная длина названия блога.
+pub(crate) const MAX_TENANT_NAME_LENGTH: u64 = 255;
+
+/// Блог (арендатор), изолированный от остальных блогов инсталляции.
+#[derive(Debug, Clone, Serialize)]
+pub struct Tenant {
+    /// Идентификатор блога.
+    pub id: i64,
+
+    /// Короткое имя блога, передаваемое в заголовке запроса.
+    pub slug: String,
+
+    /// Название блога.
+    pub name: String,
+
+    /// Доменное имя, запросы на которое относятся к блогу.
+    pub hostname: Option<String>,
+
+    /// Время создания блога.
+    pub created_at: DateTime<Utc>,
+}
+
+impl_json_response!(Tenant);
+
+/// Данные о запросе на создание блога.
+#[derive(Debug, Deserialize, Validate)]
+pub(crate) struct CreateTenantRequest {
+    /// Короткое имя блога (строчные латинские буквы, цифры и дефис).
+    #[validate(
+        length(min = 1, max = MAX_TENANT_SLUG_LENGTH),
+        custom(function = "validate_slug")
+    )]
+    pub slug: String,
+
+    /// Название блога.
+    #[validate(length(min = 1, max = MAX_TENANT_NAME_LENGTH))]
+    pub name: String,
+
+    /// Доменное имя блога.
+    #[validate(length(min = 1, max = 253), custom(function = "validate_hostname"))]
+    pub hostname: Option<String>,
+}
+
+/// Привести доменное имя из заголовка `Host` к виду, в котором оно хранится:
+/// без порта и в нижнем регистре.
+pub(crate) fn normalize_hostname(host: &str) -> String {
+    let host = match host.rsplit_once(':') {
+        Some((name, port)) if port.chars().all(|c| c.is_ascii_digit()) => name,
+        _ => host,
+    };
+
+    host.trim().trim_end_matches('.').to_ascii_lowercase()
+}
+
+/// Проверить, что короткое имя состоит из строчных латинских букв, цифр и дефиса.
+fn validate_slug(slug: &str) -> Result<(), ValidationError> {
+    let valid = slug
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        && !slug.starts_with('-')
+        && !slug.ends_with('-');
+
+    if valid {
+        Ok(())
+    } else {
+        Err(ValidationError::new("slug"))
+    }
+}
+
+/// Проверить, что доменное имя уже приведено к нормальному виду и не содержит порта.
+fn validate_hostname(hostname: &str) -> Result<(), ValidationError> {
+    let valid = normalize_hostname(hostname) == hostname
+        && hostname
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.');
+
+    if valid {
+        Ok(())
+    } else {
+        Err(ValidationError::new("hostname"))
+    }
+}
@@ -7,12 +7,15 @@ use argon2::{
     password_hash::{SaltString, rand_core::OsRng},
 };
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use sqlx::types::chrono::{DateTime, Utc};
+use std::collections::HashSet;
 
 use validator::Validate;
 
 /// Информация о пользователе.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(into = "UserResponse")]
 pub struct User {
     /// Идентификатор пользователя.
     pub id: i64,
@@ -29,11 +32,113 @@ pub struct User {
 
     /// Время создания пользователя.
     pub created_at: DateTime<Utc>,
+
+    /// Ссылка на загруженный аватар пользователя.
+    pub avatar_url: Option<String>,
+
+    /// Пользователь является администратором.
+    pub is_admin: bool,
+
+    /// Идентификатор блога, к которому относится пользователь.
+    pub tenant_id: i64,
+}
+
+/// Публичный профиль пользователя (без email-адреса и служебных полей).
+#[derive(Debug, Serialize)]
+pub struct PublicProfile {
+    /// Идентификатор пользователя.
+    pub id: i64,
+
+    /// Имя пользователя.
+    pub username: String,
+
+    /// Ссылка на аватар пользователя.
+    pub avatar_url: String,
+
+    /// Время регистрации пользователя.
+    pub created_at: DateTime<Utc>,
+
+    /// Количество публичных постов пользователя.
+    pub post_count: i64,
+}
+
+impl PublicProfile {
+    /// Создать публичный профиль пользователя с количеством его публичных постов.
+    pub(crate) fn new(user: User, post_count: i64) -> Self {
+        Self {
+            avatar_url: user.avatar_url(),
+            id: user.id,
+            username: user.username,
+            created_at: user.created_at,
+            post_count,
+        }
+    }
+}
+
+impl_json_response!(PublicProfile);
+
+impl From<PublicProfile> for crate::blog_grpc::PublicProfile {
+    fn from(profile: PublicProfile) -> Self {
+        Self {
+            id: profile.id,
+            username: profile.username,
+            avatar_url: profile.avatar_url,
+            created_at: profile.created_at.to_rfc3339(),
+            post_count: profile.post_count,
+        }
+    }
+}
+
+impl User {
+    /// Ссылка на аватар пользователя.
+    ///
+    /// Если аватар не загружен, используется Gravatar по хешу email-адреса.
+    pub(crate) fn avatar_url(&self) -> String {
+        match &self.avatar_url {
+            Some(avatar_url) => avatar_url.clone(),
+            None => gravatar_url(&self.email),
+        }
+    }
+}
+
+/// Ссылка на Gravatar по email-адресу (SHA-256 от email в нижнем регистре).
+fn gravatar_url(email: &str) -> String {
+    let hash = Sha256::digest(email.trim().to_lowercase().as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+
+    format!("https://www.gravatar.com/avatar/{hash}?d=identicon")
+}
+
+/// Представление пользователя в ответах API.
+#[derive(Serialize)]
+struct UserResponse {
+    id: i64,
+    username: String,
+    email: String,
+    avatar_url: String,
+    is_admin: bool,
+    created_at: DateTime<Utc>,
+}
+
+impl From<User> for UserResponse {
+    fn from(user: User) -> Self {
+        Self {
+            avatar_url: user.avatar_url(),
+            id: user.id,
+            username: user.username,
+            email: user.email,
+            is_admin: user.is_admin,
+            created_at: user.created_at,
+        }
+    }
 }
 
 impl From<User> for crate::blog_grpc::User {
     fn from(user: User) -> Self {
         Self {
+            avatar_url: user.avatar_url(),
             id: user.id,
             username: user.username,
             email: user.email,
@@ -42,6 +147,61 @@ impl From<User> for crate::blog_grpc::User {
     }
 }
 
+/// Минимальный интервал между запросами на смену email-адреса (в секундах).
+pub(crate) const EMAIL_CHANGE_COOLDOWN_SECS: i64 = 60;
+
+/// Время действия ссылки подтверждения нового email-адреса (в секундах).
+pub(crate) const EMAIL_CONFIRMATION_TTL_SECS: i64 = 24 * 60 * 60;
+
+/// Запрос на смену email-адреса.
+#[derive(Debug, Deserialize, Validate)]
+pub struct ChangeEmailRequest {
+    /// Новый email-адрес пользователя.
+    #[validate(email)]
+    pub email: String,
+}
+
+/// Запрос на изменение профиля пользователя.
+#[derive(Debug, Default, Deserialize, Validate)]
+pub struct UpdateProfileRequest {
+    /// Новый email-адрес (вступает в силу после перехода по ссылке из письма).
+    #[validate(email)]
+    pub email: Option<String>,
+
+    /// Удалить загруженный аватар (будет использоваться Gravatar).
+    #[serde(default)]
+    pub remove_avatar: bool,
+}
+
+impl From<crate::blog_grpc::UpdateProfileRequest> for UpdateProfileRequest {
+    fn from(req: crate::blog_grpc::UpdateProfileRequest) -> Self {
+        Self {
+            email: req.email,
+            remove_avatar: req.remove_avatar,
+        }
+    }
+}
+
+/// Параметры подтверждения email-адреса.
+#[derive(Debug, Deserialize)]
+pub struct ConfirmEmailParams {
+    /// Токен из ссылки подтверждения.
+    pub token: String,
+}
+
+/// Ожидающая подтверждения смена email-адреса.
+#[derive(Debug)]
+pub(crate) struct EmailChange {
+    /// Идентификатор пользователя.
+    pub user_id: i64,
+
+    /// Новый email-адрес пользователя.
+    pub new_email: String,
+
+    /// Время окончания действия ссылки подтверждения.
+    pub expires_at: DateTime<Utc>,
+}
+
 /// Данные о запросе на создание нового пользователя.
 #[derive(Debug, Deserialize, Validate)]
 pub struct CreateUserRequest {
@@ -53,8 +213,12 @@ pub struct CreateUserRequest {
     pub email: String,
 
     /// Пароль пользователя.
-    #[validate(length(min = 6))]
+    ///
+    /// Требования к паролю задаются политикой [`PasswordPolicy`].
     pub password: String,
+
+    /// Код приглашения (обязателен, если регистрация возможна только по приглашениям).
+    pub invite_code: Option<String>,
 }
 
 impl From<crate::blog_grpc::CreateUserRequest> for CreateUserRequest {
@@ -63,6 +227,7 @@ impl From<crate::blog_grpc::CreateUserRequest> for CreateUserRequest {
             username: req.username,
             email: req.email,
             password: req.password,
+            invite_code: req.invite_code,
         }
     }
 }
@@ -81,14 +246,127 @@ impl TryFrom<CreateUserRequest> for User {
             email: user.email,
             password_hash,
             created_at: Utc::now(),
+            avatar_url: None,
+            is_admin: false,
+            tenant_id: -1,
         })
     }
 }
 
+/// Наиболее распространенные пароли, запрещенные к использованию.
+const COMMON_PASSWORDS: &[&str] = &[
+    "123456",
+    "1234567",
+    "12345678",
+    "123456789",
+    "1234567890",
+    "qwerty",
+    "qwerty123",
+    "qwertyuiop",
+    "password",
+    "password1",
+    "passw0rd",
+    "111111",
+    "000000",
+    "123123",
+    "abc123",
+    "iloveyou",
+    "admin",
+    "letmein",
+    "welcome",
+    "monkey",
+    "dragon",
+    "football",
+];
+
+/// Политика требований к паролю пользователя.
+#[derive(Debug, Clone)]
+pub(crate) struct PasswordPolicy {
+    /// Минимальная длина пароля.
+    pub min_length: usize,
+
+    /// Требовать наличие строчной буквы.
+    pub require_lowercase: bool,
+
+    /// Требовать наличие заглавной буквы.
+    pub require_uppercase: bool,
+
+    /// Требовать наличие цифры.
+    pub require_digit: bool,
+
+    /// Требовать наличие специального символа.
+    pub require_special: bool,
+
+    /// Запрещенные пароли (в нижнем регистре).
+    pub denylist: HashSet<String>,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            min_length: 6,
+            require_lowercase: false,
+            require_uppercase: false,
+            require_digit: false,
+            require_special: false,
+            denylist: COMMON_PASSWORDS.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+}
+
+impl PasswordPolicy {
+    /// Дополнить список запрещенных паролей.
+    pub(crate) fn with_denylist(mut self, passwords: impl IntoIterator<Item = String>) -> Self {
+        self.denylist.extend(
+            passwords
+                .into_iter()
+                .map(|p| p.trim().to_lowercase())
+                .filter(|p| !p.is_empty()),
+        );
+
+        self
+    }
+
+    /// Проверить пароль на соответствие политике.
+    pub(crate) fn check(&self, password: &str) -> Result<(), UserError> {
+        let weak = |reason: &str| Err(UserError::WeakPassword(reason.to_string()));
+
+        if password.chars().count() < self.min_length {
+            return weak(&format!(
+                "длина пароля должна быть не менее {} символов",
+                self.min_length
+            ));
+        }
+
+        if self.require_lowercase && !password.chars().any(char::is_lowercase) {
+            return weak("пароль должен содержать строчную букву");
+        }
+
+        if self.require_uppercase && !password.chars().any(char::is_uppercase) {
+            return weak("пароль должен содержать заглавную букву");
+        }
+
+        if self.require_digit && !password.chars().any(|c| c.is_ascii_digit()) {
+            return weak("пароль должен содержать цифру");
+        }
+
+        if self.require_special && password.chars().all(char::is_alphanumeric) {
+            return weak("пароль должен содержать специальный символ");
+        }
+
+        if self.denylist.contains(&password.to_lowercase()) {
+            return weak("пароль слишком распространен");
+        }
+
+        Ok(())
+    }
+}
+
 /// Данные об ответе на создание нового пользователя.
 #[derive(Debug, Serialize)]
 pub struct CreateUserResponse {
-    /// JWT-токен авторизации.
+    /// JWT-токен авторизации (не передается в теле ответа при авторизации через cookie).
+    #[serde(skip_serializing_if = "String::is_empty")]
     pub token: String,
 
     /// Созданный пользователь.
@@ -128,7 +406,8 @@ impl From<crate::blog_grpc::LoginUserRequest> for LoginUserRequest {
 /// Данные об ответе на вход пользователя.
 #[derive(Debug, Serialize)]
 pub struct LoginUserResponse {
-    /// JWT-токен авторизации.
+    /// JWT-токен авторизации (не передается в теле ответа при авторизации через cookie).
+    #[serde(skip_serializing_if = "String::is_empty")]
     pub token: String,
 
     /// Информация о пользователе, который был авторизован.
@@ -145,3 +424,91 @@ impl From<LoginUserResponse> for crate::blog_grpc::LoginUserResponse {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strict_policy() -> PasswordPolicy {
+        PasswordPolicy {
+            min_length: 8,
+            require_lowercase: true,
+            require_uppercase: true,
+            require_digit: true,
+            require_special: true,
+            ..PasswordPolicy::default()
+        }
+    }
+
+    fn assert_weak(policy: &PasswordPolicy, password: &str) {
+        assert!(
+            matches!(policy.check(password), Err(UserError::WeakPassword(_))),
+            "{password} should be rejected"
+        );
+    }
+
+    #[test]
+    fn default_policy_accepts_long_enough_password() {
+        assert!(PasswordPolicy::default().check("correct horse").is_ok());
+    }
+
+    #[test]
+    fn rejects_short_password() {
+        assert_weak(&PasswordPolicy::default(), "abc12");
+    }
+
+    #[test]
+    fn min_length_counts_characters_not_bytes() {
+        let policy = PasswordPolicy {
+            min_length: 6,
+            ..PasswordPolicy::default()
+        };
+
+        assert!(policy.check("пароль").is_ok());
+        assert_weak(&policy, "парол");
+    }
+
+    #[test]
+    fn requires_lowercase_letter() {
+        assert_weak(&strict_policy(), "SECRET-PASS-1");
+    }
+
+    #[test]
+    fn requires_uppercase_letter() {
+        assert_weak(&strict_policy(), "secret-pass-1");
+    }
+
+    #[test]
+    fn requires_digit() {
+        assert_weak(&strict_policy(), "Secret-Pass");
+    }
+
+    #[test]
+    fn requires_special_character() {
+        assert_weak(&strict_policy(), "SecretPass1");
+    }
+
+    #[test]
+    fn strict_policy_accepts_compliant_password() {
+        assert!(strict_policy().check("Secret-Pass-1").is_ok());
+    }
+
+    #[test]
+    fn rejects_common_password_case_insensitively() {
+        let policy = PasswordPolicy::default();
+
+        assert_weak(&policy, "123456");
+        assert_weak(
+            &policy,
+            &COMMON_PASSWORDS[COMMON_PASSWORDS.len() - 1].to_uppercase(),
+        );
+    }
+
+    #[test]
+    fn custom_denylist_entries_are_normalized() {
+        let policy = PasswordPolicy::default().with_denylist(["  BlogSecret ".to_string()]);
+
+        assert_weak(&policy, "blogsecret");
+        assert_weak(&policy, "BLOGSECRET");
+    }
+}
@@ -0,0 +1,313 @@
+//! Резервное копирование и восстановление данных блога.
+//!
+//! Копия - логический дамп таблиц в текстовом формате `COPY` PostgreSQL, сжатый zstd.
+//! Схема в копию не входит: при восстановлении она создается миграциями сервера.
+
+use crate::infrastructure::database::{latest_migration_version, run_migrations};
+use chrono::Utc;
+use sqlx::{PgPool, Postgres, Transaction};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use tokio_stream::StreamExt;
+
+/// Таблицы, входящие в резервную копию, в порядке зависимостей внешних ключей.
+///
+/// Таблица `data_exports` не копируется: архивы выгрузок хранятся на диске,
+/// а сами выгрузки можно запросить заново.
+const TABLES: [&str; 13] = [
+    "tenants",
+    "users",
+    "posts",
+    "post_authors",
+    "post_moderation_flags",
+    "media",
+    "media_variants",
+    "api_keys",
+    "invites",
+    "feature_flags",
+    "user_settings",
+    "email_changes",
+    "login_history",
+];
+
+/// Таблицы с последовательностью для столбца `id`, которую нужно сдвинуть после восстановления.
+const SERIAL_TABLES: [&str; 7] = [
+    "tenants",
+    "users",
+    "posts",
+    "media",
+    "api_keys",
+    "invites",
+    "login_history",
+];
+
+/// Начало заголовка резервной копии.
+const HEADER_PREFIX: &str = "-- blog-server backup (migration ";
+
+/// Уровень сжатия zstd.
+const COMPRESSION_LEVEL: i32 = 3;
+
+/// Размер пачки строк, передаваемой в `COPY ... FROM STDIN` за один раз.
+const COPY_BATCH_SIZE: usize = 1 << 20;
+
+/// Сохранить данные всех таблиц в сжатый файл `out`.
+///
+/// Таблицы читаются в одной транзакции REPEATABLE READ, поэтому копия согласована
+/// и не блокирует работу сервера.
+pub(crate) async fn backup(pool: &PgPool, out: &Path) -> anyhow::Result<()> {
+    let file = BufWriter::new(File::create(out)?);
+    let mut encoder = zstd::Encoder::new(file, COMPRESSION_LEVEL)?;
+
+    let mut tx = pool.begin().await?;
+    sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ READ ONLY")
+        .execute(&mut *tx)
+        .await?;
+
+    writeln!(
+        encoder,
+        "{HEADER_PREFIX}{}, {})",
+        latest_migration_version(),
+        Utc::now().to_rfc3339()
+    )?;
+
+    for table in TABLES {
+        let columns = table_columns(&mut tx, table).await?.join(", ");
+
+        writeln!(encoder, "COPY {table} ({columns}) FROM stdin;")?;
+
+        let mut bytes = 0;
+        let mut stream = tx
+            .copy_out_raw(&format!("COPY {table} ({columns}) TO STDOUT"))
+            .await?;
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            bytes += chunk.len();
+            encoder.write_all(&chunk)?;
+        }
+
+        writeln!(encoder, "\\.")?;
+
+        tracing::info!("Таблица {table} сохранена ({bytes} байт)");
+    }
+
+    tx.commit().await?;
+    encoder.finish()?.flush()?;
+
+    Ok(())
+}
+
+/// Восстановить данные из сжатого файла `from`, созданного командой `backup`.
+///
+/// Перед восстановлением применяются миграции, а все таблицы очищаются в той же транзакции,
+/// в которой загружаются данные. Если в БД уже есть пользователи, восстановление выполняется
+/// только с `force`.
+pub(crate) async fn restore(pool: &PgPool, from: &Path, force: bool) -> anyhow::Result<()> {
+    let mut reader = BufReader::new(zstd::Decoder::new(File::open(from)?)?);
+
+    let mut header = String::new();
+    reader.read_line(&mut header)?;
+    let version = parse_header(&header)?;
+
+    if version > latest_migration_version() {
+        anyhow::bail!(
+            "Копия создана более новой версией сервера (миграция {version}); обновите сервер"
+        );
+    }
+
+    run_migrations(pool).await?;
+
+    let has_users = sqlx::query_scalar::<_, bool>("SELECT EXISTS (SELECT 1 FROM users)")
+        .fetch_one(pool)
+        .await?;
+    if has_users && !force {
+        anyhow::bail!("БД не пуста; для замены ее данных копией укажите --force");
+    }
+
+    let mut tx = pool.begin().await?;
+    sqlx::query(&format!(
+        "TRUNCATE {} RESTART IDENTITY CASCADE",
+        TABLES.join(", ")
+    ))
+    .execute(&mut *tx)
+    .await?;
+
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+
+        let statement = line.trim_end();
+        if statement.is_empty() {
+            continue;
+        }
+
+        let table = parse_copy_statement(statement)?;
+        let rows = restore_table(&mut tx, &mut reader, statement).await?;
+
+        tracing::info!("Таблица {table} восстановлена ({rows} строк)");
+    }
+
+    for table in SERIAL_TABLES {
+        sqlx::query(&format!(
+            "SELECT setval(pg_get_serial_sequence('{table}', 'id'), \
+             COALESCE(MAX(id), 0) + 1, false) FROM {table}"
+        ))
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+/// Загрузить строки одной таблицы до маркера конца данных `\.`.
+async fn restore_table(
+    tx: &mut Transaction<'_, Postgres>,
+    reader: &mut impl BufRead,
+    statement: &str,
+) -> anyhow::Result<u64> {
+    let mut copy = tx
+        .copy_in_raw(&statement.replace("FROM stdin;", "FROM STDIN"))
+        .await?;
+    let mut batch = Vec::with_capacity(COPY_BATCH_SIZE);
+
+    loop {
+        let start = batch.len();
+        if reader.read_until(b'\n', &mut batch)? == 0 {
+            copy.abort("unexpected end of backup").await?;
+            anyhow::bail!("Копия повреждена: нет конца данных для `{statement}`");
+        }
+
+        if &batch[start..] == b"\\.\n" {
+            batch.truncate(start);
+            break;
+        }
+
+        if batch.len() >= COPY_BATCH_SIZE {
+            copy.send(std::mem::take(&mut batch)).await?;
+        }
+    }
+
+    if !batch.is_empty() {
+        copy.send(batch).await?;
+    }
+
+    Ok(copy.finish().await?)
+}
+
+/// Получить версию миграции из заголовка резервной копии.
+fn parse_header(header: &str) -> anyhow::Result<i64> {
+    header
+        .strip_prefix(HEADER_PREFIX)
+        .and_then(|rest| rest.split(',').next())
+        .and_then(|version| version.parse().ok())
+        .ok_or_else(|| anyhow::anyhow!("Файл не является резервной копией blog-server"))
+}
+
+/// Проверить инструкцию `COPY` из резервной копии и вернуть имя таблицы.
+///
+/// Инструкция попадает в запрос к БД, поэтому допускаются только известные таблицы
+/// и столбцы из строчных букв, цифр и подчеркиваний.
+fn parse_copy_statement(statement: &str) -> anyhow::Result<&str> {
+    let invalid = || anyhow::anyhow!("Копия повреждена: неожиданная строка `{statement}`");
+
+    let (table, columns) = statement
+        .strip_prefix("COPY ")
+        .and_then(|rest| rest.strip_suffix(") FROM stdin;"))
+        .and_then(|rest| rest.split_once(" ("))
+        .ok_or_else(invalid)?;
+
+    let valid_columns = columns.split(", ").all(|column| {
+        !column.is_empty()
+            && column
+                .bytes()
+                .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_')
+    });
+
+    if !TABLES.contains(&table) || !valid_columns {
+        return Err(invalid());
+    }
+
+    Ok(table)
+}
+
+/// Получить список копируемых столбцов таблицы (без вычисляемых).
+async fn table_columns(
+    tx: &mut Transaction<'_, Postgres>,
+    table: &str,
+) -> anyhow::Result<Vec<String>> {
+    let columns = sqlx::query_scalar::<_, String>(
+        "SELECT column_name::TEXT FROM information_schema.columns \
+         WHERE table_schema = current_schema() AND table_name = $1 AND is_generated = 'NEVER' \
+         ORDER BY ordinal_position",
+    )
+    .bind(table)
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_header_round_trip() {
+        let header = format!(
+            "{HEADER_PREFIX}{}, {})\n",
+            latest_migration_version(),
+            Utc::now().to_rfc3339()
+        );
+
+        assert_eq!(parse_header(&header).unwrap(), latest_migration_version());
+    }
+
+    #[test]
+    fn parse_header_rejects_malformed_input() {
+        assert!(parse_header("").is_err());
+        assert!(parse_header("COPY users (id) FROM stdin;").is_err());
+        assert!(parse_header(&format!("{HEADER_PREFIX}abc, 2026-10-17T00:00:00+00:00)")).is_err());
+        assert!(parse_header(&format!("{HEADER_PREFIX}, 2026-10-17T00:00:00+00:00)")).is_err());
+        assert!(parse_header("-- pg_dump backup (migration 20261017090000, now)").is_err());
+    }
+
+    #[test]
+    fn parse_copy_statement_round_trip() {
+        for table in TABLES {
+            let statement = format!("COPY {table} (id, tenant_id, created_at) FROM stdin;");
+
+            assert_eq!(parse_copy_statement(&statement).unwrap(), table);
+        }
+    }
+
+    #[test]
+    fn parse_copy_statement_rejects_unknown_table() {
+        assert!(parse_copy_statement("COPY data_exports (id) FROM stdin;").is_err());
+        assert!(parse_copy_statement("COPY pg_authid (rolname) FROM stdin;").is_err());
+    }
+
+    #[test]
+    fn parse_copy_statement_rejects_invalid_columns() {
+        assert!(parse_copy_statement("COPY users () FROM stdin;").is_err());
+        assert!(parse_copy_statement("COPY users (id, ) FROM stdin;").is_err());
+        assert!(parse_copy_statement("COPY users (Id) FROM stdin;").is_err());
+        assert!(
+            parse_copy_statement("COPY users (id); DROP TABLE users; --) FROM stdin;").is_err()
+        );
+        assert!(parse_copy_statement("COPY users (\"id\") FROM stdin;").is_err());
+    }
+
+    #[test]
+    fn parse_copy_statement_rejects_malformed_statement() {
+        assert!(parse_copy_statement("COPY users (id) FROM STDIN").is_err());
+        assert!(parse_copy_statement("COPY users (id) TO stdout;").is_err());
+        assert!(parse_copy_statement("COPY users FROM stdin;").is_err());
+        assert!(parse_copy_statement("users (id) FROM stdin;").is_err());
+        assert!(parse_copy_statement("\\.").is_err());
+    }
+}
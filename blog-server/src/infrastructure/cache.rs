@@ -0,0 +1,51 @@
+//! Простой кэш в памяти с ограниченным временем жизни записей.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Кэш в памяти, записи которого устаревают через заданное время.
+#[derive(Debug)]
+pub(crate) struct TtlCache<K, V> {
+    /// Время жизни записи.
+    ttl: Duration,
+
+    /// Записи кэша вместе со временем их добавления.
+    entries: Mutex<HashMap<K, (Instant, V)>>,
+}
+
+impl<K: Eq + Hash, V: Clone> TtlCache<K, V> {
+    /// Создать пустой кэш с указанным временем жизни записей.
+    pub(crate) fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Получить актуальное значение по ключу.
+    pub(crate) fn get(&self, key: &K) -> Option<V> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+
+        entries
+            .get(key)
+            .filter(|(inserted_at, _)| inserted_at.elapsed() < self.ttl)
+            .map(|(_, value)| value.clone())
+    }
+
+    /// Сохранить значение, попутно удалив устаревшие записи.
+    pub(crate) fn insert(&self, key: K, value: V) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+
+        entries.retain(|_, (inserted_at, _)| inserted_at.elapsed() < self.ttl);
+        entries.insert(key, (Instant::now(), value));
+    }
+
+    /// Удалить значение по ключу.
+    pub(crate) fn invalidate(&self, key: &K) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+
+        entries.remove(key);
+    }
+}
@@ -0,0 +1,154 @@
+//! Фильтрация недопустимого содержимого постов (нецензурная лексика, спам).
+//!
+//! Встроенный фильтр проверяет текст по списку запрещенных слов; для более точной
+//! проверки можно подключить внешний API модерации.
+
+use crate::domain::error::ContentFilterError;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fmt::Debug;
+use std::path::Path;
+use tonic::async_trait;
+
+/// Реализация фильтра содержимого.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum ContentFilterBackend {
+    /// Встроенная проверка по списку запрещенных слов.
+    WordList,
+
+    /// Внешний API модерации.
+    External,
+}
+
+/// Действие с содержимым, не прошедшим фильтр.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum ContentFilterAction {
+    /// Отклонить запрос.
+    Reject,
+
+    /// Сохранить пост и отметить его для проверки модератором.
+    Flag,
+
+    /// Заменить недопустимые фрагменты звездочками.
+    Mask,
+}
+
+/// Фильтр недопустимого содержимого.
+#[async_trait]
+pub(crate) trait ContentFilter: Debug + Send + Sync {
+    /// Проверить текст.
+    ///
+    /// Возвращает недопустимые фрагменты в том виде, в котором они встречаются в тексте
+    /// (пустой список, если текст допустим).
+    async fn check(&self, text: &str) -> Result<Vec<String>, ContentFilterError>;
+}
+
+/// Заменить все вхождения недопустимых фрагментов звездочками той же длины.
+pub(crate) fn mask(text: &str, fragments: &[String]) -> String {
+    fragments
+        .iter()
+        .filter(|fragment| !fragment.is_empty())
+        .fold(text.to_string(), |text, fragment| {
+            text.replace(fragment.as_str(), &"*".repeat(fragment.chars().count()))
+        })
+}
+
+/// Проверка текста по списку запрещенных слов без учета регистра.
+#[derive(Debug, Clone)]
+pub(crate) struct WordListFilter {
+    /// Запрещенные слова в нижнем регистре.
+    words: HashSet<String>,
+}
+
+impl WordListFilter {
+    /// Загрузить список запрещенных слов из файла (по одному слову на строку).
+    ///
+    /// Пустые строки и строки, начинающиеся с `#`, пропускаются.
+    pub(crate) fn from_file(path: &Path) -> std::io::Result<Self> {
+        let words = std::fs::read_to_string(path)?
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_lowercase)
+            .collect();
+
+        Ok(Self { words })
+    }
+}
+
+#[async_trait]
+impl ContentFilter for WordListFilter {
+    async fn check(&self, text: &str) -> Result<Vec<String>, ContentFilterError> {
+        let mut found: Vec<String> = Vec::new();
+
+        for word in text
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+        {
+            if self.words.contains(&word.to_lowercase()) && !found.iter().any(|f| f == word) {
+                found.push(word.to_string());
+            }
+        }
+
+        Ok(found)
+    }
+}
+
+/// Ответ внешнего API модерации.
+#[derive(Debug, Deserialize)]
+struct ExternalFilterResponse {
+    /// Недопустимые фрагменты текста.
+    #[serde(default)]
+    matches: Vec<String>,
+}
+
+/// Проверка текста внешним API модерации.
+///
+/// API принимает `POST` с телом `{"text": "..."}` и возвращает
+/// `{"matches": ["..."]}` со списком недопустимых фрагментов.
+#[derive(Debug, Clone)]
+pub(crate) struct ExternalContentFilter {
+    /// HTTP-клиент для обращения к API.
+    client: reqwest::Client,
+
+    /// Адрес API проверки текста.
+    url: String,
+
+    /// Ключ доступа к API.
+    api_key: Option<String>,
+}
+
+impl ExternalContentFilter {
+    /// Создать фильтр, обращающийся к API по указанному адресу.
+    pub(crate) fn new(url: &str, api_key: Option<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url: url.to_string(),
+            api_key,
+        }
+    }
+}
+
+#[async_trait]
+impl ContentFilter for ExternalContentFilter {
+    async fn check(&self, text: &str) -> Result<Vec<String>, ContentFilterError> {
+        let request = self
+            .client
+            .post(&self.url)
+            .json(&serde_json::json!({ "text": text }));
+
+        let request = match &self.api_key {
+            Some(api_key) => request.bearer_auth(api_key),
+            None => request,
+        };
+
+        let response = request
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<ExternalFilterResponse>()
+            .await?;
+
+        Ok(response.matches)
+    }
+}
@@ -1,26 +1,395 @@
 //! Модуль начального взаимодействия с БД.
 
-use sqlx::{PgPool, migrate, postgres::PgPoolOptions};
+use crate::domain::admin::{AppliedMigration, MigrationStatus, PendingMigration};
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use chrono::{DateTime, Utc};
+use log::LevelFilter;
+use sqlx::migrate::Migrator;
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use sqlx::{ConnectOptions, PgPool, migrate};
+use std::collections::HashMap;
 use std::env;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 
+/// Миграции, встроенные в исполняемый файл сервера.
+pub(crate) static MIGRATOR: Migrator = migrate!();
+
+/// Интервал проверки применения миграций другим экземпляром сервера.
+const MIGRATIONS_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Максимальное число повторов транзакции после конфликта сериализации или взаимоблокировки.
+const MAX_TRANSACTION_RETRIES: u32 = 3;
+
+/// Базовая задержка перед повтором транзакции (удваивается с каждой попыткой).
+const TRANSACTION_RETRY_BASE_DELAY: Duration = Duration::from_millis(20);
+
+/// Коды ошибок PostgreSQL, после которых транзакцию можно повторить:
+/// `40001` (serialization_failure) и `40P01` (deadlock_detected).
+const RETRYABLE_SQLSTATES: [&str; 2] = ["40001", "40P01"];
+
+/// Коды ошибок PostgreSQL, означающие недоступность сервера БД: класс `08` (connection_exception),
+/// `53300` (too_many_connections) и `57P01`-`57P03` (остановка или перезапуск сервера).
+const UNAVAILABLE_SQLSTATES: [&str; 4] = ["53300", "57P01", "57P02", "57P03"];
+
+/// Ошибка вызвана недоступностью БД (потеря соединения, исчерпание или закрытие пула,
+/// перезапуск сервера БД), а не самим запросом: его можно повторить позже.
+pub(crate) fn is_connection_error(e: &sqlx::Error) -> bool {
+    match e {
+        sqlx::Error::Io(_)
+        | sqlx::Error::Tls(_)
+        | sqlx::Error::PoolTimedOut
+        | sqlx::Error::PoolClosed
+        | sqlx::Error::WorkerCrashed => true,
+        sqlx::Error::Database(e) => e.code().is_some_and(|code| {
+            code.starts_with("08") || UNAVAILABLE_SQLSTATES.contains(&code.as_ref())
+        }),
+        _ => false,
+    }
+}
+
+/// Ошибка, после которой транзакцию можно безопасно выполнить заново.
+pub(crate) trait RetryableError {
+    /// Транзакция откатилась из-за конфликта с параллельной транзакцией.
+    fn is_retryable(&self) -> bool;
+}
+
+impl RetryableError for sqlx::Error {
+    fn is_retryable(&self) -> bool {
+        self.as_database_error()
+            .and_then(|e| e.code())
+            .is_some_and(|code| RETRYABLE_SQLSTATES.contains(&code.as_ref()))
+    }
+}
+
+/// Выполнить транзакцию, повторяя ее после конфликтов сериализации и взаимоблокировок.
+///
+/// Транзакция должна целиком начинаться и фиксироваться внутри `transaction`:
+/// при повторе она выполняется заново. Между попытками выдерживается
+/// экспоненциально растущая задержка со случайной добавкой, чтобы конфликтующие
+/// запросы не повторялись одновременно.
+pub(crate) async fn retry_transaction<T, E, F, Fut>(mut transaction: F) -> Result<T, E>
+where
+    E: RetryableError,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut attempt = 0;
+
+    loop {
+        match transaction().await {
+            Err(e) if e.is_retryable() && attempt < MAX_TRANSACTION_RETRIES => {
+                attempt += 1;
+
+                let base = TRANSACTION_RETRY_BASE_DELAY * 2u32.pow(attempt - 1);
+                let jitter = base.mul_f64(f64::from(OsRng.next_u32()) / f64::from(u32::MAX));
+
+                tracing::warn!(
+                    "Конфликт параллельных транзакций, повтор {attempt} из {MAX_TRANSACTION_RETRIES}"
+                );
+                tokio::time::sleep(base + jitter).await;
+            }
+            result => return result,
+        }
+    }
+}
+
 /// Создать пул соединений.
-pub(crate) async fn create_pool() -> anyhow::Result<PgPool> {
-    let database_url = env::var("DATABASE_URL")?;
+///
+/// Запросы, выполняющиеся дольше `slow_query_threshold`, записываются в лог
+/// с уровнем WARN вместе с текстом запроса.
+pub(crate) async fn create_pool(slow_query_threshold: Duration) -> anyhow::Result<PgPool> {
+    let options = env::var("DATABASE_URL")?
+        .parse::<PgConnectOptions>()?
+        .log_slow_statements(LevelFilter::Warn, slow_query_threshold);
 
     let pool = PgPoolOptions::new()
         .max_connections(20)
         .min_connections(5)
         .acquire_timeout(Duration::from_secs(5))
-        .connect(&database_url)
+        .connect_with(options)
         .await?;
 
     Ok(pool)
 }
 
+/// Создать пул соединений с репликой для чтения, если задана переменная `DATABASE_READ_URL`.
+///
+/// Соединения устанавливаются лениво, поэтому недоступная реплика не мешает запуску
+/// сервера: до первой успешной проверки ее состояния чтение идет из основной БД.
+pub(crate) fn create_replica_pool(
+    slow_query_threshold: Duration,
+) -> anyhow::Result<Option<PgPool>> {
+    let Ok(database_url) = env::var("DATABASE_READ_URL") else {
+        return Ok(None);
+    };
+
+    let options = database_url
+        .parse::<PgConnectOptions>()?
+        .log_slow_statements(LevelFilter::Warn, slow_query_threshold);
+
+    let pool = PgPoolOptions::new()
+        .max_connections(20)
+        .acquire_timeout(Duration::from_secs(2))
+        .connect_lazy_with(options);
+
+    Ok(Some(pool))
+}
+
+/// Состояние соединений с БД по результатам последних проверок.
+#[derive(Debug, Clone)]
+pub(crate) struct DatabaseHealth {
+    /// Прошла ли основная БД последнюю проверку состояния.
+    primary: Arc<AtomicBool>,
+
+    /// Прошла ли реплика последнюю проверку состояния (`None` - реплика не задана).
+    replica: Option<Arc<AtomicBool>>,
+}
+
+impl Default for DatabaseHealth {
+    /// Основная БД считается доступной до первой проверки, реплика не задана.
+    fn default() -> Self {
+        Self {
+            primary: Arc::new(AtomicBool::new(true)),
+            replica: None,
+        }
+    }
+}
+
+impl DatabaseHealth {
+    /// Доступна ли основная БД.
+    pub(crate) fn primary_available(&self) -> bool {
+        self.primary.load(Ordering::Relaxed)
+    }
+
+    /// Доступна ли реплика (`None` - реплика не задана).
+    pub(crate) fn replica_available(&self) -> Option<bool> {
+        self.replica
+            .as_ref()
+            .map(|healthy| healthy.load(Ordering::Relaxed))
+    }
+}
+
+/// Пул соединений для запросов только на чтение.
+///
+/// Запросы направляются в реплику, пока она проходит периодическую проверку
+/// состояния, и в основную БД - если реплика не задана или недоступна.
+#[derive(Debug, Clone)]
+pub(crate) struct ReadPool {
+    /// Пул соединений с основной БД.
+    primary: PgPool,
+
+    /// Пул соединений с репликой.
+    replica: Option<PgPool>,
+
+    /// Результаты последних проверок состояния основной БД и реплики.
+    health: DatabaseHealth,
+}
+
+impl ReadPool {
+    /// Создать пул для чтения из основной БД и, если задана, из реплики.
+    pub(crate) fn new(primary: PgPool, replica: Option<PgPool>) -> Self {
+        let health = DatabaseHealth {
+            replica: replica.as_ref().map(|_| Arc::new(AtomicBool::new(false))),
+            ..DatabaseHealth::default()
+        };
+
+        Self {
+            primary,
+            replica,
+            health,
+        }
+    }
+
+    /// Получить пул соединений, в который следует направить запрос на чтение.
+    pub(crate) fn get(&self) -> &PgPool {
+        match &self.replica {
+            Some(replica) if self.health.replica_available() == Some(true) => replica,
+            _ => &self.primary,
+        }
+    }
+
+    /// Состояние соединений с основной БД и репликой.
+    pub(crate) fn health(&self) -> DatabaseHealth {
+        self.health.clone()
+    }
+
+    /// Запустить фоновую проверку состояния основной БД и реплики с указанным интервалом.
+    ///
+    /// Проверочный запрос заставляет пул заново установить соединения, потерянные
+    /// при перезапуске БД, а смена состояния записывается в лог.
+    pub(crate) fn spawn_health_check(&self, interval: Duration) {
+        spawn_pool_check(
+            self.primary.clone(),
+            self.health.primary.clone(),
+            interval,
+            "Основная БД доступна",
+            "Основная БД недоступна",
+        );
+
+        if let (Some(replica), Some(healthy)) = (&self.replica, &self.health.replica) {
+            spawn_pool_check(
+                replica.clone(),
+                healthy.clone(),
+                interval,
+                "Реплика БД доступна, чтение направляется в нее",
+                "Реплика БД недоступна, чтение из основной БД",
+            );
+        }
+    }
+}
+
+/// Периодически проверять пул соединений и записывать результат в `healthy`.
+fn spawn_pool_check(
+    pool: PgPool,
+    healthy: Arc<AtomicBool>,
+    interval: Duration,
+    up_message: &'static str,
+    down_message: &'static str,
+) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(interval);
+        let mut last_healthy = None;
+
+        loop {
+            interval.tick().await;
+
+            let result = sqlx::query("SELECT 1").execute(&pool).await;
+            healthy.store(result.is_ok(), Ordering::Relaxed);
+
+            if last_healthy == Some(result.is_ok()) {
+                continue;
+            }
+
+            last_healthy = Some(result.is_ok());
+
+            match result {
+                Ok(_) => tracing::info!("{up_message}"),
+                Err(e) => tracing::warn!("{down_message}: {e}"),
+            }
+        }
+    });
+}
+
 /// Актуализировать миграции в БД.
 pub(crate) async fn run_migrations(pool: &PgPool) -> anyhow::Result<()> {
-    migrate!().run(pool).await?;
+    MIGRATOR.run(pool).await?;
 
     Ok(())
 }
+
+/// Версия последней миграции, встроенной в исполняемый файл сервера.
+pub(crate) fn latest_migration_version() -> i64 {
+    MIGRATOR
+        .iter()
+        .map(|migration| migration.version)
+        .max()
+        .unwrap_or_default()
+}
+
+/// Получить описания миграций, которые еще не применены к БД.
+pub(crate) async fn pending_migrations(pool: &PgPool) -> anyhow::Result<Vec<String>> {
+    let pending = migration_status(pool)
+        .await?
+        .pending
+        .into_iter()
+        .map(|migration| format!("{} {}", migration.version, migration.description))
+        .collect();
+
+    Ok(pending)
+}
+
+/// Ожидать, пока другой экземпляр сервера применит миграции (не дольше `timeout`).
+///
+/// Используется, когда миграции применяет отдельный экземпляр или команда `migrate`.
+pub(crate) async fn wait_for_migrations(pool: &PgPool, timeout: Duration) -> anyhow::Result<()> {
+    let deadline = tokio::time::Instant::now() + timeout;
+
+    loop {
+        let pending = pending_migrations(pool).await?;
+
+        if pending.is_empty() {
+            return Ok(());
+        }
+
+        if tokio::time::Instant::now() >= deadline {
+            anyhow::bail!("Не применены миграции: {}", pending.join(", "));
+        }
+
+        tracing::info!(
+            "Ожидание применения миграций другим экземпляром (не применено: {})",
+            pending.len()
+        );
+        tokio::time::sleep(MIGRATIONS_POLL_INTERVAL.min(timeout)).await;
+    }
+}
+
+/// Запись таблицы примененных миграций.
+#[derive(sqlx::FromRow)]
+struct MigrationRow {
+    /// Версия миграции.
+    version: i64,
+
+    /// Описание миграции.
+    description: String,
+
+    /// Время применения миграции.
+    installed_on: DateTime<Utc>,
+
+    /// Контрольная сумма миграции на момент применения.
+    checksum: Vec<u8>,
+
+    /// Длительность применения миграции в наносекундах.
+    execution_time: i64,
+}
+
+/// Получить примененные и непримененные миграции БД.
+pub(crate) async fn migration_status(pool: &PgPool) -> Result<MigrationStatus, sqlx::Error> {
+    let initialized: bool =
+        sqlx::query_scalar("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+            .fetch_one(pool)
+            .await?;
+
+    let rows: Vec<MigrationRow> = if initialized {
+        sqlx::query_as(
+            "SELECT version, description, installed_on, checksum, execution_time
+            FROM _sqlx_migrations WHERE success ORDER BY version",
+        )
+        .fetch_all(pool)
+        .await?
+    } else {
+        vec![]
+    };
+
+    let known: HashMap<i64, &[u8]> = MIGRATOR
+        .iter()
+        .filter(|migration| !migration.migration_type.is_down_migration())
+        .map(|migration| (migration.version, migration.checksum.as_ref()))
+        .collect();
+
+    let pending = MIGRATOR
+        .iter()
+        .filter(|migration| !migration.migration_type.is_down_migration())
+        .filter(|migration| !rows.iter().any(|row| row.version == migration.version))
+        .map(|migration| PendingMigration {
+            version: migration.version,
+            description: migration.description.to_string(),
+        })
+        .collect();
+
+    let applied = rows
+        .into_iter()
+        .map(|row| AppliedMigration {
+            modified: known
+                .get(&row.version)
+                .is_some_and(|known| *known != row.checksum.as_slice()),
+            version: row.version,
+            description: row.description,
+            installed_on: row.installed_on,
+            execution_time_ms: row.execution_time / 1_000_000,
+        })
+        .collect();
+
+    Ok(MigrationStatus { applied, pending })
+}
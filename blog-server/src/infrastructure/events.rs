@@ -0,0 +1,64 @@
+//! Внутренняя шина доменных событий.
+
+use crate::domain::event::DomainEvent;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+
+/// Шина доменных событий поверх широковещательного канала.
+///
+/// Каждый подписчик получает все события, опубликованные после подписки. Подписчик,
+/// не успевающий обрабатывать события, пропускает самые старые из них.
+#[derive(Debug)]
+pub(crate) struct EventBus {
+    /// Отправитель широковещательного канала.
+    sender: broadcast::Sender<DomainEvent>,
+}
+
+impl EventBus {
+    /// Создать шину с указанной емкостью очереди каждого подписчика.
+    pub(crate) fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+
+        Self { sender }
+    }
+
+    /// Опубликовать событие.
+    ///
+    /// Если подписчиков нет, событие отбрасывается.
+    pub(crate) fn publish(&self, event: DomainEvent) {
+        if self.sender.send(event).is_err() {
+            tracing::debug!("Нет подписчиков на доменные события");
+        }
+    }
+
+    /// Подписаться на события.
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<DomainEvent> {
+        self.sender.subscribe()
+    }
+}
+
+/// Запустить фоновую запись доменных событий в журнал.
+pub(crate) fn spawn_logger(mut events: broadcast::Receiver<DomainEvent>) {
+    tokio::spawn(async move {
+        loop {
+            match events.recv().await {
+                Ok(DomainEvent::UserRegistered { user_id, username }) => {
+                    tracing::info!(user_id, %username, "Зарегистрирован пользователь");
+                }
+                Ok(DomainEvent::PostCreated(post)) => {
+                    tracing::info!(post_id = post.id, author_id = post.author_id, "Создан пост");
+                }
+                Ok(DomainEvent::PostUpdated(post)) => {
+                    tracing::info!(post_id = post.id, version = post.version, "Изменен пост");
+                }
+                Ok(DomainEvent::PostDeleted { id, author_id }) => {
+                    tracing::info!(post_id = id, author_id, "Удален пост");
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Журнал доменных событий пропустил {skipped} событий");
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    });
+}
@@ -0,0 +1,157 @@
+//! Флаги функциональности.
+//!
+//! Значения по умолчанию задаются аргументами запуска, администратор может переопределить
+//! их во время работы сервера. Переопределения хранятся в базе данных и периодически
+//! перечитываются, чтобы изменения доходили до всех экземпляров сервера.
+
+use crate::domain::feature::{Feature, FeatureFlag};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+/// Хранилище флагов функциональности.
+#[derive(Debug)]
+pub(crate) struct FeatureFlags {
+    /// Пул соединений с БД.
+    pool: PgPool,
+
+    /// Значения флагов из конфигурации.
+    defaults: HashMap<Feature, bool>,
+
+    /// Значения, переопределенные администратором.
+    overrides: RwLock<HashMap<Feature, bool>>,
+}
+
+impl FeatureFlags {
+    /// Создать хранилище флагов со значениями по умолчанию.
+    pub(crate) fn new(pool: PgPool) -> Self {
+        Self {
+            pool,
+            defaults: Feature::ALL
+                .iter()
+                .map(|&feature| (feature, feature.enabled_by_default()))
+                .collect(),
+            overrides: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Отключить функции по умолчанию.
+    pub(crate) fn with_disabled(mut self, features: &[Feature]) -> Self {
+        self.defaults
+            .extend(features.iter().map(|&feature| (feature, false)));
+        self
+    }
+
+    /// Включить функции по умолчанию.
+    pub(crate) fn with_enabled(mut self, features: &[Feature]) -> Self {
+        self.defaults
+            .extend(features.iter().map(|&feature| (feature, true)));
+        self
+    }
+
+    /// Проверить, включена ли функция.
+    pub(crate) fn is_enabled(&self, feature: Feature) -> bool {
+        self.get(feature).enabled
+    }
+
+    /// Получить состояние всех флагов.
+    pub(crate) fn list(&self) -> Vec<FeatureFlag> {
+        Feature::ALL
+            .iter()
+            .map(|&feature| self.get(feature))
+            .collect()
+    }
+
+    /// Получить состояние флага.
+    pub(crate) fn get(&self, feature: Feature) -> FeatureFlag {
+        let overrides = self.overrides.read().unwrap_or_else(|e| e.into_inner());
+
+        FeatureFlag {
+            name: feature,
+            enabled: overrides
+                .get(&feature)
+                .or_else(|| self.defaults.get(&feature))
+                .copied()
+                .unwrap_or_else(|| feature.enabled_by_default()),
+            overridden: overrides.contains_key(&feature),
+        }
+    }
+
+    /// Включить или отключить функцию.
+    pub(crate) async fn set(
+        &self,
+        feature: Feature,
+        enabled: bool,
+    ) -> Result<FeatureFlag, sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO feature_flags (name, enabled)
+            VALUES ($1, $2)
+            ON CONFLICT (name) DO UPDATE SET enabled = EXCLUDED.enabled, updated_at = NOW()"#,
+            feature.as_str(),
+            enabled
+        )
+        .execute(&self.pool)
+        .await?;
+
+        self.overrides
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(feature, enabled);
+
+        Ok(self.get(feature))
+    }
+
+    /// Сбросить переопределение флага, вернув значение из конфигурации.
+    pub(crate) async fn reset(&self, feature: Feature) -> Result<FeatureFlag, sqlx::Error> {
+        sqlx::query!(
+            "DELETE FROM feature_flags WHERE name = $1",
+            feature.as_str()
+        )
+        .execute(&self.pool)
+        .await?;
+
+        self.overrides
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&feature);
+
+        Ok(self.get(feature))
+    }
+
+    /// Перечитать переопределения флагов из БД.
+    ///
+    /// Флаги неизвестных функций игнорируются.
+    pub(crate) async fn reload(&self) -> Result<(), sqlx::Error> {
+        let rows = sqlx::query!("SELECT name, enabled FROM feature_flags")
+            .fetch_all(&self.pool)
+            .await?;
+
+        let overrides = rows
+            .into_iter()
+            .filter_map(|row| Feature::from_name(&row.name).map(|feature| (feature, row.enabled)))
+            .collect();
+
+        *self.overrides.write().unwrap_or_else(|e| e.into_inner()) = overrides;
+
+        Ok(())
+    }
+
+    /// Запустить фоновое обновление флагов с указанным интервалом.
+    pub(crate) fn spawn_reload(self: &Arc<Self>, interval: Duration) {
+        let flags = self.clone();
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(interval);
+            interval.tick().await;
+
+            loop {
+                interval.tick().await;
+
+                if let Err(e) = flags.reload().await {
+                    tracing::warn!("Не удалось обновить флаги функциональности: {e}");
+                }
+            }
+        });
+    }
+}
@@ -1,10 +1,25 @@
 //! Модуль взаимодействия с JWT-токенами.
 
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 use std::time::Duration;
 
-use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation, decode, encode};
-use sqlx::types::chrono::Utc;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use jsonwebtoken::jwk::{
+    AlgorithmParameters, CommonParameters, EllipticCurve, Jwk, JwkSet, KeyAlgorithm,
+    OctetKeyPairParameters, OctetKeyPairType, PublicKeyUse, RSAKeyParameters, RSAKeyType,
+    ThumbprintHash,
+};
+use jsonwebtoken::{
+    Algorithm, DecodingKey, EncodingKey, Header, Validation, decode, decode_header, encode,
+};
+use rsa::pkcs8::DecodePublicKey;
+use rsa::traits::PublicKeyParts;
+use sqlx::types::chrono::{DateTime, Utc};
+
+use crate::domain::tenant::DEFAULT_TENANT_ID;
 
 /// Загрузить JWT-токен из переменной окружения.
 pub(crate) fn load_secret() -> anyhow::Result<String> {
@@ -17,6 +32,32 @@ pub(crate) fn load_secret() -> anyhow::Result<String> {
     Ok(secret)
 }
 
+/// Алгоритм подписи JWT-токенов.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum SigningAlgorithm {
+    /// HMAC с SHA-256 и секретом из переменной окружения JWT_SECRET.
+    #[value(name = "HS256")]
+    Hs256,
+
+    /// RSA с SHA-256, ключи загружаются из PEM-файлов.
+    #[value(name = "RS256")]
+    Rs256,
+
+    /// Ed25519, ключи загружаются из PEM-файлов.
+    #[value(name = "EdDSA")]
+    EdDsa,
+}
+
+impl From<SigningAlgorithm> for Algorithm {
+    fn from(algorithm: SigningAlgorithm) -> Self {
+        match algorithm {
+            SigningAlgorithm::Hs256 => Algorithm::HS256,
+            SigningAlgorithm::Rs256 => Algorithm::RS256,
+            SigningAlgorithm::EdDsa => Algorithm::EdDSA,
+        }
+    }
+}
+
 /// Аттрибуты пользователя.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct Claims {
@@ -26,8 +67,172 @@ pub(crate) struct Claims {
     /// Имя пользователя.
     pub username: String,
 
+    /// Идентификатор блога пользователя.
+    ///
+    /// Токены, выпущенные до появления нескольких блогов, относятся к основному блогу.
+    #[serde(default = "default_tenant_id")]
+    pub tenant_id: i64,
+
     /// Время истечения токена.
     pub exp: usize,
+
+    /// Издатель токена.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iss: Option<String>,
+
+    /// Получатель токена.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aud: Option<String>,
+}
+
+/// Блог, к которому относятся токены без поля `tenant_id`.
+fn default_tenant_id() -> i64 {
+    DEFAULT_TENANT_ID
+}
+
+/// Тип токена, выпускаемого для доступа к отдельному ресурсу.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ResourceTokenType {
+    /// Ссылка для совместного доступа к посту.
+    PostShare,
+}
+
+/// Аттрибуты токена ссылки для совместного доступа к посту.
+///
+/// Отличаются от [`Claims`] обязательным полем `typ`, поэтому токены авторизации
+/// и токены ссылок не могут быть использованы взаимозаменяемо.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ShareClaims {
+    /// Тип токена.
+    pub typ: ResourceTokenType,
+
+    /// Идентификатор поста.
+    pub post_id: i64,
+
+    /// Время истечения токена.
+    pub exp: usize,
+
+    /// Издатель токена.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iss: Option<String>,
+
+    /// Получатель токена.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aud: Option<String>,
+}
+
+/// Параметры выпускаемых токенов.
+#[derive(Debug, Clone)]
+pub(crate) struct TokenSettings {
+    /// Время жизни токена.
+    pub ttl: Duration,
+
+    /// Издатель токена (`iss`), проверяется при наличии.
+    pub issuer: Option<String>,
+
+    /// Получатель токена (`aud`), проверяется при наличии.
+    pub audience: Option<String>,
+
+    /// Допустимое расхождение часов при проверке времени жизни токена.
+    pub leeway: Duration,
+}
+
+impl Default for TokenSettings {
+    fn default() -> Self {
+        Self {
+            ttl: Duration::from_secs(24 * 60 * 60),
+            issuer: None,
+            audience: None,
+            leeway: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Ключ проверки подписи JWT-токенов.
+#[derive(Debug)]
+struct VerificationKey {
+    /// Идентификатор ключа (заголовок `kid`).
+    kid: Option<String>,
+
+    /// Алгоритм подписи.
+    algorithm: Algorithm,
+
+    /// Ключ расшифрования.
+    decoding: DecodingKey,
+
+    /// Публичное представление ключа (отсутствует для симметричных ключей).
+    jwk: Option<Jwk>,
+
+    /// Момент, после которого ключ перестает приниматься.
+    expires_at: Option<DateTime<Utc>>,
+}
+
+impl VerificationKey {
+    /// Загрузить публичный ключ из PEM-файла.
+    fn from_public_pem(algorithm: SigningAlgorithm, path: &Path) -> anyhow::Result<Self> {
+        let pem = std::fs::read(path)
+            .map_err(|e| anyhow::anyhow!("Не удалось прочитать ключ {}: {e}", path.display()))?;
+
+        let (decoding, parameters, key_algorithm) = match algorithm {
+            SigningAlgorithm::Hs256 => {
+                anyhow::bail!("Алгоритм HS256 не использует публичные ключи")
+            }
+            SigningAlgorithm::Rs256 => {
+                let public_key =
+                    rsa::RsaPublicKey::from_public_key_pem(std::str::from_utf8(&pem)?)?;
+
+                let parameters = AlgorithmParameters::RSA(RSAKeyParameters {
+                    key_type: RSAKeyType::RSA,
+                    n: URL_SAFE_NO_PAD.encode(public_key.n().to_bytes_be()),
+                    e: URL_SAFE_NO_PAD.encode(public_key.e().to_bytes_be()),
+                });
+
+                (
+                    DecodingKey::from_rsa_pem(&pem)?,
+                    parameters,
+                    KeyAlgorithm::RS256,
+                )
+            }
+            SigningAlgorithm::EdDsa => {
+                let decoding = DecodingKey::from_ed_pem(&pem)?;
+
+                let parameters = AlgorithmParameters::OctetKeyPair(OctetKeyPairParameters {
+                    key_type: OctetKeyPairType::OctetKeyPair,
+                    curve: EllipticCurve::Ed25519,
+                    x: URL_SAFE_NO_PAD.encode(decoding.as_bytes()),
+                });
+
+                (decoding, parameters, KeyAlgorithm::EdDSA)
+            }
+        };
+
+        let mut jwk = Jwk {
+            common: CommonParameters {
+                public_key_use: Some(PublicKeyUse::Signature),
+                key_algorithm: Some(key_algorithm),
+                ..Default::default()
+            },
+            algorithm: parameters,
+        };
+
+        let kid = jwk.thumbprint(ThumbprintHash::SHA256);
+        jwk.common.key_id = Some(kid.clone());
+
+        Ok(Self {
+            kid: Some(kid),
+            algorithm: algorithm.into(),
+            decoding,
+            jwk: Some(jwk),
+            expires_at: None,
+        })
+    }
+
+    /// Проверить, истек ли срок действия ключа.
+    fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| Utc::now() > expires_at)
+    }
 }
 
 /// Сервис взаимодействия с JWT-токенами.
@@ -36,8 +241,14 @@ pub(crate) struct JwtService {
     /// Ключ шифрования.
     encoding: EncodingKey,
 
-    /// Ключ расшифрования.
-    decoding: DecodingKey,
+    /// Заголовок, с которым подписываются новые токены.
+    header: Header,
+
+    /// Ключи проверки подписи: текущий и, при ротации, предыдущий.
+    keys: Vec<VerificationKey>,
+
+    /// Параметры выпускаемых токенов.
+    settings: TokenSettings,
 }
 
 impl JwtService {
@@ -48,31 +259,258 @@ impl JwtService {
             DecodingKey::from_secret(secret.as_bytes()),
         );
 
-        Self { encoding, decoding }
+        let key = VerificationKey {
+            kid: None,
+            algorithm: Algorithm::HS256,
+            decoding,
+            jwk: None,
+            expires_at: None,
+        };
+
+        Self {
+            encoding,
+            header: Header::default(),
+            keys: vec![key],
+            settings: TokenSettings::default(),
+        }
     }
 
-    /// Генерация JWT-токена с временем жизни 24 часа.
-    pub(crate) fn generate_token(&self, user_id: i64, username: &str) -> anyhow::Result<String> {
-        let exp = (Utc::now() + Duration::from_secs(24 * 60 * 60)).timestamp() as usize;
+    /// Создание сервиса из пары асимметричных ключей в формате PEM.
+    pub(crate) fn from_key_files(
+        algorithm: SigningAlgorithm,
+        private_key: &Path,
+        public_key: &Path,
+    ) -> anyhow::Result<Self> {
+        let pem = std::fs::read(private_key).map_err(|e| {
+            anyhow::anyhow!("Не удалось прочитать ключ {}: {e}", private_key.display())
+        })?;
+
+        let encoding = match algorithm {
+            SigningAlgorithm::Hs256 => {
+                anyhow::bail!("Алгоритм HS256 не использует файлы ключей")
+            }
+            SigningAlgorithm::Rs256 => EncodingKey::from_rsa_pem(&pem)?,
+            SigningAlgorithm::EdDsa => EncodingKey::from_ed_pem(&pem)?,
+        };
+
+        let key = VerificationKey::from_public_pem(algorithm, public_key)?;
+
+        let mut header = Header::new(algorithm.into());
+        header.kid = key.kid.clone();
+
+        Ok(Self {
+            encoding,
+            header,
+            keys: vec![key],
+            settings: TokenSettings::default(),
+        })
+    }
+
+    /// Добавить предыдущий публичный ключ, токены которого принимаются в течение `grace`.
+    pub(crate) fn with_previous_key(
+        mut self,
+        algorithm: SigningAlgorithm,
+        public_key: &Path,
+        grace: Duration,
+    ) -> anyhow::Result<Self> {
+        let mut key = VerificationKey::from_public_pem(algorithm, public_key)?;
+        key.expires_at = Some(Utc::now() + grace);
+
+        if key.kid == self.header.kid {
+            anyhow::bail!("Предыдущий ключ совпадает с текущим");
+        }
+
+        self.keys.push(key);
+
+        Ok(self)
+    }
+
+    /// Установить параметры выпускаемых токенов.
+    pub(crate) fn with_token_settings(mut self, settings: TokenSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Время жизни выпускаемых токенов.
+    pub(crate) fn token_ttl(&self) -> Duration {
+        self.settings.ttl
+    }
+
+    /// Генерация JWT-токена с настроенным временем жизни.
+    pub(crate) fn generate_token(
+        &self,
+        user_id: i64,
+        username: &str,
+        tenant_id: i64,
+    ) -> anyhow::Result<String> {
+        let exp = (Utc::now() + self.settings.ttl).timestamp() as usize;
         let claims = Claims {
             user_id,
             username: username.to_string(),
+            tenant_id,
             exp,
+            iss: self.settings.issuer.clone(),
+            aud: self.settings.audience.clone(),
         };
 
-        let header = Header::default();
-
-        let token = encode(&header, &claims, &self.encoding)?;
+        let token = encode(&self.header, &claims, &self.encoding)?;
 
         Ok(token)
     }
 
     /// Проверка и декодирование токена.
     pub(crate) fn verify_token(&self, token: &str) -> anyhow::Result<Claims> {
-        let validator = Validation::default();
+        self.decode_claims(token)
+    }
+
+    /// Генерация токена ссылки для совместного доступа к посту.
+    pub(crate) fn generate_share_token(
+        &self,
+        post_id: i64,
+        ttl: Duration,
+    ) -> anyhow::Result<(String, DateTime<Utc>)> {
+        let expires_at = Utc::now() + ttl;
+        let claims = ShareClaims {
+            typ: ResourceTokenType::PostShare,
+            post_id,
+            exp: expires_at.timestamp() as usize,
+            iss: self.settings.issuer.clone(),
+            aud: self.settings.audience.clone(),
+        };
+
+        let token = encode(&self.header, &claims, &self.encoding)?;
+
+        Ok((token, expires_at))
+    }
+
+    /// Проверка токена ссылки для совместного доступа и получение идентификатора поста.
+    pub(crate) fn verify_share_token(&self, token: &str) -> anyhow::Result<i64> {
+        let claims: ShareClaims = self.decode_claims(token)?;
+
+        if claims.typ != ResourceTokenType::PostShare {
+            anyhow::bail!("Некорректный тип токена");
+        }
+
+        Ok(claims.post_id)
+    }
+
+    /// Проверка подписи и декодирование аттрибутов токена.
+    ///
+    /// Ключ проверки выбирается по заголовку `kid`; токены без него проверяются текущим ключом.
+    fn decode_claims<T: DeserializeOwned>(&self, token: &str) -> anyhow::Result<T> {
+        let header = decode_header(token)?;
+
+        let key = match &header.kid {
+            Some(kid) => self.keys.iter().find(|key| key.kid.as_ref() == Some(kid)),
+            None => self.keys.first(),
+        }
+        .ok_or(anyhow::anyhow!("Неизвестный ключ подписи токена"))?;
+
+        if key.is_expired() {
+            anyhow::bail!("Срок действия ключа подписи токена истек");
+        }
+
+        let mut validator = Validation::new(key.algorithm);
+        validator.leeway = self.settings.leeway.as_secs();
 
-        let decoded = decode::<Claims>(token, &self.decoding, &validator)?;
+        if let Some(issuer) = &self.settings.issuer {
+            validator.set_issuer(&[issuer]);
+        }
+
+        if let Some(audience) = &self.settings.audience {
+            validator.set_audience(&[audience]);
+        }
+
+        let decoded = decode::<T>(token, &key.decoding, &validator)?;
 
         Ok(decoded.claims)
     }
+
+    /// Набор действующих публичных ключей в формате JWKS.
+    pub(crate) fn jwks(&self) -> JwkSet {
+        JwkSet {
+            keys: self
+                .keys
+                .iter()
+                .filter(|key| !key.is_expired())
+                .filter_map(|key| key.jwk.clone())
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "0123456789abcdef0123456789abcdef";
+
+    fn service() -> JwtService {
+        JwtService::new(SECRET).with_token_settings(TokenSettings {
+            issuer: Some("blog".to_string()),
+            audience: Some("blog-clients".to_string()),
+            leeway: Duration::from_secs(60),
+            ..TokenSettings::default()
+        })
+    }
+
+    /// Подписать токен с временем истечения `exp_offset` секунд от текущего момента.
+    fn token(exp_offset: i64, iss: &str, aud: &str) -> String {
+        let claims = Claims {
+            user_id: 1,
+            username: "alice".to_string(),
+            tenant_id: DEFAULT_TENANT_ID,
+            exp: (Utc::now().timestamp() + exp_offset) as usize,
+            iss: Some(iss.to_string()),
+            aud: Some(aud.to_string()),
+        };
+
+        encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(SECRET.as_bytes()),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn generated_token_is_verified() {
+        let service = service();
+        let token = service
+            .generate_token(1, "alice", DEFAULT_TENANT_ID)
+            .unwrap();
+
+        let claims = service.verify_token(&token).unwrap();
+
+        assert_eq!(claims.user_id, 1);
+        assert_eq!(claims.username, "alice");
+    }
+
+    #[test]
+    fn expired_token_within_leeway_is_accepted() {
+        let token = token(-30, "blog", "blog-clients");
+
+        assert!(service().verify_token(&token).is_ok());
+    }
+
+    #[test]
+    fn expired_token_beyond_leeway_is_rejected() {
+        let token = token(-120, "blog", "blog-clients");
+
+        assert!(service().verify_token(&token).is_err());
+    }
+
+    #[test]
+    fn issuer_mismatch_is_rejected() {
+        let token = token(3600, "other", "blog-clients");
+
+        assert!(service().verify_token(&token).is_err());
+    }
+
+    #[test]
+    fn audience_mismatch_is_rejected() {
+        let token = token(3600, "blog", "other");
+
+        assert!(service().verify_token(&token).is_err());
+    }
 }
@@ -0,0 +1,77 @@
+//! Адреса для прослушивания входящих соединений (TCP или Unix-сокет).
+
+use std::fmt;
+use std::io;
+use std::net::SocketAddr;
+use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+use tokio::net::UnixListener;
+
+/// Адрес для прослушивания входящих соединений.
+#[derive(Debug, Clone)]
+pub(crate) enum ListenAddr {
+    /// TCP-адрес.
+    Tcp(SocketAddr),
+
+    /// Unix-сокет (например, для работы за локальным обратным прокси).
+    Unix {
+        /// Путь к файлу сокета.
+        path: PathBuf,
+
+        /// Права доступа к файлу сокета.
+        mode: u32,
+    },
+}
+
+impl ListenAddr {
+    /// Выбрать Unix-сокет, если задан путь к нему, иначе TCP-адрес.
+    pub(crate) fn new(socket: Option<&Path>, addr: SocketAddr, mode: u32) -> Self {
+        match socket {
+            Some(path) => Self::Unix {
+                path: path.to_path_buf(),
+                mode,
+            },
+            None => Self::Tcp(addr),
+        }
+    }
+}
+
+impl fmt::Display for ListenAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp(addr) => write!(f, "{addr}"),
+            Self::Unix { path, .. } => write!(f, "unix:{}", path.display()),
+        }
+    }
+}
+
+/// Создать Unix-сокет и выставить права доступа к его файлу.
+///
+/// Оставшийся от предыдущего запуска файл сокета удаляется; файл другого типа
+/// по этому пути не трогается, и создание сокета завершается ошибкой.
+pub(crate) fn bind_unix_socket(path: &Path, mode: u32) -> io::Result<UnixListener> {
+    match std::fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_socket() => std::fs::remove_file(path)?,
+        Ok(_) => {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("Файл {} не является сокетом", path.display()),
+            ));
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e),
+    }
+
+    let listener = UnixListener::bind(path)?;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode))?;
+
+    Ok(listener)
+}
+
+/// Разобрать права доступа к файлу сокета в восьмеричной записи (например, `660`).
+pub(crate) fn parse_socket_mode(value: &str) -> Result<u32, String> {
+    u32::from_str_radix(value, 8)
+        .ok()
+        .filter(|mode| *mode <= 0o777)
+        .ok_or_else(|| format!("Некорректные права доступа: {value}"))
+}
@@ -1,19 +1,187 @@
 //! Модуль логгирования.
 
+use axum::http::HeaderMap;
+use std::path::PathBuf;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::fmt::MakeWriter;
 use tracing_subscriber::fmt::time::ChronoUtc;
-use tracing_subscriber::{EnvFilter, layer::SubscriberExt, util::SubscriberInitExt};
+use tracing_subscriber::{
+    EnvFilter, Layer, Registry, layer::SubscriberExt, util::SubscriberInitExt,
+};
 
-/// Инициализация логгера с указанием уровня логгирования.
-pub(crate) fn init_logging(log_level: &str) {
+/// Префикс имен файлов логов.
+const LOG_FILE_PREFIX: &str = "blog-server";
+
+/// Расширение имен файлов логов.
+const LOG_FILE_SUFFIX: &str = "log";
+
+/// Формат записей лога.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum LogFormat {
+    /// Текстовый формат для чтения человеком.
+    Pretty,
+
+    /// JSON, по одному объекту на строку (для сбора в ELK, Loki и т.п.).
+    Json,
+}
+
+/// Периодичность ротации файлов логов.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum LogRotation {
+    /// Новый файл каждый час.
+    Hourly,
+
+    /// Новый файл каждые сутки.
+    Daily,
+
+    /// Без ротации.
+    Never,
+}
+
+impl From<LogRotation> for Rotation {
+    fn from(rotation: LogRotation) -> Self {
+        match rotation {
+            LogRotation::Hourly => Rotation::HOURLY,
+            LogRotation::Daily => Rotation::DAILY,
+            LogRotation::Never => Rotation::NEVER,
+        }
+    }
+}
+
+/// Настройки логгирования.
+#[derive(Debug, Clone)]
+pub(crate) struct LogSettings {
+    /// Уровень логгирования.
+    pub level: String,
+
+    /// Формат записей лога.
+    pub format: LogFormat,
+
+    /// Каталог для файлов логов (`None` - логи выводятся только в stdout).
+    pub dir: Option<PathBuf>,
+
+    /// Периодичность ротации файлов логов.
+    pub rotation: LogRotation,
+
+    /// Число хранимых файлов логов (более старые удаляются при ротации).
+    pub max_files: usize,
+}
+
+/// Инициализация логгера.
+///
+/// Логи всегда выводятся в stdout, а при указанном каталоге дублируются в файлы
+/// с ротацией. Возвращаемый guard нужно хранить до завершения работы сервера:
+/// при его удалении в файл дописываются оставшиеся в буфере записи.
+pub(crate) fn init_logging(settings: &LogSettings) -> anyhow::Result<Option<WorkerGuard>> {
     let filter = EnvFilter::new(format!(
-        "blog_server={},axum=info,tower=info,tower_http=info",
-        log_level.to_lowercase()
+        "blog_server={},axum=info,tower=info,tower_http=info,sqlx::query=warn",
+        settings.level.to_lowercase()
     ));
 
-    let format = tracing_subscriber::fmt::layer().with_timer(ChronoUtc::rfc_3339());
+    let mut layers = vec![format_layer(settings.format, std::io::stdout, true)];
+    let mut guard = None;
+
+    if let Some(dir) = &settings.dir {
+        let appender = RollingFileAppender::builder()
+            .rotation(settings.rotation.into())
+            .filename_prefix(LOG_FILE_PREFIX)
+            .filename_suffix(LOG_FILE_SUFFIX)
+            .max_log_files(settings.max_files)
+            .build(dir)?;
+        let (writer, worker_guard) = tracing_appender::non_blocking(appender);
+
+        layers.push(format_layer(settings.format, writer, false));
+        guard = Some(worker_guard);
+    }
 
     tracing_subscriber::registry()
+        .with(layers)
         .with(filter)
-        .with(format)
         .init();
+
+    Ok(guard)
+}
+
+/// Создать слой вывода логов в указанном формате.
+///
+/// В формате JSON поля текущего спана (например, идентификаторы запроса
+/// и пользователя) выводятся в объекте `span`.
+fn format_layer<W>(
+    format: LogFormat,
+    writer: W,
+    ansi: bool,
+) -> Box<dyn Layer<Registry> + Send + Sync>
+where
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let layer = tracing_subscriber::fmt::layer()
+        .with_timer(ChronoUtc::rfc_3339())
+        .with_writer(writer)
+        .with_ansi(ansi);
+
+    match format {
+        LogFormat::Pretty => layer.boxed(),
+        LogFormat::Json => layer
+            .json()
+            .with_current_span(true)
+            .with_span_list(false)
+            .boxed(),
+    }
+}
+
+/// Заголовок с контекстом трассировки W3C Trace Context.
+pub(crate) const TRACEPARENT: &str = "traceparent";
+
+/// Контекст трассировки клиента из заголовка (метаданных gRPC) `traceparent`.
+///
+/// Записывается в спан запроса, чтобы связать записи сервера со спаном клиента.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct TraceParent<'a> {
+    /// Идентификатор трассировки.
+    pub(crate) trace_id: &'a str,
+
+    /// Идентификатор спана клиента, родительского для запроса.
+    pub(crate) parent_span_id: &'a str,
+}
+
+impl<'a> TraceParent<'a> {
+    /// Прочитать контекст из заголовков запроса.
+    ///
+    /// Отсутствующий или некорректный заголовок дает пустой контекст.
+    pub(crate) fn from_headers(headers: &'a HeaderMap) -> Self {
+        headers
+            .get(TRACEPARENT)
+            .and_then(|value| value.to_str().ok())
+            .and_then(Self::parse)
+            .unwrap_or_default()
+    }
+
+    /// Разобрать значение `traceparent` (`00-<trace-id>-<span-id>-<flags>`).
+    fn parse(value: &'a str) -> Option<Self> {
+        let mut parts = value.trim().split('-');
+        let (version, trace_id, parent_span_id, flags) =
+            (parts.next()?, parts.next()?, parts.next()?, parts.next()?);
+
+        let is_hex = |part: &str, len: usize| {
+            part.len() == len
+                && part
+                    .bytes()
+                    .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+        };
+        let is_zero = |part: &str| part.bytes().all(|b| b == b'0');
+
+        let valid = is_hex(version, 2)
+            && version != "ff"
+            && is_hex(trace_id, 32)
+            && !is_zero(trace_id)
+            && is_hex(parent_span_id, 16)
+            && !is_zero(parent_span_id)
+            && is_hex(flags, 2);
+
+        valid.then_some(Self {
+            trace_id,
+            parent_span_id,
+        })
+    }
 }
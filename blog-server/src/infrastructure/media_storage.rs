@@ -0,0 +1,77 @@
+//! Файловое хранилище загруженных изображений.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Хранилище файлов изображений в локальном каталоге.
+#[derive(Debug, Clone)]
+pub(crate) struct MediaStorage {
+    /// Корневой каталог хранилища.
+    root: PathBuf,
+}
+
+impl MediaStorage {
+    /// Создать хранилище в указанном каталоге.
+    pub(crate) fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Сохранить файл по относительному пути, создав недостающие каталоги.
+    pub(crate) async fn save(&self, path: &str, bytes: &[u8]) -> io::Result<()> {
+        let path = self.resolve(path);
+
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        tokio::fs::write(path, bytes).await
+    }
+
+    /// Прочитать файл по относительному пути.
+    pub(crate) async fn read(&self, path: &str) -> io::Result<Vec<u8>> {
+        tokio::fs::read(self.resolve(path)).await
+    }
+
+    /// Получить идентификаторы изображений, каталоги которых не изменялись дольше `min_age`.
+    ///
+    /// Файлы каждого изображения хранятся в каталоге с именем, равным его идентификатору.
+    pub(crate) async fn list_media_dirs(&self, min_age: Duration) -> io::Result<Vec<i64>> {
+        let mut entries = match tokio::fs::read_dir(&self.root).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e),
+        };
+
+        let mut ids = vec![];
+
+        while let Some(entry) = entries.next_entry().await? {
+            let Some(id) = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.parse().ok())
+            else {
+                continue;
+            };
+
+            let metadata = entry.metadata().await?;
+            let age = metadata.modified()?.elapsed().unwrap_or_default();
+
+            if metadata.is_dir() && age >= min_age {
+                ids.push(id);
+            }
+        }
+
+        Ok(ids)
+    }
+
+    /// Удалить каталог изображения со всеми его вариантами.
+    pub(crate) async fn remove_media_dir(&self, media_id: i64) -> io::Result<()> {
+        tokio::fs::remove_dir_all(self.resolve(&media_id.to_string())).await
+    }
+
+    /// Получить полный путь к файлу внутри хранилища.
+    fn resolve(&self, path: &str) -> PathBuf {
+        self.root.join(Path::new(path))
+    }
+}
@@ -1,3 +1,12 @@
+pub(crate) mod backup;
+pub(crate) mod cache;
+pub(crate) mod content_filter;
 pub(crate) mod database;
+pub(crate) mod events;
+pub(crate) mod features;
 pub(crate) mod jwt;
+pub(crate) mod listener;
 pub(crate) mod logging;
+pub(crate) mod mailer;
+pub(crate) mod media_storage;
+pub(crate) mod search;
@@ -0,0 +1,298 @@
+//! Поисковый индекс постов.
+//!
+//! По умолчанию используется полнотекстовый поиск Postgres; для больших инсталляций
+//! индекс можно вынести во внешний поисковый движок (Meilisearch).
+
+use crate::domain::error::SearchError;
+use crate::domain::event::DomainEvent;
+use crate::domain::post::Post;
+use crate::infrastructure::database::ReadPool;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+use tonic::async_trait;
+
+/// Поисковый движок, в котором индексируются посты.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum SearchBackend {
+    /// Полнотекстовый поиск Postgres.
+    Postgres,
+
+    /// Внешний сервис Meilisearch.
+    Meilisearch,
+}
+
+/// Поисковый индекс постов.
+///
+/// Индекс обновляется сервисом блога при создании, изменении и удалении постов.
+#[async_trait]
+pub(crate) trait SearchIndex: Debug + Send + Sync {
+    /// Добавить пост в индекс или обновить его.
+    async fn index_post(&self, post: &Post) -> Result<(), SearchError>;
+
+    /// Удалить пост из индекса.
+    async fn remove_post(&self, id: i64) -> Result<(), SearchError>;
+
+    /// Найти посты блога по запросу.
+    ///
+    /// Возвращает идентификаторы постов в порядке релевантности. Приватные посты
+    /// включаются только для их авторов, включая соавторов (`viewer_id`), `author_id`
+    /// оставляет только посты указанного автора.
+    async fn search(
+        &self,
+        tenant_id: i64,
+        query: &str,
+        author_id: Option<i64>,
+        viewer_id: Option<i64>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<i64>, SearchError>;
+}
+
+/// Запустить фоновое обновление поискового индекса по доменным событиям постов.
+///
+/// Ошибка индексации не отменяет изменение поста и только записывается в лог.
+pub(crate) fn spawn_indexer(
+    index: Arc<dyn SearchIndex>,
+    mut events: broadcast::Receiver<DomainEvent>,
+) {
+    tokio::spawn(async move {
+        loop {
+            let result = match events.recv().await {
+                Ok(DomainEvent::PostCreated(post) | DomainEvent::PostUpdated(post)) => {
+                    index.index_post(&post).await.map_err(|e| (post.id, e))
+                }
+                Ok(DomainEvent::PostDeleted { id, .. }) => {
+                    index.remove_post(id).await.map_err(|e| (id, e))
+                }
+                Ok(DomainEvent::UserRegistered { .. }) => Ok(()),
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!(
+                        "Поисковый индекс пропустил {skipped} событий и может быть неактуален"
+                    );
+                    Ok(())
+                }
+                Err(RecvError::Closed) => break,
+            };
+
+            if let Err((id, e)) = result {
+                tracing::warn!("Не удалось обновить пост {id} в поисковом индексе: {e}");
+            }
+        }
+    });
+}
+
+/// Полнотекстовый поиск Postgres по заголовку и содержимому постов.
+///
+/// Индекс поддерживается самой базой данных, поэтому обновлять его отдельно не требуется.
+#[derive(Debug, Clone)]
+pub(crate) struct PostgresSearchIndex {
+    /// Пул соединений с БД для запросов на чтение.
+    pool: ReadPool,
+}
+
+impl PostgresSearchIndex {
+    /// Создать новый экземпляр поискового индекса.
+    pub(crate) fn new(pool: ReadPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl SearchIndex for PostgresSearchIndex {
+    async fn index_post(&self, _post: &Post) -> Result<(), SearchError> {
+        Ok(())
+    }
+
+    async fn remove_post(&self, _id: i64) -> Result<(), SearchError> {
+        Ok(())
+    }
+
+    async fn search(
+        &self,
+        tenant_id: i64,
+        query: &str,
+        author_id: Option<i64>,
+        viewer_id: Option<i64>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<i64>, SearchError> {
+        let ids = sqlx::query_scalar!(
+            "SELECT id FROM posts
+            WHERE to_tsvector('simple', title || ' ' || content) @@ websearch_to_tsquery('simple', $1)
+                AND tenant_id = $5
+                AND (NOT is_private OR EXISTS(
+                    SELECT 1 FROM post_authors pa WHERE pa.post_id = posts.id AND pa.user_id = $2
+                ))
+                AND ($6::BIGINT IS NULL OR author_id = $6)
+            ORDER BY ts_rank(
+                to_tsvector('simple', title || ' ' || content),
+                websearch_to_tsquery('simple', $1)
+            ) DESC, id DESC
+            LIMIT $3 OFFSET $4",
+            query,
+            viewer_id,
+            limit,
+            offset,
+            tenant_id,
+            author_id
+        )
+        .fetch_all(self.pool.get())
+        .await?;
+
+        Ok(ids)
+    }
+}
+
+/// Документ поста в индексе Meilisearch.
+#[derive(Debug, Serialize)]
+struct MeilisearchDocument<'a> {
+    id: i64,
+    title: &'a str,
+    content: &'a str,
+    excerpt: Option<&'a str>,
+    author_id: i64,
+    author_ids: &'a [i64],
+    is_private: bool,
+    tenant_id: i64,
+}
+
+impl<'a> From<&'a Post> for MeilisearchDocument<'a> {
+    fn from(post: &'a Post) -> Self {
+        Self {
+            id: post.id,
+            title: &post.title,
+            content: &post.content,
+            excerpt: post.excerpt.as_deref(),
+            author_id: post.author_id,
+            author_ids: &post.author_ids,
+            is_private: post.is_private,
+            tenant_id: post.tenant_id,
+        }
+    }
+}
+
+/// Найденный в Meilisearch документ.
+#[derive(Debug, Deserialize)]
+struct MeilisearchHit {
+    id: i64,
+}
+
+/// Ответ Meilisearch на поисковый запрос.
+#[derive(Debug, Deserialize)]
+struct MeilisearchResponse {
+    hits: Vec<MeilisearchHit>,
+}
+
+/// Поисковый индекс во внешнем сервисе Meilisearch.
+#[derive(Debug, Clone)]
+pub(crate) struct MeilisearchIndex {
+    /// HTTP-клиент для обращения к Meilisearch.
+    client: reqwest::Client,
+
+    /// Адрес индекса постов (`{url}/indexes/{index}`).
+    index_url: String,
+
+    /// Ключ доступа к Meilisearch.
+    api_key: Option<String>,
+}
+
+impl MeilisearchIndex {
+    /// Название индекса постов.
+    const INDEX: &str = "posts";
+
+    /// Подключиться к Meilisearch и настроить индекс постов.
+    pub(crate) async fn connect(url: &str, api_key: Option<String>) -> Result<Self, SearchError> {
+        let index = Self {
+            client: reqwest::Client::new(),
+            index_url: format!("{}/indexes/{}", url.trim_end_matches('/'), Self::INDEX),
+            api_key,
+        };
+
+        index
+            .request(reqwest::Method::PATCH, "settings")
+            .json(&serde_json::json!({
+                "searchableAttributes": ["title", "excerpt", "content"],
+                "filterableAttributes": ["author_id", "author_ids", "is_private", "tenant_id"],
+            }))
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(index)
+    }
+
+    /// Подготовить запрос к индексу постов.
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        let request = self
+            .client
+            .request(method, format!("{}/{path}", self.index_url));
+
+        match &self.api_key {
+            Some(api_key) => request.bearer_auth(api_key),
+            None => request,
+        }
+    }
+}
+
+#[async_trait]
+impl SearchIndex for MeilisearchIndex {
+    async fn index_post(&self, post: &Post) -> Result<(), SearchError> {
+        self.request(reqwest::Method::POST, "documents?primaryKey=id")
+            .json(&[MeilisearchDocument::from(post)])
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+
+    async fn remove_post(&self, id: i64) -> Result<(), SearchError> {
+        self.request(reqwest::Method::DELETE, &format!("documents/{id}"))
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+
+    async fn search(
+        &self,
+        tenant_id: i64,
+        query: &str,
+        author_id: Option<i64>,
+        viewer_id: Option<i64>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<i64>, SearchError> {
+        let mut filter = match viewer_id {
+            Some(viewer_id) => format!(
+                "tenant_id = {tenant_id} AND (is_private = false OR author_ids = {viewer_id})"
+            ),
+            None => format!("tenant_id = {tenant_id} AND is_private = false"),
+        };
+
+        if let Some(author_id) = author_id {
+            filter.push_str(&format!(" AND author_id = {author_id}"));
+        }
+
+        let response = self
+            .request(reqwest::Method::POST, "search")
+            .json(&serde_json::json!({
+                "q": query,
+                "filter": filter,
+                "limit": limit,
+                "offset": offset,
+                "attributesToRetrieve": ["id"],
+            }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<MeilisearchResponse>()
+            .await?;
+
+        Ok(response.hits.into_iter().map(|hit| hit.id).collect())
+    }
+}
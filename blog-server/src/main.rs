@@ -6,31 +6,88 @@ mod domain;
 mod infrastructure;
 mod presentation;
 
+use crate::application::admin_service::AdminService;
+use crate::application::api_key_service::ApiKeyService;
 use crate::application::auth_service::AuthService;
 use crate::application::blog_service::BlogService;
+use crate::application::cleanup_service::CleanupService;
+use crate::application::export_service::{ExportService, ExportSources};
+use crate::application::login_history_service::LoginHistoryService;
+use crate::application::media_service::MediaService;
+use crate::application::tenant_service::TenantService;
+use crate::application::user_service::UserService;
+use crate::data::admin_repository::AdminRepository;
+use crate::data::api_key_repository::ApiKeyRepository;
+use crate::data::cleanup_repository::CleanupRepository;
+use crate::data::export_repository::ExportRepository;
+use crate::data::invite_repository::InviteRepository;
+use crate::data::login_history_repository::LoginHistoryRepository;
+use crate::data::media_repository::MediaRepository;
 use crate::data::post_repository::PostRepository;
+use crate::data::settings_repository::SettingsRepository;
+use crate::data::tenant_repository::TenantRepository;
 use crate::data::user_repository::UserRepository;
-use crate::infrastructure::jwt::JwtService;
-use crate::presentation::{AppState, create_router};
-use infrastructure::database::{create_pool, run_migrations};
+use crate::domain::feature::Feature;
+use crate::domain::post::{DEFAULT_DUPLICATE_POST_WINDOW_SECS, DuplicatePostCheck};
+use crate::domain::settings::MAX_POSTS_PER_PAGE;
+use crate::domain::user::{CreateUserRequest, PasswordPolicy, User};
+use crate::infrastructure::content_filter::{
+    ContentFilter, ContentFilterAction, ContentFilterBackend, ExternalContentFilter, WordListFilter,
+};
+use crate::infrastructure::events::{self, EventBus};
+use crate::infrastructure::features::FeatureFlags;
+use crate::infrastructure::jwt::{JwtService, SigningAlgorithm, TokenSettings};
+use crate::infrastructure::mailer::{LogMailer, Mailer, SmtpMailer};
+use crate::infrastructure::media_storage::MediaStorage;
+use crate::infrastructure::search::{
+    self, MeilisearchIndex, PostgresSearchIndex, SearchBackend, SearchIndex,
+};
+use crate::presentation::caching::{
+    CacheTtls, DEFAULT_LIST_CACHE_TTL_SECS, DEFAULT_POST_CACHE_TTL_SECS,
+};
+use crate::presentation::cors::CorsPolicy;
+use crate::presentation::frontend::INDEX_FILE;
+use crate::presentation::maintenance::MaintenanceLayer;
+use crate::presentation::middleware::unix_socket_peer;
+use crate::presentation::security_headers::{
+    API_CONTENT_SECURITY_POLICY, DEFAULT_FRAME_OPTIONS, DEFAULT_REFERRER_POLICY,
+    FRONTEND_CONTENT_SECURITY_POLICY, SecurityHeaders, security_headers,
+};
+use crate::presentation::session::SessionCookies;
+use crate::presentation::{AppState, BodyLimits, RouteLimit, RouteLimits, create_router};
+use infrastructure::backup;
+use infrastructure::database::{
+    ReadPool, create_pool, create_replica_pool, pending_migrations, run_migrations,
+    wait_for_migrations,
+};
 use infrastructure::jwt;
-use infrastructure::logging::init_logging;
+use infrastructure::listener::{self, ListenAddr};
+use infrastructure::logging::{LogFormat, LogRotation, LogSettings, TraceParent, init_logging};
+use sqlx::PgPool;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::net::TcpListener;
+use tokio_stream::wrappers::UnixListenerStream;
 use tower::ServiceBuilder;
-use tower_http::cors::{Any, CorsLayer};
-use tower_http::timeout::TimeoutLayer;
+use tower::util::MapRequestLayer;
+#[cfg(debug_assertions)]
+use tower_http::cors::CorsLayer;
+use tower_http::request_id::{
+    MakeRequestUuid, PropagateRequestIdLayer, RequestId, SetRequestIdLayer,
+};
 use tower_http::trace::TraceLayer;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use std::net::SocketAddr;
+use std::path::PathBuf;
 use tower_governor::GovernorLayer;
 use tower_governor::governor::GovernorConfigBuilder;
+use validator::Validate;
 
 use crate::blog_grpc::blog_service_server::BlogServiceServer;
-use crate::presentation::grpc_service::BlogGrpcService;
+use crate::presentation::grpc_service::{BlogGrpcService, capture_request_host};
 use tonic::transport::Server;
+use tonic_web::GrpcWebLayer;
 
 pub(crate) mod blog_grpc {
     tonic::include_proto!("blog");
@@ -40,6 +97,10 @@ pub(crate) mod blog_grpc {
 #[derive(Debug, Parser)]
 #[command(version, about)]
 struct Args {
+    /// Административная команда; без нее запускается сервер.
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Адрес для прослушивания входящих соединений.
     #[arg(long, default_value = "127.0.0.1")]
     host: String,
@@ -52,88 +113,770 @@ struct Args {
     #[arg(long, default_value = "50051")]
     grpc_port: u16,
 
+    /// Путь к Unix-сокету для входящих HTTP-соединений (вместо `--host` и `--http-port`).
+    ///
+    /// IP-адрес клиента берется из заголовков `X-Forwarded-For` или `X-Real-IP`,
+    /// выставляемых обратным прокси.
+    #[arg(long, value_name = "PATH")]
+    http_socket: Option<PathBuf>,
+
+    /// Каталог собранного WASM-фронтенда (например, `blog-wasm/www`), раздаваемого
+    /// сервером по адресу `/`. Неизвестные пути обслуживаются страницей `index.html`.
+    #[arg(long, value_name = "DIR")]
+    frontend_dir: Option<PathBuf>,
+
+    /// Значение заголовка `Content-Security-Policy` (пустая строка - не отправлять).
+    ///
+    /// По умолчанию запрещает загрузку любых ресурсов, а при раздаче фронтенда
+    /// (`--frontend-dir`) разрешает скрипты, стили и WebAssembly с адреса сервера.
+    #[arg(long, value_name = "POLICY")]
+    content_security_policy: Option<String>,
+
+    /// Значение заголовка `X-Frame-Options` (пустая строка - не отправлять).
+    #[arg(long, value_name = "VALUE", default_value = DEFAULT_FRAME_OPTIONS)]
+    frame_options: String,
+
+    /// Значение заголовка `Referrer-Policy` (пустая строка - не отправлять).
+    #[arg(long, value_name = "POLICY", default_value = DEFAULT_REFERRER_POLICY)]
+    referrer_policy: String,
+
+    /// Путь к Unix-сокету для входящих GRPC-соединений (вместо `--host` и `--grpc-port`).
+    #[arg(long, value_name = "PATH")]
+    grpc_socket: Option<PathBuf>,
+
+    /// Права доступа к файлам Unix-сокетов в восьмеричной записи.
+    #[arg(long, value_name = "MODE", default_value = "660", value_parser = listener::parse_socket_mode)]
+    socket_mode: u32,
+
     /// Уровень логирования.
     ///
     /// Доступные варианты: "OFF", "ERROR", "WARN", "INFO", "DEBUG", "TRACE".
     #[arg(long, value_name = "LEVEL", default_value = "INFO")]
     log_level: String,
+
+    /// Формат записей лога.
+    #[arg(long, value_enum, default_value = "pretty")]
+    log_format: LogFormat,
+
+    /// Каталог, в который дополнительно записываются логи (с ротацией файлов).
+    #[arg(long, value_name = "DIR")]
+    log_dir: Option<PathBuf>,
+
+    /// Периодичность ротации файлов логов.
+    #[arg(long, value_enum, default_value = "daily", requires = "log_dir")]
+    log_rotation: LogRotation,
+
+    /// Число хранимых файлов логов; более старые файлы удаляются при ротации.
+    #[arg(long, value_name = "COUNT", default_value = "7", requires = "log_dir", value_parser = clap::value_parser!(u64).range(1..))]
+    log_max_files: u64,
+
+    /// Порог длительности запроса к БД (в миллисекундах), после которого запрос
+    /// записывается в лог как медленный.
+    #[arg(long, value_name = "MILLISECONDS", default_value = "500")]
+    slow_query_threshold: u64,
+
+    /// Применить миграции БД и завершить работу (аналог команды `migrate`).
+    ///
+    /// При нескольких экземплярах сервера миграции применяет один процесс с этим флагом,
+    /// а экземпляры запускаются с `--skip-migrations`.
+    #[arg(long, conflicts_with = "skip_migrations")]
+    migrate_only: bool,
+
+    /// Не применять миграции БД при запуске, а дождаться, пока их применит другой
+    /// экземпляр сервера или команда `migrate`.
+    #[arg(long)]
+    skip_migrations: bool,
+
+    /// Время ожидания применения миграций в секундах (с `--skip-migrations`); если
+    /// миграции так и не применены, сервер не запускается.
+    #[arg(
+        long,
+        value_name = "SECONDS",
+        default_value = "60",
+        requires = "skip_migrations"
+    )]
+    migrations_wait_timeout: u64,
+
+    /// Интервал проверки доступности основной БД и реплики из `DATABASE_READ_URL` (в секундах).
+    #[arg(long, value_name = "SECONDS", default_value = "10", value_parser = clap::value_parser!(u64).range(1..))]
+    replica_check_interval: u64,
+
+    /// Минимальная длина пароля пользователя.
+    #[arg(long, default_value = "6")]
+    password_min_length: usize,
+
+    /// Требовать наличие строчной буквы в пароле.
+    #[arg(long)]
+    password_require_lowercase: bool,
+
+    /// Требовать наличие заглавной буквы в пароле.
+    #[arg(long)]
+    password_require_uppercase: bool,
+
+    /// Требовать наличие цифры в пароле.
+    #[arg(long)]
+    password_require_digit: bool,
+
+    /// Требовать наличие специального символа в пароле.
+    #[arg(long)]
+    password_require_special: bool,
+
+    /// Файл с дополнительным списком запрещенных паролей (по одному на строку).
+    #[arg(long, value_name = "FILE")]
+    password_denylist: Option<PathBuf>,
+
+    /// Алгоритм подписи JWT-токенов.
+    ///
+    /// Для HS256 используется секрет из переменной окружения JWT_SECRET,
+    /// для RS256 и EdDSA - пара ключей в формате PEM.
+    #[arg(long, value_enum, default_value = "HS256")]
+    jwt_algorithm: SigningAlgorithm,
+
+    /// Файл с закрытым ключом подписи JWT-токенов (PEM).
+    #[arg(long, value_name = "FILE", required_if_eq_any([("jwt_algorithm", "RS256"), ("jwt_algorithm", "EdDSA")]))]
+    jwt_private_key: Option<PathBuf>,
+
+    /// Файл с публичным ключом проверки JWT-токенов (PEM).
+    #[arg(long, value_name = "FILE", required_if_eq_any([("jwt_algorithm", "RS256"), ("jwt_algorithm", "EdDSA")]))]
+    jwt_public_key: Option<PathBuf>,
+
+    /// Файл с публичным ключом, которым подписывались токены до ротации ключей (PEM).
+    #[arg(long, value_name = "FILE", requires = "jwt_public_key")]
+    jwt_previous_public_key: Option<PathBuf>,
+
+    /// Время в секундах после запуска, в течение которого принимаются токены предыдущего ключа.
+    #[arg(long, value_name = "SECONDS", default_value = "86400")]
+    jwt_previous_key_grace: u64,
+
+    /// Время жизни JWT-токена в секундах.
+    #[arg(long, value_name = "SECONDS", default_value = "86400", value_parser = clap::value_parser!(u64).range(1..))]
+    jwt_ttl: u64,
+
+    /// Издатель JWT-токенов (claim `iss`).
+    #[arg(long)]
+    jwt_issuer: Option<String>,
+
+    /// Получатель JWT-токенов (claim `aud`).
+    #[arg(long)]
+    jwt_audience: Option<String>,
+
+    /// Допустимое расхождение часов в секундах при проверке времени жизни JWT-токена.
+    #[arg(long, value_name = "SECONDS", default_value = "60")]
+    jwt_leeway: u64,
+
+    /// Выдавать JWT-токен в HttpOnly cookie при входе и принимать его из cookie.
+    #[arg(long)]
+    session_cookies: bool,
+
+    /// Выставлять сессионным cookie атрибут Secure (только HTTPS).
+    #[arg(long, requires = "session_cookies")]
+    session_cookies_secure: bool,
+
+    /// Максимальный размер тела запроса к эндпоинтам авторизации и пользователей (в байтах).
+    #[arg(long, value_name = "BYTES", default_value = "16384")]
+    auth_body_limit: usize,
+
+    /// Максимальный размер тела запроса к эндпоинтам постов (в байтах).
+    #[arg(long, value_name = "BYTES", default_value = "1048576")]
+    posts_body_limit: usize,
+
+    /// Максимальный размер загружаемого изображения (в байтах).
+    #[arg(long, value_name = "BYTES", default_value = "10485760")]
+    media_body_limit: usize,
+
+    /// Максимальное число одновременно обрабатываемых запросов к эндпоинтам
+    /// авторизации и учетных записей.
+    #[arg(long, value_name = "COUNT", default_value = "10", value_parser = clap::value_parser!(u64).range(1..))]
+    auth_concurrency_limit: u64,
+
+    /// Максимальное время обработки запроса к эндпоинтам авторизации и учетных записей (в секундах).
+    #[arg(long, value_name = "SECONDS", default_value = "10", value_parser = clap::value_parser!(u64).range(1..))]
+    auth_timeout: u64,
+
+    /// Максимальное число одновременно обрабатываемых запросов к публичным эндпоинтам чтения.
+    #[arg(long, value_name = "COUNT", default_value = "100", value_parser = clap::value_parser!(u64).range(1..))]
+    read_concurrency_limit: u64,
+
+    /// Максимальное время обработки запроса к публичным эндпоинтам чтения (в секундах).
+    #[arg(long, value_name = "SECONDS", default_value = "10", value_parser = clap::value_parser!(u64).range(1..))]
+    read_timeout: u64,
+
+    /// Максимальное число одновременно обрабатываемых запросов к эндпоинтам изменения
+    /// данных и администрирования.
+    #[arg(long, value_name = "COUNT", default_value = "20", value_parser = clap::value_parser!(u64).range(1..))]
+    write_concurrency_limit: u64,
+
+    /// Максимальное время обработки запроса к эндпоинтам изменения данных
+    /// и администрирования (в секундах).
+    #[arg(long, value_name = "SECONDS", default_value = "30", value_parser = clap::value_parser!(u64).range(1..))]
+    write_timeout: u64,
+
+    /// Каталог для хранения загруженных изображений.
+    #[arg(long, value_name = "DIR", default_value = "media")]
+    media_dir: PathBuf,
+
+    /// Каталог для хранения архивов с выгрузками данных пользователей.
+    #[arg(long, value_name = "DIR", default_value = "exports")]
+    export_dir: PathBuf,
+
+    /// Поисковый движок для индексации постов.
+    ///
+    /// Для Meilisearch ключ доступа берется из переменной окружения MEILISEARCH_API_KEY.
+    #[arg(long, value_enum, default_value = "postgres")]
+    search_backend: SearchBackend,
+
+    /// Адрес сервиса Meilisearch (например, `http://127.0.0.1:7700`).
+    #[arg(
+        long,
+        value_name = "URL",
+        required_if_eq("search_backend", "meilisearch")
+    )]
+    meilisearch_url: Option<String>,
+
+    /// Фильтр недопустимого содержимого постов (по умолчанию отключен).
+    ///
+    /// Для внешнего API ключ доступа берется из переменной окружения CONTENT_FILTER_API_KEY.
+    #[arg(long, value_enum)]
+    content_filter: Option<ContentFilterBackend>,
+
+    /// Файл со списком запрещенных слов (по одному на строку) для фильтра `word-list`.
+    #[arg(
+        long,
+        value_name = "FILE",
+        required_if_eq("content_filter", "word-list")
+    )]
+    content_filter_words: Option<PathBuf>,
+
+    /// Адрес внешнего API проверки содержимого для фильтра `external`.
+    #[arg(long, value_name = "URL", required_if_eq("content_filter", "external"))]
+    content_filter_url: Option<String>,
+
+    /// Действие с постом, не прошедшим фильтр: отклонить, отметить для модерации
+    /// или замаскировать недопустимые фрагменты.
+    #[arg(long, value_enum, default_value = "reject")]
+    content_filter_action: ContentFilterAction,
+
+    /// Емкость очереди доменных событий для каждого подписчика.
+    ///
+    /// Подписчик, отставший больше чем на это число событий, пропускает самые старые из них.
+    #[arg(long, value_name = "EVENTS", default_value = "1024", value_parser = clap::value_parser!(u64).range(1..))]
+    event_bus_capacity: u64,
+
+    /// Время жизни кэша похожих постов в секундах.
+    #[arg(long, value_name = "SECONDS", default_value = "300")]
+    related_posts_cache_ttl: u64,
+
+    /// Окно в секундах, в течение которого создание поста с тем же заголовком, что у
+    /// недавнего поста автора, отклоняется с кодом 409 (`0` - не проверять).
+    #[arg(long, value_name = "SECONDS", default_value_t = DEFAULT_DUPLICATE_POST_WINDOW_SECS)]
+    duplicate_post_window: u64,
+
+    /// Минимальное сходство заголовков по триграммам от 0 до 1, при котором пост считается
+    /// дубликатом; без него дубликатом считается только пост с совпадающим заголовком.
+    #[arg(long, value_name = "RATIO", value_parser = parse_similarity)]
+    duplicate_post_similarity: Option<f32>,
+
+    /// Время в секундах, в течение которого браузеры и CDN могут хранить пост, полученный
+    /// анонимным клиентом (заголовок `Cache-Control`).
+    #[arg(long, value_name = "SECONDS", default_value_t = DEFAULT_POST_CACHE_TTL_SECS)]
+    post_cache_ttl: u64,
+
+    /// Время в секундах, в течение которого браузеры и CDN могут хранить списки постов,
+    /// результаты поиска и подсказки, полученные анонимным клиентом.
+    #[arg(long, value_name = "SECONDS", default_value_t = DEFAULT_LIST_CACHE_TTL_SECS)]
+    list_cache_ttl: u64,
+
+    /// Максимальное количество элементов на странице списков (параметр `limit`).
+    ///
+    /// Запросы с большим значением отклоняются с кодом 400.
+    #[arg(long, value_name = "ITEMS", default_value_t = MAX_POSTS_PER_PAGE, value_parser = clap::value_parser!(i64).range(1..))]
+    max_page_size: i64,
+
+    /// Время жизни кэша статистики для администраторов в секундах.
+    #[arg(long, value_name = "SECONDS", default_value = "60")]
+    admin_stats_cache_ttl: u64,
+
+    /// Разрешить регистрацию только по приглашениям, которые выдают администраторы.
+    #[arg(long)]
+    invite_only: bool,
+
+    /// Запустить сервер в режиме обслуживания (только чтение).
+    ///
+    /// Режим можно выключить во время работы через флаг `maintenance_mode`.
+    #[arg(long)]
+    maintenance: bool,
+
+    /// Функции, отключенные по умолчанию (администратор может включить их во время работы).
+    ///
+    /// Доступные варианты: "registration_open", "media_uploads", "share_links", "data_exports".
+    #[arg(long, value_name = "FEATURE", value_enum)]
+    disable_feature: Vec<Feature>,
+
+    /// Файл со списком источников, которым разрешены CORS-запросы: по одному источнику
+    /// на строку, `*` - любой источник, суффикс `credentials` разрешает запросы с cookie.
+    ///
+    /// Файл перечитывается во время работы; без него используется переменная окружения
+    /// `CORS_ALLOWED_ORIGINS`.
+    #[arg(long, value_name = "FILE")]
+    cors_origins_file: Option<PathBuf>,
+
+    /// Интервал в секундах, с которым перечитывается файл `--cors-origins-file`.
+    #[arg(long, value_name = "SECONDS", default_value = "30", value_parser = clap::value_parser!(u64).range(1..))]
+    cors_reload_interval: u64,
+
+    /// Интервал в секундах, с которым перечитываются флаги функциональности из базы данных.
+    #[arg(long, value_name = "SECONDS", default_value = "30", value_parser = clap::value_parser!(u64).range(1..))]
+    feature_flags_refresh: u64,
+
+    /// Интервал в секундах между проходами очистки устаревших данных (истекшие запросы
+    /// на смену email, неиспользованные приглашения, файлы удаленных изображений).
+    #[arg(long, value_name = "SECONDS", default_value = "3600", value_parser = clap::value_parser!(u64).range(1..))]
+    cleanup_interval: u64,
+
+    /// Интервал в секундах между пересчетами популярности постов (для сортировки
+    /// `trending`); накопленные просмотры записываются в базу данных при пересчете.
+    #[arg(long, value_name = "SECONDS", default_value = "300", value_parser = clap::value_parser!(u64).range(1..))]
+    trending_interval: u64,
+
+    /// Публичный адрес сервера, используемый в ссылках из писем и в превью постов.
+    #[arg(long, value_name = "URL", default_value = "http://127.0.0.1:3000")]
+    public_url: String,
+
+    /// Заголовок запроса с кодом страны клиента, выставляемый обратным прокси
+    /// (например, `CF-IPCountry`). Используется для отметки входов из новых стран.
+    #[arg(long, value_name = "HEADER")]
+    country_header: Option<axum::http::HeaderName>,
+
+    /// Заголовок запроса с коротким именем блога. Если заголовок не передан,
+    /// блог определяется по доменному имени из `Host`, а при его отсутствии
+    /// запрос относится к основному блогу.
+    #[arg(long, value_name = "HEADER", default_value = "x-tenant")]
+    tenant_header: axum::http::HeaderName,
+
+    /// Оповещать пользователей по email о входе с нового устройства или из новой страны.
+    #[arg(long)]
+    notify_new_logins: bool,
+
+    /// Адрес отправителя писем.
+    #[arg(
+        long,
+        value_name = "ADDRESS",
+        default_value = "Blog <noreply@localhost>"
+    )]
+    mail_from: String,
+}
+
+/// Административные команды, выполняемые без запуска сервера.
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Применить миграции БД и завершить работу.
+    Migrate,
+
+    /// Проверить подключение к БД и отсутствие непримененных миграций.
+    DbCheck,
+
+    /// Назначить пользователя администратором, при необходимости создав его.
+    ///
+    /// Для создания нового пользователя нужно указать `--email`, а пароль
+    /// передать в переменной окружения BLOG_ADMIN_PASSWORD.
+    CreateAdmin {
+        /// Имя пользователя.
+        username: String,
+
+        /// Email-адрес нового пользователя.
+        #[arg(long)]
+        email: Option<String>,
+
+        /// Короткое имя блога, в котором назначается администратор.
+        #[arg(long, default_value = "default")]
+        tenant: String,
+    },
+
+    /// Сохранить данные всех таблиц в сжатую zstd резервную копию.
+    Backup {
+        /// Путь к файлу резервной копии.
+        #[arg(long)]
+        out: PathBuf,
+    },
+
+    /// Восстановить данные из резервной копии, предварительно применив миграции.
+    Restore {
+        /// Путь к файлу резервной копии.
+        #[arg(long)]
+        from: PathBuf,
+
+        /// Заменить данные, даже если БД не пуста.
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+/// Выполнить административную команду.
+async fn run_command(command: &Command, args: &Args) -> anyhow::Result<()> {
+    let pool = create_pool(Duration::from_millis(args.slow_query_threshold)).await?;
+
+    match command {
+        Command::Migrate => {
+            run_migrations(&pool).await?;
+
+            tracing::info!("Миграции применены");
+        }
+        Command::DbCheck => {
+            let pending = pending_migrations(&pool).await?;
+
+            if !pending.is_empty() {
+                anyhow::bail!("Не применены миграции: {}", pending.join(", "));
+            }
+
+            tracing::info!("Подключение к БД установлено, все миграции применены");
+        }
+        Command::CreateAdmin {
+            username,
+            email,
+            tenant,
+        } => {
+            create_admin(&pool, args, tenant, username, email.as_deref()).await?;
+        }
+        Command::Backup { out } => {
+            backup::backup(&pool, out).await?;
+
+            tracing::info!("Резервная копия сохранена в {}", out.display());
+        }
+        Command::Restore { from, force } => {
+            backup::restore(&pool, from, *force).await?;
+
+            tracing::info!("Данные восстановлены из {}", from.display());
+        }
+    }
+
+    Ok(())
+}
+
+/// Назначить пользователя блога администратором, создав его, если он не существует.
+async fn create_admin(
+    pool: &PgPool,
+    args: &Args,
+    tenant: &str,
+    username: &str,
+    email: Option<&str>,
+) -> anyhow::Result<()> {
+    let tenant = TenantRepository::new(pool.clone())
+        .get_tenant_by_slug(tenant)
+        .await?;
+    let admin_repository = AdminRepository::new(pool.clone());
+
+    if admin_repository.grant_admin(tenant.id, username).await? {
+        tracing::info!("Пользователь {username} назначен администратором");
+        return Ok(());
+    }
+
+    let email = email.ok_or_else(|| {
+        anyhow::anyhow!("Пользователь {username} не найден; для его создания укажите --email")
+    })?;
+    let password = std::env::var("BLOG_ADMIN_PASSWORD").map_err(|_| {
+        anyhow::anyhow!("Для создания пользователя задайте пароль в BLOG_ADMIN_PASSWORD")
+    })?;
+
+    let request = CreateUserRequest {
+        username: username.to_string(),
+        email: email.to_string(),
+        password,
+        invite_code: None,
+    };
+    request.validate()?;
+    create_password_policy(args)?.check(&request.password)?;
+
+    let user = User {
+        tenant_id: tenant.id,
+        ..request.try_into()?
+    };
+
+    UserRepository::new(pool.clone()).create_user(user).await?;
+    admin_repository.grant_admin(tenant.id, username).await?;
+
+    tracing::info!("Создан администратор {username}");
+
+    Ok(())
+}
+
+/// Создать сервис JWT-токенов в соответствии с аргументами запуска.
+fn create_jwt_service(args: &Args) -> anyhow::Result<JwtService> {
+    let settings = TokenSettings {
+        ttl: Duration::from_secs(args.jwt_ttl),
+        issuer: args.jwt_issuer.clone(),
+        audience: args.jwt_audience.clone(),
+        leeway: Duration::from_secs(args.jwt_leeway),
+    };
+
+    let (Some(private_key), Some(public_key)) = (&args.jwt_private_key, &args.jwt_public_key)
+    else {
+        return Ok(JwtService::new(&jwt::load_secret()?).with_token_settings(settings));
+    };
+
+    let jwt_service = JwtService::from_key_files(args.jwt_algorithm, private_key, public_key)?
+        .with_token_settings(settings);
+
+    match &args.jwt_previous_public_key {
+        Some(previous_key) => jwt_service.with_previous_key(
+            args.jwt_algorithm,
+            previous_key,
+            Duration::from_secs(args.jwt_previous_key_grace),
+        ),
+        None => Ok(jwt_service),
+    }
+}
+
+/// Создать поисковый индекс постов в соответствии с аргументами запуска.
+async fn create_search_index(args: &Args, pool: &ReadPool) -> anyhow::Result<Arc<dyn SearchIndex>> {
+    let index: Arc<dyn SearchIndex> = match (args.search_backend, &args.meilisearch_url) {
+        (SearchBackend::Meilisearch, Some(url)) => Arc::new(
+            MeilisearchIndex::connect(url, std::env::var("MEILISEARCH_API_KEY").ok()).await?,
+        ),
+        _ => Arc::new(PostgresSearchIndex::new(pool.clone())),
+    };
+
+    Ok(index)
+}
+
+/// Создать фильтр содержимого постов в соответствии с аргументами запуска.
+fn create_content_filter(args: &Args) -> anyhow::Result<Option<Arc<dyn ContentFilter>>> {
+    let filter: Arc<dyn ContentFilter> = match (
+        args.content_filter,
+        &args.content_filter_words,
+        &args.content_filter_url,
+    ) {
+        (Some(ContentFilterBackend::WordList), Some(path), _) => {
+            Arc::new(WordListFilter::from_file(path)?)
+        }
+        (Some(ContentFilterBackend::External), _, Some(url)) => Arc::new(
+            ExternalContentFilter::new(url, std::env::var("CONTENT_FILTER_API_KEY").ok()),
+        ),
+        _ => return Ok(None),
+    };
+
+    Ok(Some(filter))
+}
+
+/// Создать проверку постов на дубликаты (`None`, если окно проверки равно нулю).
+fn create_duplicate_check(args: &Args) -> Option<DuplicatePostCheck> {
+    (args.duplicate_post_window > 0).then(|| DuplicatePostCheck {
+        window: Duration::from_secs(args.duplicate_post_window),
+        similarity: args.duplicate_post_similarity,
+    })
+}
+
+/// Разобрать порог сходства заголовков (число от 0 до 1).
+fn parse_similarity(value: &str) -> Result<f32, String> {
+    value
+        .parse::<f32>()
+        .ok()
+        .filter(|ratio| (0.0..=1.0).contains(ratio))
+        .ok_or_else(|| format!("Некорректный порог сходства: {value}"))
 }
 
-/// Создания CORS-уровня middleware.
-fn create_cors_layer() -> CorsLayer {
-    use axum::http::Method;
-
-    let cors = CorsLayer::new()
-        .allow_methods([
-            Method::GET,
-            Method::POST,
-            Method::PUT,
-            Method::DELETE,
-            Method::OPTIONS,
-        ])
-        .allow_headers(Any)
-        .max_age(Duration::from_secs(3600));
-
-    #[cfg(debug_assertions)]
-    let cors = cors.allow_origin(Any);
-
-    #[cfg(not(debug_assertions))]
-    let cors = {
-        let whitelist = std::env::var("CORS_ALLOWED_ORIGINS")
-            .unwrap_or_default()
-            .split(',')
-            .filter_map(|s| s.trim().parse::<axum::http::HeaderValue>().ok())
-            .collect::<Vec<_>>();
-
-        cors.allow_origin(whitelist)
+/// Создать отправителя писем.
+///
+/// Адрес SMTP-сервера берется из переменной окружения SMTP_URL; если он не задан,
+/// письма только записываются в лог.
+fn create_mailer(args: &Args) -> anyhow::Result<Arc<dyn Mailer>> {
+    let mailer: Arc<dyn Mailer> = match std::env::var("SMTP_URL") {
+        Ok(url) => Arc::new(SmtpMailer::new(&url, &args.mail_from)?),
+        Err(_) => {
+            tracing::warn!("SMTP_URL не задан, письма будут записываться в лог");
+            Arc::new(LogMailer)
+        }
     };
 
-    cors
+    Ok(mailer)
+}
+
+/// Сформировать политику паролей из аргументов запуска.
+fn create_password_policy(args: &Args) -> anyhow::Result<PasswordPolicy> {
+    let policy = PasswordPolicy {
+        min_length: args.password_min_length,
+        require_lowercase: args.password_require_lowercase,
+        require_uppercase: args.password_require_uppercase,
+        require_digit: args.password_require_digit,
+        require_special: args.password_require_special,
+        ..Default::default()
+    };
+
+    let policy = match &args.password_denylist {
+        Some(path) => {
+            let denylist = std::fs::read_to_string(path)?;
+            policy.with_denylist(denylist.lines().map(str::to_string))
+        }
+        None => policy,
+    };
+
+    Ok(policy)
+}
+
+/// Создать политику CORS.
+///
+/// Список источников берется из файла `--cors-origins-file`, а без него - из переменной
+/// окружения `CORS_ALLOWED_ORIGINS` (через запятую, учетные данные разрешаются всем
+/// источникам при авторизации через cookie). Если не задано ни то, ни другое, в
+/// debug-сборке разрешается любой источник без учетных данных, а в release-сборке - никакой.
+fn create_cors_policy(args: &Args) -> anyhow::Result<Arc<CorsPolicy>> {
+    let credentials = args.session_cookies;
+
+    let policy = match (
+        &args.cors_origins_file,
+        std::env::var("CORS_ALLOWED_ORIGINS"),
+    ) {
+        (Some(path), _) => CorsPolicy::from_file(path.clone())?,
+        (None, Ok(list)) => CorsPolicy::from_list(&list, credentials)?,
+        (None, Err(_)) if cfg!(debug_assertions) => {
+            if credentials {
+                tracing::warn!(
+                    "Запросы с cookie из других источников разрешаются только источникам \
+                     из CORS_ALLOWED_ORIGINS или --cors-origins-file"
+                );
+            }
+
+            CorsPolicy::allow_any()
+        }
+        (None, Err(_)) => CorsPolicy::from_list("", credentials)?,
+    };
+
+    let policy = Arc::new(policy.with_tenant_header(args.tenant_header.clone()));
+    policy.spawn_reload(Duration::from_secs(args.cors_reload_interval));
+
+    Ok(policy)
+}
+
+/// Создать спан HTTP-запроса.
+///
+/// Идентификатор запроса берется из заголовка `X-Request-Id` (передается клиентом
+/// или генерируется сервером), контекст трассировки клиента - из заголовка
+/// `traceparent`, а идентификатор пользователя записывается после авторизации.
+fn request_span(request: &axum::http::Request<axum::body::Body>) -> tracing::Span {
+    let request_id = request
+        .extensions()
+        .get::<RequestId>()
+        .and_then(|id| id.header_value().to_str().ok())
+        .unwrap_or_default();
+    let trace = TraceParent::from_headers(request.headers());
+
+    tracing::info_span!(
+        "request",
+        method = %request.method(),
+        uri = %request.uri(),
+        request_id,
+        trace_id = trace.trace_id,
+        parent_span_id = trace.parent_span_id,
+        user_id = tracing::field::Empty,
+    )
+}
+
+/// Создание спана для gRPC-вызова с контекстом трассировки клиента из метаданных `traceparent`.
+fn grpc_request_span(request: &axum::http::Request<()>) -> tracing::Span {
+    let trace = TraceParent::from_headers(request.headers());
+
+    tracing::info_span!(
+        "grpc_request",
+        method = request.uri().path(),
+        trace_id = trace.trace_id,
+        parent_span_id = trace.parent_span_id,
+    )
 }
 
 /// Создание обработчика HTTP-запросов.
-async fn http_serve(app: AppState, addr: SocketAddr) -> anyhow::Result<()> {
+#[allow(clippy::too_many_arguments)]
+async fn http_serve(
+    app: AppState,
+    body_limits: BodyLimits,
+    route_limits: RouteLimits,
+    maintenance: MaintenanceLayer,
+    cors: CorsLayer,
+    security: SecurityHeaders,
+    addr: ListenAddr,
+    frontend_dir: Option<PathBuf>,
+) -> anyhow::Result<()> {
     tracing::info!("Listening HTTP connections on {}", addr);
 
     let governor_conf = GovernorConfigBuilder::default()
         .per_second(2)
         .burst_size(5)
+        .use_headers()
         .finish()
         .ok_or(anyhow::anyhow!("Failed to prepare rate limiter"))?;
 
     let middleware = ServiceBuilder::new()
-        .layer(TraceLayer::new_for_http())
+        .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
+        .layer(PropagateRequestIdLayer::x_request_id())
+        .layer(TraceLayer::new_for_http().make_span_with(request_span))
+        .layer(axum::middleware::from_fn(unix_socket_peer))
         .layer(GovernorLayer::new(governor_conf))
-        .concurrency_limit(20)
-        .layer(create_cors_layer())
-        .layer(TimeoutLayer::with_status_code(
-            axum::http::StatusCode::REQUEST_TIMEOUT,
-            Duration::from_secs(30),
-        ));
+        .layer(cors)
+        .layer(maintenance);
+
+    let router = create_router(
+        app,
+        body_limits,
+        route_limits,
+        middleware,
+        frontend_dir.as_deref(),
+    )
+    .layer(axum::middleware::from_fn_with_state(
+        Arc::new(security),
+        security_headers,
+    ));
 
-    let router = create_router(app, middleware);
+    match addr {
+        ListenAddr::Tcp(addr) => {
+            let listener = TcpListener::bind(addr).await?;
 
-    let listener = TcpListener::bind(addr).await?;
+            axum::serve(
+                listener,
+                router.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+        }
+        ListenAddr::Unix { path, mode } => {
+            let listener = listener::bind_unix_socket(&path, mode)?;
 
-    axum::serve(
-        listener,
-        router.into_make_service_with_connect_info::<SocketAddr>(),
-    )
-    .await
+            axum::serve(listener, router.into_make_service()).await
+        }
+    }
     .map_err(Into::into)
 }
 
-/// Создание обработчика gRPC-запросов.
-async fn grpc_serve(app: AppState, addr: SocketAddr) -> anyhow::Result<()> {
+/// Создание обработчика gRPC-запросов (в том числе gRPC-web).
+async fn grpc_serve(
+    app: AppState,
+    body_limits: BodyLimits,
+    maintenance: MaintenanceLayer,
+    cors: CorsLayer,
+    addr: ListenAddr,
+) -> anyhow::Result<()> {
     tracing::info!("Listening GRPC connections on {}", addr);
 
-    let grpc_service = BlogServiceServer::new(BlogGrpcService::new(app));
+    let grpc_service = BlogServiceServer::new(BlogGrpcService::new(app, body_limits.media));
+
+    // gRPC-web запросы из браузера приходят по HTTP/1.1 и преобразуются в обычные
+    // gRPC-вызовы до проверки режима обслуживания.
+    let router = Server::builder()
+        .accept_http1(true)
+        .trace_fn(grpc_request_span)
+        .layer(cors)
+        .layer(GrpcWebLayer::new())
+        .layer(MapRequestLayer::new(capture_request_host))
+        .layer(maintenance)
+        .add_service(grpc_service);
+
+    match addr {
+        ListenAddr::Tcp(addr) => router.serve(addr).await,
+        ListenAddr::Unix { path, mode } => {
+            let listener = listener::bind_unix_socket(&path, mode)?;
 
-    Server::builder()
-        .add_service(grpc_service)
-        .serve(addr)
-        .await
-        .map_err(Into::into)
+            router
+                .serve_with_incoming(UnixListenerStream::new(listener))
+                .await
+        }
+    }
+    .map_err(Into::into)
 }
 
 #[tokio::main]
@@ -142,37 +885,255 @@ async fn main() -> anyhow::Result<()> {
 
     dotenvy::dotenv()?;
 
-    init_logging(&args.log_level);
+    let _log_guard = init_logging(&LogSettings {
+        level: args.log_level.clone(),
+        format: args.log_format,
+        dir: args.log_dir.clone(),
+        rotation: args.log_rotation,
+        max_files: usize::try_from(args.log_max_files)?,
+    })?;
+
+    if let Some(command) = &args.command {
+        return run_command(command, &args).await;
+    }
+
+    if args.migrate_only {
+        return run_command(&Command::Migrate, &args).await;
+    }
 
     tracing::info!("Starting server..");
 
-    let pool = create_pool().await?;
-    run_migrations(&pool).await?;
+    let pool = create_pool(Duration::from_millis(args.slow_query_threshold)).await?;
 
-    let jwt_secret = jwt::load_secret()?;
+    if args.skip_migrations {
+        wait_for_migrations(&pool, Duration::from_secs(args.migrations_wait_timeout)).await?;
+    } else {
+        run_migrations(&pool).await?;
+    }
+
+    let read_pool = ReadPool::new(
+        pool.clone(),
+        create_replica_pool(Duration::from_millis(args.slow_query_threshold))?,
+    );
+    read_pool.spawn_health_check(Duration::from_secs(args.replica_check_interval));
 
-    let jwt_service = Arc::new(JwtService::new(&jwt_secret));
+    let jwt_service = Arc::new(create_jwt_service(&args)?);
 
     let user_repository = Arc::new(UserRepository::new(pool.clone()));
-    let post_repository = Arc::new(PostRepository::new(pool.clone()));
+    let post_repository =
+        Arc::new(PostRepository::new(pool.clone()).with_read_pool(read_pool.clone()));
+    let api_key_repository = Arc::new(ApiKeyRepository::new(pool.clone()));
+    let media_repository = Arc::new(MediaRepository::new(pool.clone()));
+    let export_repository = Arc::new(ExportRepository::new(pool.clone()));
+    let login_history_repository = Arc::new(LoginHistoryRepository::new(pool.clone()));
+    let admin_repository = Arc::new(AdminRepository::new(pool.clone()));
+    let invite_repository = Arc::new(InviteRepository::new(pool.clone()));
+    let tenant_repository = Arc::new(TenantRepository::new(pool.clone()));
+    let settings_repository = Arc::new(SettingsRepository::new(pool.clone()));
+
+    let features = FeatureFlags::new(pool.clone()).with_disabled(&args.disable_feature);
+    let features = Arc::new(if args.maintenance {
+        features.with_enabled(&[Feature::MaintenanceMode])
+    } else {
+        features
+    });
+    features.reload().await?;
+    features.spawn_reload(Duration::from_secs(args.feature_flags_refresh));
+
+    let events = Arc::new(EventBus::new(usize::try_from(args.event_bus_capacity)?));
+    let search_index = create_search_index(&args, &read_pool).await?;
+    search::spawn_indexer(search_index.clone(), events.subscribe());
+    events::spawn_logger(events.subscribe());
+
+    let mailer = create_mailer(&args)?;
+    let media_storage = Arc::new(MediaStorage::new(&args.media_dir));
 
-    let auth_service = Arc::new(AuthService::new(
+    let cleanup_service = Arc::new(CleanupService::new(
+        Arc::new(CleanupRepository::new(pool.clone())),
+        media_storage.clone(),
+    ));
+    cleanup_service.spawn(Duration::from_secs(args.cleanup_interval));
+
+    let login_history_service = Arc::new(
+        LoginHistoryService::new(
+            login_history_repository.clone(),
+            settings_repository.clone(),
+            mailer.clone(),
+        )
+        .with_notifications(args.notify_new_logins),
+    );
+    let auth_service = Arc::new(
+        AuthService::new(
+            jwt_service.clone(),
+            user_repository.clone(),
+            create_password_policy(&args)?,
+            login_history_service.clone(),
+            features.clone(),
+            invite_repository.clone(),
+            events.clone(),
+        )
+        .with_invite_only(args.invite_only),
+    );
+    let blog_service = BlogService::new(
+        post_repository.clone(),
         jwt_service.clone(),
+        search_index,
+        features.clone(),
+        events.clone(),
+    )
+    .with_related_posts_cache_ttl(Duration::from_secs(args.related_posts_cache_ttl));
+    let blog_service = match create_duplicate_check(&args) {
+        Some(check) => blog_service.with_duplicate_check(check),
+        None => blog_service,
+    };
+    let blog_service = Arc::new(match create_content_filter(&args)? {
+        Some(filter) => blog_service.with_content_filter(filter, args.content_filter_action),
+        None => blog_service,
+    });
+    blog_service.spawn_trending_updates(Duration::from_secs(args.trending_interval));
+    let api_key_service = Arc::new(ApiKeyService::new(api_key_repository.clone()));
+    let user_service = Arc::new(UserService::new(
         user_repository.clone(),
+        settings_repository.clone(),
+        mailer.clone(),
+        &args.public_url,
+    ));
+    let media_service = Arc::new(MediaService::new(
+        media_repository.clone(),
+        media_storage.clone(),
+        features.clone(),
     ));
-    let blog_service = Arc::new(BlogService::new(post_repository.clone()));
+    let export_service = Arc::new(ExportService::new(
+        export_repository.clone(),
+        ExportSources {
+            user_repository: user_repository.clone(),
+            post_repository: post_repository.clone(),
+            media_repository: media_repository.clone(),
+            api_key_repository: api_key_repository.clone(),
+            media_storage: media_storage.clone(),
+        },
+        mailer.clone(),
+        &args.export_dir,
+        &args.public_url,
+        features.clone(),
+    ));
+
+    let admin_service = Arc::new(
+        AdminService::new(
+            admin_repository.clone(),
+            features.clone(),
+            invite_repository.clone(),
+        )
+        .with_stats_cache_ttl(Duration::from_secs(args.admin_stats_cache_ttl)),
+    );
+
+    let tenant_service = Arc::new(TenantService::new(tenant_repository.clone()));
 
     let app = AppState::new(
         auth_service.clone(),
         blog_service.clone(),
         jwt_service.clone(),
+        api_key_service.clone(),
+        media_service.clone(),
+        user_service.clone(),
+        export_service.clone(),
+        admin_service.clone(),
+        tenant_service.clone(),
+    )
+    .with_tenant_header(args.tenant_header.clone())
+    .with_max_page_size(args.max_page_size)
+    .with_public_url(&args.public_url)
+    .with_database_health(read_pool.health())
+    .with_cache_ttls(CacheTtls {
+        post: Duration::from_secs(args.post_cache_ttl),
+        list: Duration::from_secs(args.list_cache_ttl),
+    });
+
+    let app = if args.session_cookies {
+        app.with_session_cookies(SessionCookies::new(
+            args.session_cookies_secure,
+            jwt_service.token_ttl(),
+        ))
+    } else {
+        app
+    };
+
+    let app = match &args.country_header {
+        Some(country_header) => app.with_country_header(country_header.clone()),
+        None => app,
+    };
+
+    let http_addr = ListenAddr::new(
+        args.http_socket.as_deref(),
+        format!("{}:{}", args.host, args.http_port).parse()?,
+        args.socket_mode,
+    );
+    let grpc_addr = ListenAddr::new(
+        args.grpc_socket.as_deref(),
+        format!("{}:{}", args.host, args.grpc_port).parse()?,
+        args.socket_mode,
     );
 
-    let http_addr = format!("{}:{}", args.host, args.http_port).parse()?;
-    let grpc_addr = format!("{}:{}", args.host, args.grpc_port).parse()?;
+    let body_limits = BodyLimits {
+        auth: args.auth_body_limit,
+        posts: args.posts_body_limit,
+        media: args.media_body_limit,
+    };
 
-    let http_serve = http_serve(app.clone(), http_addr);
-    let grpc_serve = grpc_serve(app.clone(), grpc_addr);
+    let route_limits = RouteLimits {
+        auth: RouteLimit::new(
+            usize::try_from(args.auth_concurrency_limit)?,
+            Duration::from_secs(args.auth_timeout),
+        ),
+        read: RouteLimit::new(
+            usize::try_from(args.read_concurrency_limit)?,
+            Duration::from_secs(args.read_timeout),
+        ),
+        write: RouteLimit::new(
+            usize::try_from(args.write_concurrency_limit)?,
+            Duration::from_secs(args.write_timeout),
+        ),
+    };
+
+    if let Some(dir) = &args.frontend_dir
+        && !dir.join(INDEX_FILE).is_file()
+    {
+        anyhow::bail!(
+            "В каталоге фронтенда {} отсутствует {INDEX_FILE}",
+            dir.display()
+        );
+    }
+
+    let maintenance = MaintenanceLayer::new(features.clone());
+    let cors = create_cors_policy(&args)?;
+    let security = SecurityHeaders::new(
+        args.content_security_policy
+            .as_deref()
+            .unwrap_or(match args.frontend_dir {
+                Some(_) => FRONTEND_CONTENT_SECURITY_POLICY,
+                None => API_CONTENT_SECURITY_POLICY,
+            }),
+        &args.frame_options,
+        &args.referrer_policy,
+    )?;
+
+    let http_serve = http_serve(
+        app.clone(),
+        body_limits,
+        route_limits,
+        maintenance.clone(),
+        cors.layer(app.session_cookies.is_some()),
+        security,
+        http_addr,
+        args.frontend_dir.clone(),
+    );
+    let grpc_serve = grpc_serve(
+        app.clone(),
+        body_limits,
+        maintenance,
+        cors.grpc_web_layer(),
+        grpc_addr,
+    );
 
     tokio::select! {
         result = http_serve => {
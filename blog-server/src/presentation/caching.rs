@@ -0,0 +1,132 @@
+//! Заголовки HTTP-кэширования для публичных эндпоинтов чтения.
+//!
+//! Ответы анонимным клиентам разрешается хранить браузерам и CDN в течение заданного
+//! времени. Авторизованные пользователи видят также свои приватные посты, поэтому ответы
+//! им помечаются как приватные, а заголовок `Vary` не дает общему кэшу отдать ответ
+//! анонимному клиенту авторизованному пользователю (и наоборот) или другому блогу.
+
+use crate::presentation::AppState;
+use crate::presentation::middleware::AuthUser;
+use axum::extract::{Request, State};
+use axum::http::header::{
+    AUTHORIZATION, CACHE_CONTROL, COOKIE, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, VARY,
+};
+use axum::http::{HeaderMap, HeaderName, HeaderValue, StatusCode};
+use axum::middleware::Next;
+use axum::response::Response;
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+/// Время кэширования отдельного поста по умолчанию (в секундах).
+pub(crate) const DEFAULT_POST_CACHE_TTL_SECS: u64 = 60;
+
+/// Время кэширования списков постов по умолчанию (в секундах).
+pub(crate) const DEFAULT_LIST_CACHE_TTL_SECS: u64 = 30;
+
+/// Время кэширования ответов публичных эндпоинтов.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct CacheTtls {
+    /// Отдельный пост и похожие на него посты.
+    pub post: Duration,
+
+    /// Списки постов, результаты поиска и подсказки.
+    pub list: Duration,
+}
+
+impl Default for CacheTtls {
+    fn default() -> Self {
+        Self {
+            post: Duration::from_secs(DEFAULT_POST_CACHE_TTL_SECS),
+            list: Duration::from_secs(DEFAULT_LIST_CACHE_TTL_SECS),
+        }
+    }
+}
+
+/// Правила кэширования ответов группы эндпоинтов.
+#[derive(Debug, Clone)]
+pub(crate) struct CachePolicy {
+    /// Время, в течение которого ответ анонимному клиенту считается свежим.
+    max_age: Duration,
+
+    /// Заголовки запроса, от которых зависит ответ.
+    vary: Vec<HeaderName>,
+}
+
+impl CachePolicy {
+    /// Создать правила кэширования с указанным временем хранения ответа.
+    pub(crate) fn new(state: &AppState, max_age: Duration) -> Self {
+        let mut vary = vec![AUTHORIZATION, state.tenant_header.clone()];
+
+        if state.session_cookies.is_some() {
+            vary.push(COOKIE);
+        }
+
+        Self { max_age, vary }
+    }
+}
+
+/// Middleware функция для добавления заголовков `Cache-Control` и `Vary` к успешным ответам.
+///
+/// Должна выполняться после определения пользователя (например, [`optional_auth`]).
+///
+/// [`optional_auth`]: crate::presentation::middleware::optional_auth
+pub(crate) async fn cache_headers(
+    State(policy): State<CachePolicy>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let authenticated = request.extensions().get::<AuthUser>().is_some();
+
+    let mut response = next.run(request).await;
+
+    if !matches!(response.status(), StatusCode::OK | StatusCode::NOT_MODIFIED) {
+        return response;
+    }
+
+    let cache_control = if authenticated {
+        Ok(HeaderValue::from_static("private, no-cache"))
+    } else {
+        HeaderValue::from_str(&format!("public, max-age={}", policy.max_age.as_secs()))
+    };
+
+    let headers = response.headers_mut();
+
+    if let Ok(cache_control) = cache_control {
+        headers.insert(CACHE_CONTROL, cache_control);
+    }
+
+    for name in policy.vary {
+        headers.append(VARY, HeaderValue::from(name));
+    }
+
+    response
+}
+
+/// Сформировать заголовок `Last-Modified` по времени последнего изменения.
+pub(crate) fn last_modified(modified_at: DateTime<Utc>) -> (HeaderName, String) {
+    (
+        LAST_MODIFIED,
+        modified_at.format("%a, %d %b %Y %H:%M:%S GMT").to_string(),
+    )
+}
+
+/// Проверить, не изменился ли ресурс с момента, известного клиенту.
+///
+/// Заголовок `If-None-Match` сравнивается с текущим `ETag` и имеет приоритет;
+/// `If-Modified-Since` учитывается, только если `If-None-Match` не передан.
+pub(crate) fn is_not_modified(headers: &HeaderMap, etag: &str, modified_at: DateTime<Utc>) -> bool {
+    if let Some(if_none_match) = headers.get(IF_NONE_MATCH) {
+        return if_none_match.to_str().is_ok_and(|value| {
+            value
+                .split(',')
+                .map(|tag| tag.trim().trim_start_matches("W/"))
+                .any(|tag| tag == "*" || tag == etag)
+        });
+    }
+
+    headers
+        .get(IF_MODIFIED_SINCE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| DateTime::parse_from_rfc2822(value).ok())
+        .is_some_and(|since| modified_at.timestamp() <= since.timestamp())
+}
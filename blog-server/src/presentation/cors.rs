@@ -0,0 +1,352 @@
+//! Политика CORS со списком разрешенных источников, обновляемым во время работы.
+//!
+//! Список задается файлом (по одному источнику на строку) и перечитывается в фоне,
+//! поэтому добавить или убрать источник можно без перезапуска сервера. Для каждого
+//! источника отдельно указывается, разрешены ли запросы с учетными данными (cookie);
+//! учетные данные разрешаются только явно перечисленным источникам.
+
+use crate::infrastructure::logging::TRACEPARENT;
+use crate::presentation::{DEFAULT_TENANT_HEADER, session};
+use axum::http::{HeaderName, HeaderValue, Method, header};
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tower_http::cors::{AllowCredentials, AllowOrigin, Any, CorsLayer};
+
+/// Суффикс строки списка, разрешающий запросы с учетными данными.
+const CREDENTIALS_SUFFIX: &str = "credentials";
+
+/// Заголовок с идентификатором запроса.
+const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Разрешенный источник запросов.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct AllowedOrigin {
+    /// Источник (`https://app.example.com`).
+    origin: HeaderValue,
+
+    /// Разрешены ли запросы с учетными данными.
+    credentials: bool,
+}
+
+/// Список разрешенных источников.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct CorsRules {
+    /// Разрешен любой источник (без учетных данных).
+    any: bool,
+
+    /// Явно перечисленные источники.
+    origins: Vec<AllowedOrigin>,
+}
+
+impl CorsRules {
+    /// Разобрать список источников.
+    ///
+    /// Каждая строка содержит источник или `*` (любой источник) и необязательный суффикс
+    /// `credentials`, недопустимый для `*`. Пустые строки и строки, начинающиеся с `#`,
+    /// пропускаются.
+    fn parse(text: &str) -> anyhow::Result<Self> {
+        let mut rules = Self::default();
+
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (origin, credentials) = match line.split_once(char::is_whitespace) {
+                Some((origin, suffix)) if suffix.trim() == CREDENTIALS_SUFFIX => (origin, true),
+                Some(_) => anyhow::bail!("Некорректная строка списка CORS: {line}"),
+                None => (line, false),
+            };
+
+            if origin == "*" {
+                if credentials {
+                    anyhow::bail!("Учетные данные нельзя разрешить любому источнику: {line}");
+                }
+
+                rules.any = true;
+                continue;
+            }
+
+            rules.origins.push(AllowedOrigin {
+                origin: origin
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!("Некорректный источник CORS: {origin}"))?,
+                credentials,
+            });
+        }
+
+        Ok(rules)
+    }
+
+    /// Разрешены ли учетные данные для источника (`None` - источник не разрешен).
+    fn credentials(&self, origin: &HeaderValue) -> Option<bool> {
+        self.origins
+            .iter()
+            .find(|allowed| allowed.origin == origin)
+            .map(|allowed| allowed.credentials)
+            .or(self.any.then_some(false))
+    }
+}
+
+/// Политика CORS, общая для HTTP и gRPC-web.
+#[derive(Debug)]
+pub(crate) struct CorsPolicy {
+    /// Текущий список разрешенных источников.
+    rules: RwLock<CorsRules>,
+
+    /// Файл со списком источников (`None` - список задан при запуске и не меняется).
+    path: Option<PathBuf>,
+
+    /// Заголовок с коротким именем блога.
+    tenant_header: HeaderName,
+}
+
+impl CorsPolicy {
+    /// Политика с заданным списком источников.
+    fn new(rules: CorsRules, path: Option<PathBuf>) -> Self {
+        Self {
+            rules: RwLock::new(rules),
+            path,
+            tenant_header: HeaderName::from_static(DEFAULT_TENANT_HEADER),
+        }
+    }
+
+    /// Политика с источниками через запятую; учетные данные разрешены всем перечисленным
+    /// источникам или никому.
+    pub(crate) fn from_list(list: &str, credentials: bool) -> anyhow::Result<Self> {
+        let mut rules = CorsRules::parse(&list.replace(',', "\n"))?;
+
+        for origin in &mut rules.origins {
+            origin.credentials = credentials;
+        }
+
+        Ok(Self::new(rules, None))
+    }
+
+    /// Политика, разрешающая любой источник без учетных данных (для разработки).
+    pub(crate) fn allow_any() -> Self {
+        Self::new(
+            CorsRules {
+                any: true,
+                origins: vec![],
+            },
+            None,
+        )
+    }
+
+    /// Политика со списком источников из файла.
+    pub(crate) fn from_file(path: PathBuf) -> anyhow::Result<Self> {
+        let rules = CorsRules::parse(&std::fs::read_to_string(&path)?)?;
+
+        Ok(Self::new(rules, Some(path)))
+    }
+
+    /// Разрешить передачу короткого имени блога в указанном заголовке.
+    pub(crate) fn with_tenant_header(mut self, tenant_header: HeaderName) -> Self {
+        self.tenant_header = tenant_header;
+        self
+    }
+
+    /// Перечитать список источников из файла.
+    ///
+    /// При ошибке чтения или разбора остается прежний список.
+    pub(crate) async fn reload(&self) -> anyhow::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        let rules = CorsRules::parse(&tokio::fs::read_to_string(path).await?)?;
+        let mut current = self.rules.write().unwrap_or_else(|e| e.into_inner());
+
+        if *current != rules {
+            tracing::info!(
+                "Список источников CORS обновлен: {} источников",
+                rules.origins.len()
+            );
+            *current = rules;
+        }
+
+        Ok(())
+    }
+
+    /// Запустить фоновое обновление списка источников с указанным интервалом.
+    pub(crate) fn spawn_reload(self: &Arc<Self>, interval: Duration) {
+        if self.path.is_none() {
+            return;
+        }
+
+        let policy = self.clone();
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(interval);
+            interval.tick().await;
+
+            loop {
+                interval.tick().await;
+
+                if let Err(e) = policy.reload().await {
+                    tracing::warn!("Не удалось обновить список источников CORS: {e}");
+                }
+            }
+        });
+    }
+
+    /// Разрешены ли учетные данные для источника (`None` - источник не разрешен).
+    fn credentials(&self, origin: &HeaderValue) -> Option<bool> {
+        self.rules
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .credentials(origin)
+    }
+
+    /// Разрешен ли источник; отклоненные источники записываются в лог.
+    fn allows_origin(&self, origin: &HeaderValue) -> bool {
+        let allowed = self.credentials(origin).is_some();
+
+        if !allowed {
+            tracing::debug!(origin = ?origin, "Источник запроса не разрешен политикой CORS");
+        }
+
+        allowed
+    }
+
+    /// Создать CORS-уровень middleware для HTTP API.
+    ///
+    /// При авторизации через cookie запросы с учетными данными несовместимы с wildcard-значениями,
+    /// поэтому перечисляются все заголовки, которые принимает API, а учетные данные разрешаются
+    /// только источникам, отмеченным в списке.
+    pub(crate) fn layer(self: &Arc<Self>, session_cookies: bool) -> CorsLayer {
+        let cors = CorsLayer::new()
+            .allow_methods([
+                Method::GET,
+                Method::POST,
+                Method::PUT,
+                Method::PATCH,
+                Method::DELETE,
+                Method::OPTIONS,
+            ])
+            .max_age(Duration::from_secs(3600));
+
+        let cors = if session_cookies {
+            let policy = self.clone();
+
+            cors.allow_credentials(AllowCredentials::predicate(move |origin, _| {
+                policy.credentials(origin).unwrap_or(false)
+            }))
+            .allow_headers([
+                header::AUTHORIZATION,
+                header::CONTENT_TYPE,
+                header::IF_MATCH,
+                header::IF_NONE_MATCH,
+                header::IF_MODIFIED_SINCE,
+                HeaderName::from_static(session::CSRF_HEADER),
+                HeaderName::from_static(REQUEST_ID_HEADER),
+                HeaderName::from_static(TRACEPARENT),
+                self.tenant_header.clone(),
+            ])
+        } else {
+            cors.allow_headers(Any)
+        };
+
+        let policy = self.clone();
+
+        cors.allow_origin(AllowOrigin::predicate(move |origin, _| {
+            policy.allows_origin(origin)
+        }))
+    }
+
+    /// Создать CORS-уровень middleware для gRPC-web.
+    ///
+    /// Браузерный клиент передает токен в метаданных запроса, а статус вызова читает
+    /// из заголовков ответа, поэтому они должны быть доступны скриптам.
+    pub(crate) fn grpc_web_layer(self: &Arc<Self>) -> CorsLayer {
+        self.layer(false).expose_headers([
+            HeaderName::from_static("grpc-status"),
+            HeaderName::from_static("grpc-message"),
+            HeaderName::from_static("grpc-status-details-bin"),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn origin(value: &'static str) -> HeaderValue {
+        HeaderValue::from_static(value)
+    }
+
+    #[test]
+    fn parses_wildcard_without_credentials() {
+        let rules = CorsRules::parse("*").unwrap();
+
+        assert!(rules.any);
+        assert!(rules.origins.is_empty());
+        assert_eq!(
+            rules.credentials(&origin("https://any.example.com")),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn rejects_wildcard_with_credentials() {
+        assert!(CorsRules::parse("* credentials").is_err());
+    }
+
+    #[test]
+    fn parses_exact_origins() {
+        let rules =
+            CorsRules::parse("https://app.example.com credentials\nhttp://localhost:8080").unwrap();
+
+        assert!(!rules.any);
+        assert_eq!(
+            rules.credentials(&origin("https://app.example.com")),
+            Some(true)
+        );
+        assert_eq!(
+            rules.credentials(&origin("http://localhost:8080")),
+            Some(false)
+        );
+        assert_eq!(rules.credentials(&origin("https://evil.example.com")), None);
+    }
+
+    #[test]
+    fn exact_origin_overrides_wildcard() {
+        let rules = CorsRules::parse("*\nhttps://app.example.com credentials").unwrap();
+
+        assert_eq!(
+            rules.credentials(&origin("https://app.example.com")),
+            Some(true)
+        );
+        assert_eq!(
+            rules.credentials(&origin("https://other.example.com")),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn skips_comments_and_blank_lines() {
+        let rules =
+            CorsRules::parse("# разработка\n\n   \n  https://app.example.com  \n# конец").unwrap();
+
+        assert_eq!(
+            rules.origins,
+            [AllowedOrigin {
+                origin: origin("https://app.example.com"),
+                credentials: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_suffix() {
+        assert!(CorsRules::parse("https://app.example.com cookies").is_err());
+        assert!(CorsRules::parse("https://app.example.com credentials extra").is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_origin() {
+        assert!(CorsRules::parse("https://app\u{7f}.example.com").is_err());
+    }
+}
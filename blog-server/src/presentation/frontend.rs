@@ -0,0 +1,431 @@
+//! Раздача собранного WASM-фронтенда (blog-wasm) самим сервером.
+//!
+//! Файлы фронтенда отдаются из каталога по адресу `/`. Путь, которому не соответствует
+//! ни один файл, обслуживается страницей `index.html`, чтобы маршрутизация на стороне
+//! браузера работала при переходе по прямой ссылке.
+//!
+//! Поисковые роботы и сервисы предпросмотра ссылок не выполняют скрипты, поэтому
+//! на их запросы страницы поста (`/posts/{id}`) в `index.html` добавляются
+//! мета-теги Open Graph с заголовком, описанием, обложкой и автором поста.
+
+use crate::domain::post::LinkPreview;
+use crate::presentation::AppState;
+use crate::presentation::middleware::resolve_tenant;
+use axum::Router;
+use axum::extract::{Path, Request, State};
+use axum::http::header::{CONTENT_TYPE, USER_AGENT};
+use axum::http::{HeaderMap, Uri};
+use axum::response::{Html, IntoResponse, Response};
+use axum::routing::get;
+use std::path::PathBuf;
+use tower::ServiceExt;
+use tower_http::services::{ServeDir, ServeFile};
+
+/// Главная страница фронтенда.
+pub(crate) const INDEX_FILE: &str = "index.html";
+
+/// Скрипт настроек фронтенда: при раздаче сервером блога API доступно по тому же адресу.
+const CONFIG_SCRIPT: &str = "window.BLOG_SERVER = window.location.origin;\n";
+
+/// Фрагменты `User-Agent` (в нижнем регистре) роботов, запрашивающих превью ссылок.
+const CRAWLER_AGENTS: &[&str] = &[
+    "bot",
+    "facebookexternalhit",
+    "whatsapp",
+    "vkshare",
+    "skypeuripreview",
+    "embedly",
+];
+
+/// Состояние роутера фронтенда.
+#[derive(Clone)]
+struct FrontendState {
+    /// Состояние приложения (для получения постов).
+    app: AppState,
+
+    /// Путь к главной странице фронтенда.
+    index: PathBuf,
+}
+
+/// Создать роутер для раздачи фронтенда из указанного каталога.
+pub(crate) fn frontend(dir: &std::path::Path, app: AppState) -> Router {
+    let index = dir.join(INDEX_FILE);
+
+    Router::new()
+        .route("/config.js", get(config_script))
+        .route("/posts/{id}", get(post_page))
+        .fallback_service(ServeDir::new(dir).fallback(ServeFile::new(&index)))
+        .with_state(FrontendState { app, index })
+}
+
+/// Отдать страницу поста: для роботов - с мета-тегами Open Graph, для остальных - как есть.
+async fn post_page(
+    State(frontend): State<FrontendState>,
+    Path(id): Path<String>,
+    request: Request,
+) -> Response {
+    if is_crawler(request.headers())
+        && let Ok(id) = id.parse()
+        && let Some(page) = frontend
+            .post_page(id, request.uri(), request.headers())
+            .await
+    {
+        return Html(page).into_response();
+    }
+
+    ServeFile::new(&frontend.index)
+        .oneshot(request)
+        .await
+        .into_response()
+}
+
+impl FrontendState {
+    /// Главная страница с мета-тегами превью поста.
+    ///
+    /// Если пост не найден или недоступен без авторизации, возвращается `None`.
+    async fn post_page(&self, id: i64, uri: &Uri, headers: &HeaderMap) -> Option<String> {
+        let tenant = resolve_tenant(&self.app, uri, headers).await.ok()?;
+        let post = self
+            .app
+            .blog_service
+            .get_post_with_author(tenant.id, id, None)
+            .await
+            .ok()?;
+        let index = tokio::fs::read_to_string(&self.index).await.ok()?;
+
+        Some(inject_meta_tags(&index, &post.into(), &self.app.public_url))
+    }
+}
+
+/// Является ли клиент роботом, запрашивающим превью ссылки.
+fn is_crawler(headers: &HeaderMap) -> bool {
+    headers
+        .get(USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_lowercase)
+        .is_some_and(|agent| CRAWLER_AGENTS.iter().any(|a| agent.contains(a)))
+}
+
+/// Экранировать значение атрибута HTML.
+fn escape_attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Сделать ссылку абсолютной: роботы не разрешают относительные адреса в мета-тегах.
+fn absolute_url(url: &str, public_url: &str) -> String {
+    if url.starts_with('/') && !url.starts_with("//") {
+        format!("{public_url}{url}")
+    } else {
+        url.to_string()
+    }
+}
+
+/// Добавить мета-теги Open Graph перед закрывающим тегом `</head>`.
+///
+/// Относительные ссылки на обложки (загруженные изображения) дополняются публичным
+/// адресом сервера.
+fn inject_meta_tags(index: &str, preview: &LinkPreview, public_url: &str) -> String {
+    let image = preview
+        .cover_image_url
+        .as_deref()
+        .map(|url| absolute_url(url, public_url));
+
+    let mut tags = vec![
+        ("og:type", "article"),
+        ("og:title", preview.title.as_str()),
+        ("og:description", preview.excerpt.as_str()),
+        ("article:author", preview.author.username.as_str()),
+    ];
+
+    if let Some(image) = &image {
+        tags.push(("og:image", image));
+    }
+
+    let card = match image {
+        Some(_) => "summary_large_image",
+        None => "summary",
+    };
+
+    let mut meta = format!("<meta name=\"twitter:card\" content=\"{card}\">\n");
+
+    for (property, content) in tags {
+        meta.push_str(&format!(
+            "    <meta property=\"{property}\" content=\"{}\">\n",
+            escape_attribute(content)
+        ));
+    }
+
+    match index.find("</head>") {
+        Some(position) => format!("{}    {meta}{}", &index[..position], &index[position..]),
+        None => index.to_string(),
+    }
+}
+
+/// Отдать скрипт настроек фронтенда (заменяет одноименный файл из каталога фронтенда).
+async fn config_script() -> impl IntoResponse {
+    (
+        [(CONTENT_TYPE, "text/javascript; charset=utf-8")],
+        CONFIG_SCRIPT,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::admin_service::AdminService;
+    use crate::application::api_key_service::ApiKeyService;
+    use crate::application::auth_service::AuthService;
+    use crate::application::blog_service::BlogService;
+    use crate::application::export_service::{ExportService, ExportSources};
+    use crate::application::login_history_service::LoginHistoryService;
+    use crate::application::media_service::MediaService;
+    use crate::application::tenant_service::TenantService;
+    use crate::application::user_service::UserService;
+    use crate::data::admin_repository::AdminRepository;
+    use crate::data::api_key_repository::ApiKeyRepository;
+    use crate::data::export_repository::ExportRepository;
+    use crate::data::invite_repository::InviteRepository;
+    use crate::data::login_history_repository::LoginHistoryRepository;
+    use crate::data::media_repository::MediaRepository;
+    use crate::data::post_repository::PostRepository;
+    use crate::data::settings_repository::SettingsRepository;
+    use crate::data::tenant_repository::TenantRepository;
+    use crate::data::user_repository::UserRepository;
+    use crate::domain::post::{CreatePostRequest, PostAuthor};
+    use crate::domain::user::PasswordPolicy;
+    use crate::infrastructure::database::ReadPool;
+    use crate::infrastructure::events::EventBus;
+    use crate::infrastructure::features::FeatureFlags;
+    use crate::infrastructure::jwt::JwtService;
+    use crate::infrastructure::mailer::{LogMailer, Mailer};
+    use crate::infrastructure::media_storage::MediaStorage;
+    use crate::infrastructure::search::PostgresSearchIndex;
+    use axum::http::HeaderValue;
+    use sqlx::PgPool;
+    use std::sync::Arc;
+
+    const INDEX: &str = "<html><head><title>Блог</title></head><body></body></html>";
+    const PUBLIC_URL: &str = "https://blog.example.com";
+
+    fn preview(cover_image_url: Option<&str>) -> LinkPreview {
+        LinkPreview {
+            id: 1,
+            title: "Первый пост".to_string(),
+            excerpt: "Описание".to_string(),
+            cover_image_url: cover_image_url.map(str::to_string),
+            author: PostAuthor {
+                id: 1,
+                username: "alice".to_string(),
+            },
+        }
+    }
+
+    fn user_agent(agent: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(USER_AGENT, HeaderValue::from_str(agent).unwrap());
+        headers
+    }
+
+    #[test]
+    fn escape_attribute_escapes_special_characters() {
+        assert_eq!(
+            escape_attribute(r#"Tom & "Jerry" <script>"#),
+            "Tom &amp; &quot;Jerry&quot; &lt;script&gt;"
+        );
+        assert_eq!(escape_attribute("&amp;"), "&amp;amp;");
+    }
+
+    #[test]
+    fn is_crawler_detects_link_preview_agents() {
+        for agent in [
+            "Mozilla/5.0 (compatible; Googlebot/2.1)",
+            "facebookexternalhit/1.1",
+            "TelegramBot (like TwitterBot)",
+            "WhatsApp/2.23",
+        ] {
+            assert!(is_crawler(&user_agent(agent)), "{agent}");
+        }
+
+        assert!(!is_crawler(&user_agent(
+            "Mozilla/5.0 (X11; Linux x86_64) Firefox/130.0"
+        )));
+        assert!(!is_crawler(&HeaderMap::new()));
+    }
+
+    #[test]
+    fn uploaded_cover_gets_absolute_url() {
+        let page = inject_meta_tags(INDEX, &preview(Some("/api/v1/media/7")), PUBLIC_URL);
+
+        assert!(page.contains(
+            r#"<meta property="og:image" content="https://blog.example.com/api/v1/media/7">"#
+        ));
+        assert!(page.contains(r#"<meta name="twitter:card" content="summary_large_image">"#));
+    }
+
+    #[test]
+    fn external_cover_is_kept() {
+        let page = inject_meta_tags(
+            INDEX,
+            &preview(Some("https://cdn.example.com/a.png?x=1&y=2")),
+            PUBLIC_URL,
+        );
+
+        assert!(page.contains(
+            r#"<meta property="og:image" content="https://cdn.example.com/a.png?x=1&amp;y=2">"#
+        ));
+    }
+
+    #[test]
+    fn meta_tags_are_inserted_before_head_end() {
+        let page = inject_meta_tags(INDEX, &preview(None), PUBLIC_URL);
+
+        let meta = page.find(r#"<meta property="og:title" content="Первый пост">"#);
+        assert!(meta.is_some_and(|position| position < page.find("</head>").unwrap()));
+        assert!(page.contains(r#"<meta name="twitter:card" content="summary">"#));
+        assert!(!page.contains("og:image"));
+    }
+
+    /// Состояние приложения поверх тестовой базы данных.
+    fn app_state(pool: PgPool) -> AppState {
+        let jwt_service = Arc::new(JwtService::new("0123456789abcdef0123456789abcdef"));
+        let read_pool = ReadPool::new(pool.clone(), None);
+        let features = Arc::new(FeatureFlags::new(pool.clone()));
+        let events = Arc::new(EventBus::new(16));
+        let mailer: Arc<dyn Mailer> = Arc::new(LogMailer);
+        let storage = Arc::new(MediaStorage::new(std::env::temp_dir()));
+
+        let user_repository = Arc::new(UserRepository::new(pool.clone()));
+        let post_repository = Arc::new(PostRepository::new(pool.clone()));
+        let media_repository = Arc::new(MediaRepository::new(pool.clone()));
+        let api_key_repository = Arc::new(ApiKeyRepository::new(pool.clone()));
+        let settings_repository = Arc::new(SettingsRepository::new(pool.clone()));
+        let invite_repository = Arc::new(InviteRepository::new(pool.clone()));
+
+        let login_history_service = Arc::new(LoginHistoryService::new(
+            Arc::new(LoginHistoryRepository::new(pool.clone())),
+            settings_repository.clone(),
+            mailer.clone(),
+        ));
+
+        AppState::new(
+            Arc::new(AuthService::new(
+                jwt_service.clone(),
+                user_repository.clone(),
+                PasswordPolicy::default(),
+                login_history_service,
+                features.clone(),
+                invite_repository.clone(),
+                events.clone(),
+            )),
+            Arc::new(BlogService::new(
+                post_repository.clone(),
+                jwt_service.clone(),
+                Arc::new(PostgresSearchIndex::new(read_pool)),
+                features.clone(),
+                events,
+            )),
+            jwt_service,
+            Arc::new(ApiKeyService::new(api_key_repository.clone())),
+            Arc::new(MediaService::new(
+                media_repository.clone(),
+                storage.clone(),
+                features.clone(),
+            )),
+            Arc::new(UserService::new(
+                user_repository.clone(),
+                settings_repository,
+                mailer.clone(),
+                PUBLIC_URL,
+            )),
+            Arc::new(ExportService::new(
+                Arc::new(ExportRepository::new(pool.clone())),
+                ExportSources {
+                    user_repository,
+                    post_repository,
+                    media_repository,
+                    api_key_repository,
+                    media_storage: storage,
+                },
+                mailer,
+                std::env::temp_dir(),
+                PUBLIC_URL,
+                features.clone(),
+            )),
+            Arc::new(AdminService::new(
+                Arc::new(AdminRepository::new(pool.clone())),
+                features,
+                invite_repository,
+            )),
+            Arc::new(TenantService::new(Arc::new(TenantRepository::new(pool)))),
+        )
+        .with_public_url(PUBLIC_URL)
+    }
+
+    #[sqlx::test(migrator = "crate::infrastructure::database::MIGRATOR")]
+    #[ignore = "требуется PostgreSQL (DATABASE_URL)"]
+    async fn post_page_has_meta_tags_only_for_public_posts(pool: PgPool) {
+        let author: i64 = sqlx::query_scalar(
+            "INSERT INTO users (username, email, password_hash, tenant_id)
+            VALUES ('alice', 'alice@example.com', '', 1) RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let dir = std::env::temp_dir().join(format!("blog-frontend-{}", std::process::id()));
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let index = dir.join(INDEX_FILE);
+        tokio::fs::write(&index, INDEX).await.unwrap();
+
+        let frontend = FrontendState {
+            app: app_state(pool),
+            index,
+        };
+
+        let mut posts = Vec::new();
+        for is_private in [false, true] {
+            let post = frontend
+                .app
+                .blog_service
+                .create_post(
+                    1,
+                    CreatePostRequest {
+                        title: "Пост \"с кавычками\"".into(),
+                        content: "Текст поста".into(),
+                        excerpt: None,
+                        cover_image_url: Some("/api/v1/media/7".into()),
+                        is_private,
+                        content_format: None,
+                    },
+                    author,
+                )
+                .await
+                .unwrap();
+            posts.push(post.id);
+        }
+
+        let uri = Uri::from_static("/posts/1");
+        let headers = HeaderMap::new();
+
+        let page = frontend.post_page(posts[0], &uri, &headers).await.unwrap();
+        assert!(
+            page.contains(r#"<meta property="og:title" content="Пост &quot;с кавычками&quot;">"#)
+        );
+        assert!(page.contains(r#"<meta property="article:author" content="alice">"#));
+        assert!(page.contains(
+            r#"<meta property="og:image" content="https://blog.example.com/api/v1/media/7">"#
+        ));
+
+        assert_eq!(frontend.post_page(posts[1], &uri, &headers).await, None);
+        assert_eq!(
+            frontend.post_page(posts[1] + 100, &uri, &headers).await,
+            None
+        );
+
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+}
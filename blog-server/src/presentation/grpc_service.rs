@@ -2,19 +2,53 @@
 
 use crate::blog_grpc::blog_service_server::BlogService;
 use crate::blog_grpc::{
-    CreatePostRequest, CreatePostResponse, CreateUserRequest, CreateUserResponse,
-    DeletePostRequest, DeletePostResponse, GetPostRequest, GetPostResponse, GetPostsRequest,
-    GetPostsResponse, LoginUserRequest, LoginUserResponse, UpdatePostRequest, UpdatePostResponse,
+    AddPostAuthorRequest, AddPostAuthorResponse, ArchivePostRequest, ArchivePostResponse,
+    BulkDeletePostsRequest, BulkDeletePostsResponse, CreatePostRequest, CreatePostResponse,
+    CreateShareLinkRequest, CreateShareLinkResponse, CreateUserRequest, CreateUserResponse,
+    DeletePostRequest, DeletePostResponse, GetAdminStatsRequest, GetAdminStatsResponse,
+    GetCurrentUserRequest, GetCurrentUserResponse, GetPostRequest, GetPostResponse,
+    GetPostsByIdsRequest, GetPostsByIdsResponse, GetPostsRequest, GetPostsResponse,
+    GetSharedPostRequest, GetSharedPostResponse, GetUserRequest, GetUserResponse, LoginUserRequest,
+    LoginUserResponse, SearchPostsRequest, SearchPostsResponse, UpdatePostRequest,
+    UpdatePostResponse, UpdateProfileRequest, UpdateProfileResponse, UploadMediaRequest,
+    UploadMediaResponse,
 };
-use crate::domain::error::UserError;
+use crate::domain::admin::StatsParams;
+use crate::domain::error::{MediaError, PostError, UserError};
+use crate::domain::login::LoginContext;
+use crate::domain::pagination::Pagination;
+use crate::domain::post::{PostFilter, includes_author};
 use crate::presentation::AppState;
-use tonic::{Request, Response, Status};
-use validator::Validate;
+use crate::presentation::middleware::request_host;
+use axum::http::Request as HttpRequest;
+use chrono::{DateTime, Utc};
+use tonic::{Request, Response, Status, Streaming};
+use validator::{Validate, ValidationErrors};
+
+/// Доменное имя, по которому клиент обратился к gRPC-серверу.
+#[derive(Debug, Clone)]
+pub(crate) struct RequestHost(String);
+
+/// Сохранить в расширениях запроса доменное имя, по которому обратился клиент.
+///
+/// tonic не передает обработчикам URI запроса, а в HTTP/2 доменное имя приходит
+/// в псевдозаголовке `:authority`, которого нет среди метаданных.
+pub(crate) fn capture_request_host<B>(mut request: HttpRequest<B>) -> HttpRequest<B> {
+    if let Some(host) = request_host(request.uri(), request.headers()) {
+        let host = RequestHost(host.to_string());
+        request.extensions_mut().insert(host);
+    }
+
+    request
+}
 
 /// Извлечь идентификатор пользователя из JWT-токена в заголовке авторизации.
+///
+/// Токен, выданный в другом блоге, считается некорректным.
 fn extract_user_id(
     request: &tonic::metadata::MetadataMap,
     jwt_service: &crate::infrastructure::jwt::JwtService,
+    tenant_id: i64,
 ) -> Result<i64, Status> {
     let token = request
         .get("authorization")
@@ -26,20 +60,73 @@ fn extract_user_id(
         .verify_token(token)
         .map_err(|_| Status::unauthenticated("Некорректный JWT-токен"))?;
 
+    if claims.tenant_id != tenant_id {
+        return Err(Status::unauthenticated("Некорректный JWT-токен"));
+    }
+
     Ok(claims.user_id)
 }
 
+/// Разобрать необязательное время в формате RFC 3339 из поля запроса.
+fn parse_timestamp(value: Option<&str>, field: &str) -> Result<Option<DateTime<Utc>>, Status> {
+    value
+        .map(|value| {
+            DateTime::parse_from_rfc3339(value)
+                .map(|time| time.with_timezone(&Utc))
+                .map_err(|_| {
+                    PostError::InvalidQuery(format!("Некорректное время в поле {field}!")).into()
+                })
+        })
+        .transpose()
+}
+
+/// Извлечь тело gRPC-запроса, преобразовать его в доменную модель и провалидировать.
+///
+/// Ошибка валидации преобразуется в доменную ошибку `E`, а из нее - в статус gRPC.
+fn validated_request<R, T, E>(request: Request<R>) -> Result<T, Status>
+where
+    T: From<R> + Validate,
+    E: From<ValidationErrors>,
+    Status: From<E>,
+{
+    let request = T::from(request.into_inner());
+
+    request.validate().map_err(E::from)?;
+
+    Ok(request)
+}
+
 /// gRPC-сервис сервиса блога.
 #[derive(Debug, Clone)]
 pub(crate) struct BlogGrpcService {
     /// Состояние приложения с сервисами.
     state: AppState,
+
+    /// Максимальный размер загружаемого изображения (в байтах).
+    media_body_limit: usize,
 }
 
 impl BlogGrpcService {
     /// Создать новый экземпляр gRPC-сервиса.
-    pub(crate) fn new(state: AppState) -> Self {
-        Self { state }
+    pub(crate) fn new(state: AppState, media_body_limit: usize) -> Self {
+        Self {
+            state,
+            media_body_limit,
+        }
+    }
+
+    /// Определить блог запроса по заголовку с коротким именем блога или по доменному имени.
+    async fn tenant_id<T>(&self, request: &Request<T>) -> Result<i64, Status> {
+        let slug = request
+            .metadata()
+            .get(self.state.tenant_header.as_str())
+            .and_then(|v| v.to_str().ok());
+        let host = request
+            .extensions()
+            .get::<RequestHost>()
+            .map(|host| host.0.as_str());
+
+        Ok(self.state.tenant_service.resolve(slug, host).await?)
     }
 }
 
@@ -50,11 +137,15 @@ impl BlogService for BlogGrpcService {
         &self,
         request: Request<CreateUserRequest>,
     ) -> Result<Response<CreateUserResponse>, Status> {
-        let request: crate::domain::user::CreateUserRequest = request.into_inner().into();
-        request.validate().map_err(UserError::from)?;
+        let tenant_id = self.tenant_id(&request).await?;
+        let request = validated_request::<_, _, UserError>(request)?;
 
         Ok(Response::new(
-            self.state.auth_service.register(request).await?.into(),
+            self.state
+                .auth_service
+                .register(tenant_id, request)
+                .await?
+                .into(),
         ))
     }
 
@@ -63,25 +154,107 @@ impl BlogService for BlogGrpcService {
         &self,
         request: Request<LoginUserRequest>,
     ) -> Result<Response<LoginUserResponse>, Status> {
+        let tenant_id = self.tenant_id(&request).await?;
+        let metadata = |name: &str| {
+            request
+                .metadata()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string)
+        };
+
+        let context = LoginContext {
+            ip: request.remote_addr().map(|addr| addr.ip().to_string()),
+            user_agent: metadata("user-agent"),
+            country: self
+                .state
+                .country_header
+                .as_ref()
+                .and_then(|header| metadata(header.as_str())),
+        };
+
         let request = request.into_inner().into();
 
         Ok(Response::new(
-            self.state.auth_service.login(request).await?.into(),
+            self.state
+                .auth_service
+                .login(tenant_id, request, context)
+                .await?
+                .into(),
         ))
     }
 
+    /// Получить публичный профиль пользователя по имени пользователя.
+    async fn get_user(
+        &self,
+        request: Request<GetUserRequest>,
+    ) -> Result<Response<GetUserResponse>, Status> {
+        let tenant_id = self.tenant_id(&request).await?;
+        let request = request.into_inner();
+
+        let profile = self
+            .state
+            .user_service
+            .get_public_profile(tenant_id, &request.username)
+            .await?;
+
+        Ok(Response::new(GetUserResponse {
+            profile: Some(profile.into()),
+        }))
+    }
+
+    /// Получить информацию о текущем пользователе.
+    async fn get_current_user(
+        &self,
+        request: Request<GetCurrentUserRequest>,
+    ) -> Result<Response<GetCurrentUserResponse>, Status> {
+        let tenant_id = self.tenant_id(&request).await?;
+        let user_id = extract_user_id(request.metadata(), &self.state.jwt_service, tenant_id)?;
+
+        let user = self.state.user_service.get_user(user_id).await?;
+
+        Ok(Response::new(GetCurrentUserResponse {
+            user: Some(user.into()),
+        }))
+    }
+
+    /// Изменить профиль текущего пользователя.
+    ///
+    /// Новый email-адрес вступает в силу после перехода по ссылке из письма.
+    async fn update_profile(
+        &self,
+        request: Request<UpdateProfileRequest>,
+    ) -> Result<Response<UpdateProfileResponse>, Status> {
+        let tenant_id = self.tenant_id(&request).await?;
+        let user_id = extract_user_id(request.metadata(), &self.state.jwt_service, tenant_id)?;
+        let request: crate::domain::user::UpdateProfileRequest = request.into_inner().into();
+
+        request.validate().map_err(UserError::InvalidEmail)?;
+
+        let user = self
+            .state
+            .user_service
+            .update_profile(user_id, request)
+            .await?;
+
+        Ok(Response::new(UpdateProfileResponse {
+            user: Some(user.into()),
+        }))
+    }
+
     /// Создать новый пост.
     async fn create_post(
         &self,
         request: Request<CreatePostRequest>,
     ) -> Result<Response<CreatePostResponse>, Status> {
-        let user_id = extract_user_id(request.metadata(), &self.state.jwt_service)?;
-        let request = request.into_inner().into();
+        let tenant_id = self.tenant_id(&request).await?;
+        let user_id = extract_user_id(request.metadata(), &self.state.jwt_service, tenant_id)?;
+        let request = validated_request::<_, _, PostError>(request)?;
 
         let post = self
             .state
             .blog_service
-            .create_post(request, user_id)
+            .create_post(tenant_id, request, user_id)
             .await?;
 
         Ok(Response::new(CreatePostResponse {
@@ -94,12 +267,28 @@ impl BlogService for BlogGrpcService {
         &self,
         request: Request<GetPostRequest>,
     ) -> Result<Response<GetPostResponse>, Status> {
+        let tenant_id = self.tenant_id(&request).await?;
+        let viewer_id =
+            extract_user_id(request.metadata(), &self.state.jwt_service, tenant_id).ok();
         let request = request.into_inner();
-        let post = self.state.blog_service.get_post(request.id).await?;
 
-        Ok(Response::new(GetPostResponse {
-            post: Some(post.into()),
-        }))
+        let post = if includes_author(&request.include) {
+            self.state
+                .blog_service
+                .get_post_with_author(tenant_id, request.id, viewer_id)
+                .await?
+                .into()
+        } else {
+            self.state
+                .blog_service
+                .get_post(tenant_id, request.id, viewer_id)
+                .await?
+                .into()
+        };
+
+        self.state.blog_service.record_view(request.id);
+
+        Ok(Response::new(GetPostResponse { post: Some(post) }))
     }
 
     /// Получить список постов с пагинацией.
@@ -107,16 +296,105 @@ impl BlogService for BlogGrpcService {
         &self,
         request: Request<GetPostsRequest>,
     ) -> Result<Response<GetPostsResponse>, Status> {
+        let tenant_id = self.tenant_id(&request).await?;
+        let viewer_id =
+            extract_user_id(request.metadata(), &self.state.jwt_service, tenant_id).ok();
+        let request = request.into_inner();
+        let page = Pagination::new(request.limit, request.offset, self.state.max_page_size)?;
+
+        let filter = PostFilter {
+            tenant_id,
+            viewer_id,
+            author_id: None,
+            archived: request.archived,
+            created_after: parse_timestamp(request.created_after.as_deref(), "created_after")?,
+            created_before: parse_timestamp(request.created_before.as_deref(), "created_before")?,
+            updated_since: parse_timestamp(request.updated_since.as_deref(), "updated_since")?,
+            sort: request
+                .sort
+                .as_deref()
+                .map(str::parse)
+                .transpose()?
+                .unwrap_or_default(),
+        };
+
+        let posts = if includes_author(&request.include) {
+            self.state
+                .blog_service
+                .get_posts_with_author(&filter, page.limit, page.offset)
+                .await?
+                .into_iter()
+                .map(|p| p.into())
+                .collect()
+        } else {
+            self.state
+                .blog_service
+                .get_posts(&filter, page.limit, page.offset)
+                .await?
+                .into_iter()
+                .map(|p| p.into())
+                .collect()
+        };
+        let total = self.state.blog_service.count_posts(&filter).await?;
+
+        Ok(Response::new(GetPostsResponse {
+            posts,
+            total,
+            limit: page.limit,
+            offset: page.offset,
+        }))
+    }
+
+    /// Получить посты по списку идентификаторов.
+    async fn get_posts_by_ids(
+        &self,
+        request: Request<GetPostsByIdsRequest>,
+    ) -> Result<Response<GetPostsByIdsResponse>, Status> {
+        let tenant_id = self.tenant_id(&request).await?;
+        let viewer_id =
+            extract_user_id(request.metadata(), &self.state.jwt_service, tenant_id).ok();
         let request = request.into_inner();
+
         let posts = self
             .state
             .blog_service
-            .get_posts(request.limit, request.offset)
-            .await?;
+            .get_posts_by_ids(tenant_id, &request.ids, viewer_id)
+            .await?
+            .into_iter()
+            .map(|p| p.into())
+            .collect();
 
-        Ok(Response::new(GetPostsResponse {
-            posts: posts.into_iter().map(|p| p.into()).collect(),
-        }))
+        Ok(Response::new(GetPostsByIdsResponse { posts }))
+    }
+
+    /// Найти посты по поисковому запросу.
+    async fn search_posts(
+        &self,
+        request: Request<SearchPostsRequest>,
+    ) -> Result<Response<SearchPostsResponse>, Status> {
+        let tenant_id = self.tenant_id(&request).await?;
+        let viewer_id =
+            extract_user_id(request.metadata(), &self.state.jwt_service, tenant_id).ok();
+        let request = request.into_inner();
+        let page = Pagination::new(request.limit, request.offset, self.state.max_page_size)?;
+
+        let posts = self
+            .state
+            .blog_service
+            .search_posts(
+                tenant_id,
+                &request.query,
+                request.author_id,
+                viewer_id,
+                page.limit,
+                page.offset,
+            )
+            .await?
+            .into_iter()
+            .map(|p| p.into())
+            .collect();
+
+        Ok(Response::new(SearchPostsResponse { posts }))
     }
 
     /// Обновить существующий пост.
@@ -124,13 +402,14 @@ impl BlogService for BlogGrpcService {
         &self,
         request: Request<UpdatePostRequest>,
     ) -> Result<Response<UpdatePostResponse>, Status> {
-        let user_id = extract_user_id(request.metadata(), &self.state.jwt_service)?;
-        let request = request.into_inner().into();
+        let tenant_id = self.tenant_id(&request).await?;
+        let user_id = extract_user_id(request.metadata(), &self.state.jwt_service, tenant_id)?;
+        let request = validated_request::<_, _, PostError>(request)?;
 
         let post = self
             .state
             .blog_service
-            .update_post(request, user_id)
+            .update_post(tenant_id, request, user_id)
             .await?;
 
         Ok(Response::new(UpdatePostResponse {
@@ -143,14 +422,265 @@ impl BlogService for BlogGrpcService {
         &self,
         request: Request<DeletePostRequest>,
     ) -> Result<Response<DeletePostResponse>, Status> {
-        let user_id = extract_user_id(request.metadata(), &self.state.jwt_service)?;
+        let tenant_id = self.tenant_id(&request).await?;
+        let user_id = extract_user_id(request.metadata(), &self.state.jwt_service, tenant_id)?;
         let request = request.into_inner();
 
         self.state
             .blog_service
-            .delete_post(request.id, user_id)
+            .delete_post(tenant_id, request.id, user_id)
             .await?;
 
         Ok(Response::new(DeletePostResponse {}))
     }
+
+    /// Удалить несколько постов пользователя в одной транзакции.
+    async fn bulk_delete_posts(
+        &self,
+        request: Request<BulkDeletePostsRequest>,
+    ) -> Result<Response<BulkDeletePostsResponse>, Status> {
+        let tenant_id = self.tenant_id(&request).await?;
+        let user_id = extract_user_id(request.metadata(), &self.state.jwt_service, tenant_id)?;
+        let request = request.into_inner();
+
+        let results = self
+            .state
+            .blog_service
+            .bulk_delete_posts(tenant_id, &request.ids, user_id)
+            .await?
+            .into_iter()
+            .map(|r| r.into())
+            .collect();
+
+        Ok(Response::new(BulkDeletePostsResponse { results }))
+    }
+
+    /// Перевести пост в архив.
+    async fn archive_post(
+        &self,
+        request: Request<ArchivePostRequest>,
+    ) -> Result<Response<ArchivePostResponse>, Status> {
+        let tenant_id = self.tenant_id(&request).await?;
+        let user_id = extract_user_id(request.metadata(), &self.state.jwt_service, tenant_id)?;
+        let request = request.into_inner();
+
+        let post = self
+            .state
+            .blog_service
+            .set_archived(tenant_id, request.id, true, user_id)
+            .await?;
+
+        Ok(Response::new(ArchivePostResponse {
+            post: Some(post.into()),
+        }))
+    }
+
+    /// Вернуть пост из архива.
+    async fn unarchive_post(
+        &self,
+        request: Request<ArchivePostRequest>,
+    ) -> Result<Response<ArchivePostResponse>, Status> {
+        let tenant_id = self.tenant_id(&request).await?;
+        let user_id = extract_user_id(request.metadata(), &self.state.jwt_service, tenant_id)?;
+        let request = request.into_inner();
+
+        let post = self
+            .state
+            .blog_service
+            .set_archived(tenant_id, request.id, false, user_id)
+            .await?;
+
+        Ok(Response::new(ArchivePostResponse {
+            post: Some(post.into()),
+        }))
+    }
+
+    /// Добавить соавтора поста.
+    async fn add_post_author(
+        &self,
+        request: Request<AddPostAuthorRequest>,
+    ) -> Result<Response<AddPostAuthorResponse>, Status> {
+        let tenant_id = self.tenant_id(&request).await?;
+        let user_id = extract_user_id(request.metadata(), &self.state.jwt_service, tenant_id)?;
+        let request = request.into_inner();
+        let id = request.id;
+
+        let post = self
+            .state
+            .blog_service
+            .add_post_author(tenant_id, id, request.into(), user_id)
+            .await?;
+
+        Ok(Response::new(AddPostAuthorResponse {
+            post: Some(post.into()),
+        }))
+    }
+
+    /// Загрузить изображение, переданное потоком фрагментов.
+    ///
+    /// Токен должен быть выдан в блоге запроса; поток читается только после проверки.
+    async fn upload_media(
+        &self,
+        request: Request<Streaming<UploadMediaRequest>>,
+    ) -> Result<Response<UploadMediaResponse>, Status> {
+        let tenant_id = self.tenant_id(&request).await?;
+        let user_id = extract_user_id(request.metadata(), &self.state.jwt_service, tenant_id)?;
+        let mut stream = request.into_inner();
+
+        let mut content = Vec::new();
+
+        while let Some(request) = stream.message().await? {
+            if content.len() + request.chunk.len() > self.media_body_limit {
+                return Err(MediaError::TooLarge(self.media_body_limit).into());
+            }
+
+            content.extend_from_slice(&request.chunk);
+        }
+
+        if content.is_empty() {
+            return Err(MediaError::InvalidImage("пустое изображение".to_string()).into());
+        }
+
+        let media = self.state.media_service.upload(user_id, content).await?;
+
+        Ok(Response::new(UploadMediaResponse {
+            media: Some(media.into()),
+        }))
+    }
+
+    /// Создать ссылку для совместного доступа к посту.
+    async fn create_share_link(
+        &self,
+        request: Request<CreateShareLinkRequest>,
+    ) -> Result<Response<CreateShareLinkResponse>, Status> {
+        let tenant_id = self.tenant_id(&request).await?;
+        let user_id = extract_user_id(request.metadata(), &self.state.jwt_service, tenant_id)?;
+        let id = request.get_ref().id;
+        let request = validated_request::<_, _, PostError>(request)?;
+
+        let link = self
+            .state
+            .blog_service
+            .create_share_link(tenant_id, id, request, user_id)
+            .await?;
+
+        Ok(Response::new(link.into()))
+    }
+
+    /// Получить пост по ссылке для совместного доступа.
+    async fn get_shared_post(
+        &self,
+        request: Request<GetSharedPostRequest>,
+    ) -> Result<Response<GetSharedPostResponse>, Status> {
+        let tenant_id = self.tenant_id(&request).await?;
+        let request = request.into_inner();
+
+        let post = self
+            .state
+            .blog_service
+            .get_shared_post(tenant_id, &request.token)
+            .await?;
+
+        Ok(Response::new(GetSharedPostResponse {
+            post: Some(post.into()),
+        }))
+    }
+
+    /// Получить статистику блога (только для администраторов).
+    async fn get_admin_stats(
+        &self,
+        request: Request<GetAdminStatsRequest>,
+    ) -> Result<Response<GetAdminStatsResponse>, Status> {
+        let tenant_id = self.tenant_id(&request).await?;
+        let user_id = extract_user_id(request.metadata(), &self.state.jwt_service, tenant_id)?;
+
+        self.state.admin_service.ensure_admin(user_id).await?;
+
+        let params = StatsParams {
+            days: request.into_inner().days,
+        };
+
+        let stats = self
+            .state
+            .admin_service
+            .get_stats(tenant_id, params.days()?)
+            .await?;
+
+        Ok(Response::new(stats.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::tenant::DEFAULT_TENANT_ID;
+    use crate::infrastructure::jwt::JwtService;
+    use axum::http::header::HOST;
+
+    const SECRET: &str = "0123456789abcdef0123456789abcdef";
+
+    /// Доменное имя, которое увидит gRPC-обработчик.
+    fn captured_host(request: HttpRequest<()>) -> Option<String> {
+        let request = Request::from_http(capture_request_host(request));
+
+        request
+            .extensions()
+            .get::<RequestHost>()
+            .map(|host| host.0.clone())
+    }
+
+    #[test]
+    fn host_is_taken_from_http2_authority() {
+        let request = HttpRequest::builder()
+            .uri("http://blog.example.com:50051/blog.BlogService/GetPost")
+            .body(())
+            .unwrap();
+
+        assert_eq!(
+            captured_host(request).as_deref(),
+            Some("blog.example.com:50051")
+        );
+    }
+
+    #[test]
+    fn host_falls_back_to_host_header() {
+        let request = HttpRequest::builder()
+            .uri("/blog.BlogService/GetPost")
+            .header(HOST, "blog.example.com")
+            .body(())
+            .unwrap();
+
+        assert_eq!(captured_host(request).as_deref(), Some("blog.example.com"));
+    }
+
+    #[test]
+    fn host_is_absent_without_authority() {
+        let request = HttpRequest::builder()
+            .uri("/blog.BlogService/GetPost")
+            .body(())
+            .unwrap();
+
+        assert_eq!(captured_host(request), None);
+    }
+
+    #[test]
+    fn token_from_other_tenant_is_rejected() {
+        let jwt_service = JwtService::new(SECRET);
+        let token = jwt_service
+            .generate_token(1, "alice", DEFAULT_TENANT_ID + 1)
+            .unwrap();
+
+        let mut request = Request::new(());
+        request
+            .metadata_mut()
+            .insert("authorization", format!("Bearer {token}").parse().unwrap());
+
+        let status =
+            extract_user_id(request.metadata(), &jwt_service, DEFAULT_TENANT_ID).unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unauthenticated);
+
+        let user_id =
+            extract_user_id(request.metadata(), &jwt_service, DEFAULT_TENANT_ID + 1).unwrap();
+        assert_eq!(user_id, 1);
+    }
 }
@@ -0,0 +1,108 @@
+//! Эндпоинты проверки состояния сервера для балансировщиков и оркестраторов.
+//!
+//! `/healthz` отвечает, пока процесс работает. `/readyz` сообщает о состоянии БД по
+//! результатам фоновых проверок: при недоступной основной БД сервер не может обслуживать
+//! запросы и отвечает 503, а при недоступной реплике работает в деградированном режиме
+//! (чтение идет из основной БД).
+
+use crate::infrastructure::database::DatabaseHealth;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::Serialize;
+
+/// Состояние сервера.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum ServerStatus {
+    /// Все компоненты доступны.
+    Ok,
+
+    /// Часть компонентов недоступна.
+    Degraded,
+}
+
+/// Состояние компонента сервера.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum ComponentStatus {
+    /// Компонент доступен.
+    Up,
+
+    /// Компонент недоступен.
+    Down,
+}
+
+impl From<bool> for ComponentStatus {
+    fn from(available: bool) -> Self {
+        if available { Self::Up } else { Self::Down }
+    }
+}
+
+/// Состояние соединений с БД.
+#[derive(Debug, Serialize)]
+struct DatabaseStatus {
+    /// Основная БД.
+    primary: ComponentStatus,
+
+    /// Реплика для чтения (отсутствует, если не задана).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    replica: Option<ComponentStatus>,
+}
+
+/// Ответ эндпоинта `/readyz`.
+#[derive(Debug, Serialize)]
+struct Readiness {
+    /// Общее состояние сервера.
+    status: ServerStatus,
+
+    /// Состояние соединений с БД.
+    database: DatabaseStatus,
+}
+
+/// Создать роутер эндпоинтов проверки состояния.
+pub(crate) fn health(database: DatabaseHealth) -> Router {
+    Router::new()
+        .route("/healthz", get(healthz))
+        .route("/readyz", get(readyz))
+        .with_state(database)
+}
+
+/// Сервер запущен.
+async fn healthz() -> StatusCode {
+    StatusCode::OK
+}
+
+/// Сервер готов обслуживать запросы.
+///
+/// Возвращает 503, если недоступна основная БД; при недоступной реплике
+/// возвращает 200 с состоянием `degraded`.
+async fn readyz(State(database): State<DatabaseHealth>) -> impl IntoResponse {
+    let primary = database.primary_available();
+    let replica = database.replica_available();
+
+    let status = if primary && replica != Some(false) {
+        ServerStatus::Ok
+    } else {
+        ServerStatus::Degraded
+    };
+
+    let status_code = if primary {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (
+        status_code,
+        Json(Readiness {
+            status,
+            database: DatabaseStatus {
+                primary: primary.into(),
+                replica: replica.map(ComponentStatus::from),
+            },
+        }),
+    )
+}
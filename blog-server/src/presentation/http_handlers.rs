@@ -1,25 +1,121 @@
 //! HTTP-обработчики для API сервиса блога.
 
-use crate::domain::error::UserError;
-use crate::domain::post::{CreatePostRequest, Post, UpdatePostRequest};
+use crate::domain::admin::{AdminStats, ContentReportParams, MigrationStatus, StatsParams};
+use crate::domain::api_key::{ApiKey, CreateApiKeyRequest, CreateApiKeyResponse};
+use crate::domain::error::{
+    AdminError, ApiKeyError, ExportError, MediaError, PaginationError, PostError, TenantError,
+    UserError,
+};
+use crate::domain::export::{DataExport, DataExportStatus};
+use crate::domain::feature::{FeatureFlag, SetFeatureRequest};
+use crate::domain::invite::{CreateInviteRequest, Invite};
+use crate::domain::login::{LoginContext, LoginRecord};
+use crate::domain::media::{Media, MediaSizeParams};
+use crate::domain::pagination::Pagination;
+use crate::domain::post::{
+    AddPostAuthorRequest, BulkDeleteRequest, BulkDeleteResult, CreatePostRequest,
+    CreateShareLinkRequest, GetPostsByIdsRequest, LinkPreview, Post, PostField, PostFilter,
+    PostModerationFlag, PostPage, PostPreview, PostSort, PostSuggestion, ShareLink,
+    UpdatePostRequest, includes_author,
+};
+use crate::domain::settings::UserSettings;
+use crate::domain::tenant::{CreateTenantRequest, Tenant};
 use crate::domain::user::{
-    CreateUserRequest, CreateUserResponse, LoginUserRequest, LoginUserResponse,
+    ChangeEmailRequest, ConfirmEmailParams, CreateUserRequest, CreateUserResponse,
+    LoginUserRequest, LoginUserResponse, PublicProfile, UpdateProfileRequest, User,
+};
+use crate::presentation::caching::{CachePolicy, cache_headers, is_not_modified, last_modified};
+use crate::presentation::middleware::{
+    AuthUser, CurrentTenant, admin_validator, jwt_validator, optional_auth,
+    platform_admin_validator,
 };
-use crate::infrastructure::jwt::Claims;
-use crate::presentation::AppState;
-use crate::presentation::middleware::jwt_validator;
-use axum::extract::{Path, Query, State};
-use axum::response::Result;
-use axum::routing::{delete, get, post, put};
+use crate::presentation::{AppState, BodyLimits, RouteLimit, RouteLimits};
+use axum::body::{Body, Bytes};
+use axum::extract::{ConnectInfo, DefaultBodyLimit, Path, Query, State};
+use axum::http::header::{CONTENT_DISPOSITION, CONTENT_TYPE, ETAG, IF_MATCH, USER_AGENT};
+use axum::http::{HeaderMap, HeaderName, StatusCode};
+use axum::response::{AppendHeaders, IntoResponse, Response, Result};
+use axum::routing::{delete, get, patch, post, put};
 use axum::{Extension, Json, Router, middleware};
+use chrono::{DateTime, Utc};
+use jsonwebtoken::jwk::JwkSet;
 use serde::Deserialize;
+use std::net::SocketAddr;
+use tokio_util::io::ReaderStream;
+use tower_http::limit::RequestBodyLimitLayer;
+use tower_http::timeout::TimeoutLayer;
 use validator::Validate;
 
-/// Создать роутер запросов в API.
-pub(crate) fn api(state: AppState) -> Router {
+/// Создать роутер запросов первой версии API.
+pub(crate) fn v1(state: AppState, body_limits: BodyLimits, limits: &RouteLimits) -> Router {
+    Router::new()
+        .nest(
+            "/auth",
+            with_route_limit(
+                with_body_limit(auth(state.clone()), body_limits.auth),
+                &limits.auth,
+            ),
+        )
+        .nest(
+            "/posts",
+            with_body_limit(posts(state.clone(), limits), body_limits.posts),
+        )
+        .nest(
+            "/users",
+            with_route_limit(
+                with_body_limit(users(state.clone()), body_limits.auth),
+                &limits.auth,
+            )
+            .merge(with_route_limit(
+                with_body_limit(avatar(state.clone()), body_limits.media),
+                &limits.write,
+            ))
+            .merge(with_route_limit(profiles(state.clone()), &limits.read)),
+        )
+        .nest(
+            "/media",
+            with_body_limit(media(state.clone(), limits), body_limits.media),
+        )
+        .nest(
+            "/admin",
+            with_route_limit(
+                with_body_limit(admin(state.clone()), body_limits.auth),
+                &limits.write,
+            ),
+        )
+}
+
+/// Ограничить число одновременно обрабатываемых запросов и время их обработки
+/// для группы эндпоинтов.
+///
+/// Запросы сверх лимита ожидают в очереди, а не выполненные за отведенное время
+/// запросы отклоняются со статусом 408.
+pub(crate) fn with_route_limit<S>(router: Router<S>, limit: &RouteLimit) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    router
+        .layer(TimeoutLayer::with_status_code(
+            axum::http::StatusCode::REQUEST_TIMEOUT,
+            limit.timeout,
+        ))
+        .layer(limit.concurrency.clone())
+}
+
+/// Ограничить размер тела запросов для группы эндпоинтов.
+///
+/// Запросы с превышением лимита отклоняются со статусом 413.
+fn with_body_limit(router: Router, limit: usize) -> Router {
+    router
+        .layer(DefaultBodyLimit::disable())
+        .layer(RequestBodyLimitLayer::new(limit))
+}
+
+/// Создать роутер общеизвестных эндпоинтов (`/.well-known`).
+pub(crate) fn well_known(state: AppState) -> Router {
     Router::new()
-        .nest("/auth", auth(state.clone()))
-        .nest("/posts", posts(state.clone()))
+        .route("/jwks.json", get(jwks))
+        .with_state(state)
 }
 
 /// Создать роутер для эндпоинтов авторизации.
@@ -27,20 +123,50 @@ fn auth(state: AppState) -> Router {
     Router::new()
         .route("/register", post(register))
         .route("/login", post(login))
+        .route("/logout", post(logout))
+        .route("/confirm-email", get(confirm_email))
         .with_state(state)
 }
 
 /// Создать роутер для эндпоинтов (защищенные и незащищенные) постов.
-fn posts(state: AppState) -> Router {
+fn posts(state: AppState, limits: &RouteLimits) -> Router {
+    let post_routes = Router::new()
+        .route("/{id}", get(get_post).head(head_post))
+        .route("/{id}/related", get(get_related_posts))
+        .route("/{id}/preview", get(get_post_preview))
+        .route_layer(middleware::from_fn_with_state(
+            CachePolicy::new(&state, state.cache_ttls.post),
+            cache_headers,
+        ));
+
+    let list_routes = Router::new()
+        .route("/", get(get_posts))
+        .route("/search", get(search_posts))
+        .route("/search/suggest", get(suggest_posts))
+        .route_layer(middleware::from_fn_with_state(
+            CachePolicy::new(&state, state.cache_ttls.list),
+            cache_headers,
+        ));
+
     let public_routes = Router::new()
-        .route("/{id}", get(get_post))
-        .route("/", get(get_posts));
+        .merge(post_routes)
+        .merge(list_routes)
+        .route("/batch", post(get_posts_by_ids))
+        .route("/shared/{token}", get(get_shared_post))
+        .route_layer(middleware::from_fn_with_state(state.clone(), optional_auth));
+    let public_routes = with_route_limit(public_routes, &limits.read);
 
     let protected_routes = Router::new()
         .route("/", post(create_post))
         .route("/{id}", put(update_post))
         .route("/{id}", delete(delete_post))
+        .route("/bulk-delete", post(bulk_delete_posts))
+        .route("/{id}/share", post(create_share_link))
+        .route("/{id}/archive", post(archive_post))
+        .route("/{id}/unarchive", post(unarchive_post))
+        .route("/{id}/authors", post(add_post_author))
         .route_layer(middleware::from_fn_with_state(state.clone(), jwt_validator));
+    let protected_routes = with_route_limit(protected_routes, &limits.write);
 
     Router::new()
         .merge(public_routes)
@@ -48,22 +174,177 @@ fn posts(state: AppState) -> Router {
         .with_state(state)
 }
 
+/// Создать роутер для публичных страниц авторов, адресуемых по имени пользователя.
+fn profiles(state: AppState) -> Router {
+    Router::new()
+        .route("/by-name/{username}", get(get_public_profile))
+        .route("/by-name/{username}/posts", get(get_user_posts))
+        .route_layer(middleware::from_fn_with_state(
+            CachePolicy::new(&state, state.cache_ttls.list),
+            cache_headers,
+        ))
+        .route_layer(middleware::from_fn_with_state(state.clone(), optional_auth))
+        .with_state(state)
+}
+
+/// Создать роутер для загрузки аватара (с лимитом размера тела как у изображений).
+fn avatar(state: AppState) -> Router {
+    Router::new()
+        .route("/me/avatar", put(upload_avatar))
+        .route("/me/avatar", delete(remove_avatar))
+        .route_layer(middleware::from_fn_with_state(state.clone(), jwt_validator))
+        .with_state(state)
+}
+
+/// Создать роутер для административных эндпоинтов.
+fn admin(state: AppState) -> Router {
+    let platform_routes = Router::new()
+        .route("/features", get(get_features))
+        .route("/migrations", get(get_migration_status))
+        .route("/features/{name}", put(set_feature))
+        .route("/features/{name}", delete(reset_feature))
+        .route("/tenants", get(get_tenants))
+        .route("/tenants", post(create_tenant))
+        .route("/tenants/{id}", delete(delete_tenant))
+        .route_layer(middleware::from_fn(platform_admin_validator));
+
+    Router::new()
+        .route("/stats", get(get_admin_stats))
+        .route("/content-report", get(get_content_report))
+        .route("/invites", post(create_invite))
+        .route("/moderation/posts", get(get_flagged_posts))
+        .merge(platform_routes)
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            admin_validator,
+        ))
+        .route_layer(middleware::from_fn_with_state(state.clone(), jwt_validator))
+        .with_state(state)
+}
+
+/// Создать роутер для эндпоинтов изображений.
+fn media(state: AppState, limits: &RouteLimits) -> Router {
+    let public_routes = Router::new()
+        .route("/{id}", get(get_media_image))
+        .route("/{id}/info", get(get_media));
+    let public_routes = with_route_limit(public_routes, &limits.read);
+
+    let protected_routes = Router::new()
+        .route("/", post(upload_media))
+        .route_layer(middleware::from_fn_with_state(state.clone(), jwt_validator));
+    let protected_routes = with_route_limit(protected_routes, &limits.write);
+
+    Router::new()
+        .merge(public_routes)
+        .merge(protected_routes)
+        .with_state(state)
+}
+
+/// Заголовки, устанавливающие сессионные cookie (если они включены).
+///
+/// При авторизации через cookie токен забирается из ответа, чтобы он не попал в тело
+/// и не был доступен клиентскому коду в обход HttpOnly cookie.
+fn session_headers(
+    state: &AppState,
+    token: &mut String,
+) -> AppendHeaders<Vec<(HeaderName, String)>> {
+    AppendHeaders(
+        state
+            .session_cookies
+            .as_ref()
+            .map(|cookies| cookies.issue(&std::mem::take(token)))
+            .unwrap_or_default(),
+    )
+}
+
+/// Создать роутер для эндпоинтов пользователей.
+fn users(state: AppState) -> Router {
+    let protected_routes = Router::new()
+        .route("/me", get(get_current_user))
+        .route("/me", patch(update_profile))
+        .route("/me/api-keys", post(create_api_key))
+        .route("/me/api-keys", get(get_api_keys))
+        .route("/me/api-keys/{id}", delete(revoke_api_key))
+        .route("/me/email", put(change_email))
+        .route("/me/export", post(request_export))
+        .route("/me/export", get(download_export))
+        .route("/me/logins", get(get_logins))
+        .route("/me/settings", get(get_settings))
+        .route("/me/settings", put(update_settings))
+        .route_layer(middleware::from_fn_with_state(state.clone(), jwt_validator));
+
+    Router::new().merge(protected_routes).with_state(state)
+}
+
 /// Регистрация пользователя.
 async fn register(
     State(state): State<AppState>,
+    Extension(tenant): Extension<CurrentTenant>,
     Json(request): Json<CreateUserRequest>,
-) -> Result<CreateUserResponse> {
+) -> Result<(AppendHeaders<Vec<(HeaderName, String)>>, CreateUserResponse)> {
     request.validate().map_err(UserError::from)?;
 
-    Ok(state.auth_service.register(request).await?)
+    let mut response = state.auth_service.register(tenant.id, request).await?;
+
+    Ok((session_headers(&state, &mut response.token), response))
 }
 
 /// Авторизация пользователя.
 async fn login(
     State(state): State<AppState>,
+    Extension(tenant): Extension<CurrentTenant>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Json(request): Json<LoginUserRequest>,
-) -> Result<LoginUserResponse> {
-    Ok(state.auth_service.login(request).await?)
+) -> Result<(AppendHeaders<Vec<(HeaderName, String)>>, LoginUserResponse)> {
+    let header = |name: &HeaderName| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+    };
+
+    let context = LoginContext {
+        ip: Some(addr.ip().to_string()),
+        user_agent: header(&USER_AGENT),
+        country: state.country_header.as_ref().and_then(header),
+    };
+
+    let mut response = state
+        .auth_service
+        .login(tenant.id, request, context)
+        .await?;
+
+    Ok((session_headers(&state, &mut response.token), response))
+}
+
+/// Выход пользователя (удаление сессионных cookie).
+async fn logout(
+    State(state): State<AppState>,
+) -> (
+    AppendHeaders<Vec<(HeaderName, String)>>,
+    axum::http::StatusCode,
+) {
+    let headers = state
+        .session_cookies
+        .as_ref()
+        .map(|cookies| cookies.clear())
+        .unwrap_or_default();
+
+    (AppendHeaders(headers), axum::http::StatusCode::NO_CONTENT)
+}
+
+/// Подтверждение нового email-адреса по ссылке из письма.
+async fn confirm_email(
+    State(state): State<AppState>,
+    Query(params): Query<ConfirmEmailParams>,
+) -> Result<Json<User>> {
+    Ok(Json(state.user_service.confirm_email(&params.token).await?))
+}
+
+/// Получить набор публичных ключей для проверки JWT-токенов.
+async fn jwks(State(state): State<AppState>) -> Json<JwkSet> {
+    Json(state.jwt_service.jwks())
 }
 
 /// Параметры пагинации для запросов.
@@ -78,6 +359,90 @@ struct PaginationParams {
     offset: i64,
 }
 
+impl PaginationParams {
+    /// Проверить параметры с учетом максимального размера страницы сервера.
+    fn validate(&self, state: &AppState) -> Result<Pagination, PaginationError> {
+        Pagination::new(self.limit, self.offset, state.max_page_size)
+    }
+}
+
+/// Параметры расширения ответа связанными сущностями.
+#[derive(Debug, Deserialize)]
+struct IncludeParams {
+    /// Список связанных сущностей через запятую (например, `author`).
+    #[serde(default)]
+    include: String,
+}
+
+impl IncludeParams {
+    /// Запрошено ли включение автора поста.
+    fn author(&self) -> bool {
+        includes_author(self.include.split(','))
+    }
+}
+
+/// Параметры отбора постов в списке.
+#[derive(Debug, Deserialize)]
+struct FilterParams {
+    /// Только архивные (`true`) или только неархивные (`false`) посты.
+    archived: Option<bool>,
+
+    /// Только посты, созданные позже указанного времени (RFC 3339).
+    created_after: Option<DateTime<Utc>>,
+
+    /// Только посты, созданные раньше указанного времени (RFC 3339).
+    created_before: Option<DateTime<Utc>>,
+
+    /// Только посты, измененные начиная с указанного времени (RFC 3339).
+    updated_since: Option<DateTime<Utc>>,
+
+    /// Порядок постов: `newest` (по умолчанию) или `trending`.
+    #[serde(default)]
+    sort: PostSort,
+}
+
+impl FilterParams {
+    /// Условия отбора постов блога для пользователя `viewer_id`.
+    fn into_filter(self, tenant_id: i64, viewer_id: Option<i64>) -> PostFilter {
+        PostFilter {
+            tenant_id,
+            viewer_id,
+            author_id: None,
+            archived: self.archived,
+            created_after: self.created_after,
+            created_before: self.created_before,
+            updated_since: self.updated_since,
+            sort: self.sort,
+        }
+    }
+}
+
+/// Параметры поискового запроса.
+#[derive(Debug, Deserialize)]
+struct SearchParams {
+    /// Строка поиска.
+    #[serde(default)]
+    q: String,
+
+    /// Искать только среди постов указанного автора.
+    author_id: Option<i64>,
+}
+
+/// Параметры запроса подсказок для поиска.
+#[derive(Debug, Deserialize)]
+struct SuggestParams {
+    /// Начало строки поиска.
+    #[serde(default)]
+    q: String,
+}
+
+/// Параметры выборки отдельных полей поста.
+#[derive(Debug, Deserialize)]
+struct FieldsParams {
+    /// Список полей поста через запятую (например, `id,title,created_at`).
+    fields: Option<String>,
+}
+
 /// Получить значение по умолчанию для максимального количества результатов.
 fn default_limit() -> i64 {
     10
@@ -86,58 +451,803 @@ fn default_limit() -> i64 {
 /// Создать новый пост.
 async fn create_post(
     State(state): State<AppState>,
-    Extension(claims): Extension<Claims>,
+    Extension(tenant): Extension<CurrentTenant>,
+    Extension(user): Extension<AuthUser>,
     Json(request): Json<CreatePostRequest>,
 ) -> Result<(axum::http::StatusCode, Post)> {
+    request.validate().map_err(PostError::from)?;
+
     Ok((
         axum::http::StatusCode::CREATED,
         state
             .blog_service
-            .create_post(request, claims.user_id)
+            .create_post(tenant.id, request, user.user_id)
             .await?,
     ))
 }
 
+/// Сформировать заголовок `ETag` по версии поста.
+fn post_etag(post: &Post) -> [(HeaderName, String); 1] {
+    [(ETAG, format!("\"{}\"", post.version))]
+}
+
+/// Сформировать заголовки `ETag` и `Last-Modified` поста по его версии и времени изменения.
+fn post_validators(version: i64, updated_at: DateTime<Utc>) -> [(HeaderName, String); 2] {
+    [(ETAG, format!("\"{version}\"")), last_modified(updated_at)]
+}
+
+/// Сформировать ответ со статусом 304, если пост не изменился с момента,
+/// известного клиенту (`If-None-Match` или `If-Modified-Since`).
+fn not_modified(headers: &HeaderMap, version: i64, updated_at: DateTime<Utc>) -> Option<Response> {
+    let validators = post_validators(version, updated_at);
+
+    is_not_modified(headers, &validators[0].1, updated_at)
+        .then(|| (StatusCode::NOT_MODIFIED, validators).into_response())
+}
+
+/// Получить ожидаемую версию поста из заголовка `If-Match`.
+fn parse_if_match(headers: &HeaderMap) -> Result<Option<i64>, PostError> {
+    let Some(value) = headers.get(IF_MATCH) else {
+        return Ok(None);
+    };
+
+    value
+        .to_str()
+        .ok()
+        .map(|v| v.trim().trim_start_matches("W/").trim_matches('"'))
+        .and_then(|v| v.parse().ok())
+        .map(Some)
+        .ok_or(PostError::Conflict)
+}
+
+/// Получить посты, похожие на указанный.
+async fn get_related_posts(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<CurrentTenant>,
+    viewer: Option<Extension<AuthUser>>,
+    Path(id): Path<i64>,
+) -> Result<Json<Vec<PostPreview>>> {
+    let posts = state
+        .blog_service
+        .get_related_posts(tenant.id, id, viewer.map(|Extension(user)| user.user_id))
+        .await?;
+
+    Ok(Json(posts.into_iter().map(PostPreview::from).collect()))
+}
+
+/// Получить превью поста для отображения ссылки на него.
+async fn get_post_preview(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<CurrentTenant>,
+    viewer: Option<Extension<AuthUser>>,
+    Path(id): Path<i64>,
+) -> Result<LinkPreview> {
+    let post = state
+        .blog_service
+        .get_post_with_author(tenant.id, id, viewer.map(|Extension(user)| user.user_id))
+        .await?;
+
+    Ok(post.into())
+}
+
+/// Найти посты по поисковому запросу.
+async fn search_posts(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<CurrentTenant>,
+    viewer: Option<Extension<AuthUser>>,
+    Query(search): Query<SearchParams>,
+    Query(params): Query<PaginationParams>,
+) -> Result<Json<Vec<PostPreview>>> {
+    let page = params.validate(&state)?;
+
+    let posts = state
+        .blog_service
+        .search_posts(
+            tenant.id,
+            &search.q,
+            search.author_id,
+            viewer.map(|Extension(user)| user.user_id),
+            page.limit,
+            page.offset,
+        )
+        .await?;
+
+    Ok(Json(posts.into_iter().map(PostPreview::from).collect()))
+}
+
+/// Получить подсказки для строки поиска постов.
+async fn suggest_posts(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<CurrentTenant>,
+    viewer: Option<Extension<AuthUser>>,
+    Query(params): Query<SuggestParams>,
+) -> Result<Json<Vec<PostSuggestion>>> {
+    let suggestions = state
+        .blog_service
+        .suggest_posts(
+            tenant.id,
+            &params.q,
+            viewer.map(|Extension(user)| user.user_id),
+        )
+        .await?;
+
+    Ok(Json(suggestions))
+}
+
 /// Получить пост по идентификатору.
-async fn get_post(State(state): State<AppState>, Path(id): Path<i64>) -> Result<Post> {
-    Ok(state.blog_service.get_post(id).await?)
+async fn get_post(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<CurrentTenant>,
+    viewer: Option<Extension<AuthUser>>,
+    Path(id): Path<i64>,
+    Query(include): Query<IncludeParams>,
+    headers: HeaderMap,
+) -> Result<Response> {
+    let viewer_id = viewer.map(|Extension(user)| user.user_id);
+
+    if include.author() {
+        let post = state
+            .blog_service
+            .get_post_with_author(tenant.id, id, viewer_id)
+            .await?;
+        state.blog_service.record_view(id);
+
+        if let Some(response) = not_modified(&headers, post.post.version, post.post.updated_at) {
+            return Ok(response);
+        }
+
+        return Ok((
+            post_validators(post.post.version, post.post.updated_at),
+            post,
+        )
+            .into_response());
+    }
+
+    let post = state
+        .blog_service
+        .get_post(tenant.id, id, viewer_id)
+        .await?;
+    state.blog_service.record_view(id);
+
+    if let Some(response) = not_modified(&headers, post.version, post.updated_at) {
+        return Ok(response);
+    }
+
+    Ok((post_validators(post.version, post.updated_at), post).into_response())
+}
+
+/// Получить заголовки поста без его содержимого.
+///
+/// Позволяет проверить существование поста и его актуальность (`ETag`, `Last-Modified`)
+/// без чтения текста поста из базы данных.
+async fn head_post(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<CurrentTenant>,
+    viewer: Option<Extension<AuthUser>>,
+    Path(id): Path<i64>,
+    headers: HeaderMap,
+) -> Result<Response> {
+    let post = state
+        .blog_service
+        .get_post_meta(tenant.id, id, viewer.map(|Extension(user)| user.user_id))
+        .await?;
+
+    if let Some(response) = not_modified(&headers, post.version, post.updated_at) {
+        return Ok(response);
+    }
+
+    Ok(post_validators(post.version, post.updated_at).into_response())
 }
 
 /// Получить список постов с пагинацией.
+///
+/// По умолчанию возвращаются все поля, кроме полного содержимого поста. Посты возвращаются
+/// вместе с общим количеством постов, удовлетворяющих условиям отбора.
 async fn get_posts(
     State(state): State<AppState>,
+    Extension(tenant): Extension<CurrentTenant>,
+    viewer: Option<Extension<AuthUser>>,
+    Query(params): Query<PaginationParams>,
+    Query(include): Query<IncludeParams>,
+    Query(fields): Query<FieldsParams>,
+    Query(filter): Query<FilterParams>,
+) -> Result<Json<PostPage>> {
+    let filter = filter.into_filter(tenant.id, viewer.map(|Extension(user)| user.user_id));
+
+    post_page(&state, filter, params, include, fields).await
+}
+
+/// Получить публичный профиль автора по имени пользователя.
+async fn get_public_profile(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<CurrentTenant>,
+    Path(username): Path<String>,
+) -> Result<PublicProfile> {
+    Ok(state
+        .user_service
+        .get_public_profile(tenant.id, &username)
+        .await?)
+}
+
+/// Получить посты автора по имени пользователя.
+///
+/// Параметры пагинации, выборки полей и отбора такие же, как у списка постов.
+#[allow(clippy::too_many_arguments)]
+async fn get_user_posts(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<CurrentTenant>,
+    viewer: Option<Extension<AuthUser>>,
+    Path(username): Path<String>,
     Query(params): Query<PaginationParams>,
+    Query(include): Query<IncludeParams>,
+    Query(fields): Query<FieldsParams>,
+    Query(filter): Query<FilterParams>,
+) -> Result<Json<PostPage>> {
+    let author = state
+        .user_service
+        .get_public_profile(tenant.id, &username)
+        .await?;
+
+    let filter = PostFilter {
+        author_id: Some(author.id),
+        ..filter.into_filter(tenant.id, viewer.map(|Extension(user)| user.user_id))
+    };
+
+    post_page(&state, filter, params, include, fields).await
+}
+
+/// Получить страницу постов, удовлетворяющих условиям отбора, вместе с их общим количеством.
+async fn post_page(
+    state: &AppState,
+    filter: PostFilter,
+    params: PaginationParams,
+    include: IncludeParams,
+    fields: FieldsParams,
+) -> Result<Json<PostPage>> {
+    let page = params.validate(state)?;
+
+    let fields = match fields.fields {
+        Some(fields) => PostField::parse_list(&fields)?,
+        None if filter.sort == PostSort::Trending => PostField::TRENDING_LIST.to_vec(),
+        None => PostField::DEFAULT_LIST.to_vec(),
+    };
+
+    let posts = state
+        .blog_service
+        .get_post_previews(&fields, include.author(), &filter, page.limit, page.offset)
+        .await?;
+
+    Ok(Json(posts))
+}
+
+/// Получить посты по списку идентификаторов за один запрос.
+///
+/// Посты возвращаются в порядке запроса; отсутствующие и недоступные посты пропускаются.
+async fn get_posts_by_ids(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<CurrentTenant>,
+    viewer: Option<Extension<AuthUser>>,
+    Json(request): Json<GetPostsByIdsRequest>,
 ) -> Result<Json<Vec<Post>>> {
-    Ok(Json(
-        state
-            .blog_service
-            .get_posts(params.limit, params.offset)
-            .await?,
-    ))
+    let posts = state
+        .blog_service
+        .get_posts_by_ids(
+            tenant.id,
+            &request.ids,
+            viewer.map(|Extension(user)| user.user_id),
+        )
+        .await?;
+
+    Ok(Json(posts))
 }
 
 /// Обновить существующий пост.
+///
+/// Ожидаемая версия поста может быть передана в заголовке `If-Match` или в поле `expected_version`.
 async fn update_post(
     State(state): State<AppState>,
-    Extension(claims): Extension<Claims>,
+    Extension(tenant): Extension<CurrentTenant>,
+    Extension(user): Extension<AuthUser>,
     Path(id): Path<i64>,
+    headers: HeaderMap,
     Json(mut request): Json<UpdatePostRequest>,
-) -> Result<Post> {
+) -> Result<([(HeaderName, String); 1], Post)> {
+    request.validate().map_err(PostError::from)?;
+
     request.id = id;
 
-    Ok(state
+    if let Some(version) = parse_if_match(&headers)? {
+        request.expected_version = Some(version);
+    }
+
+    let post = state
         .blog_service
-        .update_post(request, claims.user_id)
-        .await?)
+        .update_post(tenant.id, request, user.user_id)
+        .await?;
+
+    Ok((post_etag(&post), post))
 }
 
 /// Удалить пост.
 async fn delete_post(
     State(state): State<AppState>,
-    Extension(claims): Extension<Claims>,
+    Extension(tenant): Extension<CurrentTenant>,
+    Extension(user): Extension<AuthUser>,
+    Path(id): Path<i64>,
+) -> Result<axum::http::StatusCode> {
+    state
+        .blog_service
+        .delete_post(tenant.id, id, user.user_id)
+        .await?;
+
+    Ok(axum::http::StatusCode::NO_CONTENT)
+}
+
+/// Удалить несколько постов пользователя в одной транзакции.
+///
+/// Для каждого поста возвращается результат удаления; отсутствующие и чужие посты
+/// не прерывают удаление остальных.
+async fn bulk_delete_posts(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<CurrentTenant>,
+    Extension(user): Extension<AuthUser>,
+    Json(request): Json<BulkDeleteRequest>,
+) -> Result<Json<Vec<BulkDeleteResult>>> {
+    let results = state
+        .blog_service
+        .bulk_delete_posts(tenant.id, &request.ids, user.user_id)
+        .await?;
+
+    Ok(Json(results))
+}
+
+/// Перевести пост в архив.
+async fn archive_post(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<CurrentTenant>,
+    Extension(user): Extension<AuthUser>,
+    Path(id): Path<i64>,
+) -> Result<([(HeaderName, String); 1], Post)> {
+    let post = state
+        .blog_service
+        .set_archived(tenant.id, id, true, user.user_id)
+        .await?;
+
+    Ok((post_etag(&post), post))
+}
+
+/// Вернуть пост из архива.
+async fn unarchive_post(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<CurrentTenant>,
+    Extension(user): Extension<AuthUser>,
+    Path(id): Path<i64>,
+) -> Result<([(HeaderName, String); 1], Post)> {
+    let post = state
+        .blog_service
+        .set_archived(tenant.id, id, false, user.user_id)
+        .await?;
+
+    Ok((post_etag(&post), post))
+}
+
+/// Добавить соавтора поста.
+async fn add_post_author(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<CurrentTenant>,
+    Extension(user): Extension<AuthUser>,
+    Path(id): Path<i64>,
+    Json(request): Json<AddPostAuthorRequest>,
+) -> Result<([(HeaderName, String); 1], Post)> {
+    let post = state
+        .blog_service
+        .add_post_author(tenant.id, id, request, user.user_id)
+        .await?;
+
+    Ok((post_etag(&post), post))
+}
+
+/// Создать ссылку для совместного доступа к посту.
+async fn create_share_link(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<CurrentTenant>,
+    Extension(user): Extension<AuthUser>,
+    Path(id): Path<i64>,
+    request: Option<Json<CreateShareLinkRequest>>,
+) -> Result<(axum::http::StatusCode, ShareLink)> {
+    let request = request.map(|Json(request)| request).unwrap_or_default();
+    request.validate().map_err(PostError::from)?;
+
+    let link = state
+        .blog_service
+        .create_share_link(tenant.id, id, request, user.user_id)
+        .await?;
+
+    Ok((axum::http::StatusCode::CREATED, link))
+}
+
+/// Получить пост по ссылке для совместного доступа.
+async fn get_shared_post(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<CurrentTenant>,
+    Path(token): Path<String>,
+) -> Result<Post> {
+    Ok(state
+        .blog_service
+        .get_shared_post(tenant.id, &token)
+        .await?)
+}
+
+/// Запретить управление API-ключами при авторизации самим API-ключом.
+fn ensure_not_api_key(user: &AuthUser) -> Result<(), ApiKeyError> {
+    match user.api_key_scope {
+        Some(_) => Err(ApiKeyError::Forbidden),
+        None => Ok(()),
+    }
+}
+
+/// Создать персональный API-ключ.
+async fn create_api_key(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Json(request): Json<CreateApiKeyRequest>,
+) -> Result<(axum::http::StatusCode, CreateApiKeyResponse)> {
+    ensure_not_api_key(&user)?;
+    request.validate().map_err(ApiKeyError::from)?;
+
+    Ok((
+        axum::http::StatusCode::CREATED,
+        state
+            .api_key_service
+            .create_api_key(request, user.user_id)
+            .await?,
+    ))
+}
+
+/// Получить список персональных API-ключей.
+async fn get_api_keys(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+) -> Result<Json<Vec<ApiKey>>> {
+    ensure_not_api_key(&user)?;
+
+    Ok(Json(
+        state.api_key_service.get_api_keys(user.user_id).await?,
+    ))
+}
+
+/// Отозвать персональный API-ключ.
+async fn revoke_api_key(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(id): Path<i64>,
+) -> Result<axum::http::StatusCode> {
+    ensure_not_api_key(&user)?;
+
+    state
+        .api_key_service
+        .revoke_api_key(id, user.user_id)
+        .await?;
+
+    Ok(axum::http::StatusCode::NO_CONTENT)
+}
+
+/// Запросить смену email-адреса (ссылка подтверждения отправляется на новый адрес).
+async fn change_email(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Json(request): Json<ChangeEmailRequest>,
+) -> Result<axum::http::StatusCode> {
+    ensure_not_api_key(&user)?;
+    request.validate().map_err(UserError::InvalidEmail)?;
+
+    state
+        .user_service
+        .request_email_change(user.user_id, request)
+        .await?;
+
+    Ok(axum::http::StatusCode::ACCEPTED)
+}
+
+/// Получить информацию о текущем пользователе.
+async fn get_current_user(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+) -> Result<Json<User>> {
+    Ok(Json(state.user_service.get_user(user.user_id).await?))
+}
+
+/// Изменить профиль текущего пользователя.
+///
+/// Новый email-адрес вступает в силу после перехода по ссылке из письма.
+async fn update_profile(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Json(request): Json<UpdateProfileRequest>,
+) -> Result<Json<User>> {
+    ensure_not_api_key(&user)?;
+    request.validate().map_err(UserError::InvalidEmail)?;
+
+    Ok(Json(
+        state
+            .user_service
+            .update_profile(user.user_id, request)
+            .await?,
+    ))
+}
+
+/// Получить историю входов пользователя.
+async fn get_logins(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Query(params): Query<PaginationParams>,
+) -> Result<Json<Vec<LoginRecord>>> {
+    ensure_not_api_key(&user)?;
+    let page = params.validate(&state)?;
+
+    Ok(Json(
+        state
+            .auth_service
+            .get_logins(user.user_id, page.limit, page.offset)
+            .await?,
+    ))
+}
+
+/// Получить настройки пользователя.
+async fn get_settings(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+) -> Result<UserSettings> {
+    Ok(state.user_service.get_settings(user.user_id).await?)
+}
+
+/// Сохранить настройки пользователя.
+///
+/// Настройки заменяются целиком: не переданные ключи принимают значения по умолчанию.
+async fn update_settings(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Json(settings): Json<UserSettings>,
+) -> Result<UserSettings> {
+    ensure_not_api_key(&user)?;
+    settings.validate().map_err(UserError::InvalidSettings)?;
+
+    Ok(state
+        .user_service
+        .update_settings(user.user_id, settings)
+        .await?)
+}
+
+/// Запросить выгрузку данных пользователя (архив готовится в фоне).
+async fn request_export(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+) -> Result<(axum::http::StatusCode, DataExport)> {
+    ensure_not_api_key(&user)?;
+
+    Ok((
+        axum::http::StatusCode::ACCEPTED,
+        state.export_service.request_export(user.user_id).await?,
+    ))
+}
+
+/// Скачать архив с данными пользователя.
+///
+/// Пока архив готовится, возвращается состояние выгрузки со статусом 202.
+async fn download_export(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+) -> Result<Response> {
+    ensure_not_api_key(&user)?;
+
+    let export = state.export_service.get_export(user.user_id).await?;
+
+    match export.status {
+        DataExportStatus::Ready => {
+            let archive = state.export_service.open_archive(user.user_id).await?;
+
+            Ok((
+                [
+                    (CONTENT_TYPE, "application/zip"),
+                    (
+                        CONTENT_DISPOSITION,
+                        "attachment; filename=\"blog-export.zip\"",
+                    ),
+                ],
+                Body::from_stream(ReaderStream::new(archive)),
+            )
+                .into_response())
+        }
+        DataExportStatus::Pending => Ok((axum::http::StatusCode::ACCEPTED, export).into_response()),
+        DataExportStatus::Failed => Err(ExportError::ExportFailed.into()),
+    }
+}
+
+/// Загрузить изображение (тело запроса - содержимое файла).
+async fn upload_media(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    body: Bytes,
+) -> Result<(axum::http::StatusCode, Media)> {
+    if body.is_empty() {
+        return Err(MediaError::InvalidImage("пустое тело запроса".to_string()).into());
+    }
+
+    Ok((
+        axum::http::StatusCode::CREATED,
+        state
+            .media_service
+            .upload(user.user_id, body.to_vec())
+            .await?,
+    ))
+}
+
+/// Получить информацию об изображении и его готовых вариантах.
+async fn get_media(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<CurrentTenant>,
+    Path(id): Path<i64>,
+) -> Result<Media> {
+    Ok(state.media_service.get_media(tenant.id, id).await?)
+}
+
+/// Получить изображение нужного размера (`?size=thumbnail|medium|original`).
+async fn get_media_image(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<CurrentTenant>,
+    Path(id): Path<i64>,
+    Query(params): Query<MediaSizeParams>,
+) -> Result<([(HeaderName, String); 1], Vec<u8>)> {
+    let (content_type, bytes) = state
+        .media_service
+        .get_image(tenant.id, id, params.size)
+        .await?;
+
+    Ok(([(CONTENT_TYPE, content_type)], bytes))
+}
+
+/// Загрузить аватар пользователя (тело запроса - содержимое изображения).
+async fn upload_avatar(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    body: Bytes,
+) -> Result<Json<User>> {
+    if body.is_empty() {
+        return Err(MediaError::InvalidImage("пустое тело запроса".to_string()).into());
+    }
+
+    let media = state
+        .media_service
+        .upload(user.user_id, body.to_vec())
+        .await?;
+
+    Ok(Json(
+        state.user_service.set_avatar(user.user_id, &media).await?,
+    ))
+}
+
+/// Удалить аватар пользователя.
+async fn remove_avatar(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+) -> Result<Json<User>> {
+    Ok(Json(state.user_service.remove_avatar(user.user_id).await?))
+}
+
+/// Получить статистику блога (только для администраторов).
+async fn get_admin_stats(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<CurrentTenant>,
+    Query(params): Query<StatsParams>,
+) -> Result<AdminStats> {
+    Ok(state
+        .admin_service
+        .get_stats(tenant.id, params.days()?)
+        .await?)
+}
+
+/// Получить отчет о содержимом блога в формате JSON или CSV (только для администраторов).
+async fn get_content_report(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<CurrentTenant>,
+    Query(params): Query<ContentReportParams>,
+) -> Result<Response> {
+    let report = state
+        .admin_service
+        .get_content_report(tenant.id, params.days()?)
+        .await?;
+
+    Ok(report.into_response_as(params.format))
+}
+
+/// Получить посты, отмеченные для проверки модератором (только для администраторов).
+async fn get_flagged_posts(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<CurrentTenant>,
+) -> Result<Json<Vec<PostModerationFlag>>> {
+    Ok(Json(
+        state.admin_service.get_flagged_posts(tenant.id).await?,
+    ))
+}
+
+/// Получить состояние флагов функциональности (только для администраторов).
+async fn get_features(State(state): State<AppState>) -> Json<Vec<FeatureFlag>> {
+    Json(state.admin_service.get_features())
+}
+
+/// Получить примененные и непримененные миграции БД (только для администраторов основного блога).
+async fn get_migration_status(State(state): State<AppState>) -> Result<MigrationStatus> {
+    Ok(state.admin_service.get_migration_status().await?)
+}
+
+/// Включить или отключить функцию (только для администраторов).
+async fn set_feature(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(name): Path<String>,
+    Json(request): Json<SetFeatureRequest>,
+) -> Result<FeatureFlag> {
+    Ok(state
+        .admin_service
+        .set_feature(&name, request.enabled, user.user_id)
+        .await?)
+}
+
+/// Вернуть флагу функциональности значение из конфигурации (только для администраторов).
+async fn reset_feature(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(name): Path<String>,
+) -> Result<FeatureFlag> {
+    Ok(state
+        .admin_service
+        .reset_feature(&name, user.user_id)
+        .await?)
+}
+
+/// Создать приглашение для регистрации (только для администраторов).
+async fn create_invite(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    request: Option<Json<CreateInviteRequest>>,
+) -> Result<(axum::http::StatusCode, Invite)> {
+    let request = request.map(|Json(request)| request).unwrap_or_default();
+    request
+        .validate()
+        .map_err(|e| AdminError::InvalidQuery(e.to_string()))?;
+
+    let invite = state
+        .admin_service
+        .create_invite(request, user.user_id)
+        .await?;
+
+    Ok((axum::http::StatusCode::CREATED, invite))
+}
+
+/// Получить список блогов инсталляции (только для администраторов основного блога).
+async fn get_tenants(State(state): State<AppState>) -> Result<Json<Vec<Tenant>>> {
+    Ok(Json(state.tenant_service.get_tenants().await?))
+}
+
+/// Создать блог (только для администраторов основного блога).
+async fn create_tenant(
+    State(state): State<AppState>,
+    Json(request): Json<CreateTenantRequest>,
+) -> Result<(axum::http::StatusCode, Tenant)> {
+    request.validate().map_err(TenantError::from)?;
+
+    Ok((
+        axum::http::StatusCode::CREATED,
+        state.tenant_service.create_tenant(request).await?,
+    ))
+}
+
+/// Удалить пустой блог (только для администраторов основного блога).
+async fn delete_tenant(
+    State(state): State<AppState>,
     Path(id): Path<i64>,
 ) -> Result<axum::http::StatusCode> {
-    state.blog_service.delete_post(id, claims.user_id).await?;
+    state.tenant_service.delete_tenant(id).await?;
 
     Ok(axum::http::StatusCode::NO_CONTENT)
 }
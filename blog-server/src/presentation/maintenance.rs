@@ -0,0 +1,250 @@
+//! Режим обслуживания.
+//!
+//! Пока включен флаг [`Feature::MaintenanceMode`], изменяющие запросы к HTTP и gRPC API
+//! отклоняются со статусом 503 (`UNAVAILABLE` для gRPC), а чтение данных остается доступным.
+//! Вход в систему и административные эндпоинты доступны, чтобы режим можно было выключить.
+
+use crate::domain::feature::Feature;
+use crate::infrastructure::features::FeatureFlags;
+use crate::presentation::ApiVersion;
+use axum::body::Bytes;
+use axum::http::header::CONTENT_TYPE;
+use axum::http::{HeaderValue, Method, Request, Response, StatusCode};
+use serde::Serialize;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+
+/// Описание ошибки, возвращаемое в режиме обслуживания.
+const MAINTENANCE_MESSAGE: &str =
+    "Сервер находится на обслуживании, изменение данных временно недоступно!";
+
+/// gRPC-методы, доступные в режиме обслуживания.
+const GRPC_READ_METHODS: &[&str] = &[
+    "Login",
+    "GetUser",
+    "GetCurrentUser",
+    "GetPost",
+    "GetPosts",
+    "GetPostsByIds",
+    "SearchPosts",
+    "GetSharedPost",
+    "GetAdminStats",
+];
+
+/// Префиксы HTTP-эндпоинтов (без `/api` и номера версии), изменяющие запросы к которым
+/// доступны в режиме обслуживания.
+const HTTP_ALLOWED_PREFIXES: &[&str] = &["/auth/login", "/auth/logout", "/posts/batch", "/admin/"];
+
+/// Префиксы HTTP-эндпоинтов (без `/api` и номера версии), которые изменяют данные
+/// в ответ на `GET`-запрос и поэтому недоступны в режиме обслуживания.
+const HTTP_WRITING_READ_PREFIXES: &[&str] = &["/auth/confirm-email"];
+
+/// Тело ответа об ошибке в режиме обслуживания.
+#[derive(Debug, Serialize)]
+struct MaintenanceErrorBody {
+    /// Код ошибки.
+    error: &'static str,
+
+    /// Описание ошибки.
+    message: &'static str,
+}
+
+/// Тело ответа, в котором можно передать описание ошибки.
+pub(crate) trait ErrorBody: Default + Send + 'static {
+    /// Создать тело ответа из байтов.
+    fn from_bytes(bytes: Bytes) -> Self;
+}
+
+impl ErrorBody for axum::body::Body {
+    fn from_bytes(bytes: Bytes) -> Self {
+        Self::from(bytes)
+    }
+}
+
+impl ErrorBody for tonic::body::Body {
+    fn from_bytes(bytes: Bytes) -> Self {
+        Self::new(axum::body::Body::from(bytes))
+    }
+}
+
+/// Уровень middleware, отклоняющий изменяющие запросы в режиме обслуживания.
+///
+/// Используется и HTTP-, и gRPC-сервером.
+#[derive(Debug, Clone)]
+pub(crate) struct MaintenanceLayer {
+    /// Флаги функциональности.
+    features: Arc<FeatureFlags>,
+}
+
+impl MaintenanceLayer {
+    /// Создать уровень middleware режима обслуживания.
+    pub(crate) fn new(features: Arc<FeatureFlags>) -> Self {
+        Self { features }
+    }
+}
+
+impl<S> Layer<S> for MaintenanceLayer {
+    type Service = MaintenanceService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        MaintenanceService {
+            inner,
+            features: self.features.clone(),
+        }
+    }
+}
+
+/// Сервис, отклоняющий изменяющие запросы в режиме обслуживания.
+#[derive(Debug, Clone)]
+pub(crate) struct MaintenanceService<S> {
+    /// Внутренний сервис.
+    inner: S,
+
+    /// Флаги функциональности.
+    features: Arc<FeatureFlags>,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for MaintenanceService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+    ResBody: ErrorBody,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        if self.features.is_enabled(Feature::MaintenanceMode) {
+            if is_grpc(&request) {
+                if !is_grpc_read(&request) {
+                    let response = tonic::Status::unavailable(MAINTENANCE_MESSAGE).into_http();
+                    return Box::pin(async move { Ok(response) });
+                }
+            } else if !is_http_read(&request) {
+                let response = maintenance_response();
+                return Box::pin(async move { Ok(response) });
+            }
+        }
+
+        Box::pin(self.inner.call(request))
+    }
+}
+
+/// Проверить, является ли запрос gRPC-вызовом.
+fn is_grpc<B>(request: &Request<B>) -> bool {
+    request
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/grpc"))
+}
+
+/// Проверить, допустим ли gRPC-вызов в режиме обслуживания.
+fn is_grpc_read<B>(request: &Request<B>) -> bool {
+    request
+        .uri()
+        .path()
+        .rsplit_once('/')
+        .is_some_and(|(_, method)| GRPC_READ_METHODS.contains(&method))
+}
+
+/// Проверить, допустим ли HTTP-запрос в режиме обслуживания.
+fn is_http_read<B>(request: &Request<B>) -> bool {
+    let path = endpoint_path(request.uri().path());
+
+    if matches!(
+        *request.method(),
+        Method::GET | Method::HEAD | Method::OPTIONS
+    ) {
+        return !HTTP_WRITING_READ_PREFIXES
+            .iter()
+            .any(|prefix| path.starts_with(prefix));
+    }
+
+    HTTP_ALLOWED_PREFIXES
+        .iter()
+        .any(|prefix| path.starts_with(prefix))
+}
+
+/// Путь эндпоинта без префикса `/api` и номера версии API.
+fn endpoint_path(path: &str) -> &str {
+    let path = path.strip_prefix("/api").unwrap_or(path);
+
+    ApiVersion::ALL
+        .iter()
+        .find_map(|version| {
+            path.strip_prefix('/')?
+                .strip_prefix(version.as_str())
+                .filter(|rest| rest.starts_with('/'))
+        })
+        .unwrap_or(path)
+}
+
+/// Сформировать HTTP-ответ об ошибке режима обслуживания.
+fn maintenance_response<B: ErrorBody>() -> Response<B> {
+    let body = serde_json::to_vec(&MaintenanceErrorBody {
+        error: "maintenance",
+        message: MAINTENANCE_MESSAGE,
+    })
+    .unwrap_or_default();
+
+    let mut response = Response::new(B::from_bytes(body.into()));
+    *response.status_mut() = StatusCode::SERVICE_UNAVAILABLE;
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(method: Method, path: &str) -> Request<()> {
+        Request::builder()
+            .method(method)
+            .uri(path)
+            .body(())
+            .unwrap()
+    }
+
+    #[test]
+    fn get_requests_are_reads() {
+        assert!(is_http_read(&request(Method::GET, "/api/v1/posts")));
+        assert!(is_http_read(&request(Method::HEAD, "/api/v1/posts/1")));
+        assert!(is_http_read(&request(Method::GET, "/api/posts/1")));
+    }
+
+    #[test]
+    fn email_confirmation_is_blocked() {
+        for path in [
+            "/api/v1/auth/confirm-email?token=abc",
+            "/api/auth/confirm-email?token=abc",
+        ] {
+            assert!(!is_http_read(&request(Method::GET, path)), "{path}");
+        }
+    }
+
+    #[test]
+    fn writes_are_blocked_except_allowed_prefixes() {
+        assert!(!is_http_read(&request(Method::POST, "/api/v1/posts")));
+        assert!(!is_http_read(&request(
+            Method::POST,
+            "/api/v1/auth/register"
+        )));
+        assert!(is_http_read(&request(Method::POST, "/api/v1/auth/login")));
+        assert!(is_http_read(&request(
+            Method::PUT,
+            "/api/v1/admin/features"
+        )));
+    }
+}
@@ -1,32 +1,286 @@
 //! Функционал middleware.
 
-use crate::presentation::AppState;
+use crate::domain::api_key::ApiKeyScope;
+use crate::domain::error::{AdminError, TenantError};
+use crate::domain::tenant::DEFAULT_TENANT_ID;
+use crate::presentation::session::{SESSION_COOKIE, read_cookie, verify_csrf};
+use crate::presentation::{ApiVersion, AppState};
 use axum::{
-    extract::{Request, State},
-    http::StatusCode,
+    Extension,
+    extract::{ConnectInfo, Request, State},
+    http::{
+        HeaderMap, HeaderValue, Method, StatusCode, Uri,
+        header::{HOST, LINK},
+    },
     middleware::Next,
-    response::Response,
+    response::{IntoResponse, Response},
 };
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+/// Блог (арендатор), к которому относится запрос.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct CurrentTenant {
+    /// Идентификатор блога.
+    pub id: i64,
+}
+
+/// Авторизованный пользователь, от имени которого выполняется запрос.
+#[derive(Debug, Clone)]
+pub(crate) struct AuthUser {
+    /// Идентификатор пользователя.
+    pub user_id: i64,
+
+    /// Область действия API-ключа (отсутствует при авторизации JWT-токеном).
+    pub api_key_scope: Option<ApiKeyScope>,
+}
+
+/// Middleware функция для определения блога, к которому относится запрос.
+///
+/// Блог выбирается по короткому имени из заголовка [`AppState::tenant_header`], а при его
+/// отсутствии - по доменному имени, по которому обратился клиент. Запрос к несуществующему блогу отклоняется со статусом 404.
+pub(crate) async fn tenant_resolver(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    match resolve_tenant(&state, request.uri(), request.headers()).await {
+        Ok(tenant) => {
+            request.extensions_mut().insert(tenant);
+            next.run(request).await
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Определить блог запроса по его адресу и заголовкам.
+pub(crate) async fn resolve_tenant(
+    state: &AppState,
+    uri: &Uri,
+    headers: &HeaderMap,
+) -> Result<CurrentTenant, TenantError> {
+    let slug = headers
+        .get(&state.tenant_header)
+        .and_then(|v| v.to_str().ok());
+
+    let id = state
+        .tenant_service
+        .resolve(slug, request_host(uri, headers))
+        .await?;
+
+    Ok(CurrentTenant { id })
+}
+
+/// Доменное имя, по которому клиент обратился к серверу.
+///
+/// В HTTP/2 оно передается в псевдозаголовке `:authority` и попадает в URI запроса,
+/// в HTTP/1.1 - в заголовке `Host`.
+pub(crate) fn request_host<'a>(uri: &'a Uri, headers: &'a HeaderMap) -> Option<&'a str> {
+    uri.authority()
+        .map(|authority| authority.as_str())
+        .or_else(|| headers.get(HOST).and_then(|v| v.to_str().ok()))
+}
 
 /// Middleware функция для валидации JWT токена.
+///
+/// Токен берется из заголовка `Authorization`, а при включенных сессионных cookie - из cookie.
+/// Изменяющие запросы, авторизованные через cookie, дополнительно проходят проверку CSRF.
+/// Вместо JWT-токена может быть передан персональный API-ключ (`Authorization: ApiKey ...`).
 pub(crate) async fn jwt_validator(
     State(state): State<AppState>,
+    Extension(tenant): Extension<CurrentTenant>,
     mut request: Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
-    let token = request
-        .headers()
-        .get("Authorization")
-        .and_then(|v| v.to_str().ok())
-        .and_then(|s| s.strip_prefix("Bearer "))
+    let user = authenticate(&state, tenant, request.method(), request.headers())
+        .await?
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+
+    tracing::Span::current().record("user_id", user.user_id);
+    request.extensions_mut().insert(user);
+
+    Ok(next.run(request).await)
+}
+
+/// Middleware функция для необязательной авторизации.
+///
+/// Если запрос содержит корректные данные авторизации, пользователь добавляется в расширения
+/// запроса; в остальных случаях запрос обрабатывается как анонимный.
+pub(crate) async fn optional_auth(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<CurrentTenant>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    if let Ok(Some(user)) = authenticate(&state, tenant, request.method(), request.headers()).await
+    {
+        tracing::Span::current().record("user_id", user.user_id);
+        request.extensions_mut().insert(user);
+    }
+
+    next.run(request).await
+}
+
+/// Middleware функция для проверки прав администратора.
+///
+/// Должна выполняться после [`jwt_validator`]; административные эндпоинты недоступны
+/// при авторизации API-ключом.
+pub(crate) async fn admin_validator(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let user = request
+        .extensions()
+        .get::<AuthUser>()
         .ok_or(StatusCode::UNAUTHORIZED)?;
 
+    if user.api_key_scope.is_some() {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    match state.admin_service.ensure_admin(user.user_id).await {
+        Ok(()) => Ok(next.run(request).await),
+        Err(AdminError::Forbidden) => Err(StatusCode::FORBIDDEN),
+        Err(e) => {
+            tracing::error!("Не удалось проверить права администратора: {e}");
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Middleware функция для проверки прав администратора основного блога.
+///
+/// Должна выполняться после [`admin_validator`]: управление блогами и флагами
+/// функциональности, общими для всей инсталляции, доступно только администраторам
+/// основного блога.
+pub(crate) async fn platform_admin_validator(
+    Extension(tenant): Extension<CurrentTenant>,
+    request: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    if tenant.id != DEFAULT_TENANT_ID {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    Ok(next.run(request).await)
+}
+
+/// Определить пользователя, от имени которого выполняется запрос.
+///
+/// Возвращает `None`, если запрос не содержит данных авторизации. Данные авторизации
+/// пользователя другого блога отклоняются.
+async fn authenticate(
+    state: &AppState,
+    tenant: CurrentTenant,
+    method: &Method,
+    headers: &HeaderMap,
+) -> Result<Option<AuthUser>, StatusCode> {
+    let authorization = headers.get("Authorization").and_then(|v| v.to_str().ok());
+
+    if let Some(key) = authorization.and_then(|s| s.strip_prefix("ApiKey ")) {
+        let owner = state
+            .api_key_service
+            .authenticate(key)
+            .await
+            .map_err(|_| StatusCode::UNAUTHORIZED)?;
+
+        if owner.tenant_id != tenant.id {
+            return Err(StatusCode::UNAUTHORIZED);
+        }
+
+        if owner.scope == ApiKeyScope::Read && !method.is_safe() {
+            return Err(StatusCode::FORBIDDEN);
+        }
+
+        return Ok(Some(AuthUser {
+            user_id: owner.user_id,
+            api_key_scope: Some(owner.scope),
+        }));
+    }
+
+    let bearer = authorization.and_then(|s| s.strip_prefix("Bearer "));
+
+    let token = match (bearer, &state.session_cookies) {
+        (Some(token), _) => token,
+        (None, Some(_)) => {
+            let Some(token) = read_cookie(headers, SESSION_COOKIE) else {
+                return Ok(None);
+            };
+
+            if !method.is_safe() && !verify_csrf(headers) {
+                return Err(StatusCode::FORBIDDEN);
+            }
+
+            token
+        }
+        (None, None) => return Ok(None),
+    };
+
     let claims = state
         .jwt_service
         .verify_token(token)
         .map_err(|_| StatusCode::UNAUTHORIZED)?;
 
-    request.extensions_mut().insert(claims);
+    if claims.tenant_id != tenant.id {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
 
-    Ok(next.run(request).await)
+    Ok(Some(AuthUser {
+        user_id: claims.user_id,
+        api_key_scope: None,
+    }))
+}
+
+/// Middleware функция для устаревшего пути API без номера версии (`/api/...`).
+///
+/// Добавляет к ответу заголовок `Deprecation` и ссылку на тот же эндпоинт в актуальной версии API.
+pub(crate) async fn deprecated_api(request: Request, next: Next) -> Response {
+    let successor = format!(
+        "</api/{}{}>; rel=\"successor-version\"",
+        ApiVersion::LEGACY.as_str(),
+        request.uri().path()
+    );
+
+    let mut response = next.run(request).await;
+
+    let headers = response.headers_mut();
+    headers.insert("deprecation", HeaderValue::from_static("true"));
+
+    if let Ok(link) = HeaderValue::from_str(&successor) {
+        headers.insert(LINK, link);
+    }
+
+    response
+}
+
+/// Middleware функция для запросов, пришедших через Unix-сокет.
+///
+/// У таких соединений нет IP-адреса клиента, поэтому он берется из заголовков
+/// `X-Forwarded-For` или `X-Real-IP`, выставленных локальным обратным прокси, а при их
+/// отсутствии клиентом считается локальный хост. Запросы по TCP не изменяются.
+pub(crate) async fn unix_socket_peer(mut request: Request, next: Next) -> Response {
+    if request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .is_none()
+    {
+        let header = |name: &str| {
+            request
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.split(',').next())
+                .and_then(|v| v.trim().parse::<IpAddr>().ok())
+        };
+
+        let ip = header("x-forwarded-for")
+            .or_else(|| header("x-real-ip"))
+            .unwrap_or(IpAddr::V4(Ipv4Addr::LOCALHOST));
+
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(SocketAddr::new(ip, 0)));
+    }
+
+    next.run(request).await
 }
@@ -1,43 +1,256 @@
+pub(crate) mod caching;
+pub(crate) mod cors;
+pub(crate) mod frontend;
 pub(crate) mod grpc_service;
+mod health;
 mod http_handlers;
+pub(crate) mod maintenance;
 pub(crate) mod middleware;
+mod negotiation;
+pub(crate) mod security_headers;
+pub(crate) mod session;
 
-use http_handlers::api;
+use http_handlers::{well_known, with_route_limit};
 use std::convert::Infallible;
 
+use crate::application::admin_service::AdminService;
+use crate::application::api_key_service::ApiKeyService;
 use crate::application::auth_service::AuthService;
 use crate::application::blog_service::BlogService;
+use crate::application::export_service::ExportService;
+use crate::application::media_service::MediaService;
+use crate::application::tenant_service::TenantService;
+use crate::application::user_service::UserService;
+use crate::domain::settings::MAX_POSTS_PER_PAGE;
+use crate::infrastructure::database::DatabaseHealth;
 use crate::infrastructure::jwt::JwtService;
+use crate::presentation::caching::CacheTtls;
+use crate::presentation::middleware::{deprecated_api, tenant_resolver};
+use crate::presentation::negotiation::content_negotiation;
+use crate::presentation::session::SessionCookies;
 use axum::Router;
 use axum::extract::Request;
+use axum::http::{HeaderName, StatusCode};
 use axum::response::IntoResponse;
 use axum::routing::Route;
+use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 use tonic::codegen::Service;
+use tower::limit::GlobalConcurrencyLimitLayer;
 use tower::{Layer, ServiceBuilder};
 
+/// Заголовок с коротким именем блога по умолчанию.
+pub(crate) const DEFAULT_TENANT_HEADER: &str = "x-tenant";
+
+/// Ограничения числа одновременно обрабатываемых запросов и времени обработки
+/// для группы эндпоинтов.
+#[derive(Debug, Clone)]
+pub(crate) struct RouteLimit {
+    /// Лимит одновременно обрабатываемых запросов, общий для всех эндпоинтов группы.
+    pub concurrency: GlobalConcurrencyLimitLayer,
+
+    /// Максимальное время обработки запроса (по его истечении возвращается статус 408).
+    pub timeout: Duration,
+}
+
+impl RouteLimit {
+    /// Создать ограничения для группы эндпоинтов.
+    pub(crate) fn new(concurrency: usize, timeout: Duration) -> Self {
+        Self {
+            concurrency: GlobalConcurrencyLimitLayer::new(concurrency),
+            timeout,
+        }
+    }
+}
+
+/// Ограничения нагрузки для групп эндпоинтов.
+#[derive(Debug, Clone)]
+pub(crate) struct RouteLimits {
+    /// Эндпоинты авторизации и учетных записей пользователей.
+    pub auth: RouteLimit,
+
+    /// Публичные эндпоинты чтения постов и изображений.
+    pub read: RouteLimit,
+
+    /// Эндпоинты изменения данных и административные эндпоинты.
+    pub write: RouteLimit,
+}
+
+/// Ограничения размера тела запроса для групп эндпоинтов (в байтах).
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BodyLimits {
+    /// Эндпоинты авторизации и пользователей.
+    pub auth: usize,
+
+    /// Эндпоинты постов.
+    pub posts: usize,
+
+    /// Эндпоинты загрузки изображений.
+    pub media: usize,
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct AppState {
     pub auth_service: Arc<AuthService>,
     pub blog_service: Arc<BlogService>,
     pub jwt_service: Arc<JwtService>,
+    pub api_key_service: Arc<ApiKeyService>,
+    pub media_service: Arc<MediaService>,
+    pub user_service: Arc<UserService>,
+    pub export_service: Arc<ExportService>,
+    pub admin_service: Arc<AdminService>,
+    pub tenant_service: Arc<TenantService>,
+    pub session_cookies: Option<SessionCookies>,
+
+    /// Заголовок с кодом страны клиента, выставляемый обратным прокси.
+    pub country_header: Option<HeaderName>,
+
+    /// Заголовок с коротким именем блога, к которому относится запрос.
+    pub tenant_header: HeaderName,
+
+    /// Время кэширования ответов публичных эндпоинтов.
+    pub cache_ttls: CacheTtls,
+
+    /// Максимальное количество элементов на странице списка.
+    pub max_page_size: i64,
+
+    /// Состояние соединений с БД для проверки готовности (`/readyz`).
+    pub database_health: DatabaseHealth,
+
+    /// Публичный адрес сервера (без завершающего `/`) для абсолютных ссылок.
+    pub public_url: String,
 }
 
 impl AppState {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         auth_service: Arc<AuthService>,
         blog_service: Arc<BlogService>,
         jwt_service: Arc<JwtService>,
+        api_key_service: Arc<ApiKeyService>,
+        media_service: Arc<MediaService>,
+        user_service: Arc<UserService>,
+        export_service: Arc<ExportService>,
+        admin_service: Arc<AdminService>,
+        tenant_service: Arc<TenantService>,
     ) -> Self {
         Self {
             auth_service,
             blog_service,
             jwt_service,
+            api_key_service,
+            media_service,
+            user_service,
+            export_service,
+            admin_service,
+            tenant_service,
+            session_cookies: None,
+            country_header: None,
+            tenant_header: HeaderName::from_static(DEFAULT_TENANT_HEADER),
+            cache_ttls: CacheTtls::default(),
+            max_page_size: MAX_POSTS_PER_PAGE,
+            database_health: DatabaseHealth::default(),
+            public_url: String::new(),
+        }
+    }
+
+    /// Задать время кэширования ответов публичных эндпоинтов.
+    pub(crate) fn with_cache_ttls(mut self, cache_ttls: CacheTtls) -> Self {
+        self.cache_ttls = cache_ttls;
+        self
+    }
+
+    /// Задать источник состояния соединений с БД.
+    pub(crate) fn with_database_health(mut self, database_health: DatabaseHealth) -> Self {
+        self.database_health = database_health;
+        self
+    }
+
+    /// Задать максимальное количество элементов на странице списка.
+    pub(crate) fn with_max_page_size(mut self, max_page_size: i64) -> Self {
+        self.max_page_size = max_page_size;
+        self
+    }
+
+    /// Задать публичный адрес сервера.
+    pub(crate) fn with_public_url(mut self, public_url: &str) -> Self {
+        self.public_url = public_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Определять блог запроса по указанному заголовку.
+    pub(crate) fn with_tenant_header(mut self, tenant_header: HeaderName) -> Self {
+        self.tenant_header = tenant_header;
+        self
+    }
+
+    /// Определять страну клиента по заголовку запроса.
+    pub(crate) fn with_country_header(mut self, country_header: HeaderName) -> Self {
+        self.country_header = Some(country_header);
+        self
+    }
+
+    /// Включить авторизацию через сессионные cookie.
+    pub(crate) fn with_session_cookies(mut self, session_cookies: SessionCookies) -> Self {
+        self.session_cookies = Some(session_cookies);
+        self
+    }
+}
+
+/// Версия HTTP API.
+///
+/// Каждая версия монтируется под `/api/{версия}` со своим набором обработчиков,
+/// поэтому несовместимые изменения выпускаются новой версией рядом с существующими.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ApiVersion {
+    /// Первая версия API.
+    V1,
+}
+
+impl ApiVersion {
+    /// Все поддерживаемые версии API.
+    pub(crate) const ALL: &'static [ApiVersion] = &[ApiVersion::V1];
+
+    /// Версия, доступная по устаревшему пути `/api` без номера версии.
+    pub(crate) const LEGACY: ApiVersion = ApiVersion::V1;
+
+    /// Сегмент пути с номером версии.
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            ApiVersion::V1 => "v1",
         }
     }
+
+    /// Создать роутер с обработчиками версии.
+    ///
+    /// Неизвестные пути внутри версии API получают статус 404, а не страницу фронтенда.
+    fn router(&self, state: AppState, body_limits: BodyLimits, limits: &RouteLimits) -> Router {
+        let router = match self {
+            ApiVersion::V1 => http_handlers::v1(state, body_limits, limits),
+        };
+
+        router.fallback(not_found)
+    }
+}
+
+/// Ответ на запрос к несуществующему эндпоинту.
+async fn not_found() -> StatusCode {
+    StatusCode::NOT_FOUND
 }
 
-pub(crate) fn create_router<L>(state: AppState, middleware: ServiceBuilder<L>) -> Router
+/// Создать роутер HTTP API.
+///
+/// Если указан каталог фронтенда, его файлы раздаются по адресу `/` без ограничения
+/// частоты запросов: браузер загружает их пачкой при открытии страницы. Эндпоинты проверки
+/// состояния (`/healthz`, `/readyz`) также не ограничиваются и не зависят от блога запроса.
+pub(crate) fn create_router<L>(
+    state: AppState,
+    body_limits: BodyLimits,
+    route_limits: RouteLimits,
+    middleware: ServiceBuilder<L>,
+    frontend_dir: Option<&Path>,
+) -> Router
 where
     L: Layer<Route> + Clone + Send + Sync + 'static,
     L::Service: Service<Request> + Clone + Send + Sync + 'static,
@@ -45,5 +258,41 @@ where
     <L::Service as Service<Request>>::Error: Into<Infallible> + 'static,
     <L::Service as Service<Request>>::Future: Send + 'static,
 {
-    Router::new().nest("/api", api(state)).layer(middleware)
+    let router = ApiVersion::ALL
+        .iter()
+        .fold(Router::new(), |router, version| {
+            router.nest(
+                &format!("/api/{}", version.as_str()),
+                version.router(state.clone(), body_limits, &route_limits),
+            )
+        });
+
+    let health = health::health(state.database_health.clone());
+
+    let router = router
+        .nest(
+            "/api",
+            ApiVersion::LEGACY
+                .router(state.clone(), body_limits, &route_limits)
+                .layer(axum::middleware::from_fn(deprecated_api)),
+        )
+        .nest(
+            "/.well-known",
+            with_route_limit(well_known(state.clone()), &route_limits.read).fallback(not_found),
+        )
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            tenant_resolver,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            body_limits.auth.max(body_limits.posts),
+            content_negotiation,
+        ))
+        .layer(middleware)
+        .merge(health);
+
+    match frontend_dir {
+        Some(dir) => router.merge(frontend::frontend(dir, state)),
+        None => router,
+    }
 }
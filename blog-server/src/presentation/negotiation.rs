@@ -0,0 +1,144 @@
+//! Согласование формата данных HTTP API.
+//!
+//! Помимо JSON поддерживается MessagePack: тело запроса с `Content-Type: application/msgpack`
+//! перекодируется в JSON перед передачей обработчику, а JSON-ответ перекодируется в MessagePack,
+//! если клиент передал `Accept: application/msgpack`. Обработчики работают только с JSON.
+
+use axum::body::{Body, Bytes};
+use axum::extract::{Request, State};
+use axum::http::header::{ACCEPT, CONTENT_LENGTH, CONTENT_TYPE, VARY};
+use axum::http::{HeaderMap, HeaderValue, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+/// MIME-тип MessagePack.
+const MSGPACK: &str = "application/msgpack";
+
+/// Устаревший MIME-тип MessagePack, который также принимается от клиентов.
+const MSGPACK_LEGACY: &str = "application/x-msgpack";
+
+/// MIME-тип JSON.
+const JSON: &str = "application/json";
+
+/// Middleware функция для согласования формата тел запросов и ответов.
+///
+/// Тело запроса в формате MessagePack читается не больше `body_limit` байт.
+pub(crate) async fn content_negotiation(
+    State(body_limit): State<usize>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let accepts_msgpack = wants_msgpack(request.headers());
+
+    let request = if is_msgpack(request.headers().get(CONTENT_TYPE)) {
+        match msgpack_to_json(request, body_limit).await {
+            Ok(request) => request,
+            Err(status) => return status.into_response(),
+        }
+    } else {
+        request
+    };
+
+    let mut response = next.run(request).await;
+
+    if !is_json(response.headers().get(CONTENT_TYPE)) {
+        return response;
+    }
+
+    response
+        .headers_mut()
+        .append(VARY, HeaderValue::from_static("accept"));
+
+    if accepts_msgpack {
+        json_to_msgpack(response).await
+    } else {
+        response
+    }
+}
+
+/// Проверить, запрашивает ли клиент ответ в формате MessagePack.
+fn wants_msgpack(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(is_msgpack_type)
+}
+
+/// Проверить, указан ли в заголовке MIME-тип MessagePack.
+fn is_msgpack(value: Option<&HeaderValue>) -> bool {
+    value
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(is_msgpack_type)
+}
+
+/// Проверить, указан ли в заголовке MIME-тип JSON.
+fn is_json(value: Option<&HeaderValue>) -> bool {
+    value
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| essence(value).eq_ignore_ascii_case(JSON))
+}
+
+/// Проверить, является ли MIME-тип типом MessagePack.
+fn is_msgpack_type(media_type: &str) -> bool {
+    let essence = essence(media_type);
+
+    essence.eq_ignore_ascii_case(MSGPACK) || essence.eq_ignore_ascii_case(MSGPACK_LEGACY)
+}
+
+/// MIME-тип без параметров.
+fn essence(media_type: &str) -> &str {
+    media_type.split(';').next().unwrap_or_default().trim()
+}
+
+/// Перекодировать тело запроса из MessagePack в JSON.
+async fn msgpack_to_json(request: Request, body_limit: usize) -> Result<Request, StatusCode> {
+    let (mut parts, body) = request.into_parts();
+
+    let bytes = axum::body::to_bytes(body, body_limit)
+        .await
+        .map_err(|_| StatusCode::PAYLOAD_TOO_LARGE)?;
+
+    let value =
+        rmp_serde::from_slice::<serde_json::Value>(&bytes).map_err(|_| StatusCode::BAD_REQUEST)?;
+
+    let json = serde_json::to_vec(&value).map_err(|_| StatusCode::BAD_REQUEST)?;
+
+    parts
+        .headers
+        .insert(CONTENT_TYPE, HeaderValue::from_static(JSON));
+    parts.headers.remove(CONTENT_LENGTH);
+
+    Ok(Request::from_parts(parts, Body::from(json)))
+}
+
+/// Перекодировать JSON-ответ в MessagePack.
+///
+/// Если ответ не удается перекодировать, он возвращается в исходном виде.
+async fn json_to_msgpack(response: Response) -> Response {
+    let (mut parts, body) = response.into_parts();
+
+    let bytes = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::error!("Не удалось прочитать тело ответа: {e}");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let encoded = serde_json::from_slice::<serde_json::Value>(&bytes)
+        .ok()
+        .and_then(|value| rmp_serde::to_vec_named(&value).ok());
+
+    let Some(encoded) = encoded else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+
+    parts
+        .headers
+        .insert(CONTENT_TYPE, HeaderValue::from_static(MSGPACK));
+    parts.headers.remove(CONTENT_LENGTH);
+
+    Response::from_parts(parts, Body::from(Bytes::from(encoded)))
+}
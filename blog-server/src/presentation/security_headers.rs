@@ -0,0 +1,93 @@
+//! Заголовки безопасности HTTP-ответов.
+//!
+//! К каждому ответу добавляются `Content-Security-Policy`, `X-Content-Type-Options`,
+//! `X-Frame-Options` и `Referrer-Policy`, если обработчик не выставил их сам. Политика
+//! CSP по умолчанию зависит от того, раздает ли сервер WASM-фронтенд: для API страницы
+//! не нужны никакие ресурсы, а фронтенду нужны свои скрипты, стили и WebAssembly.
+
+use axum::extract::{Request, State};
+use axum::http::header::{
+    CONTENT_SECURITY_POLICY, REFERRER_POLICY, X_CONTENT_TYPE_OPTIONS, X_FRAME_OPTIONS,
+};
+use axum::http::{HeaderName, HeaderValue};
+use axum::middleware::Next;
+use axum::response::Response;
+use std::sync::Arc;
+
+/// Политика CSP для сервера, раздающего только API.
+pub(crate) const API_CONTENT_SECURITY_POLICY: &str = "default-src 'none'; frame-ancestors 'none'";
+
+/// Политика CSP для сервера, раздающего WASM-фронтенд.
+///
+/// Разметка фронтенда использует атрибуты `style`, а изображения постов и аватары
+/// могут находиться на сторонних HTTPS-адресах.
+pub(crate) const FRONTEND_CONTENT_SECURITY_POLICY: &str = "default-src 'self'; \
+    script-src 'self' 'wasm-unsafe-eval'; style-src 'self' 'unsafe-inline'; \
+    img-src 'self' data: blob: https:; connect-src 'self'; object-src 'none'; \
+    base-uri 'self'; frame-ancestors 'none'";
+
+/// Значение `X-Frame-Options` по умолчанию.
+pub(crate) const DEFAULT_FRAME_OPTIONS: &str = "DENY";
+
+/// Значение `Referrer-Policy` по умолчанию.
+pub(crate) const DEFAULT_REFERRER_POLICY: &str = "strict-origin-when-cross-origin";
+
+/// Набор заголовков безопасности, добавляемых к ответам.
+#[derive(Debug, Clone)]
+pub(crate) struct SecurityHeaders {
+    /// Заголовки и их значения.
+    headers: Vec<(HeaderName, HeaderValue)>,
+}
+
+impl SecurityHeaders {
+    /// Создать набор заголовков.
+    ///
+    /// `X-Content-Type-Options: nosniff` добавляется всегда; остальные заголовки
+    /// с пустым значением не добавляются.
+    pub(crate) fn new(
+        content_security_policy: &str,
+        frame_options: &str,
+        referrer_policy: &str,
+    ) -> anyhow::Result<Self> {
+        let mut headers = vec![(X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff"))];
+
+        for (name, value) in [
+            (CONTENT_SECURITY_POLICY, content_security_policy),
+            (X_FRAME_OPTIONS, frame_options),
+            (REFERRER_POLICY, referrer_policy),
+        ] {
+            let value = value.trim();
+
+            if value.is_empty() {
+                continue;
+            }
+
+            let value = HeaderValue::from_str(value)
+                .map_err(|_| anyhow::anyhow!("Некорректное значение заголовка {name}: {value}"))?;
+
+            headers.push((name, value));
+        }
+
+        Ok(Self { headers })
+    }
+}
+
+/// Middleware функция, добавляющая заголовки безопасности к ответу.
+///
+/// Заголовки, уже выставленные обработчиком, не перезаписываются.
+pub(crate) async fn security_headers(
+    State(security): State<Arc<SecurityHeaders>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let mut response = next.run(request).await;
+    let headers = response.headers_mut();
+
+    for (name, value) in &security.headers {
+        if !headers.contains_key(name) {
+            headers.insert(name.clone(), value.clone());
+        }
+    }
+
+    response
+}
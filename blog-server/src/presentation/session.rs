@@ -0,0 +1,108 @@
+//! Авторизация через сессионные cookie с защитой от CSRF.
+//!
+//! При входе сервер выставляет HttpOnly cookie с JWT-токеном и отдельную cookie с CSRF-токеном,
+//! доступную клиентскому коду. Изменяющие запросы, авторизованные через cookie, должны
+//! дублировать CSRF-токен в заголовке `X-CSRF-Token` (double-submit).
+
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use axum::http::header::{COOKIE, SET_COOKIE};
+use axum::http::{HeaderMap, HeaderName};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use std::time::Duration;
+
+/// Имя cookie с JWT-токеном.
+pub(crate) const SESSION_COOKIE: &str = "blog_session";
+
+/// Имя cookie с CSRF-токеном.
+pub(crate) const CSRF_COOKIE: &str = "blog_csrf";
+
+/// Заголовок, в котором клиент дублирует CSRF-токен.
+pub(crate) const CSRF_HEADER: &str = "x-csrf-token";
+
+/// Настройки сессионных cookie.
+#[derive(Debug, Clone)]
+pub(crate) struct SessionCookies {
+    /// Выставлять атрибут `Secure` (только HTTPS).
+    secure: bool,
+
+    /// Время жизни cookie.
+    max_age: Duration,
+}
+
+impl SessionCookies {
+    /// Создать настройки сессионных cookie.
+    pub(crate) fn new(secure: bool, max_age: Duration) -> Self {
+        Self { secure, max_age }
+    }
+
+    /// Заголовки, устанавливающие сессию для выданного JWT-токена.
+    pub(crate) fn issue(&self, token: &str) -> Vec<(HeaderName, String)> {
+        let max_age = self.max_age.as_secs();
+
+        vec![
+            (
+                SET_COOKIE,
+                self.cookie(SESSION_COOKIE, token, max_age, true),
+            ),
+            (
+                SET_COOKIE,
+                self.cookie(CSRF_COOKIE, &generate_csrf_token(), max_age, false),
+            ),
+        ]
+    }
+
+    /// Заголовки, удаляющие сессию.
+    pub(crate) fn clear(&self) -> Vec<(HeaderName, String)> {
+        vec![
+            (SET_COOKIE, self.cookie(SESSION_COOKIE, "", 0, true)),
+            (SET_COOKIE, self.cookie(CSRF_COOKIE, "", 0, false)),
+        ]
+    }
+
+    /// Сформировать значение заголовка `Set-Cookie`.
+    fn cookie(&self, name: &str, value: &str, max_age: u64, http_only: bool) -> String {
+        let mut cookie = format!("{name}={value}; Path=/; Max-Age={max_age}; SameSite=Strict");
+
+        if http_only {
+            cookie.push_str("; HttpOnly");
+        }
+
+        if self.secure {
+            cookie.push_str("; Secure");
+        }
+
+        cookie
+    }
+}
+
+/// Получить значение cookie из заголовков запроса.
+pub(crate) fn read_cookie<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get_all(COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+        .filter(|value| !value.is_empty())
+}
+
+/// Проверить, что CSRF-токен из заголовка совпадает с токеном из cookie.
+pub(crate) fn verify_csrf(headers: &HeaderMap) -> bool {
+    let header = headers.get(CSRF_HEADER).and_then(|v| v.to_str().ok());
+
+    match (read_cookie(headers, CSRF_COOKIE), header) {
+        (Some(cookie), Some(header)) => cookie == header,
+        _ => false,
+    }
+}
+
+/// Сгенерировать случайный CSRF-токен.
+fn generate_csrf_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+
+    URL_SAFE_NO_PAD.encode(bytes)
+}
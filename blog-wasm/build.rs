@@ -0,0 +1,11 @@
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tonic_prost_build::configure()
+        .build_server(false)
+        .build_client(true)
+        .build_transport(false)
+        .compile_protos(&["proto/blog.proto"], &["proto"])?;
+
+    println!("cargo:rerun-if-changed=proto/blog.proto");
+
+    Ok(())
+}
@@ -0,0 +1,301 @@
+//! Взаимодействие с сервером блога по протоколу gRPC-web.
+
+use crate::{AuthResponse, Post, PostAuthor, PostPage, PostPreview, User};
+use blog_grpc::blog_service_client::BlogServiceClient;
+use blog_grpc::{
+    CreatePostRequest, CreateUserRequest, DeletePostRequest, GetPostRequest, GetPostsRequest,
+    LoginUserRequest, SearchPostsRequest, UpdatePostRequest,
+};
+use tonic::{Request, Status};
+use tonic_web_wasm_client::Client;
+use wasm_bindgen::JsValue;
+
+#[allow(unreachable_pub)]
+mod blog_grpc {
+    tonic::include_proto!("blog");
+}
+
+/// gRPC-web клиент сервиса блога.
+pub(crate) struct GrpcWebClient {
+    /// Сгенерированный gRPC-клиент поверх fetch API браузера.
+    inner: BlogServiceClient<Client>,
+}
+
+impl GrpcWebClient {
+    /// Создать клиент для gRPC-сервера по указанному адресу.
+    pub(crate) fn new(url: String) -> Self {
+        Self {
+            inner: BlogServiceClient::new(Client::new(url)),
+        }
+    }
+
+    /// Регистрация пользователя.
+    pub(crate) async fn register(
+        &self,
+        username: String,
+        email: String,
+        password: String,
+        invite_code: Option<String>,
+    ) -> Result<AuthResponse, JsValue> {
+        let response = self
+            .inner
+            .clone()
+            .register(CreateUserRequest {
+                username,
+                email,
+                password,
+                invite_code,
+            })
+            .await
+            .map_err(|status| status_error("Регистрация не удалась", status))?
+            .into_inner();
+
+        auth_response(response.token, response.user)
+    }
+
+    /// Авторизация пользователя.
+    pub(crate) async fn login(
+        &self,
+        username: String,
+        password: String,
+    ) -> Result<AuthResponse, JsValue> {
+        let response = self
+            .inner
+            .clone()
+            .login(LoginUserRequest { username, password })
+            .await
+            .map_err(|status| status_error("Ошибка авторизации", status))?
+            .into_inner();
+
+        auth_response(response.token, response.user)
+    }
+
+    /// Загрузить страницу постов.
+    pub(crate) async fn load_posts(&self, limit: i64, offset: i64) -> Result<PostPage, JsValue> {
+        let response = self
+            .inner
+            .clone()
+            .get_posts(GetPostsRequest {
+                limit,
+                offset,
+                include: vec!["author".to_string()],
+                archived: None,
+                created_after: None,
+                created_before: None,
+                updated_since: None,
+                sort: None,
+            })
+            .await
+            .map_err(|status| status_error("Не удалось загрузить посты", status))?
+            .into_inner();
+
+        Ok(PostPage {
+            posts: response.posts.into_iter().map(PostPreview::from).collect(),
+            total: response.total,
+            limit: response.limit,
+            offset: response.offset,
+            page: 0,
+            total_pages: 0,
+        }
+        .paginate())
+    }
+
+    /// Найти посты по поисковому запросу.
+    pub(crate) async fn search_posts(
+        &self,
+        query: String,
+        limit: i64,
+    ) -> Result<Vec<PostPreview>, JsValue> {
+        let response = self
+            .inner
+            .clone()
+            .search_posts(SearchPostsRequest {
+                query,
+                author_id: None,
+                limit,
+                offset: 0,
+            })
+            .await
+            .map_err(|status| status_error("Не удалось выполнить поиск", status))?
+            .into_inner();
+
+        Ok(response.posts.into_iter().map(PostPreview::from).collect())
+    }
+
+    /// Загрузить пост целиком.
+    pub(crate) async fn load_post(&self, id: i64) -> Result<Post, JsValue> {
+        let response = self
+            .inner
+            .clone()
+            .get_post(GetPostRequest {
+                id,
+                include: vec![],
+            })
+            .await
+            .map_err(|status| status_error("Не удалось загрузить пост", status))?
+            .into_inner();
+
+        post(response.post)
+    }
+
+    /// Создать пост.
+    ///
+    /// Ошибка возвращается статусом gRPC, чтобы вызывающий код мог распознать
+    /// отклоненную авторизацию (как и в остальных методах с токеном).
+    pub(crate) async fn create_post(
+        &self,
+        token: &str,
+        title: String,
+        content: String,
+        excerpt: Option<String>,
+        cover_image_url: Option<String>,
+        content_format: Option<String>,
+    ) -> Result<Post, Status> {
+        let request = authorized(
+            CreatePostRequest {
+                title,
+                content,
+                excerpt,
+                cover_image_url,
+                is_private: false,
+                content_format,
+            },
+            token,
+        )?;
+
+        let response = self.inner.clone().create_post(request).await?.into_inner();
+
+        authorized_post(response.post)
+    }
+
+    /// Обновить пост.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) async fn update_post(
+        &self,
+        token: &str,
+        id: i64,
+        title: Option<String>,
+        content: Option<String>,
+        excerpt: Option<String>,
+        cover_image_url: Option<String>,
+        content_format: Option<String>,
+    ) -> Result<Post, Status> {
+        let request = authorized(
+            UpdatePostRequest {
+                id,
+                title,
+                content,
+                expected_version: None,
+                excerpt,
+                cover_image_url,
+                is_private: None,
+                content_format,
+            },
+            token,
+        )?;
+
+        let response = self.inner.clone().update_post(request).await?.into_inner();
+
+        authorized_post(response.post)
+    }
+
+    /// Удалить пост.
+    pub(crate) async fn delete_post(&self, token: &str, id: i64) -> Result<(), Status> {
+        let request = authorized(DeletePostRequest { id }, token)?;
+
+        self.inner.clone().delete_post(request).await?;
+
+        Ok(())
+    }
+}
+
+/// Сформировать запрос с JWT-токеном в метаданных.
+fn authorized<T>(message: T, token: &str) -> Result<Request<T>, Status> {
+    let mut request = Request::new(message);
+
+    request.metadata_mut().insert(
+        "authorization",
+        format!("Bearer {token}")
+            .parse()
+            .map_err(|_| Status::unauthenticated("Некорректный токен авторизации"))?,
+    );
+
+    Ok(request)
+}
+
+/// Сформировать ошибку из статуса gRPC-вызова.
+pub(crate) fn status_error(context: &str, status: Status) -> JsValue {
+    JsValue::from_str(&format!("{context}: {}", status.message()))
+}
+
+/// Извлечь пост из ответа сервера.
+fn post(post: Option<blog_grpc::Post>) -> Result<Post, JsValue> {
+    post.map(Post::from)
+        .ok_or_else(|| JsValue::from_str("Сервер не вернул пост"))
+}
+
+/// Извлечь пост из ответа сервера на запрос с авторизацией.
+fn authorized_post(post: Option<blog_grpc::Post>) -> Result<Post, Status> {
+    post.map(Post::from)
+        .ok_or_else(|| Status::internal("Сервер не вернул пост"))
+}
+
+/// Сформировать ответ авторизации.
+fn auth_response(token: String, user: Option<blog_grpc::User>) -> Result<AuthResponse, JsValue> {
+    let user = user.ok_or_else(|| JsValue::from_str("Сервер не вернул пользователя"))?;
+
+    Ok(AuthResponse {
+        token,
+        user: User {
+            id: user.id,
+            username: user.username,
+            email: user.email,
+            avatar_url: user.avatar_url,
+            created_at: user.created_at,
+        },
+    })
+}
+
+impl From<blog_grpc::Post> for Post {
+    fn from(post: blog_grpc::Post) -> Self {
+        Self {
+            id: post.id,
+            title: post.title,
+            content: post.content,
+            excerpt: post.excerpt,
+            cover_image_url: post.cover_image_url,
+            is_private: post.is_private,
+            archived_at: post.archived_at,
+            author_id: post.author_id,
+            author_ids: post.author_ids,
+            created_at: post.created_at,
+            updated_at: post.updated_at,
+            version: post.version,
+            reading_time_minutes: post.reading_time_minutes,
+            content_format: post.content_format,
+            content_html: post.content_html,
+        }
+    }
+}
+
+impl From<blog_grpc::Post> for PostPreview {
+    fn from(post: blog_grpc::Post) -> Self {
+        Self {
+            id: post.id,
+            title: post.title,
+            excerpt: post.excerpt,
+            cover_image_url: post.cover_image_url,
+            is_private: post.is_private,
+            archived_at: post.archived_at,
+            author_id: post.author_id,
+            author: post.author.map(|author| PostAuthor {
+                id: author.id,
+                username: author.username,
+            }),
+            created_at: post.created_at,
+            updated_at: post.updated_at,
+            version: post.version,
+            reading_time_minutes: post.reading_time_minutes,
+        }
+    }
+}
@@ -2,15 +2,96 @@
 
 #![deny(unreachable_pub)]
 
-use gloo_net::http::Request;
+mod grpc_web;
+mod render;
+mod upload;
+mod validation;
+
+pub use validation::{validate_email, validate_password, validate_username};
+
+use crate::grpc_web::GrpcWebClient;
+use gloo_net::http::{Method, Request, RequestBuilder, Response};
 use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use tonic::{Code, Status};
+use wasm_bindgen::JsCast;
 use wasm_bindgen::prelude::*;
-use web_sys::window;
+use web_sys::{
+    CustomEvent, CustomEventInit, File, HtmlDocument, RequestCredentials, Storage, window,
+};
+
+/// Имя cookie, в которой сервер передает CSRF-токен.
+const CSRF_COOKIE: &str = "blog_csrf";
+
+/// Заголовок, в котором CSRF-токен дублируется при изменяющих запросах.
+const CSRF_HEADER: &str = "X-CSRF-Token";
+
+/// Ключ localStorage, под которым сохраняется JWT-токен.
+const TOKEN_STORAGE_KEY: &str = "blog_token";
+
+/// Событие, которое отправляется в `window`, когда сервер отклонил авторизацию.
+///
+/// Сервер не выдает токенов обновления, поэтому истекший токен удаляется,
+/// а странице остается предложить пользователю войти снова.
+const UNAUTHORIZED_EVENT: &str = "blog:unauthorized";
+
+/// Причина изменения авторизации: вход, регистрация или установка токена.
+const AUTH_LOGIN: &str = "login";
+
+/// Причина изменения авторизации: выход пользователя.
+const AUTH_LOGOUT: &str = "logout";
+
+/// Причина изменения авторизации: сервер отклонил токен или сессию.
+const AUTH_EXPIRED: &str = "expired";
+
+/// Причина изменения авторизации: токен загружен из localStorage.
+const AUTH_RESTORED: &str = "restored";
+
+/// Прочитать значение cookie, доступной из клиентского кода.
+fn read_cookie(name: &str) -> Option<String> {
+    let cookies = window()?
+        .document()?
+        .dyn_into::<HtmlDocument>()
+        .ok()?
+        .cookie()
+        .ok()?;
+
+    cookies
+        .split(';')
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value.to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Хранилище localStorage браузера.
+fn local_storage() -> Result<Storage, JsValue> {
+    window()
+        .ok_or_else(|| JsValue::from_str("Не удалось получить объект window"))?
+        .local_storage()
+        .map_err(|_| JsValue::from_str("Не удалось получить доступ к localStorage"))?
+        .ok_or_else(|| JsValue::from_str("localStorage недоступен"))
+}
+
+/// Отправить в `window` событие с данными `detail`.
+fn dispatch_event(name: &str, detail: &JsValue) -> Result<(), JsValue> {
+    let init = CustomEventInit::new();
+    init.set_detail(detail);
+
+    let event = CustomEvent::new_with_event_init_dict(name, &init)?;
+
+    window()
+        .ok_or_else(|| JsValue::from_str("Не удалось получить объект window"))?
+        .dispatch_event(&event)?;
+
+    Ok(())
+}
 
 #[derive(Debug, Deserialize)]
 /// Ответ сервера с JWT-токеном при авторизации.
 pub struct AuthResponse {
-    /// JWT-токен для последующих запросов.
+    /// JWT-токен для последующих запросов (пустой при авторизации через cookie).
+    #[serde(default)]
     token: String,
 
     /// Авторизованный пользователь.
@@ -29,6 +110,10 @@ pub struct User {
     /// Email-адрес пользователя.
     pub email: String,
 
+    /// Ссылка на аватар пользователя.
+    #[serde(default)]
+    pub avatar_url: String,
+
     /// Время создания пользователя.
     pub created_at: String,
 }
@@ -45,16 +130,179 @@ pub struct Post {
     /// Содержимое поста.
     pub content: String,
 
+    /// Краткое описание поста.
+    pub excerpt: Option<String>,
+
+    /// Ссылка на изображение обложки поста.
+    pub cover_image_url: Option<String>,
+
+    /// Признак приватного поста.
+    #[serde(default)]
+    pub is_private: bool,
+
+    /// Время перевода поста в архив.
+    #[serde(default)]
+    pub archived_at: Option<String>,
+
+    /// Идентификатор пользователя-автора поста.
+    pub author_id: i64,
+
+    /// Идентификаторы всех авторов поста (владельца и соавторов).
+    #[serde(default)]
+    pub author_ids: Vec<i64>,
+
+    /// Время создания поста.
+    pub created_at: String,
+
+    /// Время последнего обновления поста.
+    pub updated_at: String,
+
+    /// Версия поста.
+    pub version: i64,
+
+    /// Оценка времени чтения поста (в минутах).
+    #[serde(default)]
+    pub reading_time_minutes: i32,
+
+    /// Формат содержимого поста (`markdown`, `plain` или `html`).
+    #[serde(default = "default_content_format")]
+    pub content_format: String,
+
+    /// HTML-представление содержимого, сформированное сервером (только для Markdown).
+    #[serde(default)]
+    pub content_html: Option<String>,
+}
+
+/// Формат содержимого поста по умолчанию.
+fn default_content_format() -> String {
+    "plain".to_string()
+}
+
+/// Информация о посте в списке (без полного содержимого).
+#[derive(Deserialize, Serialize)]
+pub struct PostPreview {
+    /// Идентификатор поста.
+    pub id: i64,
+
+    /// Заголовок поста.
+    pub title: String,
+
+    /// Краткое описание поста.
+    pub excerpt: Option<String>,
+
+    /// Ссылка на изображение обложки поста.
+    pub cover_image_url: Option<String>,
+
+    /// Признак приватного поста.
+    #[serde(default)]
+    pub is_private: bool,
+
+    /// Время перевода поста в архив.
+    #[serde(default)]
+    pub archived_at: Option<String>,
+
     /// Идентификатор пользователя-автора поста.
     pub author_id: i64,
 
+    /// Автор поста.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<PostAuthor>,
+
     /// Время создания поста.
     pub created_at: String,
 
     /// Время последнего обновления поста.
     pub updated_at: String,
+
+    /// Версия поста.
+    pub version: i64,
+
+    /// Оценка времени чтения поста (в минутах).
+    #[serde(default)]
+    pub reading_time_minutes: i32,
+}
+
+/// Автор поста.
+#[derive(Deserialize, Serialize)]
+pub struct PostAuthor {
+    /// Идентификатор пользователя.
+    pub id: i64,
+
+    /// Имя пользователя.
+    pub username: String,
+}
+
+/// Загруженное изображение.
+#[derive(Deserialize)]
+struct Media {
+    /// Ссылка на изображение относительно адреса сервера.
+    url: String,
+}
+
+/// Изменяемые поля профиля пользователя.
+#[derive(Deserialize, Serialize)]
+struct ProfileUpdate {
+    /// Новый email-адрес (требует подтверждения по ссылке из письма).
+    email: Option<String>,
+
+    /// Удалить загруженный аватар (будет использоваться Gravatar).
+    #[serde(default)]
+    remove_avatar: bool,
+}
+
+/// Подсказка для строки поиска постов.
+#[derive(Deserialize, Serialize)]
+pub struct PostSuggestion {
+    /// Идентификатор поста.
+    pub id: i64,
+
+    /// Заголовок поста.
+    pub title: String,
+}
+
+/// Страница списка постов вместе с общим количеством постов.
+#[derive(Deserialize, Serialize)]
+pub struct PostPage {
+    /// Посты на странице.
+    pub posts: Vec<PostPreview>,
+
+    /// Общее количество постов.
+    pub total: i64,
+
+    /// Максимальное количество постов на странице.
+    pub limit: i64,
+
+    /// Смещение от первого поста.
+    pub offset: i64,
+
+    /// Номер страницы (начиная с 1).
+    #[serde(default)]
+    pub page: i64,
+
+    /// Общее количество страниц (0, если постов нет).
+    #[serde(default)]
+    pub total_pages: i64,
+}
+
+impl PostPage {
+    /// Рассчитать номер страницы и количество страниц по смещению и размеру страницы.
+    fn paginate(mut self) -> Self {
+        let limit = self.limit.max(1);
+
+        self.page = self.offset / limit + 1;
+        self.total_pages = (self.total + limit - 1) / limit;
+        self
+    }
 }
 
+/// Событие, которое отправляется в `window` после загрузки страницы постов.
+///
+/// В `detail` передаются `{ page, total_pages, total }`.
+const PAGE_EVENT: &str = "blog:page";
+
+/// Количество постов на странице по умолчанию.
+const DEFAULT_PAGE_SIZE: i64 = 10;
+
 /// Клиентское приложение блога для взаимодействия с сервером.
 #[wasm_bindgen]
 pub struct BlogApp {
@@ -62,7 +310,275 @@ pub struct BlogApp {
     server: String,
 
     /// JWT-токен авторизации.
-    token: Option<String>,
+    ///
+    /// Сбрасывается и при обработке ответа 401, поэтому допускает изменение через `&self`.
+    token: RefCell<Option<String>>,
+
+    /// Авторизация через сессионные cookie вместо хранения токена.
+    cookie_session: bool,
+
+    /// Клиент gRPC-web, используемый вместо REST API, если задан.
+    grpc_web: Option<GrpcWebClient>,
+
+    /// Количество постов на странице.
+    page_size: i64,
+
+    /// Номер последней загруженной страницы постов (начиная с 1).
+    page: i64,
+
+    /// Общее количество страниц постов по последней загрузке.
+    total_pages: i64,
+
+    /// Идентификатор элемента, в который отрисовываются загруженные посты.
+    posts_container: Option<String>,
+
+    /// HTML-шаблон карточки поста.
+    post_template: String,
+
+    /// HTML-шаблон, который выводится вместо списка, если постов нет.
+    empty_template: String,
+
+    /// Обработчики изменения состояния авторизации ([`BlogApp::on_auth_change`]).
+    auth_listeners: RefCell<Vec<js_sys::Function>>,
+}
+
+impl BlogApp {
+    /// Адрес эндпоинта REST API.
+    fn api_url(&self, path: &str) -> String {
+        format!("{}/api/v1{}", self.server, path)
+    }
+
+    /// Сформировать запрос к REST API.
+    ///
+    /// В режиме сессионных cookie браузеру разрешается передавать и сохранять cookie.
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let request = RequestBuilder::new(&self.api_url(path)).method(method);
+
+        if self.cookie_session {
+            request.credentials(RequestCredentials::Include)
+        } else {
+            request
+        }
+    }
+
+    /// Сформировать запрос к REST API с данными авторизации.
+    ///
+    /// В режиме сессионных cookie браузер сам передает cookie, а CSRF-токен дублируется
+    /// в заголовке; иначе используется сохраненный JWT-токен.
+    fn authorized(&self, method: Method, path: &str) -> Result<RequestBuilder, JsValue> {
+        let (name, value) = self.auth_header()?;
+
+        Ok(self.request(method, path).header(name, &value))
+    }
+
+    /// Заголовок с данными авторизации: CSRF-токен в режиме сессионных cookie или JWT-токен.
+    fn auth_header(&self) -> Result<(&'static str, String), JsValue> {
+        if self.cookie_session {
+            let csrf = read_cookie(CSRF_COOKIE)
+                .ok_or_else(|| JsValue::from_str("Пользователь не авторизован"))?;
+
+            return Ok((CSRF_HEADER, csrf));
+        }
+
+        let token = self
+            .get_token()
+            .ok_or_else(|| JsValue::from_str("Пользователь не авторизован"))?;
+
+        Ok(("Authorization", format!("Bearer {}", token)))
+    }
+
+    /// Отправить запрос к REST API.
+    async fn send(&self, request: Request) -> Result<Response, JsValue> {
+        request
+            .send()
+            .await
+            .map_err(|e| JsValue::from_str(&format!("Не удалось отправить запрос: {}", e)))
+    }
+
+    /// Отправить запрос с данными авторизации ([`BlogApp::authorized`]).
+    ///
+    /// Ответ 401 означает, что токен истек или сессия завершена: авторизация сбрасывается
+    /// ([`BlogApp::expire_session`]), а ответ возвращается для обработки как обычно.
+    async fn send_authorized(&self, request: Request) -> Result<Response, JsValue> {
+        let response = self.send(request).await?;
+
+        if response.status() == 401 {
+            self.expire_session()?;
+        }
+
+        Ok(response)
+    }
+
+    /// Обработать результат gRPC-вызова с авторизацией (аналог [`BlogApp::send_authorized`]).
+    fn grpc_authorized<T>(&self, context: &str, result: Result<T, Status>) -> Result<T, JsValue> {
+        result.or_else(|status| {
+            if status.code() == Code::Unauthenticated {
+                self.expire_session()?;
+            }
+
+            Err(grpc_web::status_error(context, status))
+        })
+    }
+
+    /// JWT-токен для запросов по gRPC-web (сессионные cookie в gRPC не поддерживаются).
+    fn grpc_token(&self) -> Result<String, JsValue> {
+        self.get_token()
+            .ok_or_else(|| JsValue::from_str("Пользователь не авторизован"))
+    }
+
+    /// Сохранить токен из ответа на вход (в режиме cookie токен хранится браузером).
+    ///
+    /// Токен также сохраняется в localStorage, если оно доступно.
+    fn accept_token(&self, token: String) {
+        if !self.cookie_session {
+            if let Ok(storage) = local_storage() {
+                let _ = storage.set_item(TOKEN_STORAGE_KEY, &token);
+            }
+
+            self.token.replace(Some(token));
+        }
+
+        self.notify_auth_change(AUTH_LOGIN);
+    }
+
+    /// Вызвать обработчики изменения состояния авторизации с `{ authenticated, reason }`.
+    ///
+    /// Обработчики вызываются асинхронно, после завершения текущего вызова `BlogApp`,
+    /// чтобы они могли обращаться к приложению. Исключение в одном обработчике не мешает
+    /// вызову остальных.
+    fn notify_auth_change(&self, reason: &str) {
+        let state = serde_json::json!({
+            "authenticated": self.is_authenticated(),
+            "reason": reason,
+        })
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL);
+
+        let listeners = self.auth_listeners.borrow().clone();
+
+        wasm_bindgen_futures::spawn_local(async move {
+            for listener in listeners {
+                let _ = listener.call1(&JsValue::NULL, &state);
+            }
+        });
+    }
+
+    /// Забыть токен в памяти и в localStorage.
+    fn forget_token(&self) -> Result<(), JsValue> {
+        self.token.replace(None);
+
+        local_storage()?
+            .remove_item(TOKEN_STORAGE_KEY)
+            .map_err(|_| JsValue::from_str("Не удалось удалить токен из localStorage"))
+    }
+
+    /// Сформировать ошибку `{ status, message, fields }` по ответу сервера с кодом ошибки.
+    ///
+    /// `fields` - ошибки валидации по полям в формате сервера (`{ поле: [{ code, message }] }`),
+    /// если сервер их вернул; `message` - описание ошибки сервера или `context` с кодом ответа.
+    async fn api_error(&self, response: Response, context: &str) -> JsValue {
+        let status = response.status();
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+
+        let message = body["error"]
+            .as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("{}, код: {}", context, status));
+
+        serde_json::json!({
+            "status": status,
+            "message": message,
+            "fields": body.get("fields"),
+        })
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or_else(|_| JsValue::from_str(&message))
+    }
+
+    /// Сбросить авторизацию, отклоненную сервером, и сообщить об этом событием [`UNAUTHORIZED_EVENT`].
+    fn expire_session(&self) -> Result<(), JsValue> {
+        // Если localStorage недоступен, токен все равно удаляется из памяти.
+        let _ = self.forget_token();
+        self.notify_auth_change(AUTH_EXPIRED);
+
+        dispatch_event(UNAUTHORIZED_EVENT, &JsValue::NULL)
+    }
+
+    /// Запросить страницу постов у сервера.
+    async fn fetch_posts(&self, limit: i64, offset: i64) -> Result<PostPage, JsValue> {
+        if let Some(grpc_web) = &self.grpc_web {
+            return grpc_web.load_posts(limit, offset).await;
+        }
+
+        let request = self
+            .request(Method::GET, "/posts")
+            .query([
+                ("limit", limit.to_string()),
+                ("offset", offset.to_string()),
+                ("include", "author".to_string()),
+            ])
+            .build()
+            .map_err(|e| JsValue::from_str(&format!("Не удалось сформировать запрос: {}", e)))?;
+
+        let response = self.send(request).await?;
+
+        if !response.ok() {
+            return Err(JsValue::from_str(&format!(
+                "Не удалось загрузить посты, код: {}",
+                response.status()
+            )));
+        }
+
+        let page: PostPage = response
+            .json()
+            .await
+            .map_err(|e| JsValue::from_str(&format!("Не удалось обработать ответ: {}", e)))?;
+
+        Ok(page.paginate())
+    }
+
+    /// Запомнить положение загруженной страницы и сообщить о нем событием [`PAGE_EVENT`].
+    ///
+    /// Если задан контейнер для постов ([`BlogApp::render_posts_into`]), посты отрисовываются в нем.
+    fn track_page(&mut self, page: PostPage) -> Result<JsValue, JsValue> {
+        self.page = page.page;
+        self.total_pages = page.total_pages;
+
+        if let Some(container_id) = &self.posts_container {
+            self.render_posts(container_id, &page.posts)?;
+        }
+
+        let detail = serde_json::json!({
+            "page": page.page,
+            "total_pages": page.total_pages,
+            "total": page.total,
+        })
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|e| JsValue::from_str(&format!("Не удалось сериализовать событие: {}", e)))?;
+
+        dispatch_event(PAGE_EVENT, &detail)?;
+
+        serde_wasm_bindgen::to_value(&page)
+            .map_err(|e| JsValue::from_str(&format!("Не удалось сериализовать посты: {}", e)))
+    }
+
+    /// Отрисовать посты в элементе с идентификатором `container_id`.
+    fn render_posts(&self, container_id: &str, posts: &[PostPreview]) -> Result<(), JsValue> {
+        let document = window()
+            .and_then(|window| window.document())
+            .ok_or_else(|| JsValue::from_str("Не удалось получить объект document"))?;
+
+        let container = document.get_element_by_id(container_id).ok_or_else(|| {
+            JsValue::from_str(&format!("Элемент #{} не найден на странице", container_id))
+        })?;
+
+        render::render_posts(
+            &document,
+            &container,
+            posts,
+            &self.post_template,
+            &self.empty_template,
+        )
+    }
 }
 
 #[wasm_bindgen]
@@ -71,35 +587,102 @@ impl BlogApp {
     pub fn new(server: String) -> Self {
         Self {
             server,
-            token: None,
+            token: RefCell::new(None),
+            cookie_session: false,
+            grpc_web: None,
+            page_size: DEFAULT_PAGE_SIZE,
+            page: 1,
+            total_pages: 0,
+            posts_container: None,
+            post_template: render::DEFAULT_POST_TEMPLATE.to_string(),
+            empty_template: render::DEFAULT_EMPTY_TEMPLATE.to_string(),
+            auth_listeners: RefCell::new(vec![]),
         }
     }
 
+    /// Взаимодействовать с сервером по gRPC-web вместо REST API.
+    ///
+    /// `url` - адрес gRPC-сервера (например, `http://127.0.0.1:50051`). Авторизация
+    /// выполняется JWT-токеном, поэтому режим сессионных cookie при этом отключается.
+    pub fn enable_grpc_web(&mut self, url: String) {
+        self.grpc_web = Some(GrpcWebClient::new(url));
+        self.cookie_session = false;
+    }
+
+    /// Включить авторизацию через сессионные cookie (сервер запущен с `--session-cookies`).
+    ///
+    /// В этом режиме JWT-токен не сохраняется на стороне клиента.
+    pub fn enable_cookie_session(&mut self) {
+        self.cookie_session = true;
+        self.token.replace(None);
+    }
+
+    /// Выход пользователя с удалением сессии на сервере.
+    pub async fn logout(&mut self) -> Result<JsValue, JsValue> {
+        let request = self
+            .request(Method::POST, "/auth/logout")
+            .build()
+            .map_err(|e| JsValue::from_str(&format!("Не удалось сформировать запрос: {}", e)))?;
+
+        let response = self.send(request).await?;
+
+        if !response.ok() {
+            return Err(JsValue::from_str(&format!(
+                "Не удалось выйти, код: {}",
+                response.status()
+            )));
+        }
+
+        self.forget_token()?;
+        self.notify_auth_change(AUTH_LOGOUT);
+
+        serde_wasm_bindgen::to_value(&serde_json::json!({
+            "success": true,
+        }))
+        .map_err(|e| JsValue::from_str(&format!("Не удалось сериализовать ответ: {}", e)))
+    }
+
     /// Регистрация пользователя.
+    ///
+    /// Код приглашения требуется, если сервер разрешает регистрацию только по приглашениям.
     pub async fn register(
         &mut self,
         username: String,
         email: String,
         password: String,
+        invite_code: Option<String>,
     ) -> Result<JsValue, JsValue> {
-        let url = format!("{}/api/auth/register", self.server);
+        if let Some(grpc_web) = &self.grpc_web {
+            let auth_response = grpc_web
+                .register(username, email, password, invite_code)
+                .await?;
+
+            self.accept_token(auth_response.token);
+
+            return serde_wasm_bindgen::to_value(&serde_json::json!({
+                "user": auth_response.user,
+            }))
+            .map_err(|e| JsValue::from_str(&format!("Не удалось сериализовать ответ: {}", e)));
+        }
 
         let payload = serde_json::json!({
             "username": username,
             "email": email,
             "password": password,
+            "invite_code": invite_code,
         });
 
-        let response = Request::post(&url)
+        let request = self
+            .request(Method::POST, "/auth/register")
             .json(&payload)
-            .map_err(|e| JsValue::from_str(&format!("Не удалось сериализовать запрос: {}", e)))?
-            .send()
-            .await
-            .map_err(|e| JsValue::from_str(&format!("Не удалось отправить запрос: {}", e)))?;
+            .map_err(|e| JsValue::from_str(&format!("Не удалось сериализовать запрос: {}", e)))?;
+
+        let response = self.send(request).await?;
 
         if !response.ok() {
             let msg = match response.status() {
                 400 => "Некорректные данные для регистрации!".to_string(),
+                403 => "Регистрация закрыта или требует действительного приглашения!".to_string(),
                 409 => "Пользователь уже существует!".to_string(),
                 status => {
                     format!("Регистрация не удалась, код: {}", status)
@@ -114,7 +697,7 @@ impl BlogApp {
             .await
             .map_err(|e| JsValue::from_str(&format!("Не удалось обработать ответ: {}", e)))?;
 
-        self.token = Some(auth_response.token.clone());
+        self.accept_token(auth_response.token);
 
         serde_wasm_bindgen::to_value(&serde_json::json!({
             "user": auth_response.user,
@@ -124,19 +707,28 @@ impl BlogApp {
 
     /// Авторизация пользователя.
     pub async fn login(&mut self, username: String, password: String) -> Result<JsValue, JsValue> {
-        let url = format!("{}/api/auth/login", self.server);
+        if let Some(grpc_web) = &self.grpc_web {
+            let auth_response = grpc_web.login(username, password).await?;
+
+            self.accept_token(auth_response.token);
+
+            return serde_wasm_bindgen::to_value(&serde_json::json!({
+                "user": auth_response.user,
+            }))
+            .map_err(|e| JsValue::from_str(&format!("Не удалось сериализовать ответ: {}", e)));
+        }
 
         let payload = serde_json::json! ({
             "username": username,
             "password": password,
         });
 
-        let response = Request::post(&url)
+        let request = self
+            .request(Method::POST, "/auth/login")
             .json(&payload)
-            .map_err(|e| JsValue::from_str(&format!("Не удалось сформировать запрос: {}", e)))?
-            .send()
-            .await
-            .map_err(|e| JsValue::from_str(&format!("Не удалось отправить запрос: {}", e)))?;
+            .map_err(|e| JsValue::from_str(&format!("Не удалось сформировать запрос: {}", e)))?;
+
+        let response = self.send(request).await?;
 
         if !response.ok() {
             let msg = match response.status() {
@@ -155,7 +747,7 @@ impl BlogApp {
             .await
             .map_err(|e| JsValue::from_str(&format!("Не удалось обработать ответ: {}", e)))?;
 
-        self.token = Some(auth_response.token.clone());
+        self.accept_token(auth_response.token);
 
         serde_wasm_bindgen::to_value(&serde_json::json!({
             "user": auth_response.user,
@@ -163,53 +755,268 @@ impl BlogApp {
         .map_err(|e| JsValue::from_str(&format!("Не удалось сериализовать ответ: {}", e)))
     }
 
-    /// Загрузить посты (с пагинацией).
-    pub async fn load_posts(&self, limit: i64, offset: i64) -> Result<JsValue, JsValue> {
-        let url = format!("{}/api/posts", self.server);
+    /// Загрузить страницу постов (`{ posts, total, limit, offset, page, total_pages }`).
+    ///
+    /// Размер и номер страницы запоминаются для [`BlogApp::next_page`] и [`BlogApp::prev_page`].
+    pub async fn load_posts(&mut self, limit: i64, offset: i64) -> Result<JsValue, JsValue> {
+        let page = self.fetch_posts(limit, offset).await?;
 
-        let response = Request::get(&url)
-            .query([("limit", limit.to_string()), ("offset", offset.to_string())])
-            .send()
+        self.page_size = limit.max(1);
+        self.track_page(page)
+    }
+
+    /// Загрузить страницу постов по номеру (начиная с 1).
+    ///
+    /// Если страницы больше нет (например, после удаления постов), загружается последняя.
+    pub async fn load_page(&mut self, page: i64) -> Result<JsValue, JsValue> {
+        let offset = (page.max(1) - 1) * self.page_size;
+        let mut posts = self.fetch_posts(self.page_size, offset).await?;
+
+        if posts.posts.is_empty() && posts.page > posts.total_pages && posts.total_pages > 0 {
+            let offset = (posts.total_pages - 1) * self.page_size;
+            posts = self.fetch_posts(self.page_size, offset).await?;
+        }
+
+        self.track_page(posts)
+    }
+
+    /// Загрузить следующую страницу постов (на последней странице - обновить ее).
+    pub async fn next_page(&mut self) -> Result<JsValue, JsValue> {
+        let page = (self.page + 1).min(self.total_pages.max(1));
+
+        self.load_page(page).await
+    }
+
+    /// Загрузить предыдущую страницу постов (на первой странице - обновить ее).
+    pub async fn prev_page(&mut self) -> Result<JsValue, JsValue> {
+        self.load_page(self.page - 1).await
+    }
+
+    /// Обновить текущую страницу постов.
+    pub async fn reload_posts(&mut self) -> Result<JsValue, JsValue> {
+        self.load_page(self.page).await
+    }
+
+    /// Задать количество постов на странице и вернуться к первой странице.
+    pub fn set_page_size(&mut self, page_size: i64) {
+        self.page_size = page_size.max(1);
+        self.page = 1;
+    }
+
+    /// Загрузить текущую страницу постов и отрисовать ее в элементе с идентификатором `container_id`.
+    ///
+    /// Элемент запоминается: последующие [`BlogApp::load_page`], [`BlogApp::next_page`],
+    /// [`BlogApp::prev_page`] и [`BlogApp::reload_posts`] также перерисовывают в нем посты.
+    /// Карточки строятся по шаблону [`BlogApp::set_post_template`].
+    pub async fn render_posts_into(&mut self, container_id: String) -> Result<JsValue, JsValue> {
+        self.posts_container = Some(container_id);
+        self.reload_posts().await
+    }
+
+    /// Задать HTML-шаблон карточки поста.
+    ///
+    /// Подстановки (значения экранируются): `{id}`, `{title}`, `{excerpt}`, `{cover_image_url}`,
+    /// `{author}`, `{author_id}`, `{time}` (например, `5 минут назад`), `{created_at}`,
+    /// `{updated_at}`, `{reading_time}`.
+    pub fn set_post_template(&mut self, template: String) {
+        self.post_template = template;
+    }
+
+    /// Задать HTML-шаблон, который выводится вместо списка, если постов нет.
+    pub fn set_empty_template(&mut self, template: String) {
+        self.empty_template = template;
+    }
+
+    /// Номер текущей страницы постов (начиная с 1).
+    pub fn current_page(&self) -> i64 {
+        self.page
+    }
+
+    /// Общее количество страниц постов по последней загрузке.
+    pub fn total_pages(&self) -> i64 {
+        self.total_pages
+    }
+
+    /// Найти посты по поисковому запросу (не больше, чем постов на странице).
+    ///
+    /// Возвращает массив постов в том же формате, что и [`BlogApp::load_posts`].
+    pub async fn search_posts(&self, query: String) -> Result<JsValue, JsValue> {
+        let posts = match &self.grpc_web {
+            Some(grpc_web) => grpc_web.search_posts(query, self.page_size).await?,
+            None => {
+                let request = self
+                    .request(Method::GET, "/posts/search")
+                    .query([
+                        ("q", query),
+                        ("limit", self.page_size.to_string()),
+                        ("offset", "0".to_string()),
+                    ])
+                    .build()
+                    .map_err(|e| {
+                        JsValue::from_str(&format!("Не удалось сформировать запрос: {}", e))
+                    })?;
+
+                let response = self.send(request).await?;
+
+                if !response.ok() {
+                    return Err(JsValue::from_str(&format!(
+                        "Не удалось выполнить поиск, код: {}",
+                        response.status()
+                    )));
+                }
+
+                response.json::<Vec<PostPreview>>().await.map_err(|e| {
+                    JsValue::from_str(&format!("Не удалось обработать ответ: {}", e))
+                })?
+            }
+        };
+
+        serde_wasm_bindgen::to_value(&posts)
+            .map_err(|e| JsValue::from_str(&format!("Не удалось сериализовать посты: {}", e)))
+    }
+
+    /// Получить подсказки для строки поиска: массив `{ id, title }`.
+    ///
+    /// Подсказки доступны только через REST API, в том числе в режиме gRPC-web.
+    pub async fn suggest(&self, query: String) -> Result<JsValue, JsValue> {
+        let request = self
+            .request(Method::GET, "/posts/search/suggest")
+            .query([("q", query)])
+            .build()
+            .map_err(|e| JsValue::from_str(&format!("Не удалось сформировать запрос: {}", e)))?;
+
+        let response = self.send(request).await?;
+
+        if !response.ok() {
+            return Err(JsValue::from_str(&format!(
+                "Не удалось загрузить подсказки, код: {}",
+                response.status()
+            )));
+        }
+
+        let suggestions: Vec<PostSuggestion> = response
+            .json()
             .await
-            .map_err(|e| JsValue::from_str(&format!("Не удалось отправить запрос: {}", e)))?;
+            .map_err(|e| JsValue::from_str(&format!("Не удалось обработать ответ: {}", e)))?;
+
+        serde_wasm_bindgen::to_value(&suggestions)
+            .map_err(|e| JsValue::from_str(&format!("Не удалось сериализовать подсказки: {}", e)))
+    }
+
+    /// Загрузить изображение и получить ссылку на него для вставки в пост или обложку.
+    ///
+    /// `on_progress(loaded, total)` вызывается по мере отправки файла. Изображения
+    /// загружаются только через REST API, в том числе в режиме gRPC-web.
+    pub async fn upload_image(
+        &self,
+        file: File,
+        on_progress: Option<js_sys::Function>,
+    ) -> Result<String, JsValue> {
+        let header = self.auth_header()?;
+
+        let response = upload::upload_file(
+            &self.api_url("/media"),
+            &[header],
+            self.cookie_session,
+            &file,
+            on_progress,
+        )
+        .await?;
+
+        if response.status == 401 {
+            self.expire_session()?;
+        }
+
+        if !(200..300).contains(&response.status) {
+            let msg = match response.status {
+                403 => "Загрузка изображений отключена".to_string(),
+                413 => "Файл слишком большой".to_string(),
+                415 | 422 => "Файл не является поддерживаемым изображением".to_string(),
+                status => format!("Не удалось загрузить изображение, код: {}", status),
+            };
+
+            return Err(JsValue::from_str(&msg));
+        }
+
+        let media: Media = serde_json::from_str(&response.body)
+            .map_err(|e| JsValue::from_str(&format!("Не удалось обработать ответ: {}", e)))?;
+
+        Ok(format!("{}{}", self.server, media.url))
+    }
+
+    /// Загрузить пост целиком (вместе с содержимым).
+    pub async fn load_post(&self, id: i64) -> Result<JsValue, JsValue> {
+        if let Some(grpc_web) = &self.grpc_web {
+            let post = grpc_web.load_post(id).await?;
+
+            return serde_wasm_bindgen::to_value(&post)
+                .map_err(|e| JsValue::from_str(&format!("Не удалось сериализовать пост: {}", e)));
+        }
+
+        let request = self
+            .request(Method::GET, &format!("/posts/{}", id))
+            .build()
+            .map_err(|e| JsValue::from_str(&format!("Не удалось сформировать запрос: {}", e)))?;
+
+        let response = self.send(request).await?;
 
         if !response.ok() {
             return Err(JsValue::from_str(&format!(
-                "Не удалось загрузить посты, код: {}",
+                "Не удалось загрузить пост, код: {}",
                 response.status()
             )));
         }
 
-        let posts: Vec<Post> = response
+        let post: Post = response
             .json()
             .await
             .map_err(|e| JsValue::from_str(&format!("Не удалось обработать ответ: {}", e)))?;
 
-        serde_wasm_bindgen::to_value(&posts)
-            .map_err(|e| JsValue::from_str(&format!("Не удалось сериализовать посты: {}", e)))
+        serde_wasm_bindgen::to_value(&post)
+            .map_err(|e| JsValue::from_str(&format!("Не удалось сериализовать пост: {}", e)))
     }
 
     /// Создать пост.
-    pub async fn create_post(&self, title: String, content: String) -> Result<JsValue, JsValue> {
-        let token = self
-            .token
-            .as_ref()
-            .ok_or_else(|| JsValue::from_str("Не авторизован"))?;
-
-        let url = format!("{}/api/posts", self.server);
+    pub async fn create_post(
+        &self,
+        title: String,
+        content: String,
+        excerpt: Option<String>,
+        cover_image_url: Option<String>,
+        content_format: Option<String>,
+    ) -> Result<JsValue, JsValue> {
+        if let Some(grpc_web) = &self.grpc_web {
+            let result = grpc_web
+                .create_post(
+                    &self.grpc_token()?,
+                    title,
+                    content,
+                    excerpt,
+                    cover_image_url,
+                    content_format,
+                )
+                .await;
+
+            let post = self.grpc_authorized("Не удалось создать пост", result)?;
+
+            return serde_wasm_bindgen::to_value(&post)
+                .map_err(|e| JsValue::from_str(&format!("Не удалось сериализовать пост: {}", e)));
+        }
 
         let payload = serde_json::json!({
             "title": title,
             "content": content,
+            "excerpt": excerpt,
+            "cover_image_url": cover_image_url,
+            "content_format": content_format,
         });
 
-        let response = Request::post(&url)
-            .header("Authorization", &format!("Bearer {}", token))
+        let request = self
+            .authorized(Method::POST, "/posts")?
             .json(&payload)
-            .map_err(|e| JsValue::from_str(&format!("Не удалось сериализовать запрос: {}", e)))?
-            .send()
-            .await
-            .map_err(|e| JsValue::from_str(&format!("Не удалось отправить запрос: {}", e)))?;
+            .map_err(|e| JsValue::from_str(&format!("Не удалось сериализовать запрос: {}", e)))?;
+
+        let response = self.send_authorized(request).await?;
 
         if !response.ok() {
             return Err(JsValue::from_str(&format!(
@@ -233,26 +1040,43 @@ impl BlogApp {
         id: i64,
         title: Option<String>,
         content: Option<String>,
+        excerpt: Option<String>,
+        cover_image_url: Option<String>,
+        content_format: Option<String>,
     ) -> Result<JsValue, JsValue> {
-        let token = self
-            .token
-            .as_ref()
-            .ok_or_else(|| JsValue::from_str("Пользователь не авторизован"))?;
-
-        let url = format!("{}/api/posts/{}", self.server, id);
+        if let Some(grpc_web) = &self.grpc_web {
+            let result = grpc_web
+                .update_post(
+                    &self.grpc_token()?,
+                    id,
+                    title,
+                    content,
+                    excerpt,
+                    cover_image_url,
+                    content_format,
+                )
+                .await;
+
+            let post = self.grpc_authorized("Не удалось обновить пост", result)?;
+
+            return serde_wasm_bindgen::to_value(&post)
+                .map_err(|e| JsValue::from_str(&format!("Не удалось сериализовать пост: {}", e)));
+        }
 
         let payload = serde_json::json!({
             "title": title,
             "content": content,
+            "excerpt": excerpt,
+            "cover_image_url": cover_image_url,
+            "content_format": content_format,
         });
 
-        let response = Request::put(&url)
-            .header("Authorization", &format!("Bearer {}", token))
+        let request = self
+            .authorized(Method::PUT, &format!("/posts/{}", id))?
             .json(&payload)
-            .map_err(|e| JsValue::from_str(&format!("Не удалось сериализовать запрос: {}", e)))?
-            .send()
-            .await
-            .map_err(|e| JsValue::from_str(&format!("Не удалось отправить запрос: {}", e)))?;
+            .map_err(|e| JsValue::from_str(&format!("Не удалось сериализовать запрос: {}", e)))?;
+
+        let response = self.send_authorized(request).await?;
 
         if !response.ok() {
             return Err(JsValue::from_str(&format!(
@@ -272,18 +1096,22 @@ impl BlogApp {
 
     /// Удалить пост.
     pub async fn delete_post(&self, id: i64) -> Result<JsValue, JsValue> {
-        let token = self
-            .token
-            .as_ref()
-            .ok_or_else(|| JsValue::from_str("Пользователь не авторизован"))?;
+        if let Some(grpc_web) = &self.grpc_web {
+            let result = grpc_web.delete_post(&self.grpc_token()?, id).await;
+            self.grpc_authorized("Не удалось удалить пост", result)?;
+
+            return serde_wasm_bindgen::to_value(&serde_json::json!({
+                "success": true,
+            }))
+            .map_err(|e| JsValue::from_str(&format!("Не удалось сериализовать ответ: {}", e)));
+        }
 
-        let url = format!("{}/api/posts/{}", self.server, id);
+        let request = self
+            .authorized(Method::DELETE, &format!("/posts/{}", id))?
+            .build()
+            .map_err(|e| JsValue::from_str(&format!("Не удалось сформировать запрос: {}", e)))?;
 
-        let response = Request::delete(&url)
-            .header("Authorization", &format!("Bearer {}", token))
-            .send()
-            .await
-            .map_err(|e| JsValue::from_str(&format!("Не удалось отправить запрос: {}", e)))?;
+        let response = self.send_authorized(request).await?;
 
         if !response.ok() {
             return Err(JsValue::from_str(&format!(
@@ -298,38 +1126,135 @@ impl BlogApp {
         .map_err(|e| JsValue::from_str(&format!("Не удалось сериализовать ответ: {}", e)))
     }
 
-    /// Проверка наличия токена.
+    /// Загрузить настройки текущего пользователя (язык, количество постов на странице и т.д.).
+    ///
+    /// Настройки доступны только через REST API, в том числе в режиме gRPC-web.
+    pub async fn get_settings(&self) -> Result<JsValue, JsValue> {
+        let request = self
+            .authorized(Method::GET, "/users/me/settings")?
+            .build()
+            .map_err(|e| JsValue::from_str(&format!("Не удалось сформировать запрос: {}", e)))?;
+
+        let response = self.send_authorized(request).await?;
+
+        if !response.ok() {
+            return Err(JsValue::from_str(&format!(
+                "Не удалось загрузить настройки, код: {}",
+                response.status()
+            )));
+        }
+
+        let settings: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| JsValue::from_str(&format!("Не удалось обработать ответ: {}", e)))?;
+
+        settings
+            .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+            .map_err(|e| JsValue::from_str(&format!("Не удалось сериализовать настройки: {}", e)))
+    }
+
+    /// Сохранить настройки текущего пользователя и получить их в сохраненном виде.
+    ///
+    /// `settings` - объект в формате [`BlogApp::get_settings`]; отсутствующие поля принимают
+    /// значения по умолчанию. При ошибке выбрасывается объект `{ status, message, fields }`.
+    pub async fn update_settings(&self, settings: JsValue) -> Result<JsValue, JsValue> {
+        let settings: serde_json::Value = serde_wasm_bindgen::from_value(settings)
+            .map_err(|e| JsValue::from_str(&format!("Некорректные настройки: {}", e)))?;
+
+        let request = self
+            .authorized(Method::PUT, "/users/me/settings")?
+            .json(&settings)
+            .map_err(|e| JsValue::from_str(&format!("Не удалось сериализовать запрос: {}", e)))?;
+
+        let response = self.send_authorized(request).await?;
+
+        if !response.ok() {
+            return Err(self
+                .api_error(response, "Не удалось сохранить настройки")
+                .await);
+        }
+
+        let settings: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| JsValue::from_str(&format!("Не удалось обработать ответ: {}", e)))?;
+
+        settings
+            .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+            .map_err(|e| JsValue::from_str(&format!("Не удалось сериализовать настройки: {}", e)))
+    }
+
+    /// Изменить профиль текущего пользователя: `{ email, remove_avatar }`.
+    ///
+    /// Новый email-адрес применяется после перехода по ссылке из письма. Возвращает профиль
+    /// в измененном виде; при ошибке выбрасывается объект `{ status, message, fields }`.
+    pub async fn update_profile(&self, profile: JsValue) -> Result<JsValue, JsValue> {
+        let profile: ProfileUpdate = serde_wasm_bindgen::from_value(profile)
+            .map_err(|e| JsValue::from_str(&format!("Некорректные данные профиля: {}", e)))?;
+
+        let request = self
+            .authorized(Method::PATCH, "/users/me")?
+            .json(&profile)
+            .map_err(|e| JsValue::from_str(&format!("Не удалось сериализовать запрос: {}", e)))?;
+
+        let response = self.send_authorized(request).await?;
+
+        if !response.ok() {
+            return Err(self
+                .api_error(response, "Не удалось изменить профиль")
+                .await);
+        }
+
+        let user: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| JsValue::from_str(&format!("Не удалось обработать ответ: {}", e)))?;
+
+        user.serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+            .map_err(|e| JsValue::from_str(&format!("Не удалось сериализовать профиль: {}", e)))
+    }
+
+    /// Подписаться на изменения состояния авторизации.
+    ///
+    /// `callback` вызывается с объектом `{ authenticated, reason }`, где `reason` - `login`
+    /// (вход, регистрация или [`BlogApp::set_token`]), `logout`, `expired` (сервер отклонил
+    /// токен или сессию) или `restored` (токен загружен из localStorage). Токенов обновления
+    /// сервер не выдает, поэтому истекшая авторизация не продлевается автоматически.
+    pub fn on_auth_change(&self, callback: js_sys::Function) {
+        self.auth_listeners.borrow_mut().push(callback);
+    }
+
+    /// Проверка наличия токена (или сессии в режиме cookie).
     pub fn is_authenticated(&self) -> bool {
-        self.token.is_some()
+        if self.cookie_session {
+            return read_cookie(CSRF_COOKIE).is_some();
+        }
+
+        self.token.borrow().is_some()
     }
 
     /// Получить токен.
     pub fn get_token(&self) -> Option<String> {
-        self.token.clone()
+        self.token.borrow().clone()
     }
 
     /// Установить токен.
     pub fn set_token(&mut self, token: String) {
-        self.token = Some(token);
+        self.token.replace(Some(token));
+        self.notify_auth_change(AUTH_LOGIN);
     }
 
     /// Сохранение JWT-токена в localStorage под ключом "blog_token".
+    ///
+    /// Токен, полученный при входе или регистрации, сохраняется автоматически.
     pub fn save_token_to_storage(&self) -> Result<JsValue, JsValue> {
         let token = self
-            .token
-            .as_ref()
+            .get_token()
             .ok_or_else(|| JsValue::from_str("Нет токена для сохранения"))?;
 
-        let window =
-            window().ok_or_else(|| JsValue::from_str("Не удалось получить объект window"))?;
-
-        let storage = window
-            .local_storage()
-            .map_err(|_| JsValue::from_str("Не удалось получить доступ к localStorage"))?
-            .ok_or_else(|| JsValue::from_str("localStorage недоступен"))?;
-
-        storage
-            .set_item("blog_token", token)
+        local_storage()?
+            .set_item(TOKEN_STORAGE_KEY, &token)
             .map_err(|_| JsValue::from_str("Не удалось сохранить токен в localStorage"))?;
 
         serde_wasm_bindgen::to_value(&serde_json::json!({
@@ -340,21 +1265,15 @@ impl BlogApp {
 
     /// Загрузка токена из localStorage.
     pub fn get_token_from_storage(&mut self) -> Result<JsValue, JsValue> {
-        let window =
-            window().ok_or_else(|| JsValue::from_str("Не удалось получить объект window"))?;
-
-        let storage = window
-            .local_storage()
-            .map_err(|_| JsValue::from_str("Не удалось получить доступ к localStorage"))?
-            .ok_or_else(|| JsValue::from_str("localStorage недоступен"))?;
-
-        let token = storage
-            .get_item("blog_token")
+        let token = local_storage()?
+            .get_item(TOKEN_STORAGE_KEY)
             .map_err(|_| JsValue::from_str("Не удалось прочитать токен из localStorage"))?;
 
         match token {
             Some(token) if !token.is_empty() => {
-                self.token = Some(token);
+                self.token.replace(Some(token));
+                self.notify_auth_change(AUTH_RESTORED);
+
                 serde_wasm_bindgen::to_value(&serde_json::json!({
                     "success": true,
                 }))
@@ -366,19 +1285,8 @@ impl BlogApp {
 
     /// Удаление токена из localStorage.
     pub fn remove_token_from_storage(&mut self) -> Result<JsValue, JsValue> {
-        let window =
-            window().ok_or_else(|| JsValue::from_str("Не удалось получить объект window"))?;
-
-        let storage = window
-            .local_storage()
-            .map_err(|_| JsValue::from_str("Не удалось получить доступ к localStorage"))?
-            .ok_or_else(|| JsValue::from_str("localStorage недоступен"))?;
-
-        storage
-            .remove_item("blog_token")
-            .map_err(|_| JsValue::from_str("Не удалось удалить токен из localStorage"))?;
-
-        self.token = None;
+        self.forget_token()?;
+        self.notify_auth_change(AUTH_LOGOUT);
 
         serde_wasm_bindgen::to_value(&serde_json::json!({
             "success": true,
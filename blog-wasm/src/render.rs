@@ -0,0 +1,168 @@
+//! Отрисовка списка постов на странице без пользовательского JavaScript.
+//!
+//! Карточка поста строится по HTML-шаблону с подстановками вида `{title}`.
+//! Шаблон задает разработчик страницы и считается доверенным, а все
+//! подставляемые значения экранируются.
+
+use crate::PostPreview;
+use wasm_bindgen::JsValue;
+use web_sys::{Document, Element};
+
+/// Шаблон карточки поста по умолчанию (классы совпадают со стилями `www/styles.css`).
+pub(crate) const DEFAULT_POST_TEMPLATE: &str = r#"<div class="post-header">
+    <div>
+        <h3 class="post-title">{title}</h3>
+        <div class="post-meta">{author} | {time}</div>
+    </div>
+</div>
+<div class="post-content">{excerpt}</div>"#;
+
+/// Шаблон, который выводится вместо списка, если постов нет.
+pub(crate) const DEFAULT_EMPTY_TEMPLATE: &str = r#"<p class="loading">Постов пока что нет</p>"#;
+
+/// Экранировать специальные символы HTML (в том числе кавычки для значений атрибутов).
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Форма существительного для числа: `forms` - для 1, для 2-4 и для 5-20 (`минуту`, `минуты`, `минут`).
+fn plural(n: i64, forms: [&str; 3]) -> &str {
+    let (n10, n100) = (n % 10, n % 100);
+
+    if n10 == 1 && n100 != 11 {
+        forms[0]
+    } else if (2..=4).contains(&n10) && !(12..=14).contains(&n100) {
+        forms[1]
+    } else {
+        forms[2]
+    }
+}
+
+/// Время относительно текущего момента: `только что`, `5 минут назад`, `2 дня назад`.
+///
+/// Даты старше месяца выводятся целиком, а нераспознанные - как есть.
+fn relative_time(timestamp: &str) -> String {
+    let millis = js_sys::Date::parse(timestamp);
+
+    if millis.is_nan() {
+        return timestamp.to_string();
+    }
+
+    let seconds = ((js_sys::Date::now() - millis) / 1000.0) as i64;
+    let (minutes, hours, days) = (seconds / 60, seconds / 3600, seconds / 86400);
+
+    if minutes < 1 {
+        "только что".to_string()
+    } else if hours < 1 {
+        format!(
+            "{minutes} {} назад",
+            plural(minutes, ["минуту", "минуты", "минут"])
+        )
+    } else if days < 1 {
+        format!("{hours} {} назад", plural(hours, ["час", "часа", "часов"]))
+    } else if days < 30 {
+        format!("{days} {} назад", plural(days, ["день", "дня", "дней"]))
+    } else {
+        js_sys::Date::new(&JsValue::from_f64(millis))
+            .to_locale_date_string("ru-RU", &JsValue::UNDEFINED)
+            .into()
+    }
+}
+
+/// Значение подстановки `{name}` для поста (`None` - неизвестная подстановка).
+fn placeholder(post: &PostPreview, name: &str) -> Option<String> {
+    let value = match name {
+        "id" => post.id.to_string(),
+        "title" => post.title.clone(),
+        "excerpt" => post.excerpt.clone().unwrap_or_default(),
+        "cover_image_url" => post.cover_image_url.clone().unwrap_or_default(),
+        "author" => match &post.author {
+            Some(author) => author.username.clone(),
+            None => format!("Пользователь #{}", post.author_id),
+        },
+        "author_id" => post.author_id.to_string(),
+        "time" => relative_time(&post.created_at),
+        "created_at" => post.created_at.clone(),
+        "updated_at" => post.updated_at.clone(),
+        "reading_time" => post.reading_time_minutes.to_string(),
+        _ => return None,
+    };
+
+    Some(escape_html(&value))
+}
+
+/// Подставить значения поста в шаблон.
+///
+/// Подстановки: `{id}`, `{title}`, `{excerpt}`, `{cover_image_url}`, `{author}`,
+/// `{author_id}`, `{time}` (относительное время создания), `{created_at}`,
+/// `{updated_at}`, `{reading_time}`. Неизвестные подстановки остаются без изменений.
+fn fill_template(template: &str, post: &PostPreview) -> String {
+    let mut html = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        html.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let value = rest
+            .find('}')
+            .and_then(|end| Some((end, placeholder(post, &rest[1..end])?)));
+
+        match value {
+            Some((end, value)) => {
+                html.push_str(&value);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                html.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    html.push_str(rest);
+    html
+}
+
+/// Заменить содержимое контейнера карточками постов.
+///
+/// Каждая карточка - элемент `article` с классом `post-card` и атрибутом `data-post-id`.
+pub(crate) fn render_posts(
+    document: &Document,
+    container: &Element,
+    posts: &[PostPreview],
+    post_template: &str,
+    empty_template: &str,
+) -> Result<(), JsValue> {
+    if posts.is_empty() {
+        container.set_inner_html(empty_template);
+        return Ok(());
+    }
+
+    container.set_inner_html("");
+
+    for post in posts {
+        let card = document.create_element("article")?;
+
+        card.set_class_name("post-card");
+        card.set_attribute("data-post-id", &post.id.to_string())?;
+        card.set_inner_html(&fill_template(post_template, post));
+
+        container.append_child(&card)?;
+    }
+
+    Ok(())
+}
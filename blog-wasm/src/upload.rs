@@ -0,0 +1,89 @@
+//! Загрузка файлов на сервер с отслеживанием прогресса.
+//!
+//! fetch API не сообщает о прогрессе отправки тела запроса, поэтому файл
+//! отправляется через `XMLHttpRequest`, а браузер читает его по частям.
+
+use js_sys::{Function, Promise};
+use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{File, ProgressEvent, XmlHttpRequest};
+
+/// Ответ сервера на загрузку файла.
+pub(crate) struct UploadResponse {
+    /// HTTP-код ответа.
+    pub status: u16,
+
+    /// Тело ответа.
+    pub body: String,
+}
+
+/// Отправить файл POST-запросом на `url`.
+///
+/// `on_progress` вызывается с двумя аргументами - количеством отправленных байт
+/// и размером файла. Ошибка возвращается, только если запрос не удалось отправить;
+/// ответ с любым HTTP-кодом возвращается как есть.
+pub(crate) async fn upload_file(
+    url: &str,
+    headers: &[(&str, String)],
+    with_credentials: bool,
+    file: &File,
+    on_progress: Option<Function>,
+) -> Result<UploadResponse, JsValue> {
+    let xhr = XmlHttpRequest::new()?;
+
+    xhr.open_with_async("POST", url, true)?;
+    xhr.set_with_credentials(with_credentials);
+
+    for (name, value) in headers {
+        xhr.set_request_header(name, value)?;
+    }
+
+    if !file.type_().is_empty() {
+        xhr.set_request_header("Content-Type", &file.type_())?;
+    }
+
+    // Обработчики должны жить до завершения запроса, поэтому хранятся до конца функции.
+    let progress = on_progress.map(|callback| {
+        Closure::<dyn FnMut(ProgressEvent)>::new(move |event: ProgressEvent| {
+            let _ = callback.call2(
+                &JsValue::NULL,
+                &JsValue::from_f64(event.loaded()),
+                &JsValue::from_f64(event.total()),
+            );
+        })
+    });
+
+    if let Some(progress) = &progress {
+        xhr.upload()?
+            .set_onprogress(Some(progress.as_ref().unchecked_ref()));
+    }
+
+    let mut handlers = None;
+    let done = Promise::new(&mut |resolve, reject| {
+        let onload = Closure::<dyn FnMut()>::new(move || {
+            let _ = resolve.call0(&JsValue::NULL);
+        });
+        let onerror = Closure::<dyn FnMut()>::new(move || {
+            let _ = reject.call1(
+                &JsValue::NULL,
+                &JsValue::from_str("Не удалось отправить файл"),
+            );
+        });
+
+        xhr.set_onload(Some(onload.as_ref().unchecked_ref()));
+        xhr.set_onerror(Some(onerror.as_ref().unchecked_ref()));
+        handlers = Some((onload, onerror));
+    });
+
+    xhr.send_with_opt_blob(Some(file))?;
+    JsFuture::from(done).await?;
+
+    drop(handlers);
+    drop(progress);
+
+    Ok(UploadResponse {
+        status: xhr.status()?,
+        body: xhr.response_text()?.unwrap_or_default(),
+    })
+}
@@ -0,0 +1,74 @@
+//! Проверка данных форм входа и регистрации до отправки запроса.
+//!
+//! Правила повторяют проверки сервера при регистрации с политикой паролей по умолчанию;
+//! если на сервере заданы более строгие требования, он отклонит запрос с описанием причины.
+//! Функции возвращают описание ошибки или `undefined`, если значение корректно.
+
+use validator::ValidateEmail;
+use wasm_bindgen::prelude::*;
+
+/// Минимальная длина пароля (как в политике паролей сервера по умолчанию).
+const PASSWORD_MIN_LENGTH: usize = 6;
+
+/// Наиболее распространенные пароли, запрещенные к использованию (как на сервере).
+const COMMON_PASSWORDS: &[&str] = &[
+    "123456",
+    "1234567",
+    "12345678",
+    "123456789",
+    "1234567890",
+    "qwerty",
+    "qwerty123",
+    "qwertyuiop",
+    "password",
+    "password1",
+    "passw0rd",
+    "111111",
+    "000000",
+    "123123",
+    "abc123",
+    "iloveyou",
+    "admin",
+    "letmein",
+    "welcome",
+    "monkey",
+    "dragon",
+    "football",
+];
+
+/// Проверить имя пользователя.
+#[wasm_bindgen]
+pub fn validate_username(username: &str) -> Option<String> {
+    if username.trim().is_empty() {
+        return Some("Имя пользователя не может быть пустым".to_string());
+    }
+
+    None
+}
+
+/// Проверить email-адрес.
+#[wasm_bindgen]
+pub fn validate_email(email: &str) -> Option<String> {
+    if !email.validate_email() {
+        return Some("Некорректный email-адрес".to_string());
+    }
+
+    None
+}
+
+/// Проверить пароль.
+#[wasm_bindgen]
+pub fn validate_password(password: &str) -> Option<String> {
+    if password.chars().count() < PASSWORD_MIN_LENGTH {
+        return Some(format!(
+            "Длина пароля должна быть не менее {} символов",
+            PASSWORD_MIN_LENGTH
+        ));
+    }
+
+    if COMMON_PASSWORDS.contains(&password.to_lowercase().as_str()) {
+        return Some("Пароль слишком распространен".to_string());
+    }
+
+    None
+}
@@ -1,9 +1,9 @@
-import init, { BlogApp } from './pkg/blog_wasm.js';
+import init, { BlogApp, validate_email, validate_password, validate_username } from './pkg/blog_wasm.js';
 
 let app = null;
 let currentUserId = null;
-let currentPage = 0;
-const POSTS_PER_PAGE = 5;
+const DEFAULT_POSTS_PER_PAGE = 5;
+let postsPerPage = DEFAULT_POSTS_PER_PAGE;
 
 // Декодирование JWT токена для извлечения user_id
 function decodeJWT(token) {
@@ -49,18 +49,60 @@ function getCurrentUserId() {
     return payload.user_id;
 }
 
+// Применить настройки пользователя (количество постов на странице и язык)
+async function applyUserSettings() {
+    if (!app.is_authenticated()) {
+        postsPerPage = DEFAULT_POSTS_PER_PAGE;
+        document.documentElement.lang = 'ru';
+        app.set_page_size(BigInt(postsPerPage));
+        return;
+    }
+
+    try {
+        const settings = await app.get_settings();
+        postsPerPage = settings.posts_per_page || DEFAULT_POSTS_PER_PAGE;
+        document.documentElement.lang = settings.language || 'ru';
+    } catch (error) {
+        console.error('Ошибка загрузки настроек:', error);
+    }
+
+    app.set_page_size(BigInt(postsPerPage));
+}
+
 // Инициализация приложения
 async function initApp() {
     try {
         await init();
 
-        const serverUrl = localStorage.getItem('blog_server') || 'http://127.0.0.1:3000';
+        const serverUrl = localStorage.getItem('blog_server') || window.BLOG_SERVER || 'http://127.0.0.1:3000';
         app = new BlogApp(serverUrl);
+        app.set_page_size(BigInt(postsPerPage));
+
+        // Состояние пагинации приходит событием после каждой загрузки страницы
+        window.addEventListener('blog:page', (event) => updatePaginationButtons(event.detail));
+
+        // Интерфейс следует за состоянием авторизации; если сервер отклонил токен или сессию,
+        // токен уже удален и остается предложить войти снова
+        app.on_auth_change(({ authenticated, reason }) => {
+            currentUserId = authenticated ? getCurrentUserId() : null;
+            updateAuthUI();
+
+            if (reason === 'expired') {
+                showError('posts-error', 'Сессия истекла, войдите снова');
+            }
+        });
+
+        // Адрес gRPC-сервера для работы по gRPC-web вместо REST API
+        const grpcWebUrl = localStorage.getItem('blog_grpc_web');
+        if (grpcWebUrl) {
+            app.enable_grpc_web(grpcWebUrl);
+        }
 
         try {
             await app.get_token_from_storage();
             currentUserId = getCurrentUserId();
             updateAuthUI();
+            await applyUserSettings();
         } catch (e) {
             console.log('Токен не найден в localStorage');
         }
@@ -101,12 +143,30 @@ function setupEventListeners() {
         await handleRegister();
     });
 
+    // Проверка полей регистрации по мере ввода
+    for (const [id, validate] of Object.entries(REGISTER_VALIDATORS)) {
+        document.getElementById(id).addEventListener('input', (e) => {
+            const problem = e.target.value ? validate(e.target.value.trim()) : undefined;
+
+            if (problem) {
+                showError('register-error', problem);
+            } else {
+                clearError('register-error');
+            }
+        });
+    }
+
     // Форма создания поста
     document.getElementById('create-post-form').addEventListener('submit', async (e) => {
         e.preventDefault();
         await handleCreatePost();
     });
 
+    // Загрузка изображения для вставки в содержание поста
+    document.getElementById('post-image').addEventListener('change', async (e) => {
+        await handleImageUpload(e.target);
+    });
+
     // Форма редактирования поста
     document.getElementById('edit-post-form').addEventListener('submit', async (e) => {
         e.preventDefault();
@@ -121,11 +181,11 @@ function setupEventListeners() {
 
     // Кнопка выхода
     document.getElementById('prev-page-btn').addEventListener('click', async () => {
-        await goToPreviousPage();
+        await loadPosts(() => app.prev_page());
     });
 
     document.getElementById('next-page-btn').addEventListener('click', async () => {
-        await goToNextPage();
+        await loadPosts(() => app.next_page());
     });
 }
 
@@ -141,7 +201,6 @@ async function handleLogin() {
 
     try {
         await app.login(username, password);
-        await app.save_token_to_storage();
 
         currentUserId = getCurrentUserId();
 
@@ -149,27 +208,53 @@ async function handleLogin() {
         clearError('login-error');
 
         updateAuthUI();
-        currentPage = 0;
-        await loadPosts();
+        await applyUserSettings();
+        await loadPosts(() => app.load_page(1n));
     } catch (error) {
         showError('login-error', `Ошибка входа: ${error}`);
     }
 }
 
+// Проверки полей формы регистрации (возвращают описание ошибки или undefined)
+const REGISTER_VALIDATORS = {
+    'register-username': validate_username,
+    'register-email': validate_email,
+    'register-password': validate_password,
+};
+
+// Первая ошибка в полях формы регистрации (undefined, если поля корректны)
+function registerFormProblem() {
+    for (const [id, validate] of Object.entries(REGISTER_VALIDATORS)) {
+        const problem = validate(document.getElementById(id).value.trim());
+
+        if (problem) {
+            return problem;
+        }
+    }
+
+    return undefined;
+}
+
 // Обработка регистрации
 async function handleRegister() {
     const username = document.getElementById('register-username').value.trim();
     const email = document.getElementById('register-email').value.trim();
     const password = document.getElementById('register-password').value.trim();
+    const inviteCode = document.getElementById('register-invite-code').value.trim();
 
     if (!username || !email || !password) {
         showError('register-error', 'Заполните все поля');
         return;
     }
 
+    const problem = registerFormProblem();
+    if (problem) {
+        showError('register-error', problem);
+        return;
+    }
+
     try {
-        await app.register(username, email, password);
-        await app.save_token_to_storage();
+        await app.register(username, email, password, inviteCode || undefined);
 
         currentUserId = getCurrentUserId();
 
@@ -177,31 +262,65 @@ async function handleRegister() {
         clearError('register-error');
 
         updateAuthUI();
-        currentPage = 0;
-        await loadPosts();
+        await applyUserSettings();
+        await loadPosts(() => app.load_page(1n));
     } catch (error) {
         showError('register-error', `Ошибка регистрации: ${error}`);
     }
 }
 
+// Загрузка изображения и вставка ссылки на него в содержание поста
+async function handleImageUpload(input) {
+    const file = input.files[0];
+    if (!file) {
+        return;
+    }
+
+    const progress = document.getElementById('post-image-progress');
+    progress.value = 0;
+    progress.style.display = 'block';
+
+    try {
+        const url = await app.upload_image(file, (loaded, total) => {
+            progress.value = total ? loaded / total : 0;
+        });
+
+        const content = document.getElementById('post-content');
+        const format = document.getElementById('post-content-format').value;
+        const image = format === 'markdown' ? `![](${url})`
+            : format === 'html' ? `<img src="${escapeHtml(url)}" alt="">`
+            : url;
+
+        content.value = content.value ? `${content.value}\n${image}` : image;
+        clearError('create-post-error');
+    } catch (error) {
+        showError('create-post-error', `Ошибка загрузки изображения: ${error}`);
+    } finally {
+        input.value = '';
+        progress.style.display = 'none';
+    }
+}
+
 // Обработка создания поста
 async function handleCreatePost() {
     const title = document.getElementById('post-title').value.trim();
     const content = document.getElementById('post-content').value.trim();
+    const contentFormat = document.getElementById('post-content-format').value;
+    const excerpt = document.getElementById('post-excerpt').value.trim();
+    const coverImageUrl = document.getElementById('post-cover-image-url').value.trim();
 
     if (!title || !content) {
-        showError('create-post-error', 'Заполните все поля');
+        showError('create-post-error', 'Заполните заголовок и содержание');
         return;
     }
 
     try {
-        await app.create_post(title, content);
+        await app.create_post(title, content, excerpt || undefined, coverImageUrl || undefined, contentFormat);
 
         document.getElementById('create-post-form').reset();
         clearError('create-post-error');
 
-        currentPage = 0;
-        await loadPosts();
+        await loadPosts(() => app.load_page(1n));
     } catch (error) {
         showError('create-post-error', `Ошибка создания поста: ${error}`);
     }
@@ -212,14 +331,17 @@ async function handleUpdatePost() {
     const id = BigInt(parseInt(document.getElementById('edit-post-id').value));
     const title = document.getElementById('edit-post-title').value.trim();
     const content = document.getElementById('edit-post-content').value.trim();
+    const contentFormat = document.getElementById('edit-post-content-format').value;
+    const excerpt = document.getElementById('edit-post-excerpt').value.trim();
+    const coverImageUrl = document.getElementById('edit-post-cover-image-url').value.trim();
 
     if (!title || !content) {
-        showError('edit-post-error', 'Заполните все поля');
+        showError('edit-post-error', 'Заполните заголовок и содержание');
         return;
     }
 
     try {
-        await app.update_post(id, title, content);
+        await app.update_post(id, title, content, excerpt || undefined, coverImageUrl || undefined, contentFormat);
 
         document.getElementById('edit-post-panel').style.display = 'none';
         document.getElementById('create-post-panel').style.display = 'block';
@@ -245,8 +367,8 @@ async function handleDeletePost(postId) {
     }
 }
 
-// Загрузка постов с пагинацией
-async function loadPosts() {
+// Загрузка постов с пагинацией (по умолчанию обновляется текущая страница)
+async function loadPosts(load = () => app.reload_posts()) {
     const postsLoading = document.getElementById('posts-loading');
     const postsList = document.getElementById('posts-list');
 
@@ -255,32 +377,18 @@ async function loadPosts() {
     clearError('posts-error');
 
     try {
-        const offset = currentPage * POSTS_PER_PAGE;
-        const posts = await app.load_posts(BigInt(POSTS_PER_PAGE + 1), BigInt(offset));
+        const page = await load();
         postsLoading.style.display = 'none';
 
-        if (posts.length === 0 && currentPage === 0) {
+        if (page.total === 0) {
             postsList.innerHTML = '<p class="loading">Постов пока что нет</p>';
-            updatePaginationButtons(false, false);
-            return;
-        }
-
-        if (posts.length === 0 && currentPage > 0) {
-            currentPage--;
-            await loadPosts();
             return;
         }
 
-        posts.forEach(post => {
-            if (postsList.childElementCount === POSTS_PER_PAGE) {
-                return;
-            }
-
+        page.posts.forEach(post => {
             const postCard = createPostCard(post);
             postsList.appendChild(postCard);
         });
-
-        updatePaginationButtons(currentPage > 0, posts.length === POSTS_PER_PAGE + 1);
     } catch (error) {
         postsLoading.style.display = 'none';
         showError('posts-error', `Ошибка загрузки постов: ${error}`);
@@ -301,14 +409,14 @@ function createPostCard(post) {
                 <div class="post-meta">
                     Идентификатор автора: ${post.author_id} |
                     Создан: ${formatDate(post.created_at)} |
-                    Обновлен: ${formatDate(post.updated_at)}
+                    Обновлен: ${formatDate(post.updated_at)}${post.is_private ? ' | Приватный' : ''}${post.archived_at ? ' | В архиве' : ''}${post.reading_time_minutes ? ` | ${post.reading_time_minutes} мин. чтения` : ''}
                 </div>
             </div>
         </div>
-        <div class="post-content">${escapeHtml(post.content)}</div>
+        ${post.excerpt ? `<div class="post-content">${escapeHtml(post.excerpt)}</div>` : ''}
         ${isAuthor ? `
         <div class="post-actions">
-            <button class="edit-btn" onclick="window.editPost(${post.id}, '${escapeHtml(post.title)}', '${escapeHtml(post.content)}')">
+            <button class="edit-btn" onclick="window.editPost(${post.id})">
                 Редактировать
             </button>
             <button class="delete-btn" onclick="window.deletePost(${post.id})">
@@ -318,14 +426,34 @@ function createPostCard(post) {
         ` : ''}
     `;
 
+    // Ссылка на обложку задается через setAttribute, а не подставляется в разметку
+    if (post.cover_image_url) {
+        const cover = document.createElement('img');
+        cover.className = 'post-cover';
+        cover.setAttribute('src', post.cover_image_url);
+        cover.setAttribute('alt', '');
+        card.querySelector('.post-header').after(cover);
+    }
+
     return card;
 }
 
-// Редактирование поста
-window.editPost = function(id, title, content) {
+// Редактирование поста (полное содержимое загружается отдельным запросом)
+window.editPost = async function(id) {
+    let post;
+    try {
+        post = await app.load_post(BigInt(id));
+    } catch (error) {
+        showError('posts-error', `Ошибка загрузки поста: ${error}`);
+        return;
+    }
+
     document.getElementById('edit-post-id').value = id;
-    document.getElementById('edit-post-title').value = title;
-    document.getElementById('edit-post-content').value = content;
+    document.getElementById('edit-post-title').value = post.title;
+    document.getElementById('edit-post-content').value = post.content;
+    document.getElementById('edit-post-content-format').value = post.content_format;
+    document.getElementById('edit-post-excerpt').value = post.excerpt ?? '';
+    document.getElementById('edit-post-cover-image-url').value = post.cover_image_url ?? '';
 
     document.getElementById('create-post-panel').style.display = 'none';
     document.getElementById('edit-post-panel').style.display = 'block';
@@ -344,8 +472,8 @@ async function handleLogout() {
         await app.remove_token_from_storage();
         currentUserId = null;
         updateAuthUI();
-        currentPage = 0;
-        await loadPosts();
+        await applyUserSettings();
+        await loadPosts(() => app.load_page(1n));
     } catch (error) {
         console.error('Ошибка выхода:', error);
     }
@@ -409,22 +537,10 @@ function showRegisterForm() {
     clearError('register-error');
 }
 
-// Навигация по страницам (назад)
-async function goToPreviousPage() {
-    if (currentPage > 0) {
-        currentPage--;
-        await loadPosts();
-    }
-}
-
-// Навигация по страницам (вперед)
-async function goToNextPage() {
-    currentPage++;
-    await loadPosts();
-}
-
-// Обновление состояния кнопок пагинации
-function updatePaginationButtons(hasPrevious, hasNext) {
+// Обновление состояния кнопок пагинации по событию загрузки страницы
+function updatePaginationButtons({ page, total_pages: totalPages }) {
+    const hasPrevious = page > 1;
+    const hasNext = page < totalPages;
     const prevBtn = document.getElementById('prev-page-btn');
     const nextBtn = document.getElementById('next-page-btn');
     const pageInfo = document.getElementById('page-info');
@@ -440,7 +556,7 @@ function updatePaginationButtons(hasPrevious, hasNext) {
     }
 
     if (pageInfo) {
-        pageInfo.textContent = `Страница ${currentPage + 1}`;
+        pageInfo.textContent = `Страница ${page} из ${Math.max(totalPages, 1)}`;
     }
 }
 
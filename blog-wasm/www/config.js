@@ -0,0 +1,4 @@
+// Адрес сервера блога по умолчанию.
+// При раздаче фронтенда самим сервером (--frontend-dir) этот файл заменяется настройками,
+// указывающими на тот же адрес, с которого загружена страница.
+window.BLOG_SERVER = 'http://127.0.0.1:3000';